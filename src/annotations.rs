@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config_dir;
+
+/// Free-text notes on videos, keyed by video ID, persisted as annotations.json.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Annotations {
+    pub notes: BTreeMap<String, String>,
+}
+
+impl Annotations {
+    pub fn note(&self, video_id: &str) -> Option<&str> {
+        self.notes.get(video_id).map(String::as_str)
+    }
+
+    pub fn has_note(&self, video_id: &str) -> bool {
+        self.notes.contains_key(video_id)
+    }
+
+    /// Store the note for a video; a blank note removes the entry.
+    pub fn set(&mut self, video_id: &str, text: &str) {
+        if text.trim().is_empty() {
+            self.notes.remove(video_id);
+        } else {
+            self.notes.insert(video_id.to_owned(), text.to_owned());
+        }
+    }
+}
+
+fn annotations_path() -> PathBuf {
+    config_dir::get().annotations_path()
+}
+
+pub fn load() -> Annotations {
+    fs::read(annotations_path())
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(annotations: &Annotations) -> std::io::Result<()> {
+    if !config_dir::get().writable {
+        return Ok(());
+    }
+    let path = annotations_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(annotations)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_notes_are_removed_and_round_trip_as_json() {
+        let mut annotations = Annotations::default();
+        annotations.set("abc", "good intro, use at 4:30");
+        annotations.set("def", "temp");
+        annotations.set("def", "   ");
+        assert!(annotations.has_note("abc"));
+        assert!(!annotations.has_note("def"));
+
+        let json = serde_json::to_string(&annotations).unwrap();
+        let back: Annotations = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.note("abc"), Some("good intro, use at 4:30"));
+    }
+}
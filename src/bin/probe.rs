@@ -1,8 +1,25 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use time::{Duration, OffsetDateTime};
 
-use YTSearch::prefs::{self, Prefs, TimeWindow};
-use YTSearch::search_runner::{self, RunMode};
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use YTSearch::{
+    Prefs, ProgressSink, RunMode, SearchProgress, TimeWindow, VideoDetails, add_missing_defaults,
+    build_query_params, clock_skew_warning, init_config_dir, load_prefs, parse_region_code,
+    resolve_window, run_searches,
+};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// Summary line plus one row per video
+    Table,
+    /// One VideoDetails JSON object per line for the merged results (or per preset with
+    /// --stream); the summary goes to stderr
+    Jsonl,
+}
 
 #[derive(Parser, Debug)]
 #[command(about = "Inspect YTSearch queries from the terminal")]
@@ -39,9 +56,27 @@ struct Args {
     #[arg(long)]
     dry_run: bool,
 
-    /// Limit printed results
-    #[arg(long, default_value_t = 10)]
-    limit: usize,
+    /// Limit printed results (table default 10; jsonl prints every result)
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Print search stages (pages, detail and channel lookups) to stderr as they start
+    #[arg(long)]
+    verbose: bool,
+
+    /// With --format jsonl, print each preset's videos as soon as it finishes instead of the
+    /// merged list at the end. Streamed rows are pre-merge: source_presets names only the
+    /// preset that found the video, and the run's cap is not applied yet.
+    #[arg(long)]
+    stream: bool,
+
+    /// Folder holding prefs.json (also YTSEARCH_CONFIG_DIR)
+    #[arg(long, value_name = "PATH")]
+    config_dir: Option<PathBuf>,
 }
 
 fn override_window(prefs: &mut Prefs, hours: Option<i64>) {
@@ -62,12 +97,32 @@ fn override_window(prefs: &mut Prefs, hours: Option<i64>) {
     }
 }
 
+/// Write one video as a JSON line. A closed pipe (`| head`) ends the probe quietly.
+fn print_jsonl(out: &mut impl Write, video: &VideoDetails) {
+    let written = serde_json::to_writer(&mut *out, video)
+        .map_err(io::Error::from)
+        .and_then(|()| writeln!(out))
+        .and_then(|()| out.flush());
+    match written {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => std::process::exit(0),
+        Err(err) => {
+            eprintln!("Error: writing to stdout failed: {err}");
+            std::process::exit(1);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    let config = init_config_dir(args.config_dir.clone());
+    if let Some(note) = config.status_note() {
+        eprintln!("{note}");
+    }
 
-    let mut prefs = prefs::load_or_default();
-    prefs::add_missing_defaults(&mut prefs);
+    let mut prefs = load_prefs();
+    add_missing_defaults(&mut prefs);
     prefs.blocked_channels = prefs
         .blocked_channels
         .into_iter()
@@ -99,12 +154,9 @@ async fn main() -> anyhow::Result<()> {
 
     override_window(&mut prefs, args.hours);
 
-    if let Some(region) = args.region.as_ref().map(|s| s.trim()) {
-        if region.eq_ignore_ascii_case("none") || region.is_empty() {
-            prefs.global.region_code = None;
-        } else {
-            prefs.global.region_code = Some(region.to_uppercase());
-        }
+    if let Some(region) = args.region.as_ref() {
+        prefs.search_defaults.region_code =
+            parse_region_code(region).map_err(anyhow::Error::msg)?;
     }
 
     for search in &mut prefs.searches {
@@ -130,9 +182,9 @@ async fn main() -> anyhow::Result<()> {
 
     if args.dry_run {
         for search in &prefs.searches {
-            let pref_global = &prefs.global;
-            let mut params = search_runner::build_query_params(pref_global, search)?;
-            if let Some(window) = search_runner::resolve_window(pref_global, search) {
+            let pref_global = &prefs.search_defaults;
+            let mut params = build_query_params(pref_global, search)?;
+            if let Some(window) = resolve_window(pref_global, search) {
                 params.push(("publishedAfter", window.start_rfc3339.clone()));
                 params.push(("publishedBefore", window.end_rfc3339.clone()));
             }
@@ -141,9 +193,65 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
-    match search_runner::run_searches(prefs, mode).await {
+    let jsonl = args.format == OutputFormat::Jsonl;
+    let stream = jsonl && args.stream;
+    let verbose = args.verbose;
+    let limit = args.limit.unwrap_or(usize::MAX);
+    let (progress, printer) = if verbose || stream {
+        let (tx, rx) = mpsc::channel::<SearchProgress>();
+        let sink = if stream {
+            ProgressSink::new(tx).with_preset_videos()
+        } else {
+            ProgressSink::new(tx)
+        };
+        let printer = std::thread::spawn(move || {
+            // Streamed JSON Lines go out as each preset finishes, so a long run can be piped
+            // into jq (or cut short) before the last preset; a video several presets found
+            // prints once, under the first preset that found it.
+            let mut printed: HashSet<String> = HashSet::new();
+            let stdout = io::stdout();
+            for event in rx {
+                if verbose {
+                    eprintln!("{event}");
+                }
+                if let SearchProgress::PresetDone {
+                    videos: Some(videos),
+                    ..
+                } = event
+                {
+                    let mut out = stdout.lock();
+                    for video in videos {
+                        if printed.len() >= limit {
+                            break;
+                        }
+                        if printed.insert(video.id.clone()) {
+                            print_jsonl(&mut out, &video);
+                        }
+                    }
+                }
+            }
+        });
+        (Some(sink), Some(printer))
+    } else {
+        (None, None)
+    };
+    let outcome = run_searches(
+        prefs.api_key,
+        prefs.search_defaults,
+        prefs.searches,
+        prefs.blocked_channels,
+        mode,
+        None,
+        progress,
+    )
+    .await;
+    // The sender went away with the run, so the printer ends once it has drained.
+    if let Some(printer) = printer {
+        let _ = printer.join();
+    }
+    match outcome {
         Ok(outcome) => {
-            println!(
+            let summary = format!(
                 "presets: {} pages: {} raw: {} unique: {} passed: {} kept: {} duplicates: {}",
                 outcome.presets_ran,
                 outcome.pages_fetched,
@@ -153,19 +261,36 @@ async fn main() -> anyhow::Result<()> {
                 outcome.videos.len(),
                 outcome.duplicates_within_presets + outcome.duplicates_across_presets,
             );
-            for video in outcome.videos.iter().take(args.limit) {
-                println!(
-                    "{} | {:>4}s | {} | {}",
-                    video.published_at,
-                    video.duration_secs,
-                    video.source_presets.join("+"),
-                    video.title
-                );
+            match args.format {
+                OutputFormat::Table => {
+                    println!("{summary}");
+                    for video in outcome.videos.iter().take(args.limit.unwrap_or(10)) {
+                        println!(
+                            "{} | {:>4}s | {} | {}",
+                            video.published_at,
+                            video.duration_secs,
+                            video.source_presets.join("+"),
+                            video.title
+                        );
+                    }
+                }
+                OutputFormat::Jsonl => {
+                    // Keep stdout pure JSON Lines so it pipes straight into jq; with --stream
+                    // the videos were already printed as their presets finished.
+                    if !stream {
+                        let mut out = io::stdout().lock();
+                        for video in outcome.videos.iter().take(limit) {
+                            print_jsonl(&mut out, video);
+                        }
+                    }
+                    eprintln!("{summary}");
+                }
+            }
+            if let Some(warning) = outcome.clock_skew_secs.and_then(clock_skew_warning) {
+                eprintln!("warning: {warning}");
             }
         }
-        Err(err) => {
-            eprintln!("Error: {err:?}");
-        }
+        Err(err) => return Err(err),
     }
 
     Ok(())
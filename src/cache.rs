@@ -1,7 +1,10 @@
+use crate::config_dir;
 use crate::yt::types::VideoDetails;
-use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::{fs, path::PathBuf};
+use time::OffsetDateTime;
+use time::macros::format_description;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedResults {
@@ -16,18 +19,129 @@ fn default_saved_at() -> i64 {
     0
 }
 
+/// What reading the results cache found.
+#[derive(Debug)]
+pub enum CacheLoad {
+    Missing,
+    Loaded(CachedResults),
+    /// The file exists but did not parse. It was moved aside to `backup` (`None` when that
+    /// failed or the folder is read-only) and `salvaged` holds whatever could be rescued,
+    /// which is then saved as the new cache.
+    Corrupt {
+        error: String,
+        backup: Option<PathBuf>,
+        salvaged: Option<Salvaged>,
+    },
+}
+
+/// Results rescued from a cache file that no longer parses as a whole.
+#[derive(Debug)]
+pub struct Salvaged {
+    pub results: CachedResults,
+    /// Entries in `videos` that did not deserialize and were dropped.
+    pub skipped: usize,
+}
+
 fn cache_path() -> PathBuf {
-    let proj = ProjectDirs::from("com", "yourname", "YTSearch").expect("no project dirs");
-    proj.config_dir().join("last_results.json")
+    config_dir::get().results_cache_path()
 }
 
-pub fn load_cached_results() -> Option<CachedResults> {
+pub fn load_cached_results() -> CacheLoad {
     let path = cache_path();
-    let bytes = fs::read(path).ok()?;
-    serde_json::from_slice::<CachedResults>(&bytes).ok()
+    let Ok(bytes) = fs::read(&path) else {
+        return CacheLoad::Missing;
+    };
+    match serde_json::from_slice::<CachedResults>(&bytes) {
+        Ok(mut cached) => {
+            cached
+                .videos
+                .iter_mut()
+                .for_each(VideoDetails::fill_lowercase);
+            CacheLoad::Loaded(cached)
+        }
+        Err(err) => {
+            let mut salvaged = salvage(&bytes);
+            if let Some(salvaged) = salvaged.as_mut() {
+                salvaged
+                    .results
+                    .videos
+                    .iter_mut()
+                    .for_each(VideoDetails::fill_lowercase);
+            }
+            let backup = if config_dir::get().writable {
+                move_aside(&path, OffsetDateTime::now_utc())
+                    .inspect_err(|err| eprintln!("Failed to move the unreadable cache: {err}"))
+                    .ok()
+            } else {
+                None
+            };
+            // Write what was rescued back so it survives a restart without a new search.
+            if backup.is_some()
+                && let Some(salvaged) = &salvaged
+                && let Err(err) = save_cached_results(&salvaged.results)
+            {
+                eprintln!("Failed to save the salvaged results: {err}");
+            }
+            CacheLoad::Corrupt {
+                error: err.to_string(),
+                backup,
+                salvaged,
+            }
+        }
+    }
+}
+
+/// Rename `path` to `<stem>.corrupt-<timestamp>.json` beside it and return the new path.
+fn move_aside(path: &Path, now: OffsetDateTime) -> std::io::Result<PathBuf> {
+    let stamp = now
+        .format(format_description!(
+            "[year][month][day]-[hour][minute][second]"
+        ))
+        .map_err(std::io::Error::other)?;
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("last_results");
+    let backup = path.with_file_name(format!("{stem}.corrupt-{stamp}.json"));
+    fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+/// Keep every `videos` entry that still deserializes on its own, e.g. after a field changed
+/// type. `None` when the file is not a JSON object with a `videos` array at all.
+pub fn salvage(bytes: &[u8]) -> Option<Salvaged> {
+    let value: serde_json::Value = serde_json::from_slice(bytes).ok()?;
+    let object = value.as_object()?;
+    let entries = object.get("videos")?.as_array()?;
+    let videos: Vec<VideoDetails> = entries
+        .iter()
+        .filter_map(|entry| serde_json::from_value(entry.clone()).ok())
+        .collect();
+    let text = |key: &str| {
+        object
+            .get(key)
+            .and_then(|value| value.as_str())
+            .unwrap_or_default()
+            .to_owned()
+    };
+    Some(Salvaged {
+        skipped: entries.len() - videos.len(),
+        results: CachedResults {
+            generated_at: text("generated_at"),
+            status_line: text("status_line"),
+            videos,
+            saved_at_unix: object
+                .get("saved_at_unix")
+                .and_then(|value| value.as_i64())
+                .unwrap_or_default(),
+        },
+    })
 }
 
 pub fn save_cached_results(results: &CachedResults) -> std::io::Result<()> {
+    if !config_dir::get().writable {
+        return Ok(());
+    }
     let path = cache_path();
     if let Some(dir) = path.parent() {
         fs::create_dir_all(dir)?;
@@ -42,3 +156,69 @@ pub fn clear_cached_results() -> std::io::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, duration: &str) -> String {
+        format!(
+            r#"{{"id":"{id}","title":"T {id}","title_lower":"","channel_title":"C",
+            "channel_handle":"UCx","channel_display_name":null,"channel_custom_url":null,
+            "published_at":"2024-01-01T00:00:00Z","duration_secs":{duration},
+            "default_audio_lang":null,"default_lang":null,"thumbnail_url":null,
+            "url":"https://www.youtube.com/watch?v={id}","has_caption_lang_en":null,
+            "source_presets":["P"]}}"#
+        )
+    }
+
+    #[test]
+    fn salvage_keeps_the_good_records_around_a_bad_one() {
+        let json = format!(
+            r#"{{"generated_at":"2024-01-02 10:00","status_line":"ok","saved_at_unix":1704189600,
+            "videos":[{},{},{},{{"id":"no-fields"}}]}}"#,
+            record("a", "60"),
+            record("b", r#""one minute""#),
+            record("c", "90"),
+        );
+        assert!(serde_json::from_str::<CachedResults>(&json).is_err());
+
+        let salvaged = salvage(json.as_bytes()).unwrap();
+        let ids: Vec<&str> = salvaged
+            .results
+            .videos
+            .iter()
+            .map(|v| v.id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "c"]);
+        assert_eq!(salvaged.skipped, 2);
+        assert_eq!(salvaged.results.generated_at, "2024-01-02 10:00");
+        assert_eq!(salvaged.results.saved_at_unix, 1_704_189_600);
+    }
+
+    #[test]
+    fn nothing_is_salvaged_from_truncated_or_foreign_json() {
+        assert!(salvage(br#"{"videos":[{"id":"a""#).is_none());
+        assert!(salvage(br#"[1, 2, 3]"#).is_none());
+        assert!(salvage(br#"{"videos":"nope"}"#).is_none());
+    }
+
+    #[test]
+    fn unreadable_cache_is_moved_aside_with_a_timestamp() {
+        let dir = std::env::temp_dir().join(format!("ytsearch-corrupt-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("last_results.json");
+        fs::write(&path, b"{").unwrap();
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+
+        let backup = move_aside(&path, now).unwrap();
+        assert_eq!(
+            backup.file_name().unwrap(),
+            "last_results.corrupt-20231114-221320.json"
+        );
+        assert!(!path.exists());
+        assert_eq!(fs::read(&backup).unwrap(), b"{");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config_dir;
+use crate::yt::types::VideoDetails;
+
+/// Cached channel metadata older than this is shown as stale and refetched by
+/// "Refresh all channel info".
+pub const CHANNEL_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// A channel's display name and `@handle` as of the last `channels.list` call.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChannelInfo {
+    pub title: String,
+    pub handle: Option<String>,
+    pub fetched_at_unix: i64,
+}
+
+/// Channel metadata keyed by `UC…` channel ID, persisted as channels.json.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct ChannelCache {
+    pub channels: BTreeMap<String, ChannelInfo>,
+}
+
+impl ChannelCache {
+    pub fn get(&self, channel_id: &str) -> Option<&ChannelInfo> {
+        self.channels.get(channel_id)
+    }
+
+    pub fn insert(&mut self, channel_id: &str, title: &str, handle: Option<String>, now: i64) {
+        self.channels.insert(
+            channel_id.to_owned(),
+            ChannelInfo {
+                title: title.trim().to_owned(),
+                handle,
+                fetched_at_unix: now,
+            },
+        );
+    }
+
+    /// Whether the channel was cached more than [`CHANNEL_TTL_SECS`] ago. Channels not in the
+    /// cache are not stale: there is nothing old to flag.
+    pub fn is_stale(&self, channel_id: &str, now: i64) -> bool {
+        self.get(channel_id)
+            .is_some_and(|info| now - info.fetched_at_unix > CHANNEL_TTL_SECS)
+    }
+
+    /// Record the names a search just fetched for these videos' channels.
+    pub fn record_videos(&mut self, videos: &[VideoDetails], now: i64) {
+        for video in videos {
+            let id = video.channel_handle.trim();
+            if id.is_empty() || id.starts_with('@') {
+                continue;
+            }
+            let title = video
+                .channel_display_name
+                .as_deref()
+                .unwrap_or(&video.channel_title);
+            self.insert(id, title, video.channel_custom_url.clone(), now);
+        }
+    }
+
+    /// Channel IDs worth refetching: stale cache entries plus loaded channels never cached.
+    pub fn refresh_candidates(&self, videos: &[VideoDetails], now: i64) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .channels
+            .keys()
+            .filter(|id| self.is_stale(id, now))
+            .cloned()
+            .collect();
+        for video in videos {
+            let id = video.channel_handle.trim();
+            if !id.is_empty() && !id.starts_with('@') && self.get(id).is_none() {
+                ids.push(id.to_owned());
+            }
+        }
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+}
+
+/// Copy the cached name and handle onto every video from `channel_id`; returns how many changed.
+pub fn apply_to_videos(videos: &mut [VideoDetails], channel_id: &str, info: &ChannelInfo) -> usize {
+    let mut changed = 0;
+    for video in videos
+        .iter_mut()
+        .filter(|video| video.channel_handle == channel_id)
+    {
+        let before_name = video.channel_display_name.clone();
+        let before_handle = video.channel_custom_url.clone();
+        if !info.title.is_empty() {
+            video.channel_display_name = Some(info.title.clone());
+        }
+        if info.handle.is_some() {
+            video.channel_custom_url = info.handle.clone();
+        }
+        if video.channel_display_name != before_name || video.channel_custom_url != before_handle {
+            changed += 1;
+        }
+    }
+    changed
+}
+
+fn channels_path() -> PathBuf {
+    config_dir::get().channels_path()
+}
+
+pub fn load() -> ChannelCache {
+    fs::read(channels_path())
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(cache: &ChannelCache) -> std::io::Result<()> {
+    if !config_dir::get().writable {
+        return Ok(());
+    }
+    let path = channels_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(cache)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(id: &str, channel: &str) -> VideoDetails {
+        VideoDetails {
+            id: id.into(),
+            channel_title: "Old name".into(),
+            channel_handle: channel.into(),
+            channel_display_name: Some("Old name".into()),
+            ..VideoDetails::default()
+        }
+    }
+
+    #[test]
+    fn entries_go_stale_after_the_ttl() {
+        let mut cache = ChannelCache::default();
+        cache.insert("UCa", "Alpha", None, 1_000);
+        assert!(!cache.is_stale("UCa", 1_000 + CHANNEL_TTL_SECS));
+        assert!(cache.is_stale("UCa", 1_001 + CHANNEL_TTL_SECS));
+        assert!(!cache.is_stale("UCmissing", i64::MAX));
+
+        let loaded = vec![
+            video("v1", "UCa"),
+            video("v2", "UCb"),
+            video("v3", "@handle"),
+        ];
+        assert_eq!(cache.refresh_candidates(&loaded, 1_000), vec!["UCb"]);
+        assert_eq!(
+            cache.refresh_candidates(&loaded, 2_000 + CHANNEL_TTL_SECS),
+            vec!["UCa", "UCb"]
+        );
+    }
+
+    #[test]
+    fn refreshed_names_reach_every_video_from_the_channel() {
+        let mut videos = vec![video("v1", "UCa"), video("v2", "UCa"), video("v3", "UCb")];
+        let info = ChannelInfo {
+            title: "New name".into(),
+            handle: Some("@new".into()),
+            fetched_at_unix: 0,
+        };
+        assert_eq!(apply_to_videos(&mut videos, "UCa", &info), 2);
+        assert_eq!(videos[1].channel_display_name.as_deref(), Some("New name"));
+        assert_eq!(videos[1].channel_custom_url.as_deref(), Some("@new"));
+        assert_eq!(videos[2].channel_display_name.as_deref(), Some("Old name"));
+        assert_eq!(apply_to_videos(&mut videos, "UCa", &info), 0);
+    }
+}
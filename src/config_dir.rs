@@ -0,0 +1,493 @@
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use directories::ProjectDirs;
+
+/// Environment variable that points YTSearch at an explicit config folder.
+pub const CONFIG_DIR_ENV: &str = "YTSEARCH_CONFIG_DIR";
+
+/// Qualifier, organization, and application names for the platform config folder.
+pub const QUALIFIER: &str = "com";
+pub const ORGANIZATION: &str = "DaQue";
+pub const APPLICATION: &str = "YTSearch";
+/// Placeholder organization used by earlier releases; its folder is migrated on startup.
+const LEGACY_ORGANIZATION: &str = "yourname";
+
+const PREFS_FILE: &str = "prefs.json";
+const RESULTS_CACHE_FILE: &str = "last_results.json";
+const DISMISSALS_FILE: &str = "dismissals.json";
+const ANNOTATIONS_FILE: &str = "annotations.json";
+const UI_STATE_FILE: &str = "ui_state.json";
+const CHANNELS_FILE: &str = "channels.json";
+const PRESET_STATS_FILE: &str = "preset_stats.json";
+const FIND_INDEX_FILE: &str = "find_index.json";
+const THUMBNAILS_DIR: &str = "thumbnails";
+const BACKUPS_DIR: &str = "backups";
+
+static RESOLVED: OnceLock<ConfigDir> = OnceLock::new();
+
+/// Where a config folder candidate came from, in fallback order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// `--config-dir` flag or `YTSEARCH_CONFIG_DIR`.
+    Explicit,
+    /// The platform config folder (XDG on Linux).
+    Platform,
+    /// Next to the running executable.
+    Executable,
+    /// The system temp folder.
+    Temp,
+}
+
+impl ConfigSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfigSource::Explicit => "the configured folder",
+            ConfigSource::Platform => "the user config folder",
+            ConfigSource::Executable => "the executable's folder",
+            ConfigSource::Temp => "the temp folder",
+        }
+    }
+}
+
+/// The folder holding prefs.json, the results cache, and thumbnails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDir {
+    pub path: PathBuf,
+    pub source: ConfigSource,
+    /// False when no candidate could be written; nothing will be saved this session.
+    pub writable: bool,
+    /// Candidates that were tried first and could not be written.
+    pub skipped: Vec<(ConfigSource, PathBuf)>,
+    /// Legacy folder whose contents were moved here on this launch.
+    pub migrated_from: Option<PathBuf>,
+}
+
+impl ConfigDir {
+    pub fn prefs_path(&self) -> PathBuf {
+        self.path.join(PREFS_FILE)
+    }
+
+    pub fn results_cache_path(&self) -> PathBuf {
+        self.path.join(RESULTS_CACHE_FILE)
+    }
+
+    pub fn dismissals_path(&self) -> PathBuf {
+        self.path.join(DISMISSALS_FILE)
+    }
+
+    pub fn annotations_path(&self) -> PathBuf {
+        self.path.join(ANNOTATIONS_FILE)
+    }
+
+    pub fn ui_state_path(&self) -> PathBuf {
+        self.path.join(UI_STATE_FILE)
+    }
+
+    pub fn channels_path(&self) -> PathBuf {
+        self.path.join(CHANNELS_FILE)
+    }
+
+    pub fn preset_stats_path(&self) -> PathBuf {
+        self.path.join(PRESET_STATS_FILE)
+    }
+
+    pub fn find_index_path(&self) -> PathBuf {
+        self.path.join(FIND_INDEX_FILE)
+    }
+
+    pub fn thumbnails_dir(&self) -> PathBuf {
+        self.path.join(THUMBNAILS_DIR)
+    }
+
+    pub fn backups_dir(&self) -> PathBuf {
+        self.path.join(BACKUPS_DIR)
+    }
+
+    /// Whether the app is running somewhere other than the folder the user would expect.
+    pub fn is_fallback(&self) -> bool {
+        !self.skipped.is_empty()
+    }
+
+    /// One-line status message for fallbacks, degraded mode, and migrations.
+    pub fn status_note(&self) -> Option<String> {
+        if !self.writable {
+            return Some(format!(
+                "No writable config folder found (tried {}); nothing will be saved.",
+                self.skipped
+                    .iter()
+                    .map(|(_, path)| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        let Some((first_source, first_path)) = self.skipped.first() else {
+            return self.migrated_from.as_ref().map(|old| {
+                format!(
+                    "Moved settings from {} to {}.",
+                    old.display(),
+                    self.path.display()
+                )
+            });
+        };
+        Some(format!(
+            "Could not write to {} ({}); saving to {} at {}.",
+            first_source.label(),
+            first_path.display(),
+            self.source.label(),
+            self.path.display()
+        ))
+    }
+}
+
+/// Resolve the config folder once, preferring `explicit` (from `--config-dir`) over the
+/// environment. Later calls return the first result.
+pub fn init(explicit: Option<PathBuf>) -> &'static ConfigDir {
+    RESOLVED.get_or_init(|| {
+        let exe_dir = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf));
+        let project = project_dirs().map(|proj| proj.config_dir().to_path_buf());
+        let legacy = ProjectDirs::from(QUALIFIER, LEGACY_ORGANIZATION, APPLICATION)
+            .map(|proj| proj.config_dir().to_path_buf());
+        let migrated_from = match (&legacy, &project) {
+            (Some(old), Some(new)) => match migrate_legacy(old, new) {
+                Ok(true) => Some(old.clone()),
+                Ok(false) => None,
+                Err(err) => {
+                    eprintln!("Could not move config from {}: {err}", old.display());
+                    None
+                }
+            },
+            _ => None,
+        };
+        let temp = std::env::temp_dir().join(temp_folder_name(
+            std::env::var_os("USER").or_else(|| std::env::var_os("USERNAME")),
+        ));
+        let candidates = candidates(
+            explicit,
+            std::env::var_os(CONFIG_DIR_ENV),
+            project,
+            exe_dir,
+            temp.clone(),
+        );
+        let mut dir = resolve(candidates, |path| {
+            if path == temp {
+                is_private_writable(path)
+            } else {
+                is_writable(path)
+            }
+        });
+        if dir.source == ConfigSource::Platform {
+            dir.migrated_from = migrated_from;
+        }
+        dir
+    })
+}
+
+/// Platform folders for YTSearch; every path under the user config folder goes through this.
+pub fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from(QUALIFIER, ORGANIZATION, APPLICATION)
+}
+
+/// Move files left in the legacy config folder into `new`. Nothing moves when the paths are
+/// the same (as on Linux, where only the application name is used) or `new` already holds
+/// prefs.json; entries that already exist in `new` are left behind. Returns whether anything
+/// was moved.
+pub fn migrate_legacy(old: &Path, new: &Path) -> io::Result<bool> {
+    if old == new || !old.is_dir() || new.join(PREFS_FILE).exists() {
+        return Ok(false);
+    }
+    fs::create_dir_all(new)?;
+    let mut moved = false;
+    for entry in fs::read_dir(old)? {
+        let entry = entry?;
+        let target = new.join(entry.file_name());
+        if target.exists() {
+            continue;
+        }
+        fs::rename(entry.path(), target)?;
+        moved = true;
+    }
+    // Only succeeds once the folder is empty.
+    let _ = fs::remove_dir(old);
+    Ok(moved)
+}
+
+/// The resolved config folder, resolving from the environment if [`init`] was not called.
+pub fn get() -> &'static ConfigDir {
+    init(None)
+}
+
+/// Build the fallback chain: explicit flag or env var, platform folder, executable folder, temp.
+pub fn candidates(
+    flag: Option<PathBuf>,
+    env: Option<OsString>,
+    project: Option<PathBuf>,
+    exe_dir: Option<PathBuf>,
+    temp: PathBuf,
+) -> Vec<(ConfigSource, PathBuf)> {
+    let explicit = flag.or_else(|| env.filter(|value| !value.is_empty()).map(PathBuf::from));
+    let mut list = Vec::new();
+    if let Some(path) = explicit {
+        list.push((ConfigSource::Explicit, path));
+    }
+    if let Some(path) = project {
+        list.push((ConfigSource::Platform, path));
+    }
+    if let Some(path) = exe_dir {
+        list.push((ConfigSource::Executable, path));
+    }
+    list.push((ConfigSource::Temp, temp));
+    list
+}
+
+/// Pick the first writable candidate. If none is writable, keep the first one for reads and
+/// mark the result as degraded.
+pub fn resolve(
+    candidates: Vec<(ConfigSource, PathBuf)>,
+    writable: impl Fn(&Path) -> bool,
+) -> ConfigDir {
+    let mut skipped = Vec::new();
+    for (source, path) in candidates {
+        if writable(&path) {
+            return ConfigDir {
+                path,
+                source,
+                writable: true,
+                skipped,
+                migrated_from: None,
+            };
+        }
+        skipped.push((source, path));
+    }
+    let (source, path) = skipped
+        .first()
+        .cloned()
+        .unwrap_or((ConfigSource::Temp, std::env::temp_dir()));
+    ConfigDir {
+        path,
+        source,
+        writable: false,
+        skipped,
+        migrated_from: None,
+    }
+}
+
+/// Per-user folder name under the shared temp folder, so users of one machine never share
+/// (or pre-create) each other's prefs.
+pub fn temp_folder_name(user: Option<OsString>) -> String {
+    let user: String = user
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+        .collect();
+    match user.trim_matches('.') {
+        "" => format!("{APPLICATION}-user"),
+        user => format!("{APPLICATION}-{user}"),
+    }
+}
+
+/// [`is_writable`] for a folder in the shared temp folder: on Unix it must also be owned by
+/// the current user and closed to other users, so nobody else can plant a prefs.json there.
+pub fn is_private_writable(dir: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+        if !dir.exists() && fs::DirBuilder::new().mode(0o700).create(dir).is_err() {
+            return false;
+        }
+        let probe = dir.join(".ytsearch-owner-test");
+        if fs::write(&probe, b"ok").is_err() {
+            return false;
+        }
+        // The probe file is ours, so its owner is the current user.
+        let owner = fs::metadata(&probe).map(|meta| meta.uid());
+        let _ = fs::remove_file(&probe);
+        let Ok(meta) = fs::symlink_metadata(dir) else {
+            return false;
+        };
+        meta.is_dir()
+            && owner.is_ok_and(|uid| uid == meta.uid())
+            && meta.permissions().mode() & 0o022 == 0
+    }
+    #[cfg(not(unix))]
+    {
+        is_writable(dir)
+    }
+}
+
+/// Create `dir` if needed and prove a file can be written in it.
+pub fn is_writable(dir: &Path) -> bool {
+    if fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(".ytsearch-write-test");
+    let ok = fs::write(&probe, b"ok").is_ok();
+    let _ = fs::remove_file(&probe);
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(list: &[(ConfigSource, PathBuf)]) -> Vec<ConfigSource> {
+        list.iter().map(|(source, _)| *source).collect()
+    }
+
+    #[test]
+    fn flag_beats_env_and_leads_the_chain() {
+        let list = candidates(
+            Some("/flag".into()),
+            Some("/env".into()),
+            Some("/xdg".into()),
+            Some("/exe".into()),
+            "/tmp/YTSearch".into(),
+        );
+        assert_eq!(list[0], (ConfigSource::Explicit, PathBuf::from("/flag")));
+        assert_eq!(
+            sources(&list),
+            vec![
+                ConfigSource::Explicit,
+                ConfigSource::Platform,
+                ConfigSource::Executable,
+                ConfigSource::Temp
+            ]
+        );
+    }
+
+    #[test]
+    fn env_is_used_without_a_flag_and_ignored_when_empty() {
+        let list = candidates(None, Some("/env".into()), None, None, "/tmp/Y".into());
+        assert_eq!(list[0], (ConfigSource::Explicit, PathBuf::from("/env")));
+
+        let list = candidates(
+            None,
+            Some("".into()),
+            Some("/xdg".into()),
+            None,
+            "/tmp/Y".into(),
+        );
+        assert_eq!(
+            sources(&list),
+            vec![ConfigSource::Platform, ConfigSource::Temp]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_first_writable_candidate() {
+        let list = candidates(
+            None,
+            None,
+            Some("/xdg".into()),
+            Some("/exe".into()),
+            "/tmp/Y".into(),
+        );
+        let dir = resolve(list, |path| path != Path::new("/xdg"));
+        assert_eq!(dir.path, PathBuf::from("/exe"));
+        assert_eq!(dir.source, ConfigSource::Executable);
+        assert!(dir.writable);
+        assert!(dir.is_fallback());
+        assert!(dir.status_note().unwrap().contains("/exe"));
+    }
+
+    #[test]
+    fn first_writable_candidate_is_not_a_fallback() {
+        let list = candidates(None, None, Some("/xdg".into()), None, "/tmp/Y".into());
+        let dir = resolve(list, |_| true);
+        assert_eq!(dir.source, ConfigSource::Platform);
+        assert!(!dir.is_fallback());
+        assert_eq!(dir.status_note(), None);
+    }
+
+    #[test]
+    fn nothing_writable_is_degraded() {
+        let list = candidates(
+            Some("/flag".into()),
+            None,
+            Some("/xdg".into()),
+            None,
+            "/tmp/Y".into(),
+        );
+        let dir = resolve(list, |_| false);
+        assert!(!dir.writable);
+        assert_eq!(dir.path, PathBuf::from("/flag"));
+        assert_eq!(dir.skipped.len(), 3);
+        assert!(dir.status_note().unwrap().contains("nothing will be saved"));
+    }
+
+    #[test]
+    fn temp_folder_is_per_user() {
+        assert_eq!(temp_folder_name(Some("alice".into())), "YTSearch-alice");
+        assert_eq!(temp_folder_name(Some("../bob".into())), "YTSearch-bob");
+        assert_eq!(temp_folder_name(None), "YTSearch-user");
+        assert_eq!(temp_folder_name(Some("..".into())), "YTSearch-user");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shared_temp_folders_open_to_others_are_refused() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("ytsearch-private-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        assert!(is_private_writable(&dir));
+        assert_eq!(
+            fs::metadata(&dir).unwrap().permissions().mode() & 0o777,
+            0o700
+        );
+
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(!is_private_writable(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn every_data_file_lives_under_the_resolved_folder() {
+        let list = candidates(None, None, Some("/xdg".into()), None, "/tmp/Y".into());
+        let dir = resolve(list, |_| true);
+        for path in [
+            dir.prefs_path(),
+            dir.results_cache_path(),
+            dir.dismissals_path(),
+            dir.annotations_path(),
+            dir.ui_state_path(),
+            dir.channels_path(),
+            dir.preset_stats_path(),
+            dir.find_index_path(),
+            dir.thumbnails_dir(),
+            dir.backups_dir(),
+        ] {
+            assert_eq!(path.parent(), Some(Path::new("/xdg")));
+        }
+    }
+
+    #[test]
+    fn legacy_folder_moves_into_an_empty_new_folder() {
+        let base = std::env::temp_dir().join(format!("ytsearch-migrate-{}", std::process::id()));
+        let old = base.join("yourname");
+        let new = base.join("DaQue");
+        fs::create_dir_all(old.join("thumbnails")).unwrap();
+        fs::write(old.join("prefs.json"), b"{}").unwrap();
+        fs::write(old.join("thumbnails").join("a.jpg"), b"jpg").unwrap();
+
+        assert!(migrate_legacy(&old, &new).unwrap());
+        assert!(new.join("prefs.json").exists());
+        assert!(new.join("thumbnails").join("a.jpg").exists());
+        assert!(!old.exists());
+
+        fs::create_dir_all(&old).unwrap();
+        fs::write(old.join("prefs.json"), b"{\"stale\": true}").unwrap();
+        assert!(!migrate_legacy(&old, &new).unwrap());
+        assert_eq!(fs::read(new.join("prefs.json")).unwrap(), b"{}");
+        assert!(!migrate_legacy(&new, &new).unwrap());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,401 @@
+//! Health checks behind the Diagnostics window: files, folders, the API key, the network,
+//! the clock, and presets. Each check returns a [`CheckResult`]; [`run_all`] streams them.
+
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::config_dir;
+use crate::prefs::{MySearch, Prefs, SearchDefaults};
+use crate::quota;
+use crate::search_runner;
+use crate::yt::{http, videos};
+
+/// Thumbnail caches past this size get a warning.
+pub const THUMBNAIL_CACHE_WARN_BYTES: u64 = 500 * 1024 * 1024;
+const NETWORK_CHECK_URL: &str = "https://www.googleapis.com/";
+const NETWORK_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// What the window can offer to fix a failing check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckFix {
+    OpenSettings,
+    ClearThumbnails,
+    TestApiKey,
+}
+
+impl CheckFix {
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckFix::OpenSettings => "Open settings",
+            CheckFix::ClearThumbnails => "Clear thumbnails",
+            CheckFix::TestApiKey => "Test key again",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub details: String,
+    pub fix: Option<CheckFix>,
+    /// API units the check spent.
+    pub quota_units: u32,
+}
+
+impl CheckResult {
+    fn new(name: &'static str, status: CheckStatus, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            details: details.into(),
+            fix: None,
+            quota_units: 0,
+        }
+    }
+
+    fn with_fix(mut self, fix: CheckFix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+pub const PREFS_FILE_CHECK: &str = "Prefs file";
+pub const CONFIG_DIR_CHECK: &str = "Config and cache folder";
+pub const API_KEY_CHECK: &str = "API key";
+pub const NETWORK_CHECK: &str = "Network";
+pub const CLOCK_CHECK: &str = "System clock";
+pub const THUMBNAILS_CHECK: &str = "Thumbnail cache";
+pub const PRESETS_CHECK: &str = "Presets";
+
+/// prefs.json parses and can be written; a missing file is fine until the first save.
+pub fn check_prefs_file(path: &Path) -> CheckResult {
+    let shown = path.display();
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return CheckResult::new(
+                PREFS_FILE_CHECK,
+                CheckStatus::Warn,
+                format!("{shown} does not exist yet; it is created on the first save."),
+            );
+        }
+        Err(err) => {
+            return CheckResult::new(
+                PREFS_FILE_CHECK,
+                CheckStatus::Fail,
+                format!("Cannot read {shown}: {err}"),
+            );
+        }
+    };
+    if let Err(err) = serde_json::from_str::<Prefs>(&text) {
+        return CheckResult::new(
+            PREFS_FILE_CHECK,
+            CheckStatus::Fail,
+            format!("{shown} is not valid prefs JSON: {err}"),
+        );
+    }
+    match fs::OpenOptions::new().append(true).open(path) {
+        Ok(_) => CheckResult::new(
+            PREFS_FILE_CHECK,
+            CheckStatus::Pass,
+            format!("{shown} reads and is writable."),
+        ),
+        Err(err) => CheckResult::new(
+            PREFS_FILE_CHECK,
+            CheckStatus::Fail,
+            format!("{shown} is read-only: {err}"),
+        ),
+    }
+}
+
+/// The folder holding prefs, the results cache, and thumbnails accepts new files.
+pub fn check_config_dir(dir: &Path) -> CheckResult {
+    if config_dir::is_writable(dir) {
+        CheckResult::new(
+            CONFIG_DIR_CHECK,
+            CheckStatus::Pass,
+            format!("{} is writable.", dir.display()),
+        )
+    } else {
+        CheckResult::new(
+            CONFIG_DIR_CHECK,
+            CheckStatus::Fail,
+            format!(
+                "{} is not writable; nothing will be saved. Start with --config-dir to pick \
+                 another folder.",
+                dir.display()
+            ),
+        )
+    }
+}
+
+/// Whether a key is set at all; [`check_api_key`] asks YouTube whether it works.
+pub fn check_api_key_present(api_key: &str) -> Option<CheckResult> {
+    api_key.trim().is_empty().then(|| {
+        CheckResult::new(
+            API_KEY_CHECK,
+            CheckStatus::Fail,
+            "No API key set. Paste one into Settings → General.",
+        )
+        .with_fix(CheckFix::OpenSettings)
+    })
+}
+
+/// One `videos.list` call with the key, costing a single unit.
+pub async fn check_api_key(api_key: &str) -> CheckResult {
+    if let Some(missing) = check_api_key_present(api_key) {
+        return missing;
+    }
+    let mut result = match videos::check_key(api_key).await {
+        Ok(()) => CheckResult::new(
+            API_KEY_CHECK,
+            CheckStatus::Pass,
+            "YouTube accepted the key.",
+        ),
+        Err(err) => CheckResult::new(API_KEY_CHECK, CheckStatus::Fail, err.to_string())
+            .with_fix(CheckFix::TestApiKey),
+    };
+    result.quota_units = quota::LIST_CALL_COST;
+    result
+}
+
+/// Any HTTP response from googleapis.com counts; only a failed connection fails.
+pub async fn check_network() -> CheckResult {
+    let response = http::client()
+        .head(NETWORK_CHECK_URL)
+        .timeout(std::time::Duration::from_secs(NETWORK_TIMEOUT_SECS))
+        .send()
+        .await;
+    let via = http::proxy_label()
+        .map(|proxy| format!(" via proxy {proxy}"))
+        .unwrap_or_default();
+    match response {
+        Ok(resp) => CheckResult::new(
+            NETWORK_CHECK,
+            CheckStatus::Pass,
+            format!(
+                "Reached googleapis.com{via} (HTTP {}).",
+                resp.status().as_u16()
+            ),
+        ),
+        Err(err) => CheckResult::new(
+            NETWORK_CHECK,
+            CheckStatus::Fail,
+            format!(
+                "Could not reach googleapis.com{via}: {}",
+                http::send_error(err)
+            ),
+        )
+        .with_fix(CheckFix::OpenSettings),
+    }
+}
+
+/// Search windows and the quota day come from the clock; a year far off means they are wrong.
+pub fn check_clock(now: OffsetDateTime) -> CheckResult {
+    let formatted = match now.format(&Rfc3339) {
+        Ok(text) => text,
+        Err(err) => {
+            return CheckResult::new(
+                CLOCK_CHECK,
+                CheckStatus::Fail,
+                format!("Cannot format the current time as RFC 3339: {err}"),
+            );
+        }
+    };
+    if (2024..=2100).contains(&now.year()) {
+        CheckResult::new(
+            CLOCK_CHECK,
+            CheckStatus::Pass,
+            format!("Now is {formatted}."),
+        )
+    } else {
+        CheckResult::new(
+            CLOCK_CHECK,
+            CheckStatus::Warn,
+            format!("The clock reads {formatted}; search windows will be off."),
+        )
+    }
+}
+
+/// Total size of the cached thumbnail files.
+pub fn check_thumbnail_cache(dir: &Path) -> CheckResult {
+    let (files, bytes) = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| entry.metadata().ok())
+                .filter(|meta| meta.is_file())
+                .fold((0usize, 0u64), |(files, bytes), meta| {
+                    (files + 1, bytes + meta.len())
+                })
+        })
+        .unwrap_or((0, 0));
+    let details = format!("{files} files, {}.", format_bytes(bytes));
+    if bytes > THUMBNAIL_CACHE_WARN_BYTES {
+        CheckResult::new(THUMBNAILS_CHECK, CheckStatus::Warn, details)
+            .with_fix(CheckFix::ClearThumbnails)
+    } else {
+        CheckResult::new(THUMBNAILS_CHECK, CheckStatus::Pass, details)
+    }
+}
+
+/// Presets whose query cannot be built, e.g. with no terms at all.
+pub fn check_presets(defaults: &SearchDefaults, searches: &[MySearch]) -> CheckResult {
+    let failing: Vec<String> = searches
+        .iter()
+        .filter_map(|search| {
+            search_runner::build_query_params(defaults, search)
+                .err()
+                .map(|err| format!("{}: {err}", search.name))
+        })
+        .collect();
+    if failing.is_empty() {
+        CheckResult::new(
+            PRESETS_CHECK,
+            CheckStatus::Pass,
+            format!("All {} presets build a valid query.", searches.len()),
+        )
+    } else {
+        CheckResult::new(
+            PRESETS_CHECK,
+            CheckStatus::Warn,
+            format!(
+                "{} of {} presets fail validation. {}",
+                failing.len(),
+                searches.len(),
+                failing.join("; ")
+            ),
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / MIB)
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
+}
+
+/// Run every check, sending each result as soon as it is known: the local checks first,
+/// then the network and the key. Stops early if the receiver is gone.
+pub async fn run_all(
+    api_key: String,
+    defaults: SearchDefaults,
+    searches: Vec<MySearch>,
+    tx: Sender<CheckResult>,
+) {
+    let dir = config_dir::get();
+    let local = [
+        check_prefs_file(&dir.prefs_path()),
+        check_config_dir(&dir.path),
+        check_clock(OffsetDateTime::now_utc()),
+        check_thumbnail_cache(&dir.thumbnails_dir()),
+        check_presets(&defaults, &searches),
+    ];
+    for result in local {
+        if tx.send(result).is_err() {
+            return;
+        }
+    }
+    if tx.send(check_network().await).is_err() {
+        return;
+    }
+    let _ = tx.send(check_api_key(&api_key).await);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("yts-diag-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn prefs_file_checks_existence_and_json() {
+        let dir = temp_dir("prefs");
+        let path = dir.join("prefs.json");
+        assert_eq!(check_prefs_file(&path).status, CheckStatus::Warn);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(check_prefs_file(&path).status, CheckStatus::Fail);
+
+        fs::write(&path, serde_json::to_string(&Prefs::default()).unwrap()).unwrap();
+        assert_eq!(check_prefs_file(&path).status, CheckStatus::Pass);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn clock_warns_far_from_now() {
+        let now = OffsetDateTime::from_unix_timestamp(1_760_000_000).unwrap();
+        assert_eq!(check_clock(now).status, CheckStatus::Pass);
+        assert_eq!(
+            check_clock(OffsetDateTime::UNIX_EPOCH).status,
+            CheckStatus::Warn
+        );
+    }
+
+    #[test]
+    fn thumbnail_cache_sums_file_sizes() {
+        let dir = temp_dir("thumbs");
+        fs::write(dir.join("a.png"), vec![0u8; 2048]).unwrap();
+        fs::write(dir.join("a.url"), b"https://i.ytimg.com/a").unwrap();
+        let result = check_thumbnail_cache(&dir);
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert_eq!(result.details, "2 files, 3 KB.");
+        assert_eq!(
+            check_thumbnail_cache(&dir.join("missing")).details,
+            "0 files, 0 KB."
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn presets_without_terms_fail_validation() {
+        let mut good = MySearch {
+            name: "Rust".into(),
+            ..MySearch::default()
+        };
+        good.query.any_terms = vec!["rust".into()];
+        let empty = MySearch {
+            name: "Empty".into(),
+            ..MySearch::default()
+        };
+        let defaults = SearchDefaults::default();
+        assert_eq!(
+            check_presets(&defaults, std::slice::from_ref(&good)).status,
+            CheckStatus::Pass
+        );
+        let result = check_presets(&defaults, &[good, empty]);
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(
+            result
+                .details
+                .starts_with("1 of 2 presets fail validation. Empty: ")
+        );
+    }
+
+    #[test]
+    fn missing_key_points_at_settings() {
+        let result = check_api_key_present("  ").unwrap();
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert_eq!(result.fix, Some(CheckFix::OpenSettings));
+        assert!(check_api_key_present("AIza").is_none());
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::{BTreeMap, HashSet};
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config_dir;
+
+/// Dismissals from one channel before blocking it is suggested.
+pub const DEFAULT_BLOCK_SUGGESTION_THRESHOLD: u32 = 5;
+
+/// Videos the user waved away, grouped by channel, persisted as dismissals.json.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Dismissals {
+    /// Keyed by the lowercased channel ID (or title when the ID is unknown).
+    pub channels: BTreeMap<String, ChannelDismissals>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct ChannelDismissals {
+    pub channel_id: String,
+    pub channel_title: String,
+    /// Dismissed video IDs; a video only counts once.
+    pub videos: Vec<String>,
+    /// Dismissal count when "Not now" was chosen; the suggestion returns after another
+    /// threshold's worth.
+    pub snoozed_at: usize,
+    /// "Never ask" was chosen for this channel.
+    pub never_ask: bool,
+}
+
+/// A channel the banner proposes to block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockSuggestion {
+    pub key: String,
+    pub channel_id: String,
+    pub channel_title: String,
+    pub count: usize,
+}
+
+pub fn channel_key(channel_id: &str, channel_title: &str) -> String {
+    let source = if channel_id.trim().is_empty() {
+        channel_title.trim()
+    } else {
+        channel_id.trim()
+    };
+    source.to_ascii_lowercase()
+}
+
+impl Dismissals {
+    /// Count a dismissed video. Returns false when it was already counted.
+    pub fn record(&mut self, video_id: &str, channel_id: &str, channel_title: &str) -> bool {
+        let key = channel_key(channel_id, channel_title);
+        if key.is_empty() {
+            return false;
+        }
+        let entry = self.channels.entry(key).or_default();
+        entry.channel_id = channel_id.trim().to_owned();
+        entry.channel_title = channel_title.trim().to_owned();
+        if entry.videos.iter().any(|id| id == video_id) {
+            return false;
+        }
+        entry.videos.push(video_id.to_owned());
+        true
+    }
+
+    pub fn snooze(&mut self, key: &str) {
+        if let Some(entry) = self.channels.get_mut(key) {
+            entry.snoozed_at = entry.videos.len();
+        }
+    }
+
+    pub fn never_ask(&mut self, key: &str) {
+        if let Some(entry) = self.channels.get_mut(key) {
+            entry.never_ask = true;
+        }
+    }
+
+    /// Every dismissed video ID, for keeping them out of the results.
+    pub fn video_ids(&self) -> HashSet<&str> {
+        self.channels
+            .values()
+            .flat_map(|entry| entry.videos.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+/// The channel to suggest blocking: the most-dismissed one with at least `threshold`
+/// dismissals since it was last snoozed, skipping "Never ask" and already blocked channels.
+/// A threshold of 0 turns suggestions off.
+pub fn block_suggestion(
+    dismissals: &Dismissals,
+    threshold: u32,
+    is_blocked: impl Fn(&str, &str) -> bool,
+) -> Option<BlockSuggestion> {
+    if threshold == 0 {
+        return None;
+    }
+    let threshold = threshold as usize;
+    dismissals
+        .channels
+        .iter()
+        .filter(|(_, entry)| !entry.never_ask)
+        .filter(|(_, entry)| entry.videos.len() >= entry.snoozed_at + threshold)
+        .filter(|(_, entry)| !is_blocked(&entry.channel_id, &entry.channel_title))
+        .max_by_key(|(_, entry)| entry.videos.len())
+        .map(|(key, entry)| BlockSuggestion {
+            key: key.clone(),
+            channel_id: entry.channel_id.clone(),
+            channel_title: entry.channel_title.clone(),
+            count: entry.videos.len(),
+        })
+}
+
+fn dismissals_path() -> PathBuf {
+    config_dir::get().dismissals_path()
+}
+
+pub fn load() -> Dismissals {
+    fs::read(dismissals_path())
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(dismissals: &Dismissals) -> std::io::Result<()> {
+    if !config_dir::get().writable {
+        return Ok(());
+    }
+    let path = dismissals_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(dismissals)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dismiss(dismissals: &mut Dismissals, channel: &str, count: usize) {
+        for n in 0..count {
+            dismissals.record(&format!("{channel}-{n}"), channel, channel);
+        }
+    }
+
+    fn never_blocked(_: &str, _: &str) -> bool {
+        false
+    }
+
+    #[test]
+    fn suggests_after_threshold_and_counts_videos_once() {
+        let mut dismissals = Dismissals::default();
+        dismiss(&mut dismissals, "UCspam", 4);
+        assert!(!dismissals.record("UCspam-0", "UCspam", "Spam"));
+        assert_eq!(block_suggestion(&dismissals, 5, never_blocked), None);
+
+        dismiss(&mut dismissals, "UCspam", 5);
+        let suggestion = block_suggestion(&dismissals, 5, never_blocked).unwrap();
+        assert_eq!(suggestion.key, "ucspam");
+        assert_eq!(suggestion.count, 5);
+    }
+
+    #[test]
+    fn picks_the_most_dismissed_channel() {
+        let mut dismissals = Dismissals::default();
+        dismiss(&mut dismissals, "UCa", 5);
+        dismiss(&mut dismissals, "UCb", 7);
+        let suggestion = block_suggestion(&dismissals, 5, never_blocked).unwrap();
+        assert_eq!(suggestion.channel_id, "UCb");
+    }
+
+    #[test]
+    fn not_now_waits_for_another_round_and_never_ask_sticks() {
+        let mut dismissals = Dismissals::default();
+        dismiss(&mut dismissals, "UCa", 5);
+        dismissals.snooze("uca");
+        assert_eq!(block_suggestion(&dismissals, 5, never_blocked), None);
+        dismiss(&mut dismissals, "UCa", 10);
+        assert!(block_suggestion(&dismissals, 5, never_blocked).is_some());
+
+        dismissals.never_ask("uca");
+        dismiss(&mut dismissals, "UCa", 30);
+        assert_eq!(block_suggestion(&dismissals, 5, never_blocked), None);
+    }
+
+    #[test]
+    fn blocked_channels_and_zero_threshold_are_skipped() {
+        let mut dismissals = Dismissals::default();
+        dismiss(&mut dismissals, "UCa", 6);
+        assert_eq!(block_suggestion(&dismissals, 5, |id, _| id == "UCa"), None);
+        assert_eq!(block_suggestion(&dismissals, 0, never_blocked), None);
+    }
+}
@@ -1,7 +1,9 @@
-use crate::prefs::{GlobalPrefs, MySearch};
+use std::collections::HashSet;
+
+use crate::prefs::{BlockEntry, ChannelRef, MySearch, QuerySpec, SearchDefaults};
 use crate::yt::types::VideoDetails;
 
-pub fn duration_allows(duration_secs: u64, prefs: &GlobalPrefs) -> bool {
+pub fn duration_allows(duration_secs: u64, prefs: &SearchDefaults) -> bool {
     let config = &prefs.duration_filters;
     let mut active_found = false;
     for id in &prefs.active_duration_bucket_ids {
@@ -67,20 +69,62 @@ pub fn contains_any(hay: &str, needles: &[String]) -> bool {
         .any(|needle| h.contains(&needle.to_ascii_lowercase()))
 }
 
+/// Why a video did or did not survive [`post_filter_verdict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PostFilterVerdict {
+    Keep,
+    Drop,
+    /// Passed every other filter but the preset requires its terms in the title.
+    TitleMismatch,
+    /// Passed every other filter but lacks one of the preset's all-terms while
+    /// `SearchDefaults::strict_all_terms` is on.
+    MissingAllTerms,
+}
+
 pub fn matches_post_filters(
     video: &VideoDetails,
-    prefs: &GlobalPrefs,
+    prefs: &SearchDefaults,
     search: &MySearch,
-    blocked_channels: &[String],
+    blocked: &BlockedSet,
 ) -> bool {
-    let min_secs = search
-        .min_duration_override
-        .unwrap_or(prefs.min_duration_secs) as u64;
-    if video.duration_secs < min_secs {
-        return false;
+    post_filter_verdict(video, prefs, search, blocked) == PostFilterVerdict::Keep
+}
+
+pub fn post_filter_verdict(
+    video: &VideoDetails,
+    prefs: &SearchDefaults,
+    search: &MySearch,
+    blocked: &BlockedSet,
+) -> PostFilterVerdict {
+    if !passes_base_filters(video, prefs, search, blocked) {
+        return PostFilterVerdict::Drop;
     }
+    if search.require_terms_in_title {
+        let has_terms = if search.match_tags {
+            title_or_tags_have_terms(&video.title_lower, &video.tags, &search.query)
+        } else {
+            title_has_terms(&video.title_lower, &search.query)
+        };
+        if !has_terms {
+            return PostFilterVerdict::TitleMismatch;
+        }
+    } else if prefs.strict_all_terms {
+        let tags: &[String] = if search.match_tags { &video.tags } else { &[] };
+        if !has_all_terms(&video.title_lower, tags, &search.query) {
+            return PostFilterVerdict::MissingAllTerms;
+        }
+    }
+    PostFilterVerdict::Keep
+}
 
-    if !duration_allows(video.duration_secs, prefs) {
+fn passes_base_filters(
+    video: &VideoDetails,
+    prefs: &SearchDefaults,
+    search: &MySearch,
+    blocked: &BlockedSet,
+) -> bool {
+    if !duration_policy_allows(video, prefs, search) {
         return false;
     }
 
@@ -95,32 +139,52 @@ pub fn matches_post_filters(
         }
     }
 
-    if contains_any(&video.title_lower, &search.query.not_terms) {
+    if prefs.require_captions_strict && video.caption != Some(true) {
         return false;
     }
 
-    if matches_channel(
-        &video.channel_handle,
-        &video.channel_title,
-        blocked_channels,
-    ) {
+    if prefs.only_licensed && video.licensed_content != Some(true) {
         return false;
     }
 
-    if !search.query.channel_deny.is_empty()
-        && matches_channel(
-            &video.channel_handle,
-            &video.channel_title,
-            &search.query.channel_deny,
-        )
+    if prefs.exclude_3d && video.is_3d() {
+        return false;
+    }
+
+    if prefs.hide_restricted && video.is_restricted() {
+        return false;
+    }
+
+    if contains_any(&video.title_lower, &search.query.not_terms) {
+        return false;
+    }
+    if search.match_tags
+        && video
+            .tags
+            .iter()
+            .any(|tag| contains_any(tag, &search.query.not_terms))
     {
         return false;
     }
 
+    if is_blocked(video, blocked) {
+        return false;
+    }
+
+    if matches_channel_ref_lower(
+        &video.channel_handle,
+        &video.channel_handle_lower,
+        &video.channel_title_lower,
+        &search.query.channel_deny,
+    ) {
+        return false;
+    }
+
     if !search.query.channel_allow.is_empty()
-        && !matches_channel(
+        && !matches_channel_ref_lower(
             &video.channel_handle,
-            &video.channel_title,
+            &video.channel_handle_lower,
+            &video.channel_title_lower,
             &search.query.channel_allow,
         )
     {
@@ -130,28 +194,228 @@ pub fn matches_post_filters(
     true
 }
 
+/// Apply min-duration and bucket checks only when the video has a real length.
+///
+/// Live and upcoming broadcasts report no duration yet and are always kept; videos whose
+/// duration failed to parse are kept only when `include_unknown_duration` is on.
+pub fn duration_policy_allows(
+    video: &VideoDetails,
+    prefs: &SearchDefaults,
+    search: &MySearch,
+) -> bool {
+    if video.is_live_or_upcoming() {
+        return true;
+    }
+    if video.duration_unknown {
+        return prefs.include_unknown_duration;
+    }
+
+    let min_secs = search
+        .min_duration_override
+        .unwrap_or(prefs.min_duration_secs) as u64;
+    if video.duration_secs < min_secs {
+        return false;
+    }
+
+    duration_allows(video.duration_secs, prefs)
+}
+
 fn language_is_english(code: Option<&str>) -> bool {
     code.map(|c| c.to_ascii_lowercase())
         .map(|lower| lower.starts_with("en"))
         .unwrap_or(false)
 }
 
-pub fn matches_channel(handle: &str, title: &str, patterns: &[String]) -> bool {
-    if patterns.is_empty() {
-        return false;
+/// The block list compiled once for matching: lowercased channel IDs, `@handles` (without
+/// the `@`), and channel titles, all in one key set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockedSet {
+    keys: HashSet<String>,
+}
+
+impl BlockedSet {
+    /// Compile stored block entries (`key|label` or `key|label|@handle`).
+    pub fn new(entries: &[String]) -> Self {
+        let mut keys = HashSet::new();
+        for entry in entries {
+            let parsed = BlockEntry::parse(entry);
+            if !parsed.key.is_empty() {
+                keys.insert(parsed.key);
+            }
+            if let Some(handle) = parsed.handle {
+                keys.insert(handle_key(&handle));
+            }
+        }
+        Self { keys }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Whether a channel is blocked by its ID, its `@handle`, or its exact title.
+    pub fn matches(&self, channel_id: &str, title: &str, handle: Option<&str>) -> bool {
+        !self.keys.is_empty()
+            && (self.keys.contains(&channel_id.trim().to_ascii_lowercase())
+                || self.keys.contains(&handle_key(channel_id))
+                || self
+                    .keys
+                    .contains(title.trim().to_ascii_lowercase().as_str())
+                || handle.is_some_and(|handle| self.keys.contains(&handle_key(handle))))
     }
+}
+
+/// A [`BlockedSet`] kept in step with the block list: every edit goes through
+/// [`BlockedCache::edit`], which recompiles only when the list actually changed.
+#[derive(Clone, Debug, Default)]
+pub struct BlockedCache {
+    set: BlockedSet,
+    rebuilds: usize,
+}
+
+impl BlockedCache {
+    pub fn new(entries: &[String]) -> Self {
+        Self {
+            set: BlockedSet::new(entries),
+            rebuilds: 0,
+        }
+    }
+
+    pub fn set(&self) -> &BlockedSet {
+        &self.set
+    }
+
+    /// How many times an edit recompiled the set since `new`.
+    pub fn rebuilds(&self) -> usize {
+        self.rebuilds
+    }
+
+    /// Apply `change` to the block list, recompiling the set if the list changed.
+    pub fn edit<R>(
+        &mut self,
+        entries: &mut Vec<String>,
+        change: impl FnOnce(&mut Vec<String>) -> R,
+    ) -> R {
+        let before = entries.clone();
+        let result = change(entries);
+        if *entries != before {
+            self.set = BlockedSet::new(entries);
+            self.rebuilds += 1;
+        }
+        result
+    }
+}
+
+fn handle_key(handle: &str) -> String {
+    handle.trim().trim_start_matches('@').to_ascii_lowercase()
+}
+
+/// Whether a video's channel is on the block list. Every path that hides blocked channels
+/// (search post-filters, arriving results, the block button, the cache on startup) uses this.
+pub fn is_blocked(video: &VideoDetails, blocked: &BlockedSet) -> bool {
+    blocked.matches(
+        &video.channel_handle,
+        &video.channel_title,
+        video.channel_custom_url.as_deref(),
+    )
+}
+
+/// Convenience wrapper over [`matches_channel_ref_lower`].
+pub fn matches_channel_ref(channel_id: &str, title: &str, refs: &[ChannelRef]) -> bool {
+    !refs.is_empty()
+        && matches_channel_ref_lower(
+            channel_id,
+            &channel_id.to_ascii_lowercase(),
+            &title.to_ascii_lowercase(),
+            refs,
+        )
+}
+
+/// Preset allow/deny matching: resolved channel IDs compare exactly against the video's
+/// channel ID, and unresolved entries fall back to the lowercased handle/title text match.
+pub fn matches_channel_ref_lower(
+    channel_id: &str,
+    handle_lower: &str,
+    title_lower: &str,
+    refs: &[ChannelRef],
+) -> bool {
+    refs.iter().any(|channel| match channel.id.as_deref() {
+        Some(id) => id == channel_id,
+        None => channel
+            .handle
+            .as_deref()
+            .is_some_and(|pattern| pattern_matches(handle_lower, title_lower, pattern)),
+    })
+}
 
-    let handle = handle.to_ascii_lowercase();
-    let title = title.to_ascii_lowercase();
+/// Whether a title contains at least one any-term and every all-term. Terms follow the query
+/// builder's token rules: a multi-word or quoted term must appear as a phrase, a single word
+/// must appear as a whole word.
+pub fn title_has_terms(title: &str, spec: &QuerySpec) -> bool {
+    terms_found_in(&[words(title)], spec)
+}
+
+/// Like [`title_has_terms`], but each term may also be found within a single tag.
+pub fn title_or_tags_have_terms(title: &str, tags: &[String], spec: &QuerySpec) -> bool {
+    let mut haystacks = vec![words(title)];
+    haystacks.extend(tags.iter().map(|tag| words(tag)));
+    terms_found_in(&haystacks, spec)
+}
+
+/// Whether every all-term appears in the title or within one of `tags`, ignoring any-terms.
+/// Backs `SearchDefaults::strict_all_terms`.
+pub fn has_all_terms(title: &str, tags: &[String], spec: &QuerySpec) -> bool {
+    let mut haystacks = vec![words(title)];
+    haystacks.extend(tags.iter().map(|tag| words(tag)));
+    all_terms_found_in(&haystacks, spec)
+}
+
+fn phrase_found_in(haystacks: &[Vec<String>], phrase: &[String]) -> bool {
+    haystacks
+        .iter()
+        .any(|haystack| contains_phrase(haystack, phrase))
+}
+
+fn all_terms_found_in(haystacks: &[Vec<String>], spec: &QuerySpec) -> bool {
+    spec.all_terms
+        .iter()
+        .map(|term| words(term))
+        .filter(|phrase| !phrase.is_empty())
+        .all(|phrase| phrase_found_in(haystacks, &phrase))
+}
 
-    patterns
+fn terms_found_in(haystacks: &[Vec<String>], spec: &QuerySpec) -> bool {
+    let mut any_terms = spec
+        .any_terms
         .iter()
-        .map(|p| p.trim())
-        .filter(|p| !p.is_empty())
-        .any(|pattern| {
-            let cleaned = pattern.trim_start_matches('@').to_ascii_lowercase();
-            handle == cleaned || title == cleaned || title.contains(&cleaned)
-        })
+        .filter(|t| !words(t).is_empty())
+        .peekable();
+    let any_ok = any_terms.peek().is_none()
+        || any_terms.any(|term| phrase_found_in(haystacks, &words(term)));
+    any_ok && all_terms_found_in(haystacks, spec)
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+fn contains_phrase(haystack: &[String], phrase: &[String]) -> bool {
+    !phrase.is_empty()
+        && haystack
+            .windows(phrase.len())
+            .any(|window| window == phrase)
+}
+
+fn pattern_matches(handle_lower: &str, title_lower: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return false;
+    }
+    let cleaned = pattern.trim_start_matches('@').to_ascii_lowercase();
+    handle_lower == cleaned || title_lower == cleaned || title_lower.contains(&cleaned)
 }
 
 fn looks_english(text: &str) -> bool {
@@ -178,3 +442,544 @@ fn looks_english(text: &str) -> bool {
     }
     asciiish * 100 / total >= 60
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(duration_secs: u64) -> VideoDetails {
+        VideoDetails {
+            id: "abc".into(),
+            title: "Rust news".into(),
+            title_lower: "rust news".into(),
+            duration_secs,
+            default_audio_lang: Some("en".into()),
+            ..VideoDetails::default()
+        }
+    }
+
+    #[test]
+    fn short_known_duration_is_dropped() {
+        let prefs = SearchDefaults::default();
+        let search = MySearch::default();
+        assert!(!matches_post_filters(
+            &video(10),
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        assert!(matches_post_filters(
+            &video(600),
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+    }
+
+    #[test]
+    fn live_and_upcoming_skip_duration_checks() {
+        let prefs = SearchDefaults::default();
+        let search = MySearch::default();
+        for state in ["live", "upcoming"] {
+            let mut live = video(0);
+            live.live_broadcast_content = Some(state.into());
+            live.duration_unknown = true;
+            assert!(matches_post_filters(
+                &live,
+                &prefs,
+                &search,
+                &BlockedSet::default()
+            ));
+        }
+    }
+
+    #[test]
+    fn unknown_duration_is_gated_by_pref() {
+        let mut prefs = SearchDefaults::default();
+        let search = MySearch::default();
+        let mut unknown = video(0);
+        unknown.duration_unknown = true;
+        assert!(!matches_post_filters(
+            &unknown,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+
+        prefs.include_unknown_duration = true;
+        assert!(matches_post_filters(
+            &unknown,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+    }
+
+    #[test]
+    fn strict_captions_keep_only_videos_reported_with_captions() {
+        let mut prefs = SearchDefaults::default();
+        let search = MySearch::default();
+        let mut captioned = video(600);
+        captioned.caption = Some(true);
+        let mut uncaptioned = video(600);
+        uncaptioned.caption = Some(false);
+        let unknown = video(600);
+        assert!(matches_post_filters(
+            &uncaptioned,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+
+        prefs.require_captions_strict = true;
+        assert!(matches_post_filters(
+            &captioned,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        assert!(!matches_post_filters(
+            &uncaptioned,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        assert!(!matches_post_filters(
+            &unknown,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+    }
+
+    #[test]
+    fn only_licensed_drops_unlicensed_and_unknown_videos() {
+        let mut prefs = SearchDefaults::default();
+        let search = MySearch::default();
+        let mut licensed = video(600);
+        licensed.licensed_content = Some(true);
+        let mut reupload = video(600);
+        reupload.licensed_content = Some(false);
+        assert!(matches_post_filters(
+            &reupload,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+
+        prefs.only_licensed = true;
+        assert!(matches_post_filters(
+            &licensed,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        assert!(!matches_post_filters(
+            &reupload,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        assert!(!matches_post_filters(
+            &video(600),
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+    }
+
+    #[test]
+    fn hide_restricted_drops_age_and_region_restricted_videos() {
+        let mut prefs = SearchDefaults::default();
+        let search = MySearch::default();
+        let mut adult = video(600);
+        adult.age_restricted = true;
+        let mut blocked = video(600);
+        blocked.blocked_in_region = Some(true);
+        let mut available = video(600);
+        available.blocked_in_region = Some(false);
+        let keeps = |video: &VideoDetails, prefs: &SearchDefaults| {
+            matches_post_filters(video, prefs, &search, &BlockedSet::default())
+        };
+        assert!(keeps(&adult, &prefs));
+
+        prefs.hide_restricted = true;
+        assert!(!keeps(&adult, &prefs));
+        assert!(!keeps(&blocked, &prefs));
+        assert!(keeps(&available, &prefs));
+        assert!(keeps(&video(600), &prefs));
+    }
+
+    #[test]
+    fn exclude_3d_keeps_2d_and_unknown_dimensions() {
+        let mut prefs = SearchDefaults::default();
+        let search = MySearch::default();
+        let mut flat = video(600);
+        flat.dimension = Some("2d".into());
+        let mut stereo = video(600);
+        stereo.dimension = Some("3d".into());
+        assert!(matches_post_filters(
+            &stereo,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+
+        prefs.exclude_3d = true;
+        assert!(matches_post_filters(
+            &flat,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        assert!(!matches_post_filters(
+            &stereo,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        assert!(matches_post_filters(
+            &video(600),
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+    }
+
+    #[test]
+    fn parsed_zero_length_is_not_unknown() {
+        assert_eq!(parse_iso8601_duration("PT0S"), Some(0));
+        assert_eq!(parse_iso8601_duration("P0D"), None);
+        assert_eq!(parse_iso8601_duration("PT1H2M3S"), Some(3723));
+    }
+
+    #[test]
+    fn channel_refs_prefer_resolved_ids() {
+        let resolved = ChannelRef {
+            handle: Some("@rustlang".into()),
+            id: Some("UCaYhcUwRBNscFNUKTjgPFiA".into()),
+        };
+        assert!(matches_channel_ref(
+            "UCaYhcUwRBNscFNUKTjgPFiA",
+            "Some Other Title",
+            std::slice::from_ref(&resolved)
+        ));
+        assert!(!matches_channel_ref(
+            "UCzzzzzzzzzzzzzzzzzzzzzz",
+            "rustlang",
+            &[resolved]
+        ));
+
+        let unresolved = ChannelRef::parse("@RustLang");
+        assert!(matches_channel_ref("UCx", "RustLang", &[unresolved]));
+    }
+
+    fn rust_channel() -> VideoDetails {
+        let mut video = VideoDetails {
+            channel_handle: "UCaYhcUwRBNscFNUKTjgPFiA".into(),
+            channel_title: "Rust Lang".into(),
+            channel_custom_url: Some("@rustlang".into()),
+            duration_secs: 600,
+            ..VideoDetails::default()
+        };
+        video.fill_lowercase();
+        video
+    }
+
+    #[test]
+    fn block_set_matches_ids_handles_and_exact_titles() {
+        let video = rust_channel();
+        for entry in [
+            "UCaYhcUwRBNscFNUKTjgPFiA|Rust",
+            "ucayhcuwrbnscfnuktjgpfia",
+            "rust lang",
+            "@RustLang",
+            "UCzzzzzzzzzzzzzzzzzzzzzz|Other|@rustlang",
+        ] {
+            let blocked = BlockedSet::new(&[entry.to_string()]);
+            assert!(is_blocked(&video, &blocked), "{entry} should block");
+        }
+        for entry in ["rust", "other", "UCzzzzzzzzzzzzzzzzzzzzzz|Rust Lang"] {
+            let blocked = BlockedSet::new(&[entry.to_string()]);
+            assert!(!is_blocked(&video, &blocked), "{entry} should not block");
+        }
+        assert!(!is_blocked(&video, &BlockedSet::default()));
+    }
+
+    #[test]
+    fn blocked_cache_rebuilds_on_every_edit_and_only_then() {
+        let video = rust_channel();
+        let mut list: Vec<String> = Vec::new();
+        let mut cache = BlockedCache::new(&list);
+        for _ in 0..10_000 {
+            assert!(!is_blocked(&video, cache.set()));
+        }
+        assert_eq!(cache.rebuilds(), 0);
+
+        // Block button.
+        assert!(cache.edit(&mut list, |list| {
+            crate::prefs::add_block_entry(list, "UCaYhcUwRBNscFNUKTjgPFiA", "Rust Lang")
+        }));
+        assert!(is_blocked(&video, cache.set()));
+        assert_eq!(cache.rebuilds(), 1);
+
+        // Blocking again changes nothing, so nothing is recompiled.
+        assert!(!cache.edit(&mut list, |list| {
+            crate::prefs::add_block_entry(list, "UCaYhcUwRBNscFNUKTjgPFiA", "Rust Lang")
+        }));
+        assert_eq!(cache.rebuilds(), 1);
+
+        // Handle lookup filling in the entry's `@handle`.
+        cache.edit(&mut list, |list| {
+            list[0] = "UCaYhcUwRBNscFNUKTjgPFiA|Rust Lang|@rustlang".into()
+        });
+        assert_eq!(cache.rebuilds(), 2);
+        assert_eq!(*cache.set(), BlockedSet::new(&list));
+
+        // Unblock, by handle.
+        assert!(cache.edit(&mut list, |list| {
+            crate::prefs::remove_block_entries(list, "@rustlang")
+        }));
+        assert!(!is_blocked(&video, cache.set()));
+        assert_eq!(cache.rebuilds(), 3);
+
+        // Reset defaults / import replacing the whole list.
+        cache.edit(&mut list, |list| *list = vec!["rust lang".into()]);
+        assert!(is_blocked(&video, cache.set()));
+        assert_eq!(cache.rebuilds(), 4);
+    }
+
+    #[test]
+    fn search_and_ui_paths_agree_on_blocked_videos() {
+        let prefs = SearchDefaults {
+            english_only: false,
+            min_duration_secs: 0,
+            ..SearchDefaults::default()
+        };
+        let search = MySearch::default();
+        let entries = ["@rustlang".to_string(), "Bevy Engine".to_string()];
+        let blocked = BlockedSet::new(&entries);
+        let bevy = VideoDetails {
+            channel_handle: "UCbevy".into(),
+            channel_title: "Bevy Engine".into(),
+            duration_secs: 600,
+            ..VideoDetails::default()
+        };
+        let other = VideoDetails {
+            channel_title: "Egui".into(),
+            duration_secs: 600,
+            ..VideoDetails::default()
+        };
+        for (video, expected) in [(rust_channel(), true), (bevy, true), (other, false)] {
+            // Search-time post-filter vs. the arrival filter, block button, and card badge,
+            // which all call `is_blocked` directly.
+            let dropped_at_search = !matches_post_filters(&video, &prefs, &search, &blocked);
+            let unblocked = matches_post_filters(&video, &prefs, &search, &BlockedSet::default());
+            assert!(unblocked);
+            assert_eq!(is_blocked(&video, &blocked), expected);
+            assert_eq!(dropped_at_search, expected);
+            assert_eq!(
+                is_blocked(&video, &blocked),
+                blocked.matches(
+                    &video.channel_handle,
+                    &video.channel_title,
+                    video.channel_custom_url.as_deref()
+                )
+            );
+        }
+    }
+
+    fn spec(any: &[&str], all: &[&str]) -> QuerySpec {
+        QuerySpec {
+            any_terms: any.iter().map(|t| t.to_string()).collect(),
+            all_terms: all.iter().map(|t| t.to_string()).collect(),
+            ..QuerySpec::default()
+        }
+    }
+
+    #[test]
+    fn strict_all_terms_rejects_a_title_missing_one_required_term() {
+        let mut prefs = SearchDefaults::default();
+        let mut search = MySearch {
+            query: spec(&["tutorial"], &["rust", "\"game engine\""]),
+            ..MySearch::default()
+        };
+        let titled = |title: &str| {
+            let mut video = video(600);
+            video.title_lower = title.to_ascii_lowercase();
+            video
+        };
+        let both = titled("Rust game engine from scratch");
+        let missing = titled("Rust tutorial: an engine for my game");
+        let verdict = |video: &VideoDetails, prefs: &SearchDefaults, search: &MySearch| {
+            post_filter_verdict(video, prefs, search, &BlockedSet::default())
+        };
+        assert_eq!(verdict(&missing, &prefs, &search), PostFilterVerdict::Keep);
+
+        prefs.strict_all_terms = true;
+        assert_eq!(verdict(&both, &prefs, &search), PostFilterVerdict::Keep);
+        assert_eq!(
+            verdict(&missing, &prefs, &search),
+            PostFilterVerdict::MissingAllTerms
+        );
+
+        let mut tagged = missing.clone();
+        tagged.tags = vec!["game engine".into()];
+        assert_eq!(
+            verdict(&tagged, &prefs, &search),
+            PostFilterVerdict::MissingAllTerms
+        );
+        search.match_tags = true;
+        assert_eq!(verdict(&tagged, &prefs, &search), PostFilterVerdict::Keep);
+    }
+
+    #[test]
+    fn title_terms_match_whole_words() {
+        assert!(title_has_terms(
+            "rust news this week",
+            &spec(&["rust"], &[])
+        ));
+        assert!(!title_has_terms("trusty tools", &spec(&["rust"], &[])));
+        assert!(title_has_terms(
+            "Rust: async, finally!",
+            &spec(&["ASYNC"], &[])
+        ));
+        assert!(title_has_terms("anything goes", &spec(&[], &[])));
+    }
+
+    #[test]
+    fn title_terms_match_phrases_in_order() {
+        let phrase = spec(&["\"game engine\""], &[]);
+        assert!(title_has_terms("my game engine in rust", &phrase));
+        assert!(!title_has_terms("an engine for my game", &phrase));
+        assert!(title_has_terms(
+            "Bevy game-engine tour",
+            &spec(&["game engine"], &[])
+        ));
+    }
+
+    #[test]
+    fn title_terms_need_one_any_and_every_all() {
+        let query = spec(&["bevy", "godot"], &["tutorial"]);
+        assert!(title_has_terms("godot tutorial part 1", &query));
+        assert!(!title_has_terms("godot devlog", &query));
+        assert!(!title_has_terms("unity tutorial", &query));
+    }
+
+    #[test]
+    fn title_mismatch_is_reported_separately() {
+        let prefs = SearchDefaults::default();
+        let mut search = MySearch {
+            query: spec(&["bevy"], &[]),
+            ..MySearch::default()
+        };
+        assert_eq!(
+            post_filter_verdict(&video(600), &prefs, &search, &BlockedSet::default()),
+            PostFilterVerdict::Keep
+        );
+        search.require_terms_in_title = true;
+        assert_eq!(
+            post_filter_verdict(&video(600), &prefs, &search, &BlockedSet::default()),
+            PostFilterVerdict::TitleMismatch
+        );
+        assert_eq!(
+            post_filter_verdict(&video(10), &prefs, &search, &BlockedSet::default()),
+            PostFilterVerdict::Drop
+        );
+    }
+
+    #[test]
+    fn tag_terms_count_only_when_the_preset_opts_in() {
+        let prefs = SearchDefaults::default();
+        let mut tagged = video(600);
+        tagged.tags = vec!["Bevy Engine".into(), "gamedev".into()];
+        let mut search = MySearch {
+            query: spec(&["bevy"], &["gamedev"]),
+            require_terms_in_title: true,
+            ..MySearch::default()
+        };
+        assert_eq!(
+            post_filter_verdict(&tagged, &prefs, &search, &BlockedSet::default()),
+            PostFilterVerdict::TitleMismatch
+        );
+
+        search.match_tags = true;
+        assert!(matches_post_filters(
+            &tagged,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        assert!(!matches_post_filters(
+            &video(600),
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+    }
+
+    #[test]
+    fn tag_phrases_do_not_span_separate_tags() {
+        let query = spec(&["bevy engine"], &[]);
+        let split = ["bevy".to_string(), "engine".to_string()];
+        assert!(!title_or_tags_have_terms("devlog", &split, &query));
+        assert!(title_or_tags_have_terms(
+            "devlog",
+            &["Bevy Engine".to_string()],
+            &query
+        ));
+        assert!(title_or_tags_have_terms("bevy engine devlog", &[], &query));
+    }
+
+    #[test]
+    fn not_terms_match_tags_when_enabled() {
+        let prefs = SearchDefaults::default();
+        let mut tagged = video(600);
+        tagged.tags = vec!["Reaction".into()];
+        let mut search = MySearch {
+            query: QuerySpec {
+                not_terms: vec!["reaction".into()],
+                ..QuerySpec::default()
+            },
+            ..MySearch::default()
+        };
+        assert!(matches_post_filters(
+            &tagged,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+        search.match_tags = true;
+        assert!(!matches_post_filters(
+            &tagged,
+            &prefs,
+            &search,
+            &BlockedSet::default()
+        ));
+    }
+
+    #[test]
+    fn cached_videos_without_tags_still_load() {
+        let video: VideoDetails = serde_json::from_str(
+            r#"{"id":"v","title":"t","title_lower":"t","channel_title":"c","channel_handle":"UC",
+            "channel_display_name":null,"channel_custom_url":null,"published_at":"",
+            "duration_secs":60,"default_audio_lang":null,"default_lang":null,
+            "thumbnail_url":null,"url":"","has_caption_lang_en":null,"source_presets":[]}"#,
+        )
+        .unwrap();
+        assert!(video.tags.is_empty());
+    }
+
+    #[test]
+    fn channel_refs_accept_plain_strings() {
+        let refs: Vec<ChannelRef> = serde_json::from_str(
+            r#"["@foo", "UCaYhcUwRBNscFNUKTjgPFiA", {"handle": "@bar", "id": "UCbbbbbbbbbbbbbbbbbbbbbb"}]"#,
+        )
+        .unwrap();
+        assert_eq!(refs[0].handle.as_deref(), Some("@foo"));
+        assert_eq!(refs[0].id, None);
+        assert_eq!(refs[1].id.as_deref(), Some("UCaYhcUwRBNscFNUKTjgPFiA"));
+        assert_eq!(refs[2].handle.as_deref(), Some("@bar"));
+        assert_eq!(refs[2].id.as_deref(), Some("UCbbbbbbbbbbbbbbbbbbbbbb"));
+    }
+}
@@ -0,0 +1,254 @@
+//! Channel lists from other tools (RSS reader OPML exports, CSV or plain-text lists of channel
+//! URLs and handles) turned into channel-allow presets.
+
+use crate::prefs::{self, ChannelRef, MySearch};
+
+/// One channel found in an imported file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportedChannel {
+    pub channel: ChannelRef,
+    pub title: Option<String>,
+}
+
+impl ImportedChannel {
+    /// Name used for a per-channel preset.
+    pub fn preset_name(&self) -> String {
+        self.title
+            .clone()
+            .unwrap_or_else(|| self.channel.display().to_owned())
+    }
+}
+
+/// How imported channels become presets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ChannelGrouping {
+    /// One preset allowing every channel; needs query text to search with.
+    #[default]
+    Combined,
+    /// One preset per channel, searched with `channelId` when the ID is known.
+    PerChannel,
+}
+
+/// Parse OPML when the text looks like XML, otherwise a one-column CSV/text list.
+pub fn parse_channels(text: &str) -> Vec<ImportedChannel> {
+    let found = if text.trim_start().starts_with('<') || text.contains("<opml") {
+        parse_opml(text)
+    } else {
+        parse_channel_list(text)
+    };
+    dedup(found)
+}
+
+/// Collect `<outline>` feeds whose `xmlUrl` carries a YouTube `channel_id=`.
+pub fn parse_opml(text: &str) -> Vec<ImportedChannel> {
+    let mut out = Vec::new();
+    for chunk in text.split("<outline").skip(1) {
+        let tag = chunk.split('>').next().unwrap_or_default();
+        let Some(url) = attribute(tag, "xmlUrl") else {
+            continue;
+        };
+        let Some(id) = url
+            .split(['?', '&'])
+            .find_map(|part| part.strip_prefix("channel_id="))
+        else {
+            continue;
+        };
+        if !prefs::looks_like_channel_id(id) {
+            continue;
+        }
+        let title = attribute(tag, "title")
+            .or_else(|| attribute(tag, "text"))
+            .filter(|t| !t.trim().is_empty());
+        out.push(ImportedChannel {
+            channel: ChannelRef::parse(id),
+            title,
+        });
+    }
+    out
+}
+
+/// One channel per line: channel/handle URLs, bare `@handles`, or `UC…` IDs. Only the first
+/// CSV column is read; `#` comments, blank lines, and header rows are skipped.
+pub fn parse_channel_list(text: &str) -> Vec<ImportedChannel> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let first = line.split(',').next().unwrap_or_default();
+            let title = line
+                .split(',')
+                .nth(1)
+                .map(|t| t.trim().trim_matches('"').to_owned())
+                .filter(|t| !t.is_empty());
+            let channel = channel_from_token(first.trim().trim_matches('"'))?;
+            Some(ImportedChannel { channel, title })
+        })
+        .collect()
+}
+
+/// Build presets from imported channels. IDs are left empty for the caller to assign.
+pub fn presets_from_channels(
+    channels: &[ImportedChannel],
+    grouping: ChannelGrouping,
+    combined_name: &str,
+    query_text: &str,
+) -> Vec<MySearch> {
+    let q = Some(query_text.trim().to_owned()).filter(|q| !q.is_empty());
+    let template = |name: String, allow: Vec<ChannelRef>| {
+        let mut preset = MySearch {
+            name,
+            enabled: true,
+            ..MySearch::default()
+        };
+        preset.query.q = q.clone();
+        preset.query.channel_allow = allow;
+        preset
+    };
+    match grouping {
+        ChannelGrouping::Combined if channels.is_empty() => Vec::new(),
+        ChannelGrouping::Combined => {
+            let name = combined_name.trim();
+            let name = if name.is_empty() {
+                "Imported channels"
+            } else {
+                name
+            };
+            vec![template(
+                name.to_owned(),
+                channels.iter().map(|c| c.channel.clone()).collect(),
+            )]
+        }
+        ChannelGrouping::PerChannel => channels
+            .iter()
+            .map(|c| template(c.preset_name(), vec![c.channel.clone()]))
+            .collect(),
+    }
+}
+
+fn channel_from_token(token: &str) -> Option<ChannelRef> {
+    if token.is_empty() {
+        return None;
+    }
+    if prefs::looks_like_channel_id(token) {
+        return Some(ChannelRef::parse(token));
+    }
+    if token.starts_with('@') {
+        let handle = token.split('/').next().unwrap_or_default();
+        return (handle.len() > 1).then(|| ChannelRef::parse(handle));
+    }
+    if !token.contains("youtube.com") {
+        return None;
+    }
+    let path = token
+        .split("youtube.com/")
+        .nth(1)?
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    match segments.next()? {
+        "channel" => segments
+            .next()
+            .filter(|id| prefs::looks_like_channel_id(id))
+            .map(ChannelRef::parse),
+        handle if handle.starts_with('@') && handle.len() > 1 => Some(ChannelRef::parse(handle)),
+        _ => None,
+    }
+}
+
+fn dedup(channels: Vec<ImportedChannel>) -> Vec<ImportedChannel> {
+    let mut seen = std::collections::HashSet::new();
+    channels
+        .into_iter()
+        .filter(|c| seen.insert(c.channel.display().to_ascii_lowercase()))
+        .collect()
+}
+
+/// Value of `name="…"` (or single-quoted) inside a tag, with basic XML entities decoded.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(pos) = rest.find(name) {
+        let before = rest[..pos].chars().last();
+        let after = rest[pos + name.len()..].trim_start();
+        rest = &rest[pos + name.len()..];
+        if before.is_some_and(|c| !c.is_whitespace()) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)?;
+        return Some(decode_entities(&value[1..1 + end]));
+    }
+    None
+}
+
+fn decode_entities(raw: &str) -> String {
+    raw.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPML: &str = include_str!("import_formats/fixtures/subscriptions.opml");
+    const CSV: &str = include_str!("import_formats/fixtures/channels.csv");
+
+    fn labels(channels: &[ImportedChannel]) -> Vec<&str> {
+        channels.iter().map(|c| c.channel.display()).collect()
+    }
+
+    #[test]
+    fn opml_keeps_youtube_feeds_only() {
+        let channels = parse_channels(OPML);
+        assert_eq!(
+            labels(&channels),
+            ["UCaYhcUwRBNscFNUKTjgPFiA", "UC_x5XG1OV2P6uZZ5FSM9Ttw"]
+        );
+        assert_eq!(channels[0].title.as_deref(), Some("Rust Programming"));
+        assert_eq!(channels[1].title.as_deref(), Some("Tom & Jerry's Workshop"));
+    }
+
+    #[test]
+    fn csv_accepts_urls_handles_and_ids() {
+        let channels = parse_channels(CSV);
+        assert_eq!(
+            labels(&channels),
+            [
+                "UCaYhcUwRBNscFNUKTjgPFiA",
+                "@NoBoilerplate",
+                "@fasterthanlime",
+                "@jonhoo",
+                "UC_x5XG1OV2P6uZZ5FSM9Ttw",
+            ]
+        );
+        assert_eq!(channels[0].title.as_deref(), Some("Rust Programming"));
+        assert_eq!(channels[2].title, None);
+    }
+
+    #[test]
+    fn presets_combined_or_per_channel() {
+        let channels = parse_channels(CSV);
+        let combined = presets_from_channels(&channels, ChannelGrouping::Combined, " ", "rust");
+        assert_eq!(combined.len(), 1);
+        assert_eq!(combined[0].name, "Imported channels");
+        assert_eq!(combined[0].query.q.as_deref(), Some("rust"));
+        assert_eq!(combined[0].query.channel_allow.len(), 5);
+
+        let per = presets_from_channels(&channels, ChannelGrouping::PerChannel, "", "");
+        assert_eq!(per.len(), 5);
+        assert_eq!(per[0].name, "Rust Programming");
+        assert_eq!(per[1].name, "No Boilerplate");
+        assert_eq!(per[2].name, "@fasterthanlime");
+        assert!(per.iter().all(|p| p.id.is_empty() && p.query.q.is_none()));
+    }
+}
@@ -1,8 +1,72 @@
+//! YTSearch core: presets, YouTube Data API searches, and result filtering, usable without
+//! the desktop UI.
+//!
+//! The items re-exported at the crate root are the supported surface for other front-ends;
+//! the modules behind them may still change between releases. The `probe` binary is built
+//! only on this facade.
+//!
+//! ```no_run
+//! use YTSearch::{RunMode, init_config_dir, load_prefs, run_searches};
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     init_config_dir(None);
+//!     let prefs = load_prefs();
+//!     let Some(preset) = prefs.searches.first().map(|search| search.id.clone()) else {
+//!         anyhow::bail!("no presets in prefs.json");
+//!     };
+//!     let outcome = run_searches(
+//!         prefs.api_key,
+//!         prefs.search_defaults,
+//!         prefs.searches,
+//!         prefs.blocked_channels,
+//!         RunMode::Single(preset),
+//!         None,
+//!         None,
+//!     )
+//!     .await?;
+//!     for video in &outcome.videos {
+//!         println!("{}", video.title);
+//!     }
+//!     Ok(())
+//! }
+//! ```
 #![allow(non_snake_case)]
 
+pub mod annotations;
 pub mod cache;
+pub mod channel_cache;
+pub mod config_dir;
+pub mod debug_report;
+pub mod diagnostics;
+pub mod dismissals;
 pub mod filters;
+pub mod import_formats;
 pub mod prefs;
+pub mod preset_packs;
+pub mod preset_stats;
+pub mod quota;
 pub mod search_runner;
-pub mod ui;
+pub(crate) mod ui;
 pub mod yt;
+
+pub use config_dir::{ConfigDir, init as init_config_dir};
+pub use filters::{
+    BlockedSet, PostFilterVerdict, duration_allows, is_blocked, matches_channel_ref,
+    post_filter_verdict, title_has_terms, title_or_tags_have_terms,
+};
+pub use prefs::{
+    ChannelRef, DuplicatePolicy, MySearch, Prefs, QuerySpec, SearchDefaults, TimeWindow, UiPrefs,
+    add_missing_defaults, load_or_default as load_prefs, parse_region_code,
+};
+pub use search_runner::{
+    PresetOverlap, PresetRun, ProgressSink, RawFate, RawItem, RawPage, RunMode, SearchOutcome,
+    SearchProgress, build_query_params, resolve_window, run_searches,
+};
+pub use yt::http::clock_skew_warning;
+pub use yt::types::VideoDetails;
+
+/// Build the desktop app; used by the `YTSearch` binary.
+pub fn desktop_app(cc: &eframe::CreationContext<'_>) -> Box<dyn eframe::App> {
+    Box::new(ui::AppState::new(cc))
+}
@@ -1,6 +1,20 @@
-use YTSearch::ui;
+use std::path::PathBuf;
+
+use YTSearch::{config_dir, desktop_app};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(about = "Search YouTube with saved presets")]
+struct Args {
+    /// Folder for prefs.json, cached results, and thumbnails (also YTSEARCH_CONFIG_DIR)
+    #[arg(long, value_name = "PATH")]
+    config_dir: Option<PathBuf>,
+}
 
 fn main() -> eframe::Result<()> {
+    let args = Args::parse();
+    config_dir::init(args.config_dir);
+
     let viewport = egui::ViewportBuilder::default()
         .with_inner_size([1100.0, 720.0])
         .with_min_inner_size([1100.0, 600.0]);
@@ -11,6 +25,6 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "YTSearch",
         native_options,
-        Box::new(|cc| Ok(Box::new(ui::AppState::new(cc)))),
+        Box::new(|cc| Ok(desktop_app(cc))),
     )
 }
@@ -1,29 +1,171 @@
-use directories::ProjectDirs;
+use crate::config_dir;
+use crate::dismissals;
+use crate::quota::{self, QuotaUsage};
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fs, mem, path::PathBuf};
 
+mod backup;
+mod conflicts;
+mod diff;
+
+pub use backup::{PREFS_BACKUP_KEEP, ResetLosses, backup};
+pub use conflicts::{Conflict, check_preset_conflicts};
+pub use diff::{ChangedPreset, PresetDiff, diff_presets};
+
 const DEFAULT_PREFS_JSON: &str = include_str!("prefs_defaults.json");
+pub const DEFAULT_THUMBNAIL_MAX_AGE_DAYS: u32 = 30;
+/// Videos the "Open next" button opens per click.
+pub const DEFAULT_OPEN_BATCH_SIZE: u32 = 5;
+/// Results a run keeps, newest first, before the rest are dropped.
+pub const DEFAULT_MAX_TOTAL_RESULTS: usize = 1000;
+/// Markdown produced by a card's "Copy as Markdown".
+pub const DEFAULT_SHARE_TEMPLATE: &str = "[{title}]({url}) — {channel}, {duration}";
+
+/// Region codes offered in the settings picker; any other two-letter code can be typed in.
+pub const COMMON_REGION_CODES: &[(&str, &str)] = &[
+    ("US", "United States"),
+    ("GB", "United Kingdom"),
+    ("CA", "Canada"),
+    ("AU", "Australia"),
+    ("IE", "Ireland"),
+    ("NZ", "New Zealand"),
+    ("IN", "India"),
+    ("DE", "Germany"),
+    ("FR", "France"),
+    ("ES", "Spain"),
+    ("IT", "Italy"),
+    ("NL", "Netherlands"),
+    ("SE", "Sweden"),
+    ("BR", "Brazil"),
+    ("MX", "Mexico"),
+    ("JP", "Japan"),
+    ("KR", "South Korea"),
+];
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
-#[serde(default)]
+#[serde(from = "PrefsRepr")]
 pub struct Prefs {
     pub api_key: String,
-    pub global: GlobalPrefs,
+    pub search_defaults: SearchDefaults,
+    pub ui: UiPrefs,
     pub searches: Vec<MySearch>,
     pub blocked_channels: Vec<String>,
+    /// Unix time of the last preset or settings change, compared against the results cache.
+    pub modified_at_unix: i64,
+    pub quota_usage: QuotaUsage,
+    /// Quick-search queries, most recent first, capped at `RECENT_QUERY_LIMIT`.
+    pub recent_queries: Vec<String>,
+}
+
+/// Prefs as stored on disk. Files written before the split keep every setting in one
+/// `global` object, which fills whichever of `search_defaults` and `ui` is missing.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PrefsRepr {
+    api_key: String,
+    search_defaults: Option<SearchDefaults>,
+    ui: Option<UiPrefs>,
+    global: Option<LegacyGlobal>,
+    searches: Vec<MySearch>,
+    blocked_channels: Vec<String>,
+    modified_at_unix: i64,
+    quota_usage: QuotaUsage,
+    recent_queries: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct LegacyGlobal {
+    #[serde(flatten)]
+    search_defaults: SearchDefaults,
+    #[serde(flatten)]
+    ui: UiPrefs,
+}
+
+impl From<PrefsRepr> for Prefs {
+    fn from(repr: PrefsRepr) -> Self {
+        let (legacy_search, legacy_ui) = match repr.global {
+            Some(global) => (Some(global.search_defaults), Some(global.ui)),
+            None => (None, None),
+        };
+        Prefs {
+            api_key: repr.api_key,
+            search_defaults: repr.search_defaults.or(legacy_search).unwrap_or_default(),
+            ui: repr.ui.or(legacy_ui).unwrap_or_default(),
+            searches: repr.searches,
+            blocked_channels: repr.blocked_channels,
+            modified_at_unix: repr.modified_at_unix,
+            quota_usage: repr.quota_usage,
+            recent_queries: repr.recent_queries,
+        }
+    }
 }
 
+/// Settings that shape what a search returns; the only prefs the search pipeline reads.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(default)]
-pub struct GlobalPrefs {
+pub struct SearchDefaults {
     pub default_window: TimeWindowPreset,
     pub english_only: bool,
     pub require_captions: bool,
+    /// Drop fetched videos unless videos.list reports captions, on top of the coarse
+    /// `require_captions` search parameter.
+    pub require_captions_strict: bool,
     pub verify_captions_with_oauth: bool,
+    /// Keep only videos videos.list reports as licensed content.
+    pub only_licensed: bool,
+    /// Drop videos videos.list reports as 3D.
+    pub exclude_3d: bool,
+    /// Drop age-restricted videos and videos blocked in `region_code`.
+    pub hide_restricted: bool,
+    /// Drop videos whose title (and tags, for presets that match tags) lacks any of the
+    /// preset's all-terms, which `search.list` matches only loosely.
+    pub strict_all_terms: bool,
     pub min_duration_secs: u32,
     pub duration_filters: DurationFilterConfig,
     pub active_duration_bucket_ids: Vec<String>,
     pub region_code: Option<String>,
+    pub event_type: Option<BroadcastEvent>,
+    pub include_unknown_duration: bool,
+    /// How a run folds videos returned by more than one preset.
+    pub duplicate_policy: DuplicatePolicy,
+    /// Keep at most this many results per run, newest first; 0 keeps everything.
+    pub max_total_results: usize,
+    /// Proxy for every request; when unset, HTTPS_PROXY / HTTP_PROXY apply.
+    pub proxy_url: Option<String>,
+    /// Connect directly, ignoring `proxy_url` and the proxy environment variables.
+    pub no_proxy: bool,
+    /// Debug aid: keep every search.list page of the last run for the raw response viewer.
+    pub keep_raw_responses: bool,
+    /// Unix time of the last successful search, filled into the run's snapshot from the
+    /// results cache for the "Since last search" window. Not saved in prefs.
+    #[serde(skip)]
+    pub last_search_unix: Option<i64>,
+}
+
+/// Desktop app settings the search pipeline never reads.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct UiPrefs {
+    /// Fold Single-mode runs into the loaded results instead of replacing them.
+    pub dedup_across_presets: bool,
+    pub theme: ThemeChoice,
+    pub notify_on_completion: CompletionNotice,
+    /// Cached thumbnails older than this many days are deleted on startup; 0 keeps them forever.
+    pub thumbnail_max_age_days: u32,
+    /// Daily API units a run may spend before the quota guard asks for confirmation.
+    pub daily_quota_budget: u32,
+    /// Dismissed videos from one channel before blocking it is suggested; 0 turns it off.
+    pub block_suggestion_threshold: u32,
+    /// Videos opened per "Open next" click.
+    pub open_batch_size: u32,
+    /// "Copy as Markdown" template; `{title}`, `{url}`, `{channel}`, `{duration}`, and
+    /// `{published}` are filled in.
+    pub share_template: String,
+    /// Sort every launch starts with; `None` restores the sort in use when the app closed.
+    pub default_result_sort: Option<ResultSort>,
+    /// Look up find-box queries in the persisted word index of every video seen instead of
+    /// scanning every result, and enable the "Search history" window.
+    pub indexed_find: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -135,10 +277,10 @@ impl DurationBucketConfig {
         if secs < self.min_seconds as u64 {
             return false;
         }
-        if let Some(max) = self.max_seconds {
-            if secs >= max as u64 {
-                return false;
-            }
+        if let Some(max) = self.max_seconds
+            && secs >= max as u64
+        {
+            return false;
         }
         true
     }
@@ -159,8 +301,25 @@ pub struct MySearch {
     pub english_only_override: Option<bool>,
     pub require_captions_override: Option<bool>,
     pub min_duration_override: Option<u32>,
+    pub event_type_override: Option<BroadcastEvent>,
     pub priority: i32,
     pub system: bool,
+    /// Drop results whose title lacks the preset's any/all terms.
+    pub require_terms_in_title: bool,
+    /// Let any/all/not terms also match the video's tags during post-filtering.
+    pub match_tags: bool,
+    /// Run a query over [`crate::search_runner::QUERY_LENGTH_LIMIT`] as several searches, each
+    /// with part of the any terms, and merge the results.
+    pub split_long_query: bool,
+    /// For channel-only presets (no terms or category), page each allowed channel's uploads
+    /// playlist at 1 unit per page instead of `search.list` at 100. Presets with terms keep
+    /// using `search.list`.
+    pub uploads_mode: bool,
+    /// What the preset is for, shown as its tooltip in the sidebar.
+    pub description: Option<String>,
+    /// Sidebar group, e.g. the preset pack it came from; grouped presets are listed together
+    /// under a heading.
+    pub group: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -170,9 +329,100 @@ pub struct QuerySpec {
     pub any_terms: Vec<String>,
     pub all_terms: Vec<String>,
     pub not_terms: Vec<String>,
-    pub channel_allow: Vec<String>,
-    pub channel_deny: Vec<String>,
+    pub channel_allow: Vec<ChannelRef>,
+    pub channel_deny: Vec<ChannelRef>,
     pub category_id: Option<u32>,
+    /// Run one `channelId`-scoped search per resolved allow entry instead of post-filtering.
+    pub per_channel_search: bool,
+}
+
+/// A channel entry in a preset's allow/deny list.
+///
+/// `handle` keeps what the user typed (an `@handle` or a channel name); `id` is the resolved
+/// `UC…` channel ID when known. Older prefs stored plain strings, which still deserialize.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(from = "ChannelRefRepr")]
+pub struct ChannelRef {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChannelRefRepr {
+    Plain(String),
+    Full {
+        #[serde(default)]
+        handle: Option<String>,
+        #[serde(default)]
+        id: Option<String>,
+    },
+}
+
+impl From<ChannelRefRepr> for ChannelRef {
+    fn from(repr: ChannelRefRepr) -> Self {
+        match repr {
+            ChannelRefRepr::Plain(raw) => ChannelRef::parse(&raw),
+            ChannelRefRepr::Full { handle, id } => ChannelRef {
+                handle: handle
+                    .map(|h| h.trim().to_owned())
+                    .filter(|h| !h.is_empty()),
+                id: id.map(|i| i.trim().to_owned()).filter(|i| !i.is_empty()),
+            },
+        }
+    }
+}
+
+impl ChannelRef {
+    /// Interpret user input: `UC…` channel IDs go to `id`, anything else is kept as `handle`.
+    pub fn parse(raw: &str) -> Self {
+        let trimmed = raw.trim();
+        if looks_like_channel_id(trimmed) {
+            ChannelRef {
+                handle: None,
+                id: Some(trimmed.to_owned()),
+            }
+        } else {
+            ChannelRef {
+                handle: Some(trimmed.to_owned()).filter(|h| !h.is_empty()),
+                id: None,
+            }
+        }
+    }
+
+    /// Text shown in the editor: the handle when present, otherwise the ID.
+    pub fn display(&self) -> &str {
+        self.handle
+            .as_deref()
+            .or(self.id.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Lowercased `@handle` that still needs a `channels.list forHandle` lookup.
+    pub fn unresolved_handle(&self) -> Option<String> {
+        if self.id.is_some() {
+            return None;
+        }
+        self.handle
+            .as_deref()
+            .filter(|h| h.starts_with('@') && h.len() > 1)
+            .map(handle_key)
+    }
+}
+
+/// Cache key for a handle lookup (handles are case-insensitive).
+pub fn handle_key(handle: &str) -> String {
+    handle.trim().to_ascii_lowercase()
+}
+
+pub fn looks_like_channel_id(raw: &str) -> bool {
+    raw.len() == 24
+        && raw.starts_with("UC")
+        && raw
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -183,6 +433,110 @@ pub enum TimeWindowPreset {
     D7,
     #[serde(alias = "Custom")]
     AllTime,
+    /// From the last successful search (minus a small overlap) until now.
+    SinceLastRun,
+}
+
+/// Broadcast state passed to search.list as `eventType`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BroadcastEvent {
+    Live,
+    Upcoming,
+    Completed,
+}
+
+impl BroadcastEvent {
+    pub fn api_value(self) -> &'static str {
+        match self {
+            BroadcastEvent::Live => "live",
+            BroadcastEvent::Upcoming => "upcoming",
+            BroadcastEvent::Completed => "completed",
+        }
+    }
+}
+
+/// Order of the results list.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ResultSort {
+    #[default]
+    Newest,
+    Oldest,
+    Shortest,
+    Longest,
+    Channel,
+    /// Highest source-preset priority first, then newest.
+    Priority,
+}
+
+impl ResultSort {
+    pub const ALL: [ResultSort; 6] = [
+        ResultSort::Newest,
+        ResultSort::Oldest,
+        ResultSort::Shortest,
+        ResultSort::Longest,
+        ResultSort::Channel,
+        ResultSort::Priority,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ResultSort::Newest => "Newest",
+            ResultSort::Oldest => "Oldest",
+            ResultSort::Shortest => "Shortest",
+            ResultSort::Longest => "Longest",
+            ResultSort::Channel => "Channel",
+            ResultSort::Priority => "Priority",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ThemeChoice {
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+/// How to get the user's attention when a search finishes while the window is unfocused.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CompletionNotice {
+    #[default]
+    None,
+    Flash,
+    Sound,
+    Both,
+}
+
+impl CompletionNotice {
+    pub fn flashes(self) -> bool {
+        matches!(self, CompletionNotice::Flash | CompletionNotice::Both)
+    }
+
+    pub fn plays_sound(self) -> bool {
+        matches!(self, CompletionNotice::Sound | CompletionNotice::Both)
+    }
+}
+
+/// How a run folds videos that several presets (or one preset's pages) returned.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    /// One card per video ID, tagged with every preset that found it.
+    #[default]
+    DedupById,
+    /// Like `DedupById`, and also fold videos from one channel with the same title, which
+    /// catches re-uploads.
+    DedupByIdAndChannel,
+    /// A card for every preset that returned the video.
+    KeepAll,
+}
+
+impl DuplicatePolicy {
+    pub const ALL: [DuplicatePolicy; 3] = [
+        DuplicatePolicy::DedupById,
+        DuplicatePolicy::DedupByIdAndChannel,
+        DuplicatePolicy::KeepAll,
+    ];
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -191,7 +545,7 @@ pub struct TimeWindow {
     pub end_rfc3339: String,
 }
 
-impl Default for GlobalPrefs {
+impl Default for SearchDefaults {
     fn default() -> Self {
         let duration_filters = DurationFilterConfig::default();
         let active_duration_bucket_ids = duration_filters.default_active_ids();
@@ -199,11 +553,41 @@ impl Default for GlobalPrefs {
             default_window: TimeWindowPreset::default(),
             english_only: true,
             require_captions: false,
+            require_captions_strict: false,
             verify_captions_with_oauth: false,
+            only_licensed: false,
+            exclude_3d: false,
+            hide_restricted: false,
+            strict_all_terms: false,
             min_duration_secs: 75,
             duration_filters,
             active_duration_bucket_ids,
             region_code: Some("US".into()),
+            event_type: None,
+            include_unknown_duration: false,
+            duplicate_policy: DuplicatePolicy::default(),
+            max_total_results: DEFAULT_MAX_TOTAL_RESULTS,
+            proxy_url: None,
+            no_proxy: false,
+            keep_raw_responses: false,
+            last_search_unix: None,
+        }
+    }
+}
+
+impl Default for UiPrefs {
+    fn default() -> Self {
+        Self {
+            dedup_across_presets: false,
+            theme: ThemeChoice::default(),
+            notify_on_completion: CompletionNotice::default(),
+            thumbnail_max_age_days: DEFAULT_THUMBNAIL_MAX_AGE_DAYS,
+            daily_quota_budget: quota::DEFAULT_DAILY_BUDGET,
+            block_suggestion_threshold: dismissals::DEFAULT_BLOCK_SUGGESTION_THRESHOLD,
+            open_batch_size: DEFAULT_OPEN_BATCH_SIZE,
+            share_template: DEFAULT_SHARE_TEMPLATE.to_owned(),
+            default_result_sort: None,
+            indexed_find: false,
         }
     }
 }
@@ -216,12 +600,17 @@ pub fn load_or_default() -> Prefs {
         builtin_default()
     };
     add_missing_defaults(&mut prefs);
-    normalize_duration_filters(&mut prefs.global);
+    normalize_duration_filters(&mut prefs.search_defaults);
+    normalize_region_code(&mut prefs.search_defaults);
     normalize_block_list(&mut prefs.blocked_channels);
     prefs
 }
 
+/// Write prefs.json; a no-op when no writable config folder was found.
 pub fn save(p: &Prefs) -> std::io::Result<()> {
+    if !config_dir::get().writable {
+        return Ok(());
+    }
     let path = prefs_path();
     if let Some(dir) = path.parent() {
         fs::create_dir_all(dir)?;
@@ -230,28 +619,76 @@ pub fn save(p: &Prefs) -> std::io::Result<()> {
 }
 
 fn prefs_path() -> PathBuf {
-    let proj = ProjectDirs::from("com", "yourname", "YTSearch").expect("no project dirs");
-    proj.config_dir().join("prefs.json")
+    config_dir::get().prefs_path()
+}
+
+/// Quick-search history entries kept in prefs.
+pub const RECENT_QUERY_LIMIT: usize = 10;
+
+/// Move `query` to the front of the history, dropping case-insensitive duplicates and
+/// anything past the limit. Blank queries are ignored.
+pub fn remember_query(recent: &mut Vec<String>, query: &str) {
+    let query = query.trim();
+    if query.is_empty() {
+        return;
+    }
+    recent.retain(|existing| !existing.eq_ignore_ascii_case(query));
+    recent.insert(0, query.to_owned());
+    recent.truncate(RECENT_QUERY_LIMIT);
+}
+
+/// Stamp prefs as changed so cached results generated earlier can be flagged as stale.
+pub fn mark_modified(prefs: &mut Prefs) {
+    prefs.modified_at_unix = time::OffsetDateTime::now_utc().unix_timestamp();
+}
+
+/// Whether results fetched under `before` could differ under `after`. Connection and debug
+/// settings do not count, so changing them leaves cached results current.
+pub fn search_defaults_differ(before: &SearchDefaults, after: &SearchDefaults) -> bool {
+    let comparable = |defaults: &SearchDefaults| SearchDefaults {
+        proxy_url: None,
+        no_proxy: false,
+        keep_raw_responses: false,
+        last_search_unix: None,
+        ..defaults.clone()
+    };
+    comparable(before) != comparable(after)
 }
 
 pub fn builtin_default() -> Prefs {
     serde_json::from_str(DEFAULT_PREFS_JSON).unwrap_or_default()
 }
 
+/// Add built-in presets missing by id, and give existing built-ins their description if they
+/// were saved before descriptions existed.
 pub fn add_missing_defaults(prefs: &mut Prefs) {
     let defaults = builtin_default();
     for default_search in defaults.searches {
-        if !prefs.searches.iter().any(|s| s.id == default_search.id) {
-            prefs.searches.push(default_search);
+        match prefs
+            .searches
+            .iter_mut()
+            .find(|s| s.id == default_search.id)
+        {
+            Some(existing) => {
+                if existing.description.is_none() {
+                    existing.description = default_search.description;
+                }
+            }
+            None => prefs.searches.push(default_search),
         }
     }
 }
 
-pub fn normalize_duration_filters(global: &mut GlobalPrefs) {
-    let config = &global.duration_filters;
+/// Whether prefs.json exists yet; false on a fresh install.
+pub fn prefs_file_exists() -> bool {
+    prefs_path().exists()
+}
+
+pub fn normalize_duration_filters(defaults: &mut SearchDefaults) {
+    let config = &defaults.duration_filters;
     let mut active: Vec<String> = Vec::new();
     for bucket in &config.buckets {
-        if global
+        if defaults
             .active_duration_bucket_ids
             .iter()
             .any(|id| id == &bucket.id)
@@ -268,45 +705,316 @@ pub fn normalize_duration_filters(global: &mut GlobalPrefs) {
     if !config.allow_multiple && active.len() > 1 {
         active.truncate(1);
     }
-    global.active_duration_bucket_ids = active;
+    defaults.active_duration_bucket_ids = active;
+}
+
+/// Parse a user-entered region: empty or "none" clears it, otherwise exactly two ASCII letters.
+pub fn parse_region_code(raw: &str) -> Result<Option<String>, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    if trimmed.len() == 2 && trimmed.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Ok(Some(trimmed.to_ascii_uppercase()));
+    }
+    Err(format!(
+        "Region code '{trimmed}' must be exactly two letters (e.g. US), or empty for none."
+    ))
+}
+
+/// Uppercase the stored region and drop it if it is not a valid two-letter code.
+pub fn normalize_region_code(defaults: &mut SearchDefaults) {
+    defaults.region_code = defaults
+        .region_code
+        .as_deref()
+        .and_then(|raw| parse_region_code(raw).ok().flatten());
+}
+
+/// One blocked channel, stored in prefs as `id|label` or `id|label|@handle`.
+///
+/// `key` is the lowercased match key; `raw_key` keeps the original casing so channel IDs can
+/// still be looked up through the API.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockEntry {
+    pub raw_key: String,
+    pub key: String,
+    pub label: String,
+    pub handle: Option<String>,
+}
+
+impl BlockEntry {
+    pub fn parse(entry: &str) -> Self {
+        let (raw_key, rest) = entry.trim().split_once('|').unwrap_or((entry.trim(), ""));
+        let raw_key = raw_key.trim().to_string();
+        // Channel titles may contain '|', so only a trailing `|@handle` segment is a handle.
+        let (label, handle) = match rest.rsplit_once('|') {
+            Some((label, handle))
+                if handle.trim().starts_with('@')
+                    && handle.trim().len() > 1
+                    && !handle.trim().contains(char::is_whitespace) =>
+            {
+                (label.trim(), Some(handle.trim().to_string()))
+            }
+            _ => (rest.trim(), None),
+        };
+        let label = if label.is_empty() {
+            raw_key.clone()
+        } else {
+            label.to_string()
+        };
+        BlockEntry {
+            key: raw_key.trim_start_matches('@').to_ascii_lowercase(),
+            raw_key,
+            label,
+            handle,
+        }
+    }
+
+    /// Whether the entry names a channel by text alone, with no channel ID and no looked-up
+    /// handle. Such entries hide channels whose name or handle is exactly that text; before
+    /// the block list was compiled into a `BlockedSet` they also hid every channel whose
+    /// title merely contained it.
+    pub fn is_name_only(&self) -> bool {
+        // Older entries stored channel IDs lowercased.
+        let channel_id = self.raw_key.len() == 24 && self.key.starts_with("uc");
+        self.handle.is_none() && !channel_id
+    }
+
+    pub fn format(&self) -> String {
+        match self.handle.as_deref() {
+            Some(handle) => format!("{}|{}|{}", self.raw_key, self.label, handle),
+            None => format!("{}|{}", self.raw_key, self.label),
+        }
+    }
+
+    /// Label for the block list: the stored label plus the handle once it is known.
+    pub fn display_label(&self) -> String {
+        match self.handle.as_deref() {
+            Some(handle) if !handle.eq_ignore_ascii_case(&self.label) => {
+                format!("{} ({})", self.label, handle)
+            }
+            _ => self.label.clone(),
+        }
+    }
+
+    /// Whether `query` names this entry by channel ID, handle, or stored label.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().trim_start_matches('@').to_ascii_lowercase();
+        if query.is_empty() {
+            return false;
+        }
+        self.key == query
+            || self.label.trim().to_ascii_lowercase() == query
+            || self
+                .handle
+                .as_deref()
+                .is_some_and(|h| h.trim_start_matches('@').to_ascii_lowercase() == query)
+    }
 }
 
 pub fn normalize_block_list(list: &mut Vec<String>) {
-    let mut map = BTreeMap::new();
+    let mut map: BTreeMap<String, BlockEntry> = BTreeMap::new();
     for entry in mem::take(list) {
-        let (key, label) = parse_block_entry(&entry);
-        if key.is_empty() {
+        let parsed = BlockEntry::parse(&entry);
+        if parsed.key.is_empty() {
             continue;
         }
-        map.entry(key.clone())
-            .or_insert_with(|| format!("{}|{}", key, label));
+        match map.get_mut(&parsed.key) {
+            Some(existing) => {
+                if existing.handle.is_none() {
+                    existing.handle = parsed.handle;
+                }
+            }
+            None => {
+                map.insert(parsed.key.clone(), parsed);
+            }
+        }
     }
-    *list = map.into_values().collect();
+    *list = map.values().map(BlockEntry::format).collect();
 }
 
-pub fn blocked_keys(entries: &[String]) -> Vec<String> {
-    entries
-        .iter()
-        .map(|entry| parse_block_entry(entry).0)
-        .collect()
+/// Add `raw_key|label` unless that key is already blocked; returns whether it was added.
+pub fn add_block_entry(list: &mut Vec<String>, raw_key: &str, label: &str) -> bool {
+    let key = raw_key.trim_start_matches('@').to_ascii_lowercase();
+    if list.iter().any(|entry| BlockEntry::parse(entry).key == key) {
+        return false;
+    }
+    list.push(format!("{raw_key}|{label}"));
+    normalize_block_list(list);
+    true
+}
+
+/// Remove the entries `query` names by channel ID, handle, or label; returns whether any were.
+pub fn remove_block_entries(list: &mut Vec<String>, query: &str) -> bool {
+    let original_len = list.len();
+    list.retain(|entry| !BlockEntry::parse(entry).matches(query));
+    if list.len() == original_len {
+        return false;
+    }
+    normalize_block_list(list);
+    true
 }
 
 pub fn parse_block_entry(entry: &str) -> (String, String) {
-    let trimmed = entry.trim();
-    if trimmed.is_empty() {
-        return (String::new(), String::new());
+    let parsed = BlockEntry::parse(entry);
+    (parsed.key, parsed.label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_presets_gain_descriptions_without_overwriting_existing_ones() {
+        let mut prefs = builtin_default();
+        prefs.searches[0].description = None;
+        let mut custom = prefs.searches[0].clone();
+        custom.id = "custom".into();
+        prefs.searches.push(custom);
+        add_missing_defaults(&mut prefs);
+        assert!(prefs.searches[0].description.is_some());
+        assert!(prefs.searches[1].description.is_none());
+
+        prefs.searches[0].description = Some("Mine".into());
+        add_missing_defaults(&mut prefs);
+        assert_eq!(prefs.searches[0].description.as_deref(), Some("Mine"));
+        assert_eq!(prefs.searches.len(), 2);
     }
-    if let Some((raw_key, raw_label)) = trimmed.split_once('|') {
-        let key = raw_key.trim().trim_start_matches('@').to_ascii_lowercase();
-        let label = raw_label.trim();
-        let label = if label.is_empty() {
-            raw_key.trim().to_string()
-        } else {
-            label.to_string()
-        };
-        (key, label)
-    } else {
-        let key = trimmed.trim_start_matches('@').to_ascii_lowercase();
-        (key.clone(), trimmed.to_string())
+
+    #[test]
+    fn block_entry_round_trips_with_and_without_handle() {
+        let plain = BlockEntry::parse("UCaYhcUwRBNscFNUKTjgPFiA|Rust");
+        assert_eq!(plain.key, "ucayhcuwrbnscfnuktjgpfia");
+        assert_eq!(plain.raw_key, "UCaYhcUwRBNscFNUKTjgPFiA");
+        assert_eq!(plain.handle, None);
+        assert_eq!(plain.format(), "UCaYhcUwRBNscFNUKTjgPFiA|Rust");
+
+        let with_handle = BlockEntry::parse("UCaYhcUwRBNscFNUKTjgPFiA|Rust|@rustlang");
+        assert_eq!(with_handle.handle.as_deref(), Some("@rustlang"));
+        assert_eq!(
+            with_handle.format(),
+            "UCaYhcUwRBNscFNUKTjgPFiA|Rust|@rustlang"
+        );
+        assert_eq!(with_handle.display_label(), "Rust (@rustlang)");
+
+        let piped_title = BlockEntry::parse("UCaYhcUwRBNscFNUKTjgPFiA|News | Daily");
+        assert_eq!(piped_title.label, "News | Daily");
+        assert_eq!(piped_title.handle, None);
+    }
+
+    #[test]
+    fn only_entries_without_an_id_or_handle_are_name_only() {
+        for entry in [
+            "UCaYhcUwRBNscFNUKTjgPFiA|Rust",
+            "ucayhcuwrbnscfnuktjgpfia|Rust",
+            "rust lang|Rust Lang|@rustlang",
+        ] {
+            assert!(!BlockEntry::parse(entry).is_name_only(), "{entry}");
+        }
+        for entry in ["rust lang|Rust Lang", "@rustlang", "spam"] {
+            assert!(BlockEntry::parse(entry).is_name_only(), "{entry}");
+        }
+    }
+
+    #[test]
+    fn remember_query_moves_repeats_to_front_and_caps_history() {
+        let mut recent = vec!["rust gui".to_owned(), "egui".to_owned()];
+        remember_query(&mut recent, "  EGUI ");
+        assert_eq!(recent, vec!["EGUI", "rust gui"]);
+        remember_query(&mut recent, "   ");
+        assert_eq!(recent.len(), 2);
+        for n in 0..RECENT_QUERY_LIMIT {
+            remember_query(&mut recent, &format!("query {n}"));
+        }
+        assert_eq!(recent.len(), RECENT_QUERY_LIMIT);
+        assert_eq!(recent[0], format!("query {}", RECENT_QUERY_LIMIT - 1));
+    }
+
+    #[test]
+    fn bare_entries_use_the_key_as_label() {
+        let bare = BlockEntry::parse("@SomeHandle");
+        assert_eq!(bare.key, "somehandle");
+        assert_eq!(bare.label, "@SomeHandle");
+        assert_eq!(parse_block_entry("  "), (String::new(), String::new()));
+    }
+
+    #[test]
+    fn block_entry_matches_id_handle_or_label() {
+        let entry = BlockEntry::parse("UCaYhcUwRBNscFNUKTjgPFiA|Rust Videos|@rustlang");
+        assert!(entry.matches("UCaYhcUwRBNscFNUKTjgPFiA"));
+        assert!(entry.matches("ucayhcuwrbnscfnuktjgpfia"));
+        assert!(entry.matches("@RustLang"));
+        assert!(entry.matches("rustlang"));
+        assert!(entry.matches("rust videos"));
+        assert!(!entry.matches("rust"));
+        assert!(!entry.matches(""));
+    }
+
+    #[test]
+    fn normalize_keeps_a_learned_handle() {
+        let mut list = vec![
+            "UCaYhcUwRBNscFNUKTjgPFiA|Rust".to_string(),
+            "ucayhcuwrbnscfnuktjgpfia|Rust|@rustlang".to_string(),
+            "".to_string(),
+        ];
+        normalize_block_list(&mut list);
+        assert_eq!(list, vec!["UCaYhcUwRBNscFNUKTjgPFiA|Rust|@rustlang"]);
+    }
+
+    #[test]
+    fn legacy_global_object_fills_both_halves() {
+        let legacy = r#"{
+            "api_key": "key",
+            "global": {
+                "english_only": false,
+                "min_duration_secs": 600,
+                "region_code": "GB",
+                "theme": "Dark",
+                "daily_quota_budget": 2500,
+                "open_batch_size": 3
+            },
+            "searches": []
+        }"#;
+        let prefs: Prefs = serde_json::from_str(legacy).unwrap();
+        assert_eq!(prefs.api_key, "key");
+        assert!(!prefs.search_defaults.english_only);
+        assert_eq!(prefs.search_defaults.min_duration_secs, 600);
+        assert_eq!(prefs.search_defaults.region_code.as_deref(), Some("GB"));
+        assert_eq!(prefs.ui.theme, ThemeChoice::Dark);
+        assert_eq!(prefs.ui.daily_quota_budget, 2500);
+        assert_eq!(prefs.ui.open_batch_size, 3);
+        assert_eq!(prefs.ui.default_result_sort, None);
+    }
+
+    #[test]
+    fn only_result_shaping_defaults_count_as_changes() {
+        let before = SearchDefaults::default();
+        let mut after = before.clone();
+        after.proxy_url = Some("http://proxy:8080".into());
+        after.keep_raw_responses = true;
+        assert!(!search_defaults_differ(&before, &after));
+        after.min_duration_secs += 1;
+        assert!(search_defaults_differ(&before, &after));
+    }
+
+    #[test]
+    fn default_result_sort_round_trips() {
+        let prefs: Prefs =
+            serde_json::from_str(r#"{ "ui": { "default_result_sort": "Longest" } }"#).unwrap();
+        assert_eq!(prefs.ui.default_result_sort, Some(ResultSort::Longest));
+        let json = serde_json::to_string(&prefs).unwrap();
+        let back: Prefs = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.ui.default_result_sort, Some(ResultSort::Longest));
+    }
+
+    #[test]
+    fn split_prefs_round_trip_without_a_global_object() {
+        let mut prefs = builtin_default();
+        prefs.search_defaults.min_duration_secs = 900;
+        prefs.ui.block_suggestion_threshold = 7;
+        let json = serde_json::to_string(&prefs).unwrap();
+        assert!(!json.contains("\"global\""));
+        let back: Prefs = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, prefs);
     }
 }
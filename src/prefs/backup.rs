@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use time::OffsetDateTime;
+use time::macros::format_description;
+
+use super::{Prefs, builtin_default};
+use crate::config_dir;
+
+/// Prefs backups kept in the backups folder; older ones are deleted after each new backup.
+pub const PREFS_BACKUP_KEEP: usize = 10;
+
+const BACKUP_PREFIX: &str = "prefs-";
+
+/// What "Reset defaults" would throw away.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResetLosses {
+    /// Presets that are not among the built-in ones.
+    pub custom_presets: usize,
+    pub blocked_channels: usize,
+    pub cached_videos: usize,
+}
+
+impl ResetLosses {
+    pub fn of(prefs: &Prefs, cached_videos: usize) -> Self {
+        let builtin = builtin_default();
+        Self {
+            custom_presets: prefs
+                .searches
+                .iter()
+                .filter(|search| !builtin.searches.iter().any(|b| b.id == search.id))
+                .count(),
+            blocked_channels: prefs.blocked_channels.len(),
+            cached_videos,
+        }
+    }
+
+    /// e.g. "7 custom presets, 23 blocked channels, 240 cached videos".
+    pub fn summary(&self) -> String {
+        let plural = |count: usize, noun: &str| {
+            format!("{count} {noun}{}", if count == 1 { "" } else { "s" })
+        };
+        [
+            plural(self.custom_presets, "custom preset"),
+            plural(self.blocked_channels, "blocked channel"),
+            plural(self.cached_videos, "cached video"),
+        ]
+        .join(", ")
+    }
+}
+
+/// Write `prefs` to a timestamped file in the backups folder and delete all but the newest
+/// [`PREFS_BACKUP_KEEP`]. Returns the new file's path.
+pub fn backup(prefs: &Prefs, now: OffsetDateTime) -> std::io::Result<PathBuf> {
+    let config = config_dir::get();
+    if !config.writable {
+        return Err(std::io::Error::other("the settings folder is not writable"));
+    }
+    write_backup(&config.backups_dir(), prefs, now, PREFS_BACKUP_KEEP)
+}
+
+pub(super) fn write_backup(
+    dir: &Path,
+    prefs: &Prefs,
+    now: OffsetDateTime,
+    keep: usize,
+) -> std::io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let stamp = now
+        .format(format_description!(
+            "[year][month][day]-[hour][minute][second]-[subsecond digits:3]"
+        ))
+        .map_err(std::io::Error::other)?;
+    let path = dir.join(format!("{BACKUP_PREFIX}{stamp}.json"));
+    fs::write(&path, serde_json::to_vec_pretty(prefs)?)?;
+    rotate(dir, keep)?;
+    Ok(path)
+}
+
+/// Delete backups beyond the newest `keep`; the timestamped names sort oldest first.
+fn rotate(dir: &Path, keep: usize) -> std::io::Result<()> {
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "json")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(BACKUP_PREFIX))
+        })
+        .collect();
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep);
+    for path in &backups[..excess] {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backups_round_trip_and_rotate_out_the_oldest() {
+        let dir = std::env::temp_dir().join(format!("ytsearch-backups-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut prefs = builtin_default();
+        prefs.blocked_channels = vec!["UCabc".into()];
+        let start = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+
+        let paths: Vec<PathBuf> = (0..3)
+            .map(|n| write_backup(&dir, &prefs, start + time::Duration::seconds(n), 2).unwrap())
+            .collect();
+        assert!(!paths[0].exists());
+        assert!(paths[1].exists() && paths[2].exists());
+
+        let restored: Prefs = serde_json::from_slice(&fs::read(&paths[2]).unwrap()).unwrap();
+        assert_eq!(restored.blocked_channels, prefs.blocked_channels);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn losses_count_only_presets_that_are_not_built_in() {
+        let mut prefs = builtin_default();
+        let mut custom = prefs.searches[0].clone();
+        custom.id = "custom".into();
+        prefs.searches.push(custom);
+        prefs.blocked_channels = vec!["UCa".into(), "UCb".into()];
+        let losses = ResetLosses::of(&prefs, 1);
+        assert_eq!(
+            losses.summary(),
+            "1 custom preset, 2 blocked channels, 1 cached video"
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use super::{DurationBucketConfig, MySearch, SearchDefaults};
+use crate::search_runner;
+
+/// A preset setting that quietly defeats another one. Shown as a warning; never blocks saving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// The `videoDuration` sent to search.list excludes every active length bucket.
+    DurationBuckets { param: &'static str },
+    /// Captions are required, but nothing checks that they are English.
+    CaptionsAnyLanguage,
+    /// A term is both required and excluded.
+    NotTermInAll(String),
+    /// The window override does not overlap the default window.
+    WindowOutsideDefault,
+    /// The composed query is longer than YouTube reliably accepts and splitting is off.
+    QueryTooLong { length: usize },
+}
+
+impl Conflict {
+    pub fn message(&self) -> String {
+        match self {
+            Conflict::DurationBuckets { param } => format!(
+                "Min duration asks YouTube for {param} videos only, but none of the selected \
+                 length buckets accept them, so every result is dropped."
+            ),
+            Conflict::CaptionsAnyLanguage => "Require captions accepts a caption track in any \
+                 language while English only and caption verification are off."
+                .into(),
+            Conflict::NotTermInAll(term) => {
+                format!("'{term}' is both an all term and a not term, so no video can match.")
+            }
+            Conflict::WindowOutsideDefault => "The window override lies entirely outside the \
+                 default window, so this preset finds different dates than the others."
+                .into(),
+            Conflict::QueryTooLong { length } => format!(
+                "The query is {length} characters once encoded; YouTube may truncate it beyond \
+                 ~{}. Turn on \"Split long queries\" to run the any terms in parts.",
+                search_runner::QUERY_LENGTH_LIMIT
+            ),
+        }
+    }
+}
+
+/// Check a preset against the search defaults for combinations that silently return
+/// nothing or something other than what the fields suggest.
+pub fn check_preset_conflicts(defaults: &SearchDefaults, search: &MySearch) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+
+    let min_duration = search
+        .min_duration_override
+        .unwrap_or(defaults.min_duration_secs);
+    if let Some(param) = search_runner::video_duration_param(min_duration) {
+        let (api_min, api_max) = api_duration_range(param);
+        let low = api_min.max(min_duration as u64);
+        let reachable = active_buckets(defaults).iter().any(|bucket| {
+            api_max.is_none_or(|max| (bucket.min_seconds as u64) < max)
+                && bucket.max_seconds.is_none_or(|max| max as u64 > low)
+        });
+        if !reachable {
+            conflicts.push(Conflict::DurationBuckets { param });
+        }
+    }
+
+    let require_captions = search
+        .require_captions_override
+        .unwrap_or(defaults.require_captions);
+    let english_only = search
+        .english_only_override
+        .unwrap_or(defaults.english_only);
+    if require_captions && !english_only && !defaults.verify_captions_with_oauth {
+        conflicts.push(Conflict::CaptionsAnyLanguage);
+    }
+
+    for term in &search.query.not_terms {
+        let term = term.trim();
+        if !term.is_empty()
+            && search
+                .query
+                .all_terms
+                .iter()
+                .any(|all| all.trim().eq_ignore_ascii_case(term))
+        {
+            conflicts.push(Conflict::NotTermInAll(term.to_owned()));
+        }
+    }
+
+    if let Some(window) = &search.window_override
+        && let Some(default) = search_runner::resolve_window(defaults, &MySearch::default())
+        && let (Some(start), Some(end)) = (parse(&window.start_rfc3339), parse(&window.end_rfc3339))
+        && let (Some(default_start), Some(default_end)) =
+            (parse(&default.start_rfc3339), parse(&default.end_rfc3339))
+        && (end <= default_start || start >= default_end)
+    {
+        conflicts.push(Conflict::WindowOutsideDefault);
+    }
+
+    let length = search_runner::query_length(&search.query);
+    if length > search_runner::QUERY_LENGTH_LIMIT && !search.split_long_query {
+        conflicts.push(Conflict::QueryTooLong { length });
+    }
+
+    conflicts
+}
+
+/// Lengths YouTube keeps for a `videoDuration` value, in seconds (upper bound exclusive).
+fn api_duration_range(param: &str) -> (u64, Option<u64>) {
+    match param {
+        "long" => (1200, None),
+        "medium" => (240, Some(1200)),
+        _ => (0, None),
+    }
+}
+
+/// The buckets [`crate::filters::duration_allows`] checks; empty means every length passes.
+fn active_buckets(defaults: &SearchDefaults) -> Vec<&DurationBucketConfig> {
+    let config = &defaults.duration_filters;
+    let mut active: Vec<&DurationBucketConfig> = defaults
+        .active_duration_bucket_ids
+        .iter()
+        .filter_map(|id| config.bucket_by_id(id))
+        .collect();
+    if active.is_empty() {
+        active = config
+            .buckets
+            .iter()
+            .filter(|bucket| bucket.default_selected)
+            .collect();
+    }
+    if active.is_empty() {
+        active = config.buckets.iter().collect();
+    }
+    active
+}
+
+fn parse(raw: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(raw, &Rfc3339).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prefs::TimeWindow;
+
+    fn search_defaults() -> SearchDefaults {
+        SearchDefaults {
+            english_only: true,
+            require_captions: false,
+            verify_captions_with_oauth: false,
+            min_duration_secs: 0,
+            active_duration_bucket_ids: vec!["any".into()],
+            ..SearchDefaults::default()
+        }
+    }
+
+    #[test]
+    fn defaults_have_no_conflicts() {
+        assert!(
+            check_preset_conflicts(&SearchDefaults::default(), &MySearch::default()).is_empty()
+        );
+    }
+
+    #[test]
+    fn long_min_duration_conflicts_with_short_buckets() {
+        let mut defaults = search_defaults();
+        defaults.active_duration_bucket_ids = vec!["shorts".into(), "brief".into()];
+        let mut search = MySearch {
+            min_duration_override: Some(1500),
+            ..MySearch::default()
+        };
+        assert_eq!(
+            check_preset_conflicts(&defaults, &search),
+            vec![Conflict::DurationBuckets { param: "long" }]
+        );
+
+        defaults.active_duration_bucket_ids.push("medium".into());
+        assert!(check_preset_conflicts(&defaults, &search).is_empty());
+
+        search.min_duration_override = Some(60);
+        defaults.active_duration_bucket_ids = vec!["shorts".into()];
+        assert!(check_preset_conflicts(&defaults, &search).is_empty());
+    }
+
+    #[test]
+    fn captions_without_english_or_verification_conflict() {
+        let mut defaults = search_defaults();
+        let mut search = MySearch {
+            require_captions_override: Some(true),
+            english_only_override: Some(false),
+            ..MySearch::default()
+        };
+        assert_eq!(
+            check_preset_conflicts(&defaults, &search),
+            vec![Conflict::CaptionsAnyLanguage]
+        );
+
+        defaults.verify_captions_with_oauth = true;
+        assert!(check_preset_conflicts(&defaults, &search).is_empty());
+
+        defaults.verify_captions_with_oauth = false;
+        search.english_only_override = None;
+        assert!(check_preset_conflicts(&defaults, &search).is_empty());
+    }
+
+    #[test]
+    fn not_terms_repeated_in_all_terms_conflict() {
+        let mut search = MySearch::default();
+        search.query.all_terms = vec!["Rust".into(), "tutorial".into()];
+        search.query.not_terms = vec!["rust ".into(), "reaction".into()];
+        assert_eq!(
+            check_preset_conflicts(&search_defaults(), &search),
+            vec![Conflict::NotTermInAll("rust".into())]
+        );
+    }
+
+    #[test]
+    fn window_override_outside_default_window_conflicts() {
+        let defaults = search_defaults();
+        let window = |start: &str, end: &str| MySearch {
+            window_override: Some(TimeWindow {
+                start_rfc3339: start.into(),
+                end_rfc3339: end.into(),
+            }),
+            ..MySearch::default()
+        };
+        let old = window("2001-01-01T00:00:00Z", "2001-02-01T00:00:00Z");
+        assert_eq!(
+            check_preset_conflicts(&defaults, &old),
+            vec![Conflict::WindowOutsideDefault]
+        );
+
+        let covering = window("2001-01-01T00:00:00Z", "2999-01-01T00:00:00Z");
+        assert!(check_preset_conflicts(&defaults, &covering).is_empty());
+
+        let all_time = SearchDefaults {
+            default_window: crate::prefs::TimeWindowPreset::AllTime,
+            ..search_defaults()
+        };
+        assert!(check_preset_conflicts(&all_time, &old).is_empty());
+    }
+
+    #[test]
+    fn long_queries_warn_unless_split() {
+        let mut search = MySearch::default();
+        search.query.any_terms = (0..12).map(|n| format!("term number {n}")).collect();
+        let length = search_runner::query_length(&search.query);
+        assert_eq!(
+            check_preset_conflicts(&search_defaults(), &search),
+            vec![Conflict::QueryTooLong { length }]
+        );
+        assert!(
+            Conflict::QueryTooLong { length }
+                .message()
+                .starts_with(&format!("The query is {length} characters"))
+        );
+
+        search.split_long_query = true;
+        assert!(check_preset_conflicts(&search_defaults(), &search).is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+use serde_json::Value;
+
+use super::MySearch;
+
+/// What replacing one preset list with another would do, matching presets by id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PresetDiff {
+    /// Names of incoming presets whose id is not in the current list.
+    pub added: Vec<String>,
+    /// Names of current presets whose id is not in the incoming list.
+    pub removed: Vec<String>,
+    pub changed: Vec<ChangedPreset>,
+}
+
+/// A preset present on both sides with at least one differing field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedPreset {
+    /// The incoming name.
+    pub name: String,
+    /// Dotted paths of the differing fields, e.g. "enabled" or "query.any_terms".
+    pub fields: Vec<String>,
+}
+
+impl PresetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// e.g. "Removes 2 presets: Old, Stale. Adds 1: New. Modifies 1: Rust."
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "No changes: the imported presets match the current ones.".into();
+        }
+        let mut parts = Vec::new();
+        if !self.removed.is_empty() {
+            parts.push(format!(
+                "Removes {} preset{}: {}.",
+                self.removed.len(),
+                if self.removed.len() == 1 { "" } else { "s" },
+                self.removed.join(", ")
+            ));
+        }
+        if !self.added.is_empty() {
+            parts.push(format!(
+                "Adds {}: {}.",
+                self.added.len(),
+                self.added.join(", ")
+            ));
+        }
+        if !self.changed.is_empty() {
+            let names: Vec<&str> = self.changed.iter().map(|c| c.name.as_str()).collect();
+            parts.push(format!(
+                "Modifies {}: {}.",
+                self.changed.len(),
+                names.join(", ")
+            ));
+        }
+        parts.join(" ")
+    }
+}
+
+/// Compare the current presets with an incoming list that would replace them.
+pub fn diff_presets(current: &[MySearch], incoming: &[MySearch]) -> PresetDiff {
+    let mut diff = PresetDiff::default();
+    for new in incoming {
+        match current.iter().find(|old| old.id == new.id) {
+            None => diff.added.push(new.name.clone()),
+            Some(old) => {
+                let mut fields = Vec::new();
+                changed_fields("", &to_value(old), &to_value(new), &mut fields);
+                if !fields.is_empty() {
+                    diff.changed.push(ChangedPreset {
+                        name: new.name.clone(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    diff.removed = current
+        .iter()
+        .filter(|old| !incoming.iter().any(|new| new.id == old.id))
+        .map(|old| old.name.clone())
+        .collect();
+    diff
+}
+
+fn to_value(search: &MySearch) -> Value {
+    serde_json::to_value(search).unwrap_or(Value::Null)
+}
+
+/// Push the path of every leaf that differs, descending into objects on both sides.
+fn changed_fields(prefix: &str, old: &Value, new: &Value, out: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys = old
+                .keys()
+                .chain(new.keys().filter(|key| !old.contains_key(*key)));
+            for key in keys {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                let missing = Value::Null;
+                changed_fields(
+                    &path,
+                    old.get(key).unwrap_or(&missing),
+                    new.get(key).unwrap_or(&missing),
+                    out,
+                );
+            }
+        }
+        _ if old != new => out.push(prefix.to_owned()),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(id: &str, name: &str) -> MySearch {
+        MySearch {
+            id: id.into(),
+            name: name.into(),
+            ..MySearch::default()
+        }
+    }
+
+    #[test]
+    fn identical_lists_have_no_diff() {
+        let list = vec![preset("a", "Alpha"), preset("b", "Beta")];
+        let diff = diff_presets(&list, &list);
+        assert!(diff.is_empty());
+        assert!(diff.summary().starts_with("No changes"));
+    }
+
+    #[test]
+    fn presets_are_matched_by_id() {
+        let current = vec![
+            preset("a", "Alpha"),
+            preset("b", "Beta"),
+            preset("c", "Gamma"),
+        ];
+        let mut renamed = preset("a", "Alpha 2");
+        renamed.enabled = !renamed.enabled;
+        renamed.query.any_terms = vec!["rust".into()];
+        let incoming = vec![renamed, preset("b", "Beta"), preset("d", "Delta")];
+
+        let diff = diff_presets(&current, &incoming);
+        assert_eq!(diff.added, vec!["Delta"]);
+        assert_eq!(diff.removed, vec!["Gamma"]);
+        assert_eq!(
+            diff.changed,
+            vec![ChangedPreset {
+                name: "Alpha 2".into(),
+                fields: vec!["enabled".into(), "name".into(), "query.any_terms".into()],
+            }]
+        );
+        assert_eq!(
+            diff.summary(),
+            "Removes 1 preset: Gamma. Adds 1: Delta. Modifies 1: Alpha 2."
+        );
+    }
+
+    #[test]
+    fn nested_option_fields_report_their_path() {
+        let current = vec![preset("a", "Alpha")];
+        let mut changed = preset("a", "Alpha");
+        changed.window_override = Some(crate::prefs::TimeWindow {
+            start_rfc3339: "2024-01-01T00:00:00Z".into(),
+            end_rfc3339: "2024-02-01T00:00:00Z".into(),
+        });
+        changed.min_duration_override = Some(60);
+        let diff = diff_presets(&current, &[changed]);
+        assert_eq!(
+            diff.changed[0].fields,
+            vec!["min_duration_override", "window_override"]
+        );
+    }
+}
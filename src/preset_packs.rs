@@ -0,0 +1,82 @@
+use crate::prefs::MySearch;
+
+/// A curated set of presets shipped with the app and offered in the preset gallery.
+pub struct PresetPack {
+    pub name: &'static str,
+    pub description: &'static str,
+    json: &'static str,
+}
+
+pub const PACKS: &[PresetPack] = &[
+    PresetPack {
+        name: "Rust & systems programming",
+        description: "Language releases, conference talks, and low-level deep dives.",
+        json: include_str!("preset_packs/rust_systems.json"),
+    },
+    PresetPack {
+        name: "Space launches",
+        description: "Launch streams and mission explainers.",
+        json: include_str!("preset_packs/space_launches.json"),
+    },
+    PresetPack {
+        name: "Film essays",
+        description: "Video essays on film and practical filmmaking breakdowns.",
+        json: include_str!("preset_packs/film_essays.json"),
+    },
+];
+
+impl PresetPack {
+    /// The pack's presets, each grouped under the pack name so they cluster in the sidebar.
+    pub fn presets(&self) -> Result<Vec<MySearch>, String> {
+        let mut presets: Vec<MySearch> = serde_json::from_str(self.json)
+            .map_err(|err| format!("Preset pack '{}' is invalid: {err}", self.name))?;
+        for preset in &mut presets {
+            preset.group = Some(self.name.to_owned());
+        }
+        Ok(presets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::prefs::{self, SearchDefaults};
+
+    #[test]
+    fn every_bundled_pack_parses_and_validates() {
+        let defaults = SearchDefaults::default();
+        let builtin_ids: HashSet<String> = prefs::builtin_default()
+            .searches
+            .into_iter()
+            .map(|search| search.id)
+            .collect();
+        let mut ids = HashSet::new();
+        for pack in PACKS {
+            let presets = pack.presets().unwrap();
+            assert!(!presets.is_empty(), "{} is empty", pack.name);
+            for preset in presets {
+                assert!(
+                    !preset.name.trim().is_empty(),
+                    "unnamed preset in {}",
+                    pack.name
+                );
+                assert!(
+                    preset.description.is_some(),
+                    "{} has no description",
+                    preset.name
+                );
+                assert_eq!(preset.group.as_deref(), Some(pack.name));
+                assert!(
+                    !builtin_ids.contains(&preset.id),
+                    "{} shadows a built-in",
+                    preset.id
+                );
+                assert!(ids.insert(preset.id.clone()), "duplicate id {}", preset.id);
+                let conflicts = prefs::check_preset_conflicts(&defaults, &preset);
+                assert!(conflicts.is_empty(), "{}: {conflicts:?}", preset.name);
+            }
+        }
+    }
+}
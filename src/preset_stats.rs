@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config_dir;
+use crate::search_runner::PresetRun;
+
+/// What a preset's most recent run fetched.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PresetRunStats {
+    pub ran_at_unix: i64,
+    pub pages: usize,
+    pub raw: usize,
+    /// Videos that passed the filters, before merging with other presets.
+    pub kept: usize,
+}
+
+impl PresetRunStats {
+    /// e.g. "2 page(s), raw 87, kept 41".
+    pub fn summary(&self) -> String {
+        format!(
+            "{} page(s), raw {}, kept {}",
+            self.pages, self.raw, self.kept
+        )
+    }
+}
+
+/// Last run of each preset, keyed by preset ID, persisted as preset_stats.json.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct PresetStats {
+    pub runs: BTreeMap<String, PresetRunStats>,
+}
+
+impl PresetStats {
+    pub fn last_run(&self, preset_id: &str) -> Option<&PresetRunStats> {
+        self.runs.get(preset_id)
+    }
+
+    /// Record every preset a run executed, including those that returned nothing.
+    pub fn record(&mut self, runs: &[PresetRun], now: i64) {
+        for run in runs {
+            self.runs.insert(
+                run.preset_id.clone(),
+                PresetRunStats {
+                    ran_at_unix: now,
+                    pages: run.pages,
+                    raw: run.raw,
+                    kept: run.kept,
+                },
+            );
+        }
+    }
+}
+
+fn preset_stats_path() -> PathBuf {
+    config_dir::get().preset_stats_path()
+}
+
+pub fn load() -> PresetStats {
+    fs::read(preset_stats_path())
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(stats: &PresetStats) -> std::io::Result<()> {
+    if !config_dir::get().writable {
+        return Ok(());
+    }
+    let path = preset_stats_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(stats)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_executed_preset_is_recorded_even_with_nothing_kept() {
+        let mut stats = PresetStats::default();
+        let run = |id: &str, kept: usize| PresetRun {
+            preset_id: id.into(),
+            pages: 1,
+            raw: 3,
+            kept,
+        };
+        stats.record(&[run("a", 2), run("b", 0)], 100);
+        stats.record(&[run("a", 5)], 200);
+
+        assert_eq!(stats.last_run("a").unwrap().ran_at_unix, 200);
+        assert_eq!(
+            stats.last_run("a").unwrap().summary(),
+            "1 page(s), raw 3, kept 5"
+        );
+        assert_eq!(stats.last_run("b").unwrap().ran_at_unix, 100);
+        assert!(stats.last_run("c").is_none());
+    }
+}
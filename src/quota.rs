@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, UtcOffset};
+
+/// YouTube's default daily allowance for a Data API project.
+pub const DEFAULT_DAILY_BUDGET: u32 = 10_000;
+/// Units charged per `search.list` page.
+pub const SEARCH_PAGE_COST: u32 = 100;
+/// Units charged per `videos.list` or `channels.list` call.
+pub const LIST_CALL_COST: u32 = 1;
+
+/// Units spent on the current quota day, persisted in prefs.json.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct QuotaUsage {
+    /// Quota day the units belong to, as `YYYY-MM-DD`.
+    pub day: String,
+    pub units: u32,
+}
+
+impl QuotaUsage {
+    pub fn used_on(&self, day: &str) -> u32 {
+        if self.day == day { self.units } else { 0 }
+    }
+
+    pub fn record(&mut self, day: &str, units: u32) {
+        if self.day != day {
+            self.day = day.to_owned();
+            self.units = 0;
+        }
+        self.units = self.units.saturating_add(units);
+    }
+}
+
+/// The quota day resets at midnight Pacific; a fixed UTC-8 offset is close enough.
+pub fn quota_day(now: OffsetDateTime) -> String {
+    let pacific = UtcOffset::from_hms(-8, 0, 0).expect("valid offset");
+    now.to_offset(pacific).date().to_string()
+}
+
+/// Page sources a run reads from, split by how much a page costs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunScopes {
+    /// `search.list` scopes; per-channel presets contribute one per channel.
+    pub searches: usize,
+    /// Uploads playlists read with `playlistItems.list`.
+    pub uploads: usize,
+}
+
+/// Worst-case units for paging through `scopes` searches at up to `max_pages` pages each:
+/// every page costs a search plus a `videos.list` lookup, and each scope is budgeted one
+/// `channels.list` call. Per-channel presets contribute one scope per channel.
+pub fn estimate_run_cost(scopes: usize, max_pages: usize) -> u32 {
+    let per_page = SEARCH_PAGE_COST + LIST_CALL_COST;
+    let per_scope = per_page.saturating_mul(max_pages as u32) + LIST_CALL_COST;
+    per_scope.saturating_mul(scopes as u32)
+}
+
+/// Worst-case units for reading `uploads` playlists at up to `max_pages` pages each: a
+/// `playlistItems.list` plus a `videos.list` per page, and one `channels.list` call per 50
+/// channels for the playlist lookup and again for channel details.
+pub fn estimate_uploads_cost(uploads: usize, max_pages: usize) -> u32 {
+    let per_page = 2 * LIST_CALL_COST;
+    let pages = per_page.saturating_mul(max_pages as u32);
+    let lookups = 2 * LIST_CALL_COST * uploads.div_ceil(50) as u32;
+    pages.saturating_mul(uploads as u32).saturating_add(lookups)
+}
+
+fn estimate_scopes(scopes: RunScopes, max_pages: usize) -> u32 {
+    estimate_run_cost(scopes.searches, max_pages)
+        .saturating_add(estimate_uploads_cost(scopes.uploads, max_pages))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaCheck {
+    Fits,
+    /// The run would overshoot the remaining budget. `fewer_pages` is the largest page cap
+    /// that still fits, if any.
+    Exceeds {
+        estimated: u32,
+        remaining: u32,
+        fewer_pages: Option<usize>,
+    },
+}
+
+pub fn check_run(scopes: RunScopes, max_pages: usize, budget: u32, used_today: u32) -> QuotaCheck {
+    let remaining = budget.saturating_sub(used_today);
+    let estimated = estimate_scopes(scopes, max_pages);
+    if estimated <= remaining {
+        return QuotaCheck::Fits;
+    }
+    let fewer_pages = (1..max_pages)
+        .rev()
+        .find(|pages| estimate_scopes(scopes, *pages) <= remaining);
+    QuotaCheck::Exceeds {
+        estimated,
+        remaining,
+        fewer_pages,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_counts_search_and_lookup_calls() {
+        assert_eq!(estimate_run_cost(1, 1), 102);
+        assert_eq!(estimate_run_cost(3, 2), 3 * (2 * 101 + 1));
+        assert_eq!(estimate_run_cost(0, 5), 0);
+    }
+
+    #[test]
+    fn uploads_pages_cost_list_units_not_search_units() {
+        assert_eq!(estimate_uploads_cost(1, 5), 10 + 2);
+        assert_eq!(estimate_uploads_cost(60, 1), 120 + 4);
+        assert_eq!(estimate_uploads_cost(0, 5), 0);
+        let mixed = RunScopes {
+            searches: 1,
+            uploads: 3,
+        };
+        assert_eq!(check_run(mixed, 1, 10_000, 9_890), QuotaCheck::Fits);
+    }
+
+    fn searches(searches: usize) -> RunScopes {
+        RunScopes {
+            searches,
+            uploads: 0,
+        }
+    }
+
+    #[test]
+    fn run_that_fits_is_allowed() {
+        assert_eq!(check_run(searches(2), 2, 10_000, 9_000), QuotaCheck::Fits);
+    }
+
+    #[test]
+    fn overshoot_offers_fewer_pages_when_they_fit() {
+        let check = check_run(searches(2), 5, 10_000, 9_500);
+        assert_eq!(
+            check,
+            QuotaCheck::Exceeds {
+                estimated: estimate_run_cost(2, 5),
+                remaining: 500,
+                fewer_pages: Some(2),
+            }
+        );
+        assert!(estimate_run_cost(2, 2) <= 500);
+    }
+
+    #[test]
+    fn overshoot_without_room_for_one_page_offers_nothing() {
+        let check = check_run(searches(4), 2, 10_000, 9_900);
+        assert_eq!(
+            check,
+            QuotaCheck::Exceeds {
+                estimated: estimate_run_cost(4, 2),
+                remaining: 100,
+                fewer_pages: None,
+            }
+        );
+    }
+
+    #[test]
+    fn usage_resets_on_a_new_day() {
+        let mut usage = QuotaUsage::default();
+        usage.record("2026-10-16", 300);
+        usage.record("2026-10-16", 200);
+        assert_eq!(usage.used_on("2026-10-16"), 500);
+        assert_eq!(usage.used_on("2026-10-17"), 0);
+        usage.record("2026-10-17", 101);
+        assert_eq!(usage.used_on("2026-10-17"), 101);
+        assert_eq!(usage.used_on("2026-10-16"), 0);
+    }
+}
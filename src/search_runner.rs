@@ -1,12 +1,17 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::mpsc::Sender;
 
 use anyhow::{Result, bail};
 use time::{Duration, OffsetDateTime, format_description::well_known::Rfc3339};
 
-use crate::filters;
-use crate::prefs::{self, GlobalPrefs, MySearch, Prefs, QuerySpec, TimeWindow, TimeWindowPreset};
+use crate::filters::{self, PostFilterVerdict};
+use crate::prefs::{
+    self, DuplicatePolicy, MySearch, QuerySpec, SearchDefaults, TimeWindow, TimeWindowPreset,
+};
+use crate::quota;
 use crate::yt::{
-    channels, search,
+    channels, http, playlists, search,
     types::{SearchListResponse, VideoDetails, VideoItem},
     videos,
 };
@@ -14,8 +19,11 @@ use anyhow::Context;
 use std::env;
 
 const DEFAULT_MAX_SEARCH_PAGES: usize = 2;
+/// Roughly where YouTube starts truncating or rejecting `q`, in URL-encoded bytes.
+pub const QUERY_LENGTH_LIMIT: usize = 100;
 
-fn max_search_pages() -> usize {
+/// Page cap per preset, overridable through `YTSEARCH_MAX_SEARCH_PAGES`.
+pub fn max_search_pages() -> usize {
     match env::var("YTSEARCH_MAX_SEARCH_PAGES") {
         Ok(val) => val
             .trim()
@@ -27,11 +35,91 @@ fn max_search_pages() -> usize {
     }
 }
 
+/// Coarse stage updates sent while a search runs, so the UI can say more than "Searching...".
+#[derive(Debug, Clone)]
+pub enum SearchProgress {
+    /// About to fetch `search.list` page `page` of at most `max_pages` for a preset.
+    Page {
+        preset: String,
+        page: usize,
+        max_pages: usize,
+    },
+    /// About to look up `videos.list` details for this many videos.
+    FetchingDetails(usize),
+    /// About to look up `channels.list` names for this many channels.
+    ResolvingChannels(usize),
+    /// A preset finished and kept `kept` videos. With [`ProgressSink::with_preset_videos`]
+    /// the videos ride along, as they were before being folded with other presets' (so
+    /// `source_presets` names only this one), capped, and sorted.
+    PresetDone {
+        preset: String,
+        kept: usize,
+        videos: Option<Vec<VideoDetails>>,
+    },
+}
+
+impl fmt::Display for SearchProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Page {
+                preset,
+                page,
+                max_pages,
+            } => write!(f, "Preset '{preset}': page {page}/{max_pages}"),
+            Self::FetchingDetails(count) => write!(f, "Fetching details for {count} videos"),
+            Self::ResolvingChannels(count) => write!(f, "Resolving {count} channels"),
+            Self::PresetDone { preset, kept, .. } => {
+                write!(f, "Preset '{preset}': kept {kept} videos")
+            }
+        }
+    }
+}
+
+/// Where [`run_searches`] sends [`SearchProgress`] updates.
+#[derive(Debug, Clone)]
+pub struct ProgressSink {
+    tx: Sender<SearchProgress>,
+    preset_videos: bool,
+}
+
+impl ProgressSink {
+    pub fn new(tx: Sender<SearchProgress>) -> Self {
+        Self {
+            tx,
+            preset_videos: false,
+        }
+    }
+
+    /// Attach each preset's kept videos to [`SearchProgress::PresetDone`]. Off by default,
+    /// since it copies every video a second time.
+    pub fn with_preset_videos(mut self) -> Self {
+        self.preset_videos = true;
+        self
+    }
+}
+
+impl From<Sender<SearchProgress>> for ProgressSink {
+    fn from(tx: Sender<SearchProgress>) -> Self {
+        Self::new(tx)
+    }
+}
+
+fn report(progress: Option<&ProgressSink>, event: SearchProgress) {
+    if let Some(sink) = progress {
+        // The receiver going away only means nobody is watching any more.
+        let _ = sink.tx.send(event);
+    }
+}
+
+#[non_exhaustive]
 pub enum RunMode {
     Any,
     Single(String),
+    /// Run a preset that is not (or not yet) saved in prefs, e.g. from the editor's Test button.
+    AdHoc(Box<MySearch>),
 }
 
+#[non_exhaustive]
 pub struct SearchOutcome {
     pub videos: Vec<VideoDetails>,
     pub presets_ran: usize,
@@ -41,56 +129,200 @@ pub struct SearchOutcome {
     pub raw_items: usize,
     pub unique_ids: usize,
     pub passed_filters: usize,
+    /// Results dropped because a title-strict preset's terms were missing from the title.
+    pub dropped_title_mismatch: usize,
+    /// Results dropped by `SearchDefaults::strict_all_terms` for lacking an all-term.
+    pub dropped_missing_terms: usize,
+    pub region_code: Option<String>,
+    /// API units spent by the run.
+    pub quota_units: u32,
+    /// `@handle` keys looked up before searching: `Some(id)` when found, `None` when no channel
+    /// has that handle. Lookups that failed outright are left out so they are retried.
+    pub resolved_handles: Vec<(String, Option<String>)>,
+    /// Preset pairs that returned the same videos, most shared first. Single and ad-hoc runs
+    /// have one preset, so they leave this empty.
+    pub preset_overlaps: Vec<PresetOverlap>,
+    /// Every `search.list` page with what became of each item, when
+    /// `SearchDefaults::keep_raw_responses` is on; empty otherwise.
+    pub raw_pages: Vec<RawPage>,
+    /// Oldest results dropped to stay under `SearchDefaults::max_total_results`.
+    pub dropped_over_cap: usize,
+    /// One entry per preset the run executed, in run order.
+    pub preset_runs: Vec<PresetRun>,
+    /// Local clock minus the API server's, measured only when no preset returned anything:
+    /// a clock far ahead puts `publishedAfter` in the future. See [`http::clock_skew_warning`].
+    pub clock_skew_secs: Option<i64>,
+}
+
+/// What one preset fetched during a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetRun {
+    pub preset_id: String,
+    pub pages: usize,
+    pub raw: usize,
+    /// Videos that passed the filters, before merging with other presets.
+    pub kept: usize,
+}
+
+/// One `search.list` page as returned, kept for debugging result quality.
+#[derive(Debug, Clone)]
+pub struct RawPage {
+    pub preset: String,
+    /// Channel the page was scoped to, for per-channel presets.
+    pub channel_scope: Option<String>,
+    /// 1-based page number within the scope.
+    pub page: usize,
+    pub items: Vec<RawItem>,
+}
+
+/// A `search.list` item: its ID, the snippet fields parsed from it, and its fate.
+#[derive(Debug, Clone)]
+pub struct RawItem {
+    pub video_id: String,
+    pub published_at: String,
+    pub fate: RawFate,
+}
+
+/// The stage at which a raw item was kept or dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawFate {
+    Kept,
+    /// Already returned by an earlier page of the same preset.
+    Duplicate,
+    /// `videos.list` returned no details for it (removed or private).
+    MissingFromVideos,
+    /// Dropped by the post-filters (duration, language, blocked or denied channel, terms).
+    Filtered,
+    /// Dropped because a title-strict preset's terms were missing from the title.
+    TitleMismatch,
+    /// Dropped by `strict_all_terms` for lacking one of the preset's all-terms.
+    MissingAllTerms,
+}
+
+impl RawFate {
+    pub fn label(self) -> &'static str {
+        match self {
+            RawFate::Kept => "kept",
+            RawFate::Duplicate => "dup",
+            RawFate::MissingFromVideos => "videos.list missing",
+            RawFate::Filtered => "post-filter",
+            RawFate::TitleMismatch => "title mismatch",
+            RawFate::MissingAllTerms => "missing all-term",
+        }
+    }
+}
+
+/// Two presets and how many videos both returned in one run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetOverlap {
+    pub first: String,
+    pub second: String,
+    pub shared: usize,
 }
 
 struct SingleSearchOutcome {
     videos: Vec<VideoDetails>,
     pages_fetched: usize,
+    quota_units: u32,
+    dropped_title_mismatch: usize,
+    dropped_missing_terms: usize,
     duplicates_within: usize,
     raw_items: usize,
     unique_ids: usize,
+    raw_pages: Vec<RawPage>,
 }
 
-pub async fn run_searches(prefs: Prefs, mode: RunMode) -> Result<SearchOutcome> {
-    let Prefs {
-        api_key,
-        mut global,
-        searches,
-        blocked_channels,
-    } = prefs;
+/// Page sources a run in `mode` would read: `search.list` scopes, counting each per-channel
+/// scope separately, and uploads playlists for presets in uploads mode.
+pub fn run_scope_count(searches: &[MySearch], mode: &RunMode) -> quota::RunScopes {
+    let scope_count = |search: &MySearch| {
+        let uploads = upload_channel_ids(search).len();
+        if uses_uploads_playlist(search) && uploads > 0 {
+            quota::RunScopes {
+                searches: 0,
+                uploads,
+            }
+        } else {
+            quota::RunScopes {
+                searches: search_scopes(search).len() * query_variants(search).len(),
+                uploads: 0,
+            }
+        }
+    };
+    let total = |scopes: Vec<quota::RunScopes>| {
+        scopes
+            .into_iter()
+            .fold(quota::RunScopes::default(), |sum, s| quota::RunScopes {
+                searches: sum.searches + s.searches,
+                uploads: sum.uploads + s.uploads,
+            })
+    };
+    match mode {
+        RunMode::Any => total(
+            searches
+                .iter()
+                .filter(|s| s.enabled)
+                .map(scope_count)
+                .collect(),
+        ),
+        RunMode::Single(id) => searches.iter().find(|s| &s.id == id).map_or(
+            quota::RunScopes {
+                searches: 1,
+                uploads: 0,
+            },
+            scope_count,
+        ),
+        RunMode::AdHoc(search) => scope_count(search),
+    }
+}
 
-    prefs::normalize_duration_filters(&mut global);
+/// Run the presets selected by `mode`. `max_pages` overrides [`max_search_pages`] for this run;
+/// stage updates go to `progress` when given.
+pub async fn run_searches(
+    api_key: String,
+    mut defaults: SearchDefaults,
+    searches: Vec<MySearch>,
+    blocked_channels: Vec<String>,
+    mode: RunMode,
+    max_pages: Option<usize>,
+    progress: Option<ProgressSink>,
+) -> Result<SearchOutcome> {
+    http::configure(&defaults);
+    http::reset_clock_skew();
+
+    prefs::normalize_duration_filters(&mut defaults);
 
     let api_key = api_key.trim().to_owned();
     if api_key.is_empty() {
         bail!("Set your YouTube Data API key in the settings panel first.");
     }
 
-    if searches.is_empty() {
+    if searches.is_empty() && !matches!(mode, RunMode::AdHoc(_)) {
         bail!("No searches configured. Add a preset in the settings panel.");
     }
 
-    let (targets, is_any_mode): (Vec<MySearch>, bool) = match mode {
+    let mut targets: Vec<MySearch> = match mode {
         RunMode::Any => {
             let enabled: Vec<MySearch> = searches.into_iter().filter(|s| s.enabled).collect();
             if enabled.is_empty() {
                 bail!("Enable at least one preset before running in Any mode.");
             }
-            (enabled, true)
+            enabled
         }
         RunMode::Single(selected_id) => {
             let mut iter = searches.into_iter();
             if let Some(search) = iter.find(|s| s.id == selected_id) {
-                (vec![search], false)
+                vec![search]
             } else {
                 bail!("Preset '{}' not found.", selected_id);
             }
         }
+        RunMode::AdHoc(search) => vec![*search],
     };
 
-    let blocked_keys = prefs::blocked_keys(&blocked_channels);
+    let blocked = filters::BlockedSet::new(&blocked_channels);
+    let max_pages = max_pages.unwrap_or_else(max_search_pages).max(1);
 
-    let mut index_by_id: HashMap<String, usize> = HashMap::new();
     let mut aggregated: Vec<VideoDetails> = Vec::new();
     let mut total_pages = 0usize;
     let mut presets_ran = 0usize;
@@ -99,41 +331,52 @@ pub async fn run_searches(prefs: Prefs, mode: RunMode) -> Result<SearchOutcome>
     let mut total_raw_items = 0usize;
     let mut total_unique_ids = 0usize;
     let mut total_passed_filters = 0usize;
+    let (resolved_handles, mut quota_units) = resolve_handles(&api_key, &mut targets).await;
+    let mut dropped_title_mismatch = 0usize;
+    let mut dropped_missing_terms = 0usize;
+    let mut raw_pages: Vec<RawPage> = Vec::new();
+    let mut preset_runs: Vec<PresetRun> = Vec::new();
 
     for search in targets {
-        let outcome = run_single_search(&api_key, &global, &search, &blocked_keys).await?;
+        let outcome = run_single_search(
+            &api_key,
+            &defaults,
+            &search,
+            &blocked,
+            max_pages,
+            true,
+            progress.as_ref(),
+        )
+        .await?;
         presets_ran += 1;
         total_pages += outcome.pages_fetched;
+        quota_units += outcome.quota_units;
+        dropped_title_mismatch += outcome.dropped_title_mismatch;
+        dropped_missing_terms += outcome.dropped_missing_terms;
         duplicates_within_presets += outcome.duplicates_within;
         total_raw_items += outcome.raw_items;
         total_unique_ids += outcome.unique_ids;
+        raw_pages.extend(outcome.raw_pages);
+        preset_runs.push(PresetRun {
+            preset_id: search.id.clone(),
+            pages: outcome.pages_fetched,
+            raw: outcome.raw_items,
+            kept: outcome.videos.len(),
+        });
 
-        let mut videos = outcome.videos;
-        total_passed_filters += videos.len();
-
-        if is_any_mode {
-            for video in videos.drain(..) {
-                if let Some(idx) = index_by_id.get(&video.id).copied() {
-                    let existing = &mut aggregated[idx];
-                    let new_sources = video.source_presets.clone();
-                    for source in new_sources {
-                        if !existing.source_presets.iter().any(|s| s == &source) {
-                            existing.source_presets.push(source);
-                        }
-                    }
-                    duplicates_across_presets += 1;
-                } else {
-                    let idx = aggregated.len();
-                    index_by_id.insert(video.id.clone(), idx);
-                    aggregated.push(video);
-                }
-            }
-        } else {
-            aggregated.append(&mut videos);
-        }
+        total_passed_filters += outcome.videos.len();
+        duplicates_across_presets +=
+            merge_with_policy(defaults.duplicate_policy, &mut aggregated, outcome.videos);
     }
 
     aggregated.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+    let dropped_over_cap = cap_newest(&mut aggregated, defaults.max_total_results);
+    let preset_overlaps = preset_overlaps(&aggregated);
+    let clock_skew_secs = if total_raw_items == 0 {
+        http::last_clock_skew()
+    } else {
+        None
+    };
 
     Ok(SearchOutcome {
         videos: aggregated,
@@ -144,93 +387,630 @@ pub async fn run_searches(prefs: Prefs, mode: RunMode) -> Result<SearchOutcome>
         raw_items: total_raw_items,
         unique_ids: total_unique_ids,
         passed_filters: total_passed_filters,
+        dropped_title_mismatch,
+        dropped_missing_terms,
+        region_code: defaults.region_code.clone(),
+        quota_units,
+        resolved_handles,
+        preset_overlaps,
+        raw_pages,
+        dropped_over_cap,
+        preset_runs,
+        clock_skew_secs,
     })
 }
 
-async fn run_single_search(
+/// Keep the `cap` newest videos and drop the rest; a cap of 0 keeps everything. Returns how
+/// many were dropped.
+pub fn cap_newest(videos: &mut Vec<VideoDetails>, cap: usize) -> usize {
+    if cap == 0 || videos.len() <= cap {
+        return 0;
+    }
+    videos.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+    let dropped = videos.len() - cap;
+    videos.truncate(cap);
+    dropped
+}
+
+/// Count, for every pair of presets, the merged videos tagged by both. Pairs are ordered by
+/// shared count, then by name.
+pub fn preset_overlaps(videos: &[VideoDetails]) -> Vec<PresetOverlap> {
+    let mut counts: BTreeMap<(String, String), usize> = BTreeMap::new();
+    for video in videos {
+        let sources = &video.source_presets;
+        for (i, a) in sources.iter().enumerate() {
+            for b in &sources[i + 1..] {
+                let pair = if a <= b {
+                    (a.clone(), b.clone())
+                } else {
+                    (b.clone(), a.clone())
+                };
+                *counts.entry(pair).or_default() += 1;
+            }
+        }
+    }
+    let mut overlaps: Vec<PresetOverlap> = counts
+        .into_iter()
+        .map(|((first, second), shared)| PresetOverlap {
+            first,
+            second,
+            shared,
+        })
+        .collect();
+    overlaps.sort_by_key(|overlap| std::cmp::Reverse(overlap.shared));
+    overlaps
+}
+
+/// Look up channel IDs for `@handle` allow/deny entries that have none yet, so scoped searches
+/// and matching use the exact ID. Handles no channel owns stay as text patterns. Also returns
+/// the quota units spent: every lookup attempted, including ones that failed.
+async fn resolve_handles(
+    api_key: &str,
+    targets: &mut [MySearch],
+) -> (Vec<(String, Option<String>)>, u32) {
+    let mut pending: Vec<String> = targets
+        .iter()
+        .flat_map(|search| {
+            search
+                .query
+                .channel_allow
+                .iter()
+                .chain(search.query.channel_deny.iter())
+        })
+        .filter_map(|channel| channel.unresolved_handle())
+        .collect();
+    pending.sort();
+    pending.dedup();
+
+    let quota_units = pending.len() as u32 * quota::LIST_CALL_COST;
+    let mut resolved = Vec::new();
+    for key in pending {
+        match crate::yt::resolve_handle(api_key, &key).await {
+            Ok(found) => resolved.push((key, found)),
+            Err(err) => eprintln!("Handle lookup for {key} failed: {err}"),
+        }
+    }
+
+    for search in targets.iter_mut() {
+        let refs = search
+            .query
+            .channel_allow
+            .iter_mut()
+            .chain(search.query.channel_deny.iter_mut());
+        for channel in refs {
+            let Some(key) = channel.unresolved_handle() else {
+                continue;
+            };
+            if let Some((_, Some(id))) = resolved.iter().find(|(k, _)| *k == key) {
+                channel.id = Some(id.clone());
+            }
+        }
+    }
+    (resolved, quota_units)
+}
+
+/// Append `incoming` to `target`, folding videos already present into one entry with merged
+/// `source_presets`. Returns how many incoming videos were duplicates.
+pub fn merge_by_id(target: &mut Vec<VideoDetails>, incoming: Vec<VideoDetails>) -> usize {
+    merge_by_keys(target, incoming, |video| vec![video.id.clone()])
+}
+
+/// Append `incoming` to `target`, folding duplicates as `policy` says. Returns how many
+/// incoming videos were folded into an existing entry.
+pub fn merge_with_policy(
+    policy: DuplicatePolicy,
+    target: &mut Vec<VideoDetails>,
+    incoming: Vec<VideoDetails>,
+) -> usize {
+    match policy {
+        DuplicatePolicy::DedupById => merge_by_id(target, incoming),
+        DuplicatePolicy::DedupByIdAndChannel => merge_by_keys(target, incoming, |video| {
+            let mut keys = vec![video.id.clone()];
+            let title = video.title.split_whitespace().collect::<Vec<_>>().join(" ");
+            let channel = video.channel_handle.trim();
+            if !title.is_empty() && !channel.is_empty() {
+                keys.push(format!(
+                    "{}\t{}",
+                    channel.to_ascii_lowercase(),
+                    title.to_lowercase()
+                ));
+            }
+            keys
+        }),
+        DuplicatePolicy::KeepAll => {
+            target.extend(incoming);
+            0
+        }
+    }
+}
+
+/// Fold each incoming video into the first entry sharing any of its `keys`, or append it.
+fn merge_by_keys(
+    target: &mut Vec<VideoDetails>,
+    incoming: Vec<VideoDetails>,
+    keys: impl Fn(&VideoDetails) -> Vec<String>,
+) -> usize {
+    let mut index_by_key: HashMap<String, usize> = HashMap::new();
+    for (idx, video) in target.iter().enumerate() {
+        for key in keys(video) {
+            index_by_key.entry(key).or_insert(idx);
+        }
+    }
+    let mut duplicates = 0usize;
+    for video in incoming {
+        let video_keys = keys(&video);
+        if let Some(idx) = video_keys
+            .iter()
+            .find_map(|key| index_by_key.get(key).copied())
+        {
+            let existing = &mut target[idx];
+            for source in video.source_presets {
+                if !existing.source_presets.iter().any(|s| s == &source) {
+                    existing.source_presets.push(source);
+                }
+            }
+            duplicates += 1;
+        } else {
+            for key in video_keys {
+                index_by_key.insert(key, target.len());
+            }
+            target.push(video);
+        }
+    }
+    duplicates
+}
+
+/// Add to each of `fresh` the presets that tagged the same video in `loaded`, so a run that
+/// replaces the loaded results still shows every preset a video is known to match.
+pub fn carry_source_presets(fresh: &mut [VideoDetails], loaded: &[VideoDetails]) {
+    let known: HashMap<&str, &[String]> = loaded
+        .iter()
+        .map(|video| (video.id.as_str(), video.source_presets.as_slice()))
+        .collect();
+    for video in fresh {
+        let Some(sources) = known.get(video.id.as_str()) else {
+            continue;
+        };
+        for source in *sources {
+            if !video.source_presets.contains(source) {
+                video.source_presets.push(source.clone());
+            }
+        }
+    }
+}
+
+/// Each preset's priority keyed by name, which is what `source_presets` records.
+pub fn preset_priorities(searches: &[MySearch]) -> HashMap<&str, i32> {
+    searches
+        .iter()
+        .map(|search| (search.name.as_str(), search.priority))
+        .collect()
+}
+
+/// Sort key for [`sort_by_priority`]: the highest priority among the video's presets
+/// (presets no longer known rank lowest), then the upload date, both descending.
+pub fn priority_sort_key<'a>(
+    video: &'a VideoDetails,
+    priorities: &HashMap<&str, i32>,
+) -> (std::cmp::Reverse<i32>, std::cmp::Reverse<&'a str>) {
+    let priority = video
+        .source_presets
+        .iter()
+        .filter_map(|name| priorities.get(name.as_str()).copied())
+        .max()
+        .unwrap_or(i32::MIN);
+    (
+        std::cmp::Reverse(priority),
+        std::cmp::Reverse(video.published_at.as_str()),
+    )
+}
+
+/// Order aggregated videos by their highest-priority source preset, then newest first.
+pub fn sort_by_priority(videos: &mut [VideoDetails], searches: &[MySearch]) {
+    let priorities = preset_priorities(searches);
+    videos
+        .sort_by(|a, b| priority_sort_key(a, &priorities).cmp(&priority_sort_key(b, &priorities)));
+}
+
+/// How one run's videos compare with the run before it, as indices into each run.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RunDiff {
+    /// Indices into `current` of videos `previous` did not have, in `current` order.
+    pub added: Vec<usize>,
+    /// Indices into `previous` of videos no longer returned, in `previous` order.
+    pub removed: Vec<usize>,
+    /// Indices into `current` of videos both runs returned, in `current` order.
+    pub unchanged: Vec<usize>,
+}
+
+pub fn diff_runs(previous: &[VideoDetails], current: &[VideoDetails]) -> RunDiff {
+    let previous_ids: HashSet<&str> = previous.iter().map(|v| v.id.as_str()).collect();
+    let current_ids: HashSet<&str> = current.iter().map(|v| v.id.as_str()).collect();
+    let mut diff = RunDiff::default();
+    for (index, video) in current.iter().enumerate() {
+        if previous_ids.contains(video.id.as_str()) {
+            diff.unchanged.push(index);
+        } else {
+            diff.added.push(index);
+        }
+    }
+    diff.removed = (0..previous.len())
+        .filter(|&index| !current_ids.contains(previous[index].id.as_str()))
+        .collect();
+    diff
+}
+
+/// Raw and post-filter counts from a single `search.list` page.
+pub struct PresetEstimate {
+    pub raw_items: usize,
+    pub passed_filters: usize,
+    pub quota_units: u32,
+}
+
+/// Cheap breadth check for a preset: one search page plus its `videos.list` lookup, no channel
+/// metadata. Blocked channels and the search defaults' filters still apply.
+pub async fn estimate_preset(
+    api_key: String,
+    mut defaults: SearchDefaults,
+    blocked_channels: Vec<String>,
+    search: MySearch,
+) -> Result<PresetEstimate> {
+    prefs::normalize_duration_filters(&mut defaults);
+    let api_key = api_key.trim().to_owned();
+    if api_key.is_empty() {
+        bail!("Set your YouTube Data API key in the settings panel first.");
+    }
+    let blocked = filters::BlockedSet::new(&blocked_channels);
+    let outcome = run_single_search(&api_key, &defaults, &search, &blocked, 1, false, None).await?;
+    Ok(PresetEstimate {
+        raw_items: outcome.raw_items,
+        passed_filters: outcome.videos.len(),
+        quota_units: outcome.quota_units,
+    })
+}
+
+/// Where one scope of a preset run gets its pages from.
+enum PageSource {
+    /// `search.list`, optionally scoped to a channel and with one part of a split query.
+    Search {
+        scope: Option<String>,
+        query: Option<String>,
+    },
+    /// A channel's uploads playlist through `playlistItems.list`.
+    Uploads {
+        channel_id: String,
+        playlist_id: String,
+    },
+}
+
+/// One page from either source, reduced to what the rest of the run needs.
+struct SourcePage {
+    next_page_token: Option<String>,
+    /// Video IDs with their publish time, in page order.
+    items: Vec<(String, String)>,
+    /// Items the API returned, before any window check.
+    raw_items: usize,
+    /// An uploads page reached videos older than the window, so later pages are older still.
+    reached_window_start: bool,
+}
+
+/// Whether `search` reads its allowed channels' uploads playlists instead of running
+/// `search.list`: `uploads_mode` is on and the allow list is all there is to the query.
+pub fn uses_uploads_playlist(search: &MySearch) -> bool {
+    search.uploads_mode
+        && !search.query.channel_allow.is_empty()
+        && search.query.category_id.is_none()
+        && build_query_text(&search.query).trim().is_empty()
+}
+
+/// Allowed channels with a known ID, once each, for an uploads-mode run.
+fn upload_channel_ids(search: &MySearch) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for id in search
+        .query
+        .channel_allow
+        .iter()
+        .filter_map(|c| c.id.as_ref())
+    {
+        if !ids.contains(id) {
+            ids.push(id.clone());
+        }
+    }
+    ids
+}
+
+async fn page_sources(
     api_key: &str,
-    global: &GlobalPrefs,
     search: &MySearch,
-    blocked_keys: &[String],
-) -> Result<SingleSearchOutcome> {
-    let mut base_params = build_query_params(global, search)?;
-    if let Some(window) = resolve_window(global, search) {
-        base_params.push(("publishedAfter", window.start_rfc3339.clone()));
-        base_params.push(("publishedBefore", window.end_rfc3339.clone()));
+    quota_units: &mut u32,
+) -> Result<Vec<PageSource>> {
+    let channel_ids = upload_channel_ids(search);
+    if uses_uploads_playlist(search) && !channel_ids.is_empty() {
+        let (playlists, calls) = channels::uploads_playlists(api_key, &channel_ids)
+            .await
+            .with_context(|| "channels.list failed while looking up uploads playlists")?;
+        *quota_units += calls * quota::LIST_CALL_COST;
+        return Ok(channel_ids
+            .into_iter()
+            .filter_map(|channel_id| {
+                let playlist_id = playlists.get(&channel_id)?.clone();
+                Some(PageSource::Uploads {
+                    channel_id,
+                    playlist_id,
+                })
+            })
+            .collect());
+    }
+    let variants = query_variants(search);
+    let mut sources = Vec::new();
+    for scope in search_scopes(search) {
+        sources.extend(variants.iter().map(|query| PageSource::Search {
+            scope: scope.clone(),
+            query: query.clone(),
+        }));
     }
-    base_params.push(("order", "date".to_owned()));
-    base_params.push(("maxResults", "50".to_owned()));
+    Ok(sources)
+}
+
+async fn fetch_source_page(
+    api_key: &str,
+    base_params: &[(&'static str, String)],
+    source: &PageSource,
+    window: Option<(OffsetDateTime, OffsetDateTime)>,
+    page_token: Option<&str>,
+) -> Result<SourcePage> {
+    match source {
+        PageSource::Search { scope, query } => {
+            let mut params = base_params.to_vec();
+            if let Some(query) = query {
+                params.retain(|(key, _)| *key != "q");
+                params.push(("q", query.clone()));
+            }
+            if let Some(channel_id) = scope {
+                params.push(("channelId", channel_id.clone()));
+            }
+            if let Some(token) = page_token {
+                params.push(("pageToken", token.to_owned()));
+            }
+            let SearchListResponse {
+                next_page_token,
+                items,
+            } = search::search_list(api_key, &params)
+                .await
+                .with_context(|| "search.list failed — check API key, quotas, or restrictions")?;
+            Ok(SourcePage {
+                next_page_token,
+                raw_items: items.len(),
+                items: items
+                    .into_iter()
+                    .filter_map(|item| Some((item.id.video_id?, item.snippet.published_at)))
+                    .collect(),
+                reached_window_start: false,
+            })
+        }
+        PageSource::Uploads { playlist_id, .. } => {
+            let response = playlists::playlist_items(api_key, playlist_id, page_token)
+                .await
+                .with_context(
+                    || "playlistItems.list failed — check API key, quotas, or restrictions",
+                )?;
+            let raw_items = response.items.len();
+            let mut items = Vec::new();
+            let mut reached_window_start = false;
+            for item in response.items {
+                let details = item.content_details;
+                // Private and deleted uploads have no publish time; skip them.
+                let Some(published_at) = details.video_published_at else {
+                    continue;
+                };
+                let Ok(published) = OffsetDateTime::parse(&published_at, &Rfc3339) else {
+                    continue;
+                };
+                if let Some((start, end)) = window {
+                    if published < start {
+                        reached_window_start = true;
+                        continue;
+                    }
+                    if published > end {
+                        continue;
+                    }
+                }
+                items.push((details.video_id, published_at));
+            }
+            Ok(SourcePage {
+                next_page_token: response.next_page_token,
+                items,
+                raw_items,
+                reached_window_start,
+            })
+        }
+    }
+}
 
-    let mut page_token: Option<String> = None;
+async fn run_single_search(
+    api_key: &str,
+    defaults: &SearchDefaults,
+    search: &MySearch,
+    blocked: &filters::BlockedSet,
+    max_pages: usize,
+    enrich_channels: bool,
+    progress: Option<&ProgressSink>,
+) -> Result<SingleSearchOutcome> {
     let mut pages_fetched = 0usize;
     let mut duplicates_within = 0usize;
     let mut seen_ids: HashSet<String> = HashSet::new();
     let mut collected: Vec<VideoDetails> = Vec::new();
     let mut raw_items_total = 0usize;
     let mut unique_ids_total = 0usize;
+    let mut quota_units = 0u32;
+    let mut dropped_title_mismatch = 0usize;
+    let mut dropped_missing_terms = 0usize;
+    let mut raw_pages: Vec<RawPage> = Vec::new();
 
-    while pages_fetched < max_search_pages() {
-        let mut params = base_params.clone();
-        if let Some(token) = &page_token {
-            params.push(("pageToken", token.clone()));
+    let sources = page_sources(api_key, search, &mut quota_units).await?;
+    let window = resolve_window(defaults, search);
+    let window_bounds = window.as_ref().and_then(|window| {
+        Some((
+            OffsetDateTime::parse(&window.start_rfc3339, &Rfc3339).ok()?,
+            OffsetDateTime::parse(&window.end_rfc3339, &Rfc3339).ok()?,
+        ))
+    });
+    let mut base_params = Vec::new();
+    if sources
+        .iter()
+        .any(|source| matches!(source, PageSource::Search { .. }))
+    {
+        base_params = build_query_params(defaults, search)?;
+        if let Some(window) = &window {
+            base_params.push(("publishedAfter", window.start_rfc3339.clone()));
+            base_params.push(("publishedBefore", window.end_rfc3339.clone()));
         }
+        base_params.push(("order", "date".to_owned()));
+        base_params.push(("maxResults", "50".to_owned()));
+    }
 
-        let response = search::search_list(api_key, &params)
-            .await
-            .with_context(|| "search.list failed — check API key, quotas, or restrictions")?;
-        pages_fetched += 1;
-
-        let SearchListResponse {
-            next_page_token,
-            items,
-        } = response;
-        raw_items_total += items.len();
-        let mut request_ids: Vec<String> = Vec::new();
-        for item in items {
-            if let Some(video_id) = item.id.video_id {
-                if seen_ids.insert(video_id.clone()) {
-                    request_ids.push(video_id);
+    for source in &sources {
+        let (channel_scope, page_cost) = match source {
+            PageSource::Search { scope, .. } => (scope.clone(), quota::SEARCH_PAGE_COST),
+            PageSource::Uploads { channel_id, .. } => {
+                (Some(channel_id.clone()), quota::LIST_CALL_COST)
+            }
+        };
+        let mut page_token: Option<String> = None;
+        let mut scope_pages = 0usize;
+        while scope_pages < max_pages {
+            report(
+                progress,
+                SearchProgress::Page {
+                    preset: search.name.clone(),
+                    page: scope_pages + 1,
+                    max_pages,
+                },
+            );
+            let page = fetch_source_page(
+                api_key,
+                &base_params,
+                source,
+                window_bounds,
+                page_token.as_deref(),
+            )
+            .await?;
+            pages_fetched += 1;
+            scope_pages += 1;
+            quota_units += page_cost;
+
+            raw_items_total += page.raw_items;
+            let mut raw_page = defaults.keep_raw_responses.then(|| RawPage {
+                preset: search.name.clone(),
+                channel_scope: channel_scope.clone(),
+                page: scope_pages,
+                items: Vec::new(),
+            });
+            let mut request_ids: Vec<String> = Vec::new();
+            for (video_id, published_at) in page.items {
+                let fate = if seen_ids.insert(video_id.clone()) {
+                    request_ids.push(video_id.clone());
+                    RawFate::MissingFromVideos
                 } else {
                     duplicates_within += 1;
+                    RawFate::Duplicate
+                };
+                if let Some(page) = raw_page.as_mut() {
+                    page.items.push(RawItem {
+                        video_id,
+                        published_at,
+                        fate,
+                    });
                 }
             }
-        }
-        unique_ids_total += request_ids.len();
-        if !request_ids.is_empty() {
-            let videos = videos::videos_list(api_key, &request_ids)
-                .await
-                .with_context(|| "videos.list failed — check API key, quotas, or restrictions")?;
-            for video in videos.items {
-                let mut details = map_video_item(video);
-                if filters::matches_post_filters(&details, global, search, blocked_keys) {
-                    details.source_presets.push(search.name.clone());
-                    collected.push(details);
+            unique_ids_total += request_ids.len();
+            if !request_ids.is_empty() {
+                report(progress, SearchProgress::FetchingDetails(request_ids.len()));
+                let videos = videos::videos_list(api_key, &request_ids)
+                    .await
+                    .with_context(
+                        || "videos.list failed — check API key, quotas, or restrictions",
+                    )?;
+                quota_units += quota::LIST_CALL_COST;
+                for video in videos.items {
+                    let mut details = map_video_item(video, defaults.region_code.as_deref());
+                    let verdict = filters::post_filter_verdict(&details, defaults, search, blocked);
+                    if let Some(page) = raw_page.as_mut() {
+                        mark_raw_fate(page, &details.id, verdict);
+                    }
+                    match verdict {
+                        PostFilterVerdict::Keep => {
+                            details.source_presets.push(search.name.clone());
+                            collected.push(details);
+                        }
+                        PostFilterVerdict::TitleMismatch => dropped_title_mismatch += 1,
+                        PostFilterVerdict::MissingAllTerms => dropped_missing_terms += 1,
+                        PostFilterVerdict::Drop => {}
+                    }
                 }
             }
-        }
 
-        match next_page_token {
-            Some(token) => {
-                page_token = Some(token);
+            raw_pages.extend(raw_page);
+
+            match page.next_page_token {
+                Some(token) if !page.reached_window_start => {
+                    page_token = Some(token);
+                }
+                _ => break,
             }
-            None => break,
         }
     }
 
-    if !collected.is_empty() {
-        enhance_channel_metadata(api_key, &mut collected).await;
+    if enrich_channels && !collected.is_empty() {
+        quota_units += enhance_channel_metadata(api_key, &mut collected, progress).await;
     }
+    report(
+        progress,
+        SearchProgress::PresetDone {
+            preset: search.name.clone(),
+            kept: collected.len(),
+            videos: progress
+                .is_some_and(|sink| sink.preset_videos)
+                .then(|| collected.clone()),
+        },
+    );
 
     Ok(SingleSearchOutcome {
         videos: collected,
         pages_fetched,
+        quota_units,
+        dropped_title_mismatch,
+        dropped_missing_terms,
         duplicates_within,
         raw_items: raw_items_total,
         unique_ids: unique_ids_total,
+        raw_pages,
     })
 }
 
-async fn enhance_channel_metadata(api_key: &str, videos: &mut [VideoDetails]) {
+/// Record the post-filter verdict on the page item that `videos.list` returned details for.
+fn mark_raw_fate(page: &mut RawPage, video_id: &str, verdict: PostFilterVerdict) {
+    let fate = match verdict {
+        PostFilterVerdict::Keep => RawFate::Kept,
+        PostFilterVerdict::TitleMismatch => RawFate::TitleMismatch,
+        PostFilterVerdict::MissingAllTerms => RawFate::MissingAllTerms,
+        PostFilterVerdict::Drop => RawFate::Filtered,
+    };
+    if let Some(item) = page
+        .items
+        .iter_mut()
+        .find(|item| item.video_id == video_id && item.fate == RawFate::MissingFromVideos)
+    {
+        item.fate = fate;
+    }
+}
+
+/// Fill in channel names and handles; returns the quota units spent on `channels.list`.
+async fn enhance_channel_metadata(
+    api_key: &str,
+    videos: &mut [VideoDetails],
+    progress: Option<&ProgressSink>,
+) -> u32 {
     let mut ids: Vec<String> = videos
         .iter()
         .map(|v| v.channel_handle.clone())
@@ -245,28 +1025,19 @@ async fn enhance_channel_metadata(api_key: &str, videos: &mut [VideoDetails]) {
                 video.channel_display_name = Some(video.channel_title.clone());
             }
         }
-        return;
+        return 0;
     }
 
+    report(progress, SearchProgress::ResolvingChannels(ids.len()));
     let mut metadata: HashMap<String, (String, Option<String>)> = HashMap::new();
+    let mut quota_units = 0u32;
     for chunk in ids.chunks(50) {
+        quota_units += quota::LIST_CALL_COST;
         match channels::channels_list(api_key, chunk).await {
             Ok(resp) => {
                 for item in resp.items {
                     let title = item.snippet.title.trim().to_string();
-                    let custom = item
-                        .snippet
-                        .custom_url
-                        .as_ref()
-                        .map(|url| url.trim())
-                        .filter(|url| !url.is_empty())
-                        .map(|url| {
-                            if url.starts_with('@') {
-                                url.to_string()
-                            } else {
-                                format!("@{}", url.trim_start_matches('@'))
-                            }
-                        });
+                    let custom = item.snippet.handle();
                     metadata.insert(item.id, (title, custom));
                 }
             }
@@ -294,24 +1065,42 @@ async fn enhance_channel_metadata(api_key: &str, videos: &mut [VideoDetails]) {
             video.channel_custom_url = Some(video.channel_handle.clone());
         }
     }
+    quota_units
 }
 
-pub fn resolve_window(global: &GlobalPrefs, search: &MySearch) -> Option<TimeWindow> {
+pub fn resolve_window(defaults: &SearchDefaults, search: &MySearch) -> Option<TimeWindow> {
     if let Some(override_window) = &search.window_override {
         return Some(override_window.clone());
     }
 
-    let preset = global.default_window;
-    window_for_preset(preset)
+    let preset = defaults.default_window;
+    window_for_preset(preset, defaults.last_search_unix, OffsetDateTime::now_utc())
 }
 
-fn window_for_preset(preset: TimeWindowPreset) -> Option<TimeWindow> {
-    let now = OffsetDateTime::now_utc();
+/// Overlap subtracted from the last search time so videos published around it are not missed.
+const SINCE_LAST_RUN_OVERLAP_MINUTES: i64 = 30;
+
+/// Start of the "Since last search" window: the last search minus the overlap, or 24 hours
+/// back when no search is on record (or the stored time is in the future).
+fn since_last_run_start(last_search_unix: Option<i64>, now: OffsetDateTime) -> OffsetDateTime {
+    last_search_unix
+        .and_then(|unix| OffsetDateTime::from_unix_timestamp(unix).ok())
+        .filter(|last| *last <= now)
+        .map(|last| last - Duration::minutes(SINCE_LAST_RUN_OVERLAP_MINUTES))
+        .unwrap_or(now - Duration::days(1))
+}
+
+fn window_for_preset(
+    preset: TimeWindowPreset,
+    last_search_unix: Option<i64>,
+    now: OffsetDateTime,
+) -> Option<TimeWindow> {
     let (start, end) = match preset {
         TimeWindowPreset::Today => Some((now - Duration::days(1), now)),
         TimeWindowPreset::H48 => Some((now - Duration::hours(48), now)),
         TimeWindowPreset::D7 => Some((now - Duration::days(7), now)),
         TimeWindowPreset::AllTime => None,
+        TimeWindowPreset::SinceLastRun => Some((since_last_run_start(last_search_unix, now), now)),
     }?;
 
     let start = start
@@ -329,48 +1118,150 @@ fn window_for_preset(preset: TimeWindowPreset) -> Option<TimeWindow> {
 
 /// Construct the parameter list for a search request, tolerating empty queries for system presets.
 pub fn build_query_params(
-    global: &GlobalPrefs,
+    defaults: &SearchDefaults,
     search: &MySearch,
 ) -> Result<Vec<(&'static str, String)>> {
     let mut params = Vec::new();
     let mut query_text = build_query_text(&search.query);
+    let channel_scoped = search_scopes(search).iter().all(Option::is_some);
 
     if query_text.trim().is_empty() {
         if search.system {
             query_text = "\"\"".to_string();
-        } else {
+        } else if !channel_scoped {
             bail!("Search query is empty. Add some terms to your preset.");
         }
     }
-    params.push(("q", query_text));
+    if !query_text.trim().is_empty() {
+        params.push(("q", query_text));
+    }
 
     if let Some(category_id) = search.query.category_id {
         params.push(("videoCategoryId", category_id.to_string()));
     }
 
-    if let Some(region) = global.region_code.as_ref() {
+    if let Some(region) = defaults.region_code.as_ref() {
         params.push(("regionCode", region.clone()));
     }
 
     let require_captions = search
         .require_captions_override
-        .unwrap_or(global.require_captions);
+        .unwrap_or(defaults.require_captions);
     if require_captions {
         params.push(("videoCaption", "closedCaption".to_owned()));
     }
 
+    if let Some(event) = search.event_type_override.or(defaults.event_type) {
+        params.push(("eventType", event.api_value().to_owned()));
+    }
+
     let min_duration = search
         .min_duration_override
-        .unwrap_or(global.min_duration_secs);
-    if min_duration >= 1200 {
-        params.push(("videoDuration", "long".to_owned()));
-    } else if min_duration >= 600 {
-        params.push(("videoDuration", "medium".to_owned()));
+        .unwrap_or(defaults.min_duration_secs);
+    if let Some(duration) = video_duration_param(min_duration) {
+        params.push(("videoDuration", duration.to_owned()));
     }
 
     Ok(params)
 }
 
+/// The `videoDuration` a minimum length maps to: "long" (over 20 min) from 20 minutes,
+/// "medium" (4-20 min) from 10 minutes, otherwise none.
+pub fn video_duration_param(min_duration_secs: u32) -> Option<&'static str> {
+    if min_duration_secs >= 1200 {
+        Some("long")
+    } else if min_duration_secs >= 600 {
+        Some("medium")
+    } else {
+        None
+    }
+}
+
+/// The `channelId` scopes a preset searches, one `search.list` run each; `None` is an unscoped
+/// search whose results are post-filtered against the allow list.
+///
+/// A preset allowing a single resolved channel always searches it directly. With
+/// `per_channel_search`, every resolved allow entry gets its own scope, plus an unscoped search
+/// for entries still waiting on an ID when there is query text to run it with.
+pub fn search_scopes(search: &MySearch) -> Vec<Option<String>> {
+    let allow = &search.query.channel_allow;
+    if !search.query.per_channel_search {
+        return match allow.as_slice() {
+            [only] if only.id.is_some() => vec![only.id.clone()],
+            _ => vec![None],
+        };
+    }
+
+    let mut scopes: Vec<Option<String>> = Vec::new();
+    for id in allow.iter().filter_map(|channel| channel.id.as_ref()) {
+        if !scopes.iter().any(|scope| scope.as_ref() == Some(id)) {
+            scopes.push(Some(id.clone()));
+        }
+    }
+    let needs_fallback = allow.iter().any(|channel| channel.id.is_none());
+    let has_text = !build_query_text(&search.query).trim().is_empty();
+    if scopes.is_empty() || (needs_fallback && has_text) {
+        scopes.push(None);
+    }
+    scopes
+}
+
+/// Length of a query as sent in the URL, which is what YouTube limits.
+pub fn encoded_query_length(text: &str) -> usize {
+    urlencoding::encode(text).len()
+}
+
+/// Encoded length of the `q` text a preset sends.
+pub fn query_length(spec: &QuerySpec) -> usize {
+    encoded_query_length(&build_query_text(spec))
+}
+
+/// Partition the any terms so each query stays within `limit` encoded bytes, keeping `q`,
+/// all terms, and not terms in every part. A term too long to fit with the rest still gets a
+/// part of its own. Returns one query when nothing needs splitting.
+pub fn split_query_text(spec: &QuerySpec, limit: usize) -> Vec<String> {
+    let full = build_query_text(spec);
+    if encoded_query_length(&full) <= limit {
+        return vec![full];
+    }
+    let text_with = |terms: &[String]| {
+        build_query_text(&QuerySpec {
+            any_terms: terms.to_vec(),
+            ..spec.clone()
+        })
+    };
+    let mut chunks: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    for term in spec.any_terms.iter().filter(|term| !term.trim().is_empty()) {
+        current.push(term.clone());
+        if current.len() > 1 && encoded_query_length(&text_with(&current)) > limit {
+            current.pop();
+            chunks.push(std::mem::take(&mut current));
+            current.push(term.clone());
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    if chunks.len() <= 1 {
+        return vec![full];
+    }
+    chunks.iter().map(|chunk| text_with(chunk)).collect()
+}
+
+/// Replacement `q` values for a preset run, one pass each; `[None]` runs the preset's own
+/// query once.
+fn query_variants(search: &MySearch) -> Vec<Option<String>> {
+    if !search.split_long_query {
+        return vec![None];
+    }
+    let parts = split_query_text(&search.query, QUERY_LENGTH_LIMIT);
+    if parts.len() <= 1 {
+        return vec![None];
+    }
+    parts.into_iter().map(Some).collect()
+}
+
 fn build_query_text(spec: &QuerySpec) -> String {
     let mut parts: Vec<String> = Vec::new();
     if let Some(q) = &spec.q {
@@ -423,9 +1314,20 @@ fn format_query_token(term: &str) -> String {
     }
 }
 
-fn map_video_item(item: VideoItem) -> VideoDetails {
+/// Map a videos.list item; `region` is the configured region code that
+/// `blocked_in_region` is evaluated against.
+fn map_video_item(item: VideoItem, region: Option<&str>) -> VideoDetails {
     let snippet = item.snippet;
     let content = item.content_details;
+    let region = region.map(str::trim).filter(|region| !region.is_empty());
+    let blocked_in_region = region.map(|region| {
+        content
+            .region_restriction
+            .as_ref()
+            .is_some_and(|restriction| restriction.blocks(region))
+    });
+
+    let parsed_duration = filters::parse_iso8601_duration(&content.duration);
 
     let thumbnail_url = snippet
         .thumbnails
@@ -439,15 +1341,466 @@ fn map_video_item(item: VideoItem) -> VideoDetails {
         title_lower: snippet.title.to_ascii_lowercase(),
         channel_title: snippet.channel_title.clone(),
         channel_handle: snippet.channel_id.clone(),
+        channel_title_lower: snippet.channel_title.to_ascii_lowercase(),
+        channel_handle_lower: snippet.channel_id.to_ascii_lowercase(),
         channel_display_name: None,
         channel_custom_url: None,
         published_at: snippet.published_at.clone(),
-        duration_secs: filters::parse_iso8601_duration(&content.duration).unwrap_or(0),
+        duration_secs: parsed_duration.unwrap_or(0),
         default_audio_lang: snippet.default_audio_language.clone(),
         default_lang: snippet.default_language.clone(),
         thumbnail_url,
         url: format!("https://www.youtube.com/watch?v={}", item.id),
         has_caption_lang_en: None,
+        caption: content.caption.as_deref().map(|flag| flag == "true"),
+        licensed_content: content.licensed_content,
+        dimension: content.dimension,
+        tags: snippet.tags,
         source_presets: Vec::new(),
+        live_broadcast_content: snippet.live_broadcast_content.clone(),
+        duration_unknown: parsed_duration.is_none(),
+        age_restricted: content.content_rating.is_age_restricted(),
+        blocked_in_region,
+        description: snippet.description,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(id: &str) -> VideoDetails {
+        VideoDetails {
+            id: id.into(),
+            ..VideoDetails::default()
+        }
+    }
+
+    fn ids(videos: &[VideoDetails]) -> Vec<&str> {
+        videos.iter().map(|v| v.id.as_str()).collect()
+    }
+
+    #[test]
+    fn priority_sort_uses_the_best_source_preset_then_date() {
+        let preset = |name: &str, priority: i32| MySearch {
+            name: name.into(),
+            priority,
+            ..prefs::builtin_default().searches[0].clone()
+        };
+        let searches = vec![preset("Low", 1), preset("High", 5), preset("Mid", 3)];
+        let found = |id: &str, date: &str, sources: &[&str]| VideoDetails {
+            published_at: date.into(),
+            source_presets: sources.iter().map(|s| s.to_string()).collect(),
+            ..video(id)
+        };
+        let mut videos = vec![
+            found("low-new", "2024-03-01T00:00:00Z", &["Low"]),
+            found("mid-old", "2024-01-01T00:00:00Z", &["Mid"]),
+            found("both", "2024-01-15T00:00:00Z", &["Low", "High"]),
+            found("gone", "2024-04-01T00:00:00Z", &["Deleted preset"]),
+            found("mid-new", "2024-02-01T00:00:00Z", &["Mid"]),
+            found("high", "2024-01-10T00:00:00Z", &["High"]),
+        ];
+        sort_by_priority(&mut videos, &searches);
+        assert_eq!(
+            ids(&videos),
+            vec!["both", "high", "mid-new", "mid-old", "low-new", "gone"]
+        );
+    }
+
+    #[test]
+    fn caption_flag_is_unknown_when_videos_list_omits_it() {
+        let item = |caption: &str| -> VideoItem {
+            serde_json::from_str(&format!(
+                r#"{{"id":"v","snippet":{{"title":"t","channelTitle":"c","channelId":"UC",
+                "publishedAt":"2024-01-01T00:00:00Z"}},
+                "contentDetails":{{"duration":"PT1M"{caption}}}}}"#
+            ))
+            .unwrap()
+        };
+        assert_eq!(
+            map_video_item(item(r#","caption":"true""#), None).caption,
+            Some(true)
+        );
+        assert_eq!(
+            map_video_item(item(r#","caption":"false""#), None).caption,
+            Some(false)
+        );
+        assert_eq!(map_video_item(item(""), None).caption, None);
+    }
+
+    #[test]
+    fn items_without_a_duration_load_with_an_unknown_length() {
+        let item: VideoItem = serde_json::from_str(
+            r#"{"id":"v","snippet":{"title":"t","channelTitle":"c","channelId":"UC",
+            "publishedAt":"2024-01-01T00:00:00Z"},"contentDetails":{"caption":"true"}}"#,
+        )
+        .unwrap();
+        let video = map_video_item(item, None);
+        assert!(video.duration_unknown);
+        assert_eq!(video.duration_secs, 0);
+        assert_eq!(video.caption, Some(true));
+    }
+
+    #[test]
+    fn restrictions_are_read_from_content_details() {
+        let item = |content: &str| -> VideoItem {
+            serde_json::from_str(&format!(
+                r#"{{"id":"v","snippet":{{"title":"t","channelTitle":"c","channelId":"UC",
+                "publishedAt":"2024-01-01T00:00:00Z"}},
+                "contentDetails":{{"duration":"PT1M"{content}}}}}"#
+            ))
+            .unwrap()
+        };
+        let plain = map_video_item(item(""), Some("US"));
+        assert!(!plain.age_restricted);
+        assert_eq!(plain.blocked_in_region, Some(false));
+        assert_eq!(map_video_item(item(""), None).blocked_in_region, None);
+
+        let rated = item(r#","contentRating":{"ytRating":"ytAgeRestricted"}"#);
+        assert!(map_video_item(rated, Some("US")).age_restricted);
+
+        let blocked = r#","regionRestriction":{"blocked":["DE","us"]}"#;
+        assert_eq!(
+            map_video_item(item(blocked), Some("US")).blocked_in_region,
+            Some(true)
+        );
+        assert_eq!(
+            map_video_item(item(blocked), Some("FR")).blocked_in_region,
+            Some(false)
+        );
+        let allowed = r#","regionRestriction":{"allowed":["GB"]}"#;
+        assert_eq!(
+            map_video_item(item(allowed), Some("US")).blocked_in_region,
+            Some(true)
+        );
+        assert_eq!(
+            map_video_item(item(allowed), Some("GB")).blocked_in_region,
+            Some(false)
+        );
+
+        let cached: VideoDetails = serde_json::from_str(
+            r#"{"id":"v","title":"t","title_lower":"t","channel_title":"c",
+            "channel_handle":"UC","channel_display_name":null,"channel_custom_url":null,
+            "published_at":"2024-01-01T00:00:00Z","duration_secs":60,
+            "default_audio_lang":null,"default_lang":null,"thumbnail_url":null,
+            "url":"u","has_caption_lang_en":null,"source_presets":[]}"#,
+        )
+        .unwrap();
+        assert!(!cached.is_restricted());
+    }
+
+    #[test]
+    fn raw_fate_only_overrides_items_that_reached_the_post_filters() {
+        let item = |id: &str, fate| RawItem {
+            video_id: id.into(),
+            published_at: String::new(),
+            fate,
+        };
+        let mut page = RawPage {
+            preset: "p".into(),
+            channel_scope: None,
+            page: 1,
+            items: vec![
+                item("a", RawFate::MissingFromVideos),
+                item("b", RawFate::Duplicate),
+                item("c", RawFate::MissingFromVideos),
+            ],
+        };
+        mark_raw_fate(&mut page, "a", PostFilterVerdict::Keep);
+        mark_raw_fate(&mut page, "b", PostFilterVerdict::Drop);
+        mark_raw_fate(&mut page, "c", PostFilterVerdict::TitleMismatch);
+        let fates: Vec<RawFate> = page.items.iter().map(|i| i.fate).collect();
+        assert_eq!(
+            fates,
+            vec![RawFate::Kept, RawFate::Duplicate, RawFate::TitleMismatch]
+        );
+    }
+
+    #[test]
+    fn query_length_counts_url_encoded_bytes() {
+        assert_eq!(encoded_query_length("rust"), 4);
+        assert_eq!(encoded_query_length("rust gui"), 10);
+        assert_eq!(encoded_query_length("\"a b\""), 11);
+        assert_eq!(encoded_query_length("é"), 6);
+        let spec = QuerySpec {
+            any_terms: vec!["a".into(), "b".into()],
+            ..QuerySpec::default()
+        };
+        // "(a OR b)": parentheses and spaces encode to three bytes each.
+        assert_eq!(query_length(&spec), 16);
+    }
+
+    #[test]
+    fn long_any_term_lists_split_within_the_limit() {
+        let spec = QuerySpec {
+            any_terms: (0..12).map(|n| format!("term number {n}")).collect(),
+            all_terms: vec!["rust".into()],
+            not_terms: vec!["shorts".into()],
+            ..QuerySpec::default()
+        };
+        assert!(query_length(&spec) > QUERY_LENGTH_LIMIT);
+        let parts = split_query_text(&spec, QUERY_LENGTH_LIMIT);
+        assert!(parts.len() > 1);
+        for part in &parts {
+            assert!(encoded_query_length(part) <= QUERY_LENGTH_LIMIT, "{part}");
+            assert!(part.ends_with(" rust -shorts"), "{part}");
+        }
+        let rejoined: usize = parts
+            .iter()
+            .map(|part| part.matches("term number").count())
+            .sum();
+        assert_eq!(rejoined, 12);
+
+        let short = QuerySpec {
+            any_terms: vec!["a".into(), "b".into()],
+            ..QuerySpec::default()
+        };
+        assert_eq!(
+            split_query_text(&short, QUERY_LENGTH_LIMIT),
+            vec!["(a OR b)"]
+        );
+    }
+
+    #[test]
+    fn an_oversized_term_gets_its_own_query() {
+        let spec = QuerySpec {
+            any_terms: vec!["x".repeat(120), "short".into(), "other".into()],
+            ..QuerySpec::default()
+        };
+        let parts = split_query_text(&spec, QUERY_LENGTH_LIMIT);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1], "(short OR other)");
+    }
+
+    #[test]
+    fn split_presets_count_one_scope_per_query() {
+        let mut search = MySearch::default();
+        search.query.any_terms = (0..12).map(|n| format!("term number {n}")).collect();
+        let mode = RunMode::AdHoc(Box::new(search.clone()));
+        assert_eq!(run_scope_count(&[], &mode).searches, 1);
+        search.split_long_query = true;
+        let parts = split_query_text(&search.query, QUERY_LENGTH_LIMIT).len();
+        let mode = RunMode::AdHoc(Box::new(search));
+        assert_eq!(run_scope_count(&[], &mode).searches, parts);
+    }
+
+    fn channel_preset(allow: &[&str], q: Option<&str>, per_channel: bool) -> MySearch {
+        let mut search = MySearch::default();
+        search.query.q = q.map(str::to_owned);
+        search.query.per_channel_search = per_channel;
+        search.query.channel_allow = allow
+            .iter()
+            .map(|raw| prefs::ChannelRef::parse(raw))
+            .collect();
+        search
+    }
+
+    #[test]
+    fn since_last_run_starts_before_the_last_search_or_falls_back_to_a_day() {
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let last = now - Duration::hours(5);
+        let window = window_for_preset(
+            TimeWindowPreset::SinceLastRun,
+            Some(last.unix_timestamp()),
+            now,
+        )
+        .unwrap();
+        let expected = (last - Duration::minutes(30)).format(&Rfc3339).unwrap();
+        assert_eq!(window.start_rfc3339, expected);
+        assert_eq!(window.end_rfc3339, now.format(&Rfc3339).unwrap());
+
+        let day_ago = (now - Duration::days(1)).format(&Rfc3339).unwrap();
+        let unknown = window_for_preset(TimeWindowPreset::SinceLastRun, None, now).unwrap();
+        assert_eq!(unknown.start_rfc3339, day_ago);
+        let future = Some((now + Duration::hours(1)).unix_timestamp());
+        let skewed = window_for_preset(TimeWindowPreset::SinceLastRun, future, now).unwrap();
+        assert_eq!(skewed.start_rfc3339, day_ago);
+    }
+
+    #[test]
+    fn overlaps_count_each_preset_pair_once_per_video() {
+        let tagged = |id: &str, sources: &[&str]| VideoDetails {
+            source_presets: sources.iter().map(|s| s.to_string()).collect(),
+            ..video(id)
+        };
+        let videos = vec![
+            tagged("1", &["Rust", "Egui"]),
+            tagged("2", &["Egui", "Rust", "Bevy"]),
+            tagged("3", &["Bevy"]),
+        ];
+        let overlaps = preset_overlaps(&videos);
+        assert_eq!(
+            overlaps[0],
+            PresetOverlap {
+                first: "Egui".into(),
+                second: "Rust".into(),
+                shared: 2,
+            }
+        );
+        assert_eq!(overlaps.len(), 3);
+        assert!(overlaps[1..].iter().all(|overlap| overlap.shared == 1));
+        assert!(preset_overlaps(&videos[2..]).is_empty());
+    }
+
+    /// Preset A returned v1 twice and a re-upload of v1 (v1b); preset B returned v1 and v4.
+    fn duplicate_fixture() -> (Vec<VideoDetails>, Vec<VideoDetails>) {
+        let upload = |id: &str, title: &str, preset: &str| VideoDetails {
+            title: title.into(),
+            channel_handle: "UCchan".into(),
+            source_presets: vec![preset.into()],
+            ..video(id)
+        };
+        let a = vec![
+            upload("v1", "Rust in 100 seconds", "A"),
+            upload("v1", "Rust in 100 seconds", "A"),
+            upload("v1b", "Rust  in 100 Seconds", "A"),
+        ];
+        let b = vec![
+            upload("v1", "Rust in 100 seconds", "B"),
+            upload("v4", "Egui tour", "B"),
+        ];
+        (a, b)
+    }
+
+    fn merged(policy: DuplicatePolicy) -> (Vec<VideoDetails>, usize) {
+        let (a, b) = duplicate_fixture();
+        let mut all = Vec::new();
+        let folded =
+            merge_with_policy(policy, &mut all, a) + merge_with_policy(policy, &mut all, b);
+        (all, folded)
+    }
+
+    #[test]
+    fn dedup_by_id_folds_repeats_within_and_across_presets() {
+        let (videos, folded) = merged(DuplicatePolicy::DedupById);
+        let ids: Vec<&str> = videos.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["v1", "v1b", "v4"]);
+        assert_eq!(folded, 2);
+        assert_eq!(videos[0].source_presets, vec!["A", "B"]);
+        assert_eq!(videos[1].source_presets, vec!["A"]);
+    }
+
+    #[test]
+    fn dedup_by_id_and_channel_also_folds_reuploads() {
+        let (videos, folded) = merged(DuplicatePolicy::DedupByIdAndChannel);
+        let ids: Vec<&str> = videos.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["v1", "v4"]);
+        assert_eq!(folded, 3);
+        assert_eq!(videos[0].source_presets, vec!["A", "B"]);
+    }
+
+    #[test]
+    fn keep_all_appends_every_video() {
+        let (videos, folded) = merged(DuplicatePolicy::KeepAll);
+        let ids: Vec<&str> = videos.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["v1", "v1", "v1b", "v1", "v4"]);
+        assert_eq!(folded, 0);
+        assert!(preset_overlaps(&videos).is_empty());
+    }
+
+    #[test]
+    fn replaced_results_keep_presets_known_from_the_loaded_list() {
+        let tagged = |id: &str, sources: &[&str]| VideoDetails {
+            source_presets: sources.iter().map(|s| s.to_string()).collect(),
+            ..video(id)
+        };
+        let loaded = vec![tagged("1", &["Rust", "Egui"]), tagged("2", &["Bevy"])];
+        let mut fresh = vec![tagged("1", &["Egui"]), tagged("3", &["Egui"])];
+        carry_source_presets(&mut fresh, &loaded);
+        assert_eq!(fresh[0].source_presets, vec!["Egui", "Rust"]);
+        assert_eq!(fresh[1].source_presets, vec!["Egui"]);
+    }
+
+    #[test]
+    fn per_channel_presets_scope_each_resolved_channel() {
+        let a = "UCaaaaaaaaaaaaaaaaaaaaaa";
+        let b = "UCbbbbbbbbbbbbbbbbbbbbbb";
+        let search = channel_preset(&[a, b, a], None, true);
+        assert_eq!(
+            search_scopes(&search),
+            vec![Some(a.to_owned()), Some(b.to_owned())]
+        );
+        assert!(build_query_params(&SearchDefaults::default(), &search).is_ok());
+
+        let merged = channel_preset(&[a, b], Some("rust"), false);
+        assert_eq!(search_scopes(&merged), vec![None]);
+    }
+
+    #[test]
+    fn unresolved_handles_fall_back_to_an_unscoped_search() {
+        let a = "UCaaaaaaaaaaaaaaaaaaaaaa";
+        let with_text = channel_preset(&[a, "@someone"], Some("rust"), true);
+        assert_eq!(search_scopes(&with_text), vec![Some(a.to_owned()), None]);
+
+        let without_text = channel_preset(&[a, "@someone"], None, true);
+        assert_eq!(search_scopes(&without_text), vec![Some(a.to_owned())]);
+
+        let handles_only = channel_preset(&["@someone"], None, true);
+        assert_eq!(search_scopes(&handles_only), vec![None]);
+        assert!(build_query_params(&SearchDefaults::default(), &handles_only).is_err());
+    }
+
+    #[test]
+    fn uploads_mode_reads_playlists_only_for_bare_allow_lists() {
+        let a = "UCaaaaaaaaaaaaaaaaaaaaaa";
+        let b = "UCbbbbbbbbbbbbbbbbbbbbbb";
+        let mut search = channel_preset(&[a, b, a, "@someone"], None, false);
+        assert!(!uses_uploads_playlist(&search));
+        search.uploads_mode = true;
+        assert!(uses_uploads_playlist(&search));
+        let mode = RunMode::AdHoc(Box::new(search.clone()));
+        assert_eq!(
+            run_scope_count(&[], &mode),
+            quota::RunScopes {
+                searches: 0,
+                uploads: 2,
+            }
+        );
+
+        search.query.q = Some("rust".into());
+        assert!(!uses_uploads_playlist(&search));
+        let mode = RunMode::AdHoc(Box::new(search));
+        assert_eq!(run_scope_count(&[], &mode).uploads, 0);
+    }
+
+    #[test]
+    fn cap_keeps_the_newest_and_zero_disables_it() {
+        let dated = |id: &str, published: &str| VideoDetails {
+            published_at: published.into(),
+            ..video(id)
+        };
+        let mut videos = vec![
+            dated("old", "2024-01-01T00:00:00Z"),
+            dated("new", "2024-03-01T00:00:00Z"),
+            dated("mid", "2024-02-01T00:00:00Z"),
+        ];
+        assert_eq!(cap_newest(&mut videos, 0), 0);
+        assert_eq!(cap_newest(&mut videos, 3), 0);
+        assert_eq!(cap_newest(&mut videos, 2), 1);
+        assert_eq!(ids(&videos), ["new", "mid"]);
+    }
+
+    #[test]
+    fn diff_splits_added_removed_and_unchanged() {
+        let previous = vec![video("a"), video("b"), video("c")];
+        let current = vec![video("d"), video("b"), video("e"), video("a")];
+        let diff = diff_runs(&previous, &current);
+        assert_eq!(diff.added, [0, 2]);
+        assert_eq!(diff.removed, [2]);
+        assert_eq!(diff.unchanged, [1, 3]);
+    }
+
+    #[test]
+    fn diff_against_empty_previous_is_all_new() {
+        let current = vec![video("a"), video("b")];
+        let diff = diff_runs(&[], &current);
+        assert_eq!(diff.added, [0, 1]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.unchanged.is_empty());
+
+        let diff = diff_runs(&current, &[]);
+        assert_eq!(diff.removed, [0, 1]);
+        assert!(diff.added.is_empty());
     }
 }
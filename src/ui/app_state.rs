@@ -1,85 +1,238 @@
-use crate::cache::{self, CachedResults};
+use crate::annotations::{self, Annotations};
+use crate::cache::{self, CacheLoad, CachedResults};
+use crate::channel_cache::{self, ChannelCache};
+use crate::debug_report;
+use crate::dismissals::{self, Dismissals};
 use crate::filters;
-use crate::prefs::{self, Prefs};
-use crate::search_runner::{RunMode, SearchOutcome};
+pub use crate::prefs::ResultSort;
+use crate::prefs::{self, MySearch, Prefs, ThemeChoice};
+use crate::preset_stats::{self, PresetStats};
+use crate::search_runner::{
+    self, PresetEstimate, PresetOverlap, RawPage, RunDiff, RunMode, SearchOutcome, SearchProgress,
+};
 use crate::yt::types::VideoDetails;
 use tokio::runtime::{Builder, Runtime};
 use tokio::task::JoinHandle;
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use std::sync::mpsc;
+use std::time::Duration as StdDuration;
 use time::{Duration, OffsetDateTime, format_description::well_known::Rfc3339};
 
-use egui::Context;
+use egui::{Color32, Context, RichText};
 
 use super::duration_filters::{DurationFilterState, channel_sort_key};
+use super::find_index::FindIndex;
+use super::finder;
+use super::pending_ops::{PendingOp, PendingOps};
 use super::preset_editor::{PresetEditorMode, PresetEditorState};
+use super::settings::SettingsState;
+use super::shortcuts::{Shortcut, ShortcutScope};
+use super::theme;
 use super::thumbnails::{self, ThumbnailRef};
+use super::ui_state::{self, UiState};
+use super::utils;
 
 pub enum SearchResult {
     Success(SearchOutcome),
-    Error(String),
+    Error {
+        message: String,
+        details: Option<String>,
+    },
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum ResultSort {
-    Newest,
-    Oldest,
-    Shortest,
-    Longest,
-    Channel,
+/// The most recent failure, kept around after the status line moves on.
+#[derive(Clone, Debug)]
+pub struct LastError {
+    pub message: String,
+    pub details: Option<String>,
+    pub occurred_at: OffsetDateTime,
 }
 
-impl ResultSort {
-    pub fn label(self) -> &'static str {
-        match self {
-            ResultSort::Newest => "Newest",
-            ResultSort::Oldest => "Oldest",
-            ResultSort::Shortest => "Shortest",
-            ResultSort::Longest => "Longest",
-            ResultSort::Channel => "Channel",
-        }
-    }
-}
+/// What per-card UI state is keyed by: the video ID and its index in `results_all`, so the
+/// copies `DuplicatePolicy::KeepAll` keeps each get a card of their own.
+pub type CardKey = (String, usize);
+
+/// How long background tasks get to finish when the window closes.
+const SHUTDOWN_GRACE_MS: u64 = 500;
 
 pub struct AppState {
     pub prefs: Prefs,
     pub status: String,
     pub run_any_mode: bool,
-    pub results: Vec<VideoDetails>,
+    /// Indices into `results_all` after preset scoping, find, dismissals, and the block list,
+    /// in display order. Rebuilt by `refresh_visible_results` whenever `results_all` changes.
+    pub results: Vec<usize>,
     pub results_all: Vec<VideoDetails>,
+    /// Positions in `results` that pass the Length chips, rebuilt whenever the list changes.
+    pub visible_rows: Vec<usize>,
+    /// Last measured height of each result card.
+    pub card_heights: HashMap<CardKey, f32>,
+    /// Topmost card in the results viewport, recorded by the results list each frame.
+    pub top_visible_id: Option<CardKey>,
+    /// Card to scroll to the top of the list on the next frame, set after a refresh.
+    pub pending_scroll_to: Option<CardKey>,
+    /// Scroll offset to apply on the next frame, e.g. the top after a search replaces the
+    /// results or a preset's remembered position; wins over `pending_scroll_to`.
+    pub pending_scroll_offset: Option<f32>,
+    /// Results list scroll offset as of the last frame.
+    pub results_scroll_offset: f32,
+    /// Single mode scroll offsets by preset ID ("" when no preset is selected).
+    pub preset_scroll_offsets: HashMap<String, f32>,
     pub result_sort: ResultSort,
     pub duration_filter: DurationFilterState,
     pub runtime: Runtime,
     pub selected_search_id: Option<String>,
     pub pending_task: Option<JoinHandle<()>>,
     pub search_rx: Option<mpsc::Receiver<SearchResult>>,
+    pub search_progress_rx: Option<mpsc::Receiver<SearchProgress>>,
+    /// Latest stage of the running search, shown under the spinner.
+    pub search_stage: Option<String>,
     pub is_searching: bool,
     pub preset_editor: Option<PresetEditorState>,
     pub import_dialog: Option<dialogs::ImportDialogState>,
     pub export_dialog: Option<dialogs::ExportDialogState>,
+    pub settings_dialog: Option<SettingsState>,
     pub cached_banner_until: Option<OffsetDateTime>,
+    /// Whether `results_all` came from the results cache rather than a search this session;
+    /// cleared when a live search succeeds.
+    pub results_are_cached: bool,
+    pub cache_saved_at: Option<i64>,
+    /// When the last successful search finished; survives "Clear results" for the
+    /// "Since last search" window.
+    pub last_search_unix: Option<i64>,
+    pub prefs_save_due: Option<OffsetDateTime>,
+    /// Whether the startup notice about name-only block entries matching exactly has been
+    /// shown; saved in ui_state.json.
+    pub name_block_notice_shown: bool,
+    /// Set by the footer's "Blocked channels" segment; the sidebar scrolls its block list
+    /// into view on the next frame.
+    pub scroll_to_block_list: bool,
     pub show_help_dialog: bool,
+    /// Confirmation step of "Block all visible channels", when one is showing.
+    pub bulk_block_step: Option<block_ops::BulkBlockStep>,
+    /// First-run welcome, shown when no prefs.json existed at startup.
+    pub show_welcome: bool,
+    pub last_error: Option<LastError>,
+    /// Counters from the last successful run, for the debug report.
+    pub last_run_stats: Option<debug_report::RunStats>,
+    pub status_history: report_ops::StatusHistory,
+    /// "Include result titles" in the help window's debug report.
+    pub debug_report_titles: bool,
+    pub applied_system_dark: bool,
+    pub theme_checked_at: OffsetDateTime,
     pub thumbnail_cache: thumbnails::ThumbnailCache,
+    pub channel_lookups: ChannelLookups,
+    /// Everything the run before the latest one returned; cleared when presets change.
+    pub previous_results: Option<Vec<VideoDetails>>,
+    /// `previous_results` against `results_all`, computed once per run.
+    pub run_diff: Option<RunDiff>,
+    /// `run_diff` narrowed to the rows currently shown, rebuilt with `visible_rows`.
+    pub change_rows: ChangeRows,
+    /// Which "Changes" sections are expanded: new, no longer returned, still present.
+    pub change_sections_open: [bool; 3],
+    pub show_changes: bool,
+    /// Cards whose title was clicked open past the two-row cap.
+    pub expanded_titles: HashMap<CardKey, bool>,
+    pub preset_test_task: Option<JoinHandle<()>>,
+    pub preset_test_rx: Option<mpsc::Receiver<Result<SearchOutcome, String>>>,
+    pub preset_estimate_rx: Option<mpsc::Receiver<Result<PresetEstimate, String>>>,
+    pub quota_prompt: Option<QuotaPrompt>,
+    pub diagnostics: Option<diagnostics_ops::DiagnosticsState>,
+    pub preset_gallery: Option<gallery_ops::PresetGalleryState>,
+    /// Preset ids whose results are hidden by the legend; cleared when a search starts.
+    pub view_hidden_presets: HashSet<String>,
+    /// Async operations in flight, so repeated clicks cannot start duplicates.
+    pub pending_ops: PendingOps,
+    /// Videos hidden with "Not interested", counted per channel for block suggestions.
+    pub dismissals: Dismissals,
+    /// Videos opened in the browser this session; "Open next" skips them.
+    pub opened_videos: HashSet<String>,
+    /// The "Open next" batch still being opened, if any.
+    pub open_batch: Option<OpenBatch>,
+    /// Preset pairs that shared videos in the last run, for the overlap report.
+    pub preset_overlaps: Vec<PresetOverlap>,
+    /// search.list pages of the last run, kept when `keep_raw_responses` is on.
+    pub raw_pages: Vec<RawPage>,
+    pub show_raw_responses: bool,
+    /// Preset-list filter text, matched fuzzily against preset names.
+    pub preset_filter: String,
+    /// The preset list shows merge checkboxes instead of acting on clicks.
+    pub merge_mode: bool,
+    /// Preset IDs ticked for merging.
+    pub merge_selection: BTreeSet<String>,
+    /// The "Merge presets?" confirmation is open.
+    pub merge_confirm: bool,
+    /// Quick-search box text.
+    pub quick_query: String,
+    /// Unsaved preset behind the current results when they came from the quick-search box.
+    pub quick_search: Option<MySearch>,
+    /// Notes on videos, keyed by video ID.
+    pub annotations: Annotations,
+    /// `prefs.blocked_channels` compiled for matching. Edit the list only through
+    /// `blocked.edit(&mut self.prefs.blocked_channels, ..)` so the two stay in step.
+    pub blocked: filters::BlockedCache,
+    /// Channel names and handles by channel ID, with when each was fetched.
+    pub channel_cache: ChannelCache,
+    /// When each preset last ran and what it fetched, shown under its name in the sidebar.
+    pub preset_stats: PresetStats,
+    /// When edited notes should next be written to annotations.json.
+    pub annotations_save_due: Option<OffsetDateTime>,
+    /// Cards whose note editor is expanded.
+    pub open_notes: HashSet<CardKey>,
+    /// mm:ss typed into a card's Share menu for "Copy link at time".
+    pub share_timestamp: String,
+    /// Find-box text; when set, the list ranks every loaded result by relevance.
+    pub find_query: String,
+    /// Word index over every video seen, loaded from the config folder while
+    /// `ui.indexed_find` is on.
+    pub find_index: Option<FindIndex>,
+    /// Bumped by `reindex_results` whenever `results_all` changes.
+    pub results_generation: u64,
+    /// The `results_generation` the find index last took in.
+    pub find_index_generation: Option<u64>,
+    pub history_search: HistorySearch,
 }
 
+mod annotation_ops;
+mod block_ops;
+mod change_ops;
+mod channel_ops;
+mod diagnostics_ops;
 mod dialogs;
+mod dismissal_ops;
+mod find_ops;
+mod gallery_ops;
+mod legend_ops;
+mod merge_ops;
 mod preset_ops;
+mod quick_search_ops;
+mod quota_ops;
+mod report_ops;
+mod scroll_ops;
+mod settings_ops;
+mod triage_ops;
 
+pub use change_ops::ChangeRows;
+pub use channel_ops::{ChannelLookup, ChannelLookups};
 #[allow(unused_imports)]
-pub use dialogs::{ExportDialogState, ExportMode, ImportDialogState, ImportMode};
+pub use dialogs::{ExportDialogState, ImportDialogState, ImportFormat, ImportMode};
+pub use find_ops::HistorySearch;
+pub use quota_ops::QuotaPrompt;
+pub use triage_ops::OpenBatch;
 
 impl AppState {
     /// Initialize UI state, loading prefs, cached results, and runtime.
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        super::theme::apply_gfv_theme(&cc.egui_ctx);
-
+        let first_run = !prefs::prefs_file_exists();
         let mut prefs = prefs::load_or_default();
         prefs::add_missing_defaults(&mut prefs);
         prefs::normalize_block_list(&mut prefs.blocked_channels);
-        prefs::normalize_duration_filters(&mut prefs.global);
+        crate::yt::http::configure(&prefs.search_defaults);
+        prefs::normalize_duration_filters(&mut prefs.search_defaults);
+        prefs::normalize_region_code(&mut prefs.search_defaults);
         let mut status = String::from("Ready.");
 
         if prefs.api_key.trim().is_empty() {
@@ -93,28 +246,63 @@ impl AppState {
             }
         }
 
+        if let Some(note) = crate::config_dir::get().status_note() {
+            status = note;
+        }
+
         for search in &mut prefs.searches {
             if matches!(search.query.category_id, Some(28)) {
                 search.query.category_id = None;
             }
         }
+        let system_dark = theme::system_is_dark(&cc.egui_ctx);
+        theme::apply_theme(&cc.egui_ctx, prefs.ui.theme, system_dark);
+
         let runtime = Builder::new_multi_thread()
             .enable_all()
             .build()
             .expect("failed to start tokio runtime");
-        let duration_filter = DurationFilterState::from_global(&prefs.global);
+        let duration_filter = DurationFilterState::from_defaults(&prefs.search_defaults);
         let mut initial_results_all: Vec<VideoDetails> = Vec::new();
         let mut cached_banner_until: Option<OffsetDateTime> = None;
+        let mut cache_saved_at: Option<i64> = None;
+        let mut last_search_unix: Option<i64> = None;
+        let blocked = filters::BlockedCache::new(&prefs.blocked_channels);
 
-        if let Some(mut cached) = cache::load_cached_results() {
-            let blocked_keys = prefs::blocked_keys(&prefs.blocked_channels);
-            cached.videos.retain(|video| {
-                !filters::matches_channel(
-                    &video.channel_handle,
-                    &video.channel_title,
-                    &blocked_keys,
-                )
-            });
+        let mut cache_warning: Option<String> = None;
+        let cached = match cache::load_cached_results() {
+            CacheLoad::Missing => None,
+            CacheLoad::Loaded(cached) => Some(cached),
+            CacheLoad::Corrupt {
+                error,
+                backup,
+                salvaged,
+            } => {
+                let moved = match &backup {
+                    Some(path) => format!(
+                        "moved it to {}",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    None => "left it in place".to_owned(),
+                };
+                let recovered = match &salvaged {
+                    Some(salvaged) => format!(
+                        "recovered {} of {} video(s)",
+                        salvaged.results.videos.len(),
+                        salvaged.results.videos.len() + salvaged.skipped
+                    ),
+                    None => "nothing could be recovered".to_owned(),
+                };
+                cache_warning = Some(format!(
+                    "⚠ Cached results could not be read ({error}); {moved} and {recovered}."
+                ));
+                salvaged.map(|salvaged| salvaged.results)
+            }
+        };
+        if let Some(mut cached) = cached {
+            cached
+                .videos
+                .retain(|video| !filters::is_blocked(video, blocked.set()));
             let count = cached.videos.len();
             status = if count == 0 {
                 format!("Cached {} · no videos", cached.generated_at)
@@ -127,28 +315,116 @@ impl AppState {
                 )
             };
             cached_banner_until = Some(OffsetDateTime::now_utc() + Duration::seconds(5));
+            cache_saved_at = Some(cached.saved_at_unix);
+            last_search_unix = Some(cached.saved_at_unix);
             initial_results_all = cached.videos;
         }
+        if let Some(warning) = cache_warning {
+            status = warning;
+            cached_banner_until = None;
+        }
 
+        let thumbnail_cache = thumbnails::ThumbnailCache::new();
+        if prefs.ui.thumbnail_max_age_days > 0 {
+            let days = u64::from(prefs.ui.thumbnail_max_age_days);
+            thumbnail_cache.prune_old(std::time::Duration::from_secs(days * 24 * 60 * 60));
+        }
+
+        let view = ui_state::load();
+        let name_only = prefs
+            .blocked_channels
+            .iter()
+            .filter(|entry| prefs::BlockEntry::parse(entry).is_name_only())
+            .count();
+        if name_only > 0 && !view.name_block_notice_shown {
+            status.push_str(&format!(
+                " · {name_only} blocked channel(s) are listed by name only and now hide just \
+                 channels with exactly that name, not every channel whose name contains it."
+            ));
+        }
+        let result_sort = prefs.ui.default_result_sort.unwrap_or(view.result_sort);
         let mut state = Self {
             prefs,
             status,
-            run_any_mode: true,
+            run_any_mode: view.run_any_mode,
             results: Vec::new(),
             results_all: initial_results_all,
-            result_sort: ResultSort::Newest,
+            visible_rows: Vec::new(),
+            card_heights: HashMap::new(),
+            top_visible_id: None,
+            pending_scroll_to: None,
+            pending_scroll_offset: None,
+            results_scroll_offset: 0.0,
+            preset_scroll_offsets: HashMap::new(),
+            result_sort,
             duration_filter,
             runtime,
-            selected_search_id: None,
+            selected_search_id: view.selected_search_id,
+            name_block_notice_shown: view.name_block_notice_shown || name_only > 0,
             pending_task: None,
             search_rx: None,
+            search_progress_rx: None,
+            search_stage: None,
             is_searching: false,
             preset_editor: None,
             import_dialog: None,
             export_dialog: None,
+            settings_dialog: None,
             cached_banner_until,
+            results_are_cached: cache_saved_at.is_some(),
+            cache_saved_at,
+            last_search_unix,
+            prefs_save_due: None,
+            scroll_to_block_list: false,
             show_help_dialog: false,
-            thumbnail_cache: thumbnails::ThumbnailCache::new(),
+            bulk_block_step: None,
+            show_welcome: first_run,
+            last_error: None,
+            last_run_stats: None,
+            status_history: report_ops::StatusHistory::default(),
+            debug_report_titles: false,
+            applied_system_dark: system_dark,
+            theme_checked_at: OffsetDateTime::now_utc(),
+            thumbnail_cache,
+            channel_lookups: ChannelLookups::new(),
+            previous_results: None,
+            run_diff: None,
+            change_rows: ChangeRows::default(),
+            change_sections_open: [true, true, false],
+            show_changes: false,
+            expanded_titles: HashMap::new(),
+            preset_test_task: None,
+            preset_test_rx: None,
+            preset_estimate_rx: None,
+            quota_prompt: None,
+            diagnostics: None,
+            preset_gallery: None,
+            view_hidden_presets: HashSet::new(),
+            pending_ops: PendingOps::default(),
+            dismissals: dismissals::load(),
+            opened_videos: HashSet::new(),
+            open_batch: None,
+            preset_overlaps: Vec::new(),
+            raw_pages: Vec::new(),
+            show_raw_responses: false,
+            preset_filter: String::new(),
+            merge_mode: false,
+            merge_selection: BTreeSet::new(),
+            merge_confirm: false,
+            quick_query: String::new(),
+            quick_search: None,
+            annotations: annotations::load(),
+            blocked,
+            channel_cache: channel_cache::load(),
+            preset_stats: preset_stats::load(),
+            annotations_save_due: None,
+            open_notes: HashSet::new(),
+            share_timestamp: String::new(),
+            find_query: String::new(),
+            find_index: None,
+            results_generation: 0,
+            find_index_generation: None,
+            history_search: HistorySearch::default(),
         };
         if !state.results_all.is_empty() {
             state.refresh_visible_results();
@@ -156,49 +432,86 @@ impl AppState {
             state.apply_result_sort();
         }
         state.sync_thumbnail_cache();
+        state.warm_up_thumbnails(&cc.egui_ctx);
+        state.resolve_blocked_labels();
         state
     }
 
+    /// Read the first screenful of cached results' thumbnails from disk in the background.
+    fn warm_up_thumbnails(&mut self, ctx: &Context) {
+        let videos = self
+            .visible_rows
+            .iter()
+            .take(thumbnails::WARM_UP_COUNT)
+            .map(|&row| &self.results_all[self.results[row]])
+            .filter_map(|video| Some((video.id.as_str(), video.thumbnail_url.as_deref()?)));
+        self.thumbnail_cache.warm_up(videos, ctx, &self.runtime);
+    }
+
     fn sync_duration_filter_to_prefs(&mut self) {
         let selected = self.duration_filter.selected_ids();
-        if self.prefs.global.active_duration_bucket_ids != selected {
-            self.prefs.global.active_duration_bucket_ids = selected;
+        if self.prefs.search_defaults.active_duration_bucket_ids != selected {
+            self.prefs.search_defaults.active_duration_bucket_ids = selected;
         }
     }
 
     pub fn apply_result_sort(&mut self) {
+        self.sort_results();
+        self.rebuild_visible_rows();
+    }
+
+    fn sort_results(&mut self) {
+        let all = &self.results_all;
+        if self.find_active() {
+            let query = &self.find_query;
+            self.results.sort_by_cached_key(|&index| {
+                let video = &all[index];
+                (
+                    std::cmp::Reverse(finder::relevance(query, video).unwrap_or(0)),
+                    std::cmp::Reverse(video.published_at.clone()),
+                )
+            });
+            return;
+        }
         match self.result_sort {
             ResultSort::Newest => {
                 self.results
-                    .sort_by(|a, b| b.published_at.cmp(&a.published_at));
+                    .sort_by(|&a, &b| all[b].published_at.cmp(&all[a].published_at));
             }
             ResultSort::Oldest => {
                 self.results
-                    .sort_by(|a, b| a.published_at.cmp(&b.published_at));
+                    .sort_by(|&a, &b| all[a].published_at.cmp(&all[b].published_at));
             }
             ResultSort::Channel => {
-                self.results.sort_by(|a, b| {
-                    let a_key = channel_sort_key(a);
-                    let b_key = channel_sort_key(b);
-                    a_key
-                        .cmp(&b_key)
+                self.results.sort_by(|&a, &b| {
+                    let (a, b) = (&all[a], &all[b]);
+                    channel_sort_key(a)
+                        .cmp(&channel_sort_key(b))
                         .then_with(|| b.published_at.cmp(&a.published_at))
                 });
             }
             ResultSort::Shortest => {
-                self.results.sort_by(|a, b| {
+                self.results.sort_by(|&a, &b| {
+                    let (a, b) = (&all[a], &all[b]);
                     a.duration_secs
                         .cmp(&b.duration_secs)
                         .then_with(|| b.published_at.cmp(&a.published_at))
                 });
             }
             ResultSort::Longest => {
-                self.results.sort_by(|a, b| {
+                self.results.sort_by(|&a, &b| {
+                    let (a, b) = (&all[a], &all[b]);
                     b.duration_secs
                         .cmp(&a.duration_secs)
                         .then_with(|| b.published_at.cmp(&a.published_at))
                 });
             }
+            ResultSort::Priority => {
+                let priorities = search_runner::preset_priorities(&self.prefs.searches);
+                self.results.sort_by_key(|&index| {
+                    search_runner::priority_sort_key(&all[index], &priorities)
+                });
+            }
         }
     }
 
@@ -226,22 +539,36 @@ impl AppState {
     /// Restore built-in presets while keeping API key/min duration, clearing cache/state.
     pub fn reset_to_defaults(&mut self) {
         let saved_api_key = self.prefs.api_key.clone();
-        let saved_min_duration = self.prefs.global.min_duration_secs;
+        let saved_min_duration = self.prefs.search_defaults.min_duration_secs;
 
         let mut defaults = prefs::builtin_default();
         defaults.api_key = saved_api_key;
+        defaults.quota_usage = self.prefs.quota_usage.clone();
+        defaults.recent_queries = self.prefs.recent_queries.clone();
         defaults.blocked_channels.clear();
-        defaults.global.min_duration_secs = saved_min_duration;
-        defaults.global.active_duration_bucket_ids =
-            defaults.global.duration_filters.default_active_ids();
+        defaults.search_defaults.min_duration_secs = saved_min_duration;
+        defaults.search_defaults.active_duration_bucket_ids = defaults
+            .search_defaults
+            .duration_filters
+            .default_active_ids();
 
-        prefs::normalize_duration_filters(&mut defaults.global);
+        prefs::normalize_duration_filters(&mut defaults.search_defaults);
         prefs::normalize_block_list(&mut defaults.blocked_channels);
+        prefs::mark_modified(&mut defaults);
 
+        block_ops::replace_block_list(
+            &mut self.blocked,
+            &mut self.prefs.blocked_channels,
+            &defaults.blocked_channels,
+        );
+        self.debug_check_blocked_set();
         self.prefs = defaults;
-        self.duration_filter = DurationFilterState::from_global(&self.prefs.global);
+        self.duration_filter = DurationFilterState::from_defaults(&self.prefs.search_defaults);
+        self.previous_results = None;
         self.results.clear();
+        self.visible_rows.clear();
         self.results_all.clear();
+        self.reindex_results();
         self.thumbnail_cache.clear();
         self.sync_thumbnail_cache();
         self.selected_search_id = None;
@@ -254,18 +581,110 @@ impl AppState {
         }
     }
 
+    /// "Reset defaults" from Settings: back up the current prefs first when asked, and leave
+    /// everything as it was if that backup fails.
+    pub fn reset_with_backup(&mut self, backup: bool) {
+        let backup_path = if backup {
+            match prefs::backup(&self.prefs, OffsetDateTime::now_utc()) {
+                Ok(path) => Some(path),
+                Err(err) => {
+                    self.record_error(
+                        format!("Reset cancelled: could not back up prefs: {err}"),
+                        None,
+                    );
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        self.reset_to_defaults();
+        if let Some(path) = backup_path
+            && self.status.starts_with("Defaults restored.")
+        {
+            self.status = format!(
+                "Defaults restored. Previous prefs backed up to {}.",
+                path.display()
+            );
+        }
+    }
+
+    /// Switch the theme choice and restyle immediately.
+    pub fn set_theme_choice(&mut self, ctx: &Context, choice: ThemeChoice) {
+        if self.prefs.ui.theme == choice {
+            return;
+        }
+        self.prefs.ui.theme = choice;
+        self.applied_system_dark = theme::system_is_dark(ctx);
+        theme::apply_theme(ctx, choice, self.applied_system_dark);
+        self.schedule_prefs_save();
+    }
+
+    /// Re-apply styles when the OS theme flips, checking at most once per second.
+    pub(super) fn sync_system_theme(&mut self, ctx: &Context) {
+        let now = OffsetDateTime::now_utc();
+        if now - self.theme_checked_at < Duration::seconds(1) {
+            return;
+        }
+        self.theme_checked_at = now;
+        let system_dark = theme::system_is_dark(ctx);
+        if system_dark == self.applied_system_dark {
+            return;
+        }
+        self.applied_system_dark = system_dark;
+        if self.prefs.ui.theme == ThemeChoice::System {
+            theme::apply_theme(ctx, ThemeChoice::System, system_dark);
+        }
+    }
+
+    /// Show an error in the status line and remember it for the Details view.
+    pub fn record_error(&mut self, message: String, details: Option<String>) {
+        self.status = message.clone();
+        let details = details.filter(|text| text.trim() != message.trim());
+        self.last_error = Some(LastError {
+            message,
+            details,
+            occurred_at: OffsetDateTime::now_utc(),
+        });
+    }
+
+    /// Queue a prefs save for shortly from now, coalescing bursts of edits.
+    pub fn schedule_prefs_save(&mut self) {
+        self.prefs_save_due = Some(OffsetDateTime::now_utc() + Duration::seconds(2));
+    }
+
+    /// Write prefs to disk if a scheduled save has come due.
+    pub(super) fn flush_scheduled_prefs_save(&mut self) {
+        let Some(due) = self.prefs_save_due else {
+            return;
+        };
+        if OffsetDateTime::now_utc() < due {
+            return;
+        }
+        self.prefs_save_due = None;
+        if let Err(err) = prefs::save(&self.prefs) {
+            self.record_error(format!("Failed to save prefs: {err}"), None);
+        }
+    }
+
     /// Persist duration filter selections back into preferences.
     pub(crate) fn normalize_duration_selection(&mut self) {
         self.sync_duration_filter_to_prefs();
-        prefs::normalize_duration_filters(&mut self.prefs.global);
+        prefs::normalize_duration_filters(&mut self.prefs.search_defaults);
         self.duration_filter
-            .sync_from_ids(&self.prefs.global.active_duration_bucket_ids);
+            .sync_from_ids(&self.prefs.search_defaults.active_duration_bucket_ids);
     }
 
     /// Recalculate visible results based on run mode and preset selection.
     pub fn refresh_visible_results(&mut self) {
-        let mut filtered: Vec<VideoDetails> = Vec::new();
-        if self.run_any_mode {
+        let old_order = self.visible_order();
+        let found = self.find_active().then(|| self.find_matches());
+        let all = &self.results_all;
+        let mut filtered: Vec<usize> = if let Some(found) = found {
+            found
+        } else if self.quick_search.is_some() {
+            (0..all.len()).collect()
+        } else if self.run_any_mode {
             let enabled_names: HashSet<&str> = self
                 .prefs
                 .searches
@@ -275,109 +694,299 @@ impl AppState {
                 .collect();
             if enabled_names.is_empty() {
                 self.results.clear();
+                self.visible_rows.clear();
                 return;
             }
-            for video in &self.results_all {
-                if video
-                    .source_presets
-                    .iter()
-                    .any(|name| enabled_names.contains(name.as_str()))
-                {
-                    filtered.push(video.clone());
-                }
-            }
+            (0..all.len())
+                .filter(|&index| {
+                    all[index]
+                        .source_presets
+                        .iter()
+                        .any(|name| enabled_names.contains(name.as_str()))
+                })
+                .collect()
         } else {
-            if let Some(selected_id) = self.selected_search_id.clone() {
-                if let Some(selected_preset) = self
-                    .prefs
+            let selected_name = self.selected_search_id.as_ref().map(|selected_id| {
+                self.prefs
                     .searches
                     .iter()
-                    .find(|preset| preset.id == selected_id)
-                {
-                    for video in &self.results_all {
-                        if video
+                    .find(|preset| &preset.id == selected_id)
+                    .map(|preset| preset.name.as_str())
+            });
+            match selected_name {
+                Some(Some(name)) => (0..all.len())
+                    .filter(|&index| {
+                        all[index]
                             .source_presets
                             .iter()
-                            .any(|name| name == &selected_preset.name)
-                        {
-                            filtered.push(video.clone());
-                        }
-                    }
-                } else {
+                            .any(|source| source == name)
+                    })
+                    .collect(),
+                Some(None) => {
                     self.selected_search_id = None;
-                    filtered = self.results_all.clone();
+                    (0..all.len()).collect()
                 }
-            } else {
-                filtered = self.results_all.clone();
+                None => (0..all.len()).collect(),
             }
-        }
+        };
 
+        let dismissed = self.dismissals.video_ids();
+        let blocked = self.blocked.set();
+        filtered.retain(|&index| {
+            !dismissed.contains(all[index].id.as_str())
+                && !filters::is_blocked(&all[index], blocked)
+        });
+        if !self.view_hidden_presets.is_empty() {
+            let hidden = self.view_hidden_names();
+            filtered.retain(|&index| {
+                !legend_ops::hidden_by_legend(&all[index].source_presets, &hidden)
+            });
+        }
         self.results = filtered;
         self.apply_result_sort();
+        self.restore_scroll_anchor(&old_order);
+    }
+
+    /// Whether the find box holds a query, which overrides preset scoping and the sort order.
+    pub fn find_active(&self) -> bool {
+        !self.find_query.trim().is_empty()
     }
 
-    /// Write current results to disk so next launch can reuse them.
-    pub fn persist_cached_results(&self) {
+    /// Write the results of a search that just finished to disk so next launch can reuse
+    /// them. The save time doubles as the last search time.
+    pub fn persist_cached_results(&mut self) {
         let now = OffsetDateTime::now_utc();
-        let generated_at = now.format(&Rfc3339).unwrap_or_else(|_| now.to_string());
-        let payload = CachedResults {
-            generated_at,
+        let payload = self.cached_results_payload(now);
+        self.cache_saved_at = Some(payload.saved_at_unix);
+        self.last_search_unix = Some(payload.saved_at_unix);
+        if let Err(err) = cache::save_cached_results(&payload) {
+            eprintln!("Failed to save cached results: {err}");
+        }
+    }
+
+    /// Rewrite the results cache after the loaded results changed without a search, keeping
+    /// its timestamp so the "Since last search" window does not move.
+    pub(crate) fn resave_cached_results(&mut self) -> std::io::Result<()> {
+        let saved_at = self
+            .cache_saved_at
+            .and_then(|unix| OffsetDateTime::from_unix_timestamp(unix).ok())
+            .unwrap_or_else(OffsetDateTime::now_utc);
+        let payload = self.cached_results_payload(saved_at);
+        self.cache_saved_at = Some(payload.saved_at_unix);
+        cache::save_cached_results(&payload)
+    }
+
+    fn cached_results_payload(&self, saved_at: OffsetDateTime) -> CachedResults {
+        CachedResults {
+            generated_at: saved_at
+                .format(&Rfc3339)
+                .unwrap_or_else(|_| saved_at.to_string()),
             status_line: self.status.clone(),
             videos: self.results_all.clone(),
-            saved_at_unix: now.unix_timestamp(),
+            saved_at_unix: saved_at.unix_timestamp(),
+        }
+    }
+
+    /// Save everything, then stop background work: in-flight searches are aborted and the
+    /// runtime gets a moment to wind down instead of blocking the window from closing.
+    pub fn shutdown(&mut self) {
+        self.save_everything();
+        if let Some(task) = self.pending_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.preset_test_task.take() {
+            task.abort();
+        }
+        self.search_rx = None;
+        self.search_progress_rx = None;
+        self.preset_test_rx = None;
+        self.preset_estimate_rx = None;
+        // `shutdown_timeout` needs the runtime by value; a thread-less stand-in takes its place.
+        let standby = Builder::new_current_thread()
+            .build()
+            .expect("failed to build standby runtime");
+        let runtime = std::mem::replace(&mut self.runtime, standby);
+        runtime.shutdown_timeout(StdDuration::from_millis(SHUTDOWN_GRACE_MS));
+    }
+
+    /// Write prefs, the loaded results, notes, and the view state (sort, run mode, selected
+    /// preset) in one go. Runs on exit and from "Save all"; unlike a search, it keeps the
+    /// cache's original timestamp so the "Since last search" window does not move.
+    pub fn save_everything(&mut self) {
+        let mut failures = Vec::new();
+
+        self.prefs_save_due = None;
+        if let Err(err) = prefs::save(&self.prefs) {
+            failures.push(format!("prefs ({err})"));
+        }
+
+        if !self.results_all.is_empty()
+            && let Err(err) = self.resave_cached_results()
+        {
+            failures.push(format!("results ({err})"));
+        }
+
+        self.annotations_save_due = None;
+        if let Err(err) = annotations::save(&self.annotations) {
+            failures.push(format!("notes ({err})"));
+        }
+
+        if let Err(err) = channel_cache::save(&self.channel_cache) {
+            failures.push(format!("channel info ({err})"));
+        }
+
+        if let Err(err) = preset_stats::save(&self.preset_stats) {
+            failures.push(format!("preset stats ({err})"));
+        }
+
+        let view = UiState {
+            result_sort: self.result_sort,
+            run_any_mode: self.run_any_mode,
+            selected_search_id: self.selected_search_id.clone(),
+            name_block_notice_shown: self.name_block_notice_shown,
         };
-        if let Err(err) = cache::save_cached_results(&payload) {
-            eprintln!("Failed to save cached results: {err}");
+        if let Err(err) = ui_state::save(&view) {
+            failures.push(format!("view state ({err})"));
+        }
+
+        if failures.is_empty() {
+            self.status = "Saved prefs, results, notes, and view state.".into();
+        } else {
+            self.record_error(format!("Failed to save {}.", failures.join(", ")), None);
+        }
+    }
+
+    /// Whether the loaded results were generated before the presets last changed.
+    pub fn cached_results_are_stale(&self) -> bool {
+        match self.cache_saved_at {
+            Some(saved_at) => {
+                !self.results_all.is_empty() && saved_at < self.prefs.modified_at_unix
+            }
+            None => false,
         }
     }
 
-    /// Start an async search task using current prefs and UI state.
+    /// Drop all loaded results, their thumbnails, and the on-disk cache.
+    pub fn clear_results(&mut self) {
+        self.results.clear();
+        self.visible_rows.clear();
+        self.card_heights.clear();
+        self.preset_scroll_offsets.clear();
+        self.results_all.clear();
+        self.reindex_results();
+        self.previous_results = None;
+        self.thumbnail_cache.clear();
+        self.sync_thumbnail_cache();
+        self.cache_saved_at = None;
+        self.cached_banner_until = None;
+        self.results_are_cached = false;
+        self.status = match cache::clear_cached_results() {
+            Ok(()) => "Results cleared.".into(),
+            Err(err) => format!("Results cleared, but failed to delete cache: {err}"),
+        };
+    }
+
+    /// Start a search, or hold it behind the quota prompt if it would overshoot the daily budget.
     pub fn launch_search(&mut self) {
+        if self.pending_ops.is_pending(PendingOp::Search) {
+            return;
+        }
+        self.quick_search = None;
+        self.launch_run();
+    }
+
+    /// Run the current mode, stopping at the quota prompt when the budget would be exceeded.
+    pub(super) fn launch_run(&mut self) {
+        self.normalize_duration_selection();
+        if let Some(prompt) = self.quota_prompt_for_run() {
+            self.status = format!(
+                "This run may use {} units but only {} remain today.",
+                prompt.estimated, prompt.remaining
+            );
+            self.quota_prompt = Some(prompt);
+            return;
+        }
+        self.start_search(None);
+    }
+
+    /// Spawn the search task; `max_pages` overrides the page cap for this run only. Does
+    /// nothing while a search is already running.
+    pub(crate) fn start_search(&mut self, max_pages: Option<usize>) {
+        if !self.pending_ops.begin(PendingOp::Search) {
+            return;
+        }
         if let Some(handle) = self.pending_task.take() {
             handle.abort();
         }
         self.search_rx = None;
+        self.search_progress_rx = None;
+        self.search_stage = None;
         self.results.clear();
+        self.visible_rows.clear();
+        self.view_hidden_presets.clear();
+        self.preset_overlaps.clear();
+        self.raw_pages.clear();
         self.status = "Searching...".into();
         self.is_searching = true;
         self.cached_banner_until = None;
 
         self.normalize_duration_selection();
-        let prefs_snapshot = self.prefs.clone();
-        let mode = match self.determine_run_mode(&prefs_snapshot) {
+        let mode = match self.determine_run_mode() {
             Ok(mode) => mode,
             Err(msg) => {
                 self.status = msg;
                 self.is_searching = false;
+                self.pending_ops.finish(PendingOp::Search);
                 return;
             }
         };
+        let warnings = self.run_conflict_count(&mode);
+        if warnings > 0 {
+            self.status = format!("Searching...{}", utils::warning_suffix(warnings));
+        }
 
+        let api_key = self.prefs.api_key.clone();
+        let mut defaults = self.prefs.search_defaults.clone();
+        defaults.last_search_unix = self.last_search_unix;
+        let searches = self.prefs.searches.clone();
+        let blocked_channels = self.prefs.blocked_channels.clone();
         let (tx, rx) = mpsc::channel();
+        let (progress_tx, progress_rx) = mpsc::channel();
         let task = self.runtime.spawn(async move {
-            let result = crate::search_runner::run_searches(prefs_snapshot, mode).await;
+            let result = crate::search_runner::run_searches(
+                api_key,
+                defaults,
+                searches,
+                blocked_channels,
+                mode,
+                max_pages,
+                Some(progress_tx.into()),
+            )
+            .await;
             let message = match result {
                 Ok(outcome) => SearchResult::Success(outcome),
-                Err(err) => SearchResult::Error(err.to_string()),
+                Err(err) => SearchResult::Error {
+                    message: err.to_string(),
+                    details: Some(format!("{err:?}")),
+                },
             };
             let _ = tx.send(message);
         });
         self.pending_task = Some(task);
         self.search_rx = Some(rx);
+        self.search_progress_rx = Some(progress_rx);
     }
 
     /// Derive run mode from UI state, falling back to Any if nothing is selected.
-    pub fn determine_run_mode(&self, prefs: &Prefs) -> Result<RunMode, String> {
-        if self.run_any_mode {
+    pub fn determine_run_mode(&self) -> Result<RunMode, String> {
+        if let Some(search) = &self.quick_search {
+            Ok(RunMode::AdHoc(Box::new(search.clone())))
+        } else if self.run_any_mode {
             Ok(RunMode::Any)
+        } else if let Some(id) = self.selected_search_id.clone() {
+            Ok(RunMode::Single(id))
         } else {
-            if let Some(id) = self.selected_search_id.clone() {
-                Ok(RunMode::Single(id))
-            } else if prefs.searches.is_empty() {
-                Ok(RunMode::Any)
-            } else {
-                Ok(RunMode::Any)
-            }
+            Ok(RunMode::Any)
         }
     }
 
@@ -391,69 +1000,89 @@ impl AppState {
     }
 
     pub fn block_channel(&mut self, channel_id: &str, channel_title: &str) {
-        let source = if !channel_id.trim().is_empty() {
-            channel_id.trim()
-        } else {
-            channel_title.trim()
-        };
-
-        if source.is_empty() {
+        let Some(entry) = block_ops::block_entry_for(channel_id, channel_title) else {
             self.status = "Channel identifier unavailable for blocking.".into();
             return;
-        }
-
-        let key = source.trim_start_matches('@').to_ascii_lowercase();
-        if self
-            .prefs
-            .blocked_channels
-            .iter()
-            .any(|entry| prefs::parse_block_entry(entry).0 == key)
-        {
+        };
+        let added = block_ops::add_block_entries(
+            &mut self.blocked,
+            &mut self.prefs.blocked_channels,
+            &[entry],
+        );
+        self.debug_check_blocked_set();
+        if added == 0 {
             self.status = format!("Channel '{}' already blocked.", channel_title);
             return;
         }
-
-        let label = if channel_title.trim().is_empty() {
-            source.to_string()
-        } else {
-            channel_title.trim().to_string()
-        };
-
-        self.prefs
-            .blocked_channels
-            .push(format!("{}|{}", key, label));
-        prefs::normalize_block_list(&mut self.prefs.blocked_channels);
+        self.resolve_blocked_labels();
 
         if let Err(err) = prefs::save(&self.prefs) {
             self.status = format!("Failed to save block list: {err}");
         } else {
             self.status = format!("Blocked channel: {}", channel_title);
         }
+        self.hide_blocked_results();
+    }
 
-        let blocked_keys = prefs::blocked_keys(&self.prefs.blocked_channels);
-        self.results.retain(|v| {
-            !filters::matches_channel(&v.channel_handle, &v.channel_title, &blocked_keys)
-        });
-        self.apply_result_sort();
+    /// Drop newly blocked channels from the list after the block list grew.
+    fn hide_blocked_results(&mut self) {
+        self.refresh_visible_results();
         self.cached_banner_until = None;
     }
 
+    /// Whether a loaded result survives the Length chips (unknown lengths always pass).
+    pub fn passes_length_filter(&self, video: &VideoDetails) -> bool {
+        !video.duration_is_known() || self.duration_filter.allows(video.duration_secs)
+    }
+
+    /// Results actually shown in the list after preset selection and Length chips.
+    pub fn visible_result_count(&self) -> usize {
+        self.visible_rows.len()
+    }
+
+    /// Recompute which sorted results pass the Length chips so the list does not refilter
+    /// every frame.
+    fn rebuild_visible_rows(&mut self) {
+        self.visible_rows = (0..self.results.len())
+            .filter(|&index| self.passes_length_filter(self.result(index)))
+            .collect();
+        self.rebuild_change_rows();
+    }
+
+    /// The video at `index` in `results`.
+    pub fn result(&self, index: usize) -> &VideoDetails {
+        &self.results_all[self.results[index]]
+    }
+
+    /// Key for the card at `index` in `results`.
+    pub fn card_key(&self, index: usize) -> CardKey {
+        (self.result(index).id.clone(), self.results[index])
+    }
+
     pub fn is_channel_blocked(&self, video: &VideoDetails) -> bool {
-        let blocked_keys = prefs::blocked_keys(&self.prefs.blocked_channels);
-        filters::matches_channel(&video.channel_handle, &video.channel_title, &blocked_keys)
+        filters::is_blocked(video, self.blocked.set())
+    }
+
+    /// Debug builds check after each block-list edit that no other path changed the list
+    /// without going through `blocked.edit`.
+    pub(crate) fn debug_check_blocked_set(&self) {
+        debug_assert_eq!(
+            *self.blocked.set(),
+            filters::BlockedSet::new(&self.prefs.blocked_channels),
+            "block list edited without recompiling the blocked set"
+        );
     }
 
+    /// Remove the block entry named by `channel_key`: its channel ID, handle, or stored label.
     pub fn unblock_channel(&mut self, channel_key: &str) {
-        let target = channel_key
-            .trim()
-            .trim_start_matches('@')
-            .to_ascii_lowercase();
-        let original_len = self.prefs.blocked_channels.len();
-        self.prefs
-            .blocked_channels
-            .retain(|entry| prefs::parse_block_entry(entry).0 != target);
-        if self.prefs.blocked_channels.len() != original_len {
-            prefs::normalize_block_list(&mut self.prefs.blocked_channels);
+        let removed = block_ops::remove_block_entry(
+            &mut self.blocked,
+            &mut self.prefs.blocked_channels,
+            channel_key,
+        );
+        self.debug_check_blocked_set();
+        if removed {
+            self.refresh_visible_results();
             if let Err(err) = prefs::save(&self.prefs) {
                 self.status = format!("Failed to save block list: {err}");
             } else {
@@ -468,6 +1097,17 @@ impl AppState {
         }
 
         let mut open = true;
+        let mut clear_error = false;
+        let mut run_diagnostics = false;
+        let mut save_report = false;
+        let mut block_all = false;
+        let bulk_block_count = self
+            .bulk_block_step
+            .map(|_| self.visible_unblocked_channels().len());
+        let bulk_block_hides = self
+            .bulk_block_step
+            .map(|_| self.bulk_block_impact())
+            .unwrap_or_default();
         egui::Window::new("About & Help")
             .open(&mut open)
             .collapsible(false)
@@ -480,19 +1120,168 @@ impl AppState {
                 ui.separator();
                 ui.label("API key setup:");
                 ui.small("1. Create a YouTube Data API v3 key in Google Cloud (enable the API)." );
-                ui.small("2. Paste the key into the Settings panel (left sidebar → My Searches)." );
+                ui.small("2. Paste the key into Settings → General (Settings button, top right).");
                 ui.small("   The key is saved to prefs.json inside your YTSearch config directory.");
                 ui.small("3. Press Search to fetch videos. Cached results reload automatically on startup.");
 
                 ui.separator();
                 ui.label("Documentation:");
                 ui.small("• README.md → “Where to start” covers full setup details.");
-                ui.small("• prefs.json lives under ~/.config/YTSearch/ (or platform equivalent).");
+                let config = crate::config_dir::get();
+                ui.small(format!(
+                    "• Config location: {} ({}{}).",
+                    config.path.display(),
+                    config.source.label(),
+                    if config.writable { "" } else { ", read-only: nothing is saved" }
+                ));
                 ui.small("• Search results respect filters, language, and duration buckets you pick up top.");
+
+                ui.separator();
+                ui.label("Keyboard shortcuts:");
+                for scope in ShortcutScope::ALL {
+                    ui.small(RichText::new(scope.heading()).strong());
+                    for shortcut in Shortcut::ALL.iter().filter(|s| s.scope() == scope) {
+                        ui.small(format!(
+                            "• {} — {}",
+                            shortcut.label(ctx),
+                            shortcut.description()
+                        ));
+                    }
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Something not working?");
+                    if ui.button("🩺 Diagnostics").clicked() {
+                        run_diagnostics = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Save debug report…")
+                        .on_hover_text(
+                            "One JSON file to attach to an issue: version, OS, prefs without the \
+                             API key, the last run's numbers, each preset's query, and recent \
+                             status messages",
+                        )
+                        .clicked()
+                    {
+                        save_report = true;
+                    }
+                    ui.checkbox(&mut self.debug_report_titles, "Include result titles");
+                });
+
+                egui::CollapsingHeader::new("Advanced")
+                    .default_open(self.bulk_block_step.is_some())
+                    .show(ui, |ui| {
+                        let count = bulk_block_count.unwrap_or_default();
+                        match self.bulk_block_step {
+                            None => {
+                                if ui
+                                    .button("Block all visible channels…")
+                                    .on_hover_text(
+                                        "Add every channel in the filtered results to the block list",
+                                    )
+                                    .clicked()
+                                {
+                                    self.bulk_block_step = Some(block_ops::BulkBlockStep::First);
+                                }
+                            }
+                            Some(_) if count == 0 => {
+                                ui.small("Every visible channel is already blocked.");
+                                if ui.button("OK").clicked() {
+                                    self.bulk_block_step = None;
+                                }
+                            }
+                            Some(block_ops::BulkBlockStep::First) => {
+                                ui.label(format!(
+                                    "This adds {count} channel(s) from the visible results to the block list."
+                                ));
+                                ui.label(format!("This will hide {bulk_block_hides} videos."));
+                                ui.horizontal(|ui| {
+                                    if ui.button("Continue…").clicked() {
+                                        self.bulk_block_step =
+                                            Some(block_ops::BulkBlockStep::Final);
+                                    }
+                                    if ui.button("Cancel").clicked() {
+                                        self.bulk_block_step = None;
+                                    }
+                                });
+                            }
+                            Some(block_ops::BulkBlockStep::Final) => {
+                                ui.colored_label(
+                                    Color32::from_rgb(239, 68, 68),
+                                    format!(
+                                        "Really block {count} channel(s)? Each must be unblocked by hand to undo this."
+                                    ),
+                                );
+                                ui.horizontal(|ui| {
+                                    let confirm = egui::Button::new(
+                                        RichText::new(format!("Block {count} channels"))
+                                            .color(Color32::WHITE),
+                                    )
+                                    .fill(Color32::from_rgb(239, 68, 68));
+                                    if ui.add(confirm).clicked() {
+                                        block_all = true;
+                                    }
+                                    if ui.button("Cancel").clicked() {
+                                        self.bulk_block_step = None;
+                                    }
+                                });
+                            }
+                        }
+                    });
+
+                if let Some(last) = self.last_error.as_ref() {
+                    ui.separator();
+                    ui.label("Last error:");
+                    let when = last
+                        .occurred_at
+                        .format(&Rfc3339)
+                        .unwrap_or_else(|_| last.occurred_at.to_string());
+                    ui.small(format!("At {when}"));
+                    ui.label(RichText::new(&last.message).color(Color32::from_rgb(239, 68, 68)));
+                    if let Some(details) = last.details.as_ref() {
+                        egui::CollapsingHeader::new("Details")
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                let mut text = details.as_str();
+                                egui::ScrollArea::vertical()
+                                    .max_height(220.0)
+                                    .show(ui, |ui| {
+                                        ui.add(
+                                            egui::TextEdit::multiline(&mut text)
+                                                .code_editor()
+                                                .desired_width(f32::INFINITY),
+                                        );
+                                    });
+                                if ui.button("Copy details").clicked() {
+                                    ctx.copy_text(details.clone());
+                                }
+                            });
+                    }
+                    if ui.button("Clear error").clicked() {
+                        clear_error = true;
+                    }
+                }
             });
 
+        if clear_error {
+            self.last_error = None;
+        }
+        if run_diagnostics {
+            self.open_diagnostics();
+        }
+        if save_report {
+            self.save_debug_report();
+        }
+        if block_all {
+            self.block_all_visible_channels();
+        }
+
         if !open {
             self.show_help_dialog = false;
+            self.bulk_block_step = None;
         }
     }
 }
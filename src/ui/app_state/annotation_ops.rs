@@ -0,0 +1,36 @@
+use time::{Duration, OffsetDateTime};
+
+use crate::annotations;
+
+use super::{AppState, CardKey};
+
+impl AppState {
+    /// Expand or collapse a card's note editor; collapsing saves pending edits right away.
+    pub fn toggle_note_editor(&mut self, card: &CardKey) {
+        if self.open_notes.remove(card) {
+            self.flush_annotations_save(true);
+        } else {
+            self.open_notes.insert(card.clone());
+        }
+    }
+
+    /// Store an edited note and save annotations.json once typing pauses.
+    pub fn set_video_note(&mut self, video_id: &str, text: &str) {
+        self.annotations.set(video_id, text);
+        self.annotations_save_due = Some(OffsetDateTime::now_utc() + Duration::seconds(2));
+    }
+
+    /// Write annotations to disk if a scheduled save has come due, or now when `force`.
+    pub(crate) fn flush_annotations_save(&mut self, force: bool) {
+        let Some(due) = self.annotations_save_due else {
+            return;
+        };
+        if !force && OffsetDateTime::now_utc() < due {
+            return;
+        }
+        self.annotations_save_due = None;
+        if let Err(err) = annotations::save(&self.annotations) {
+            self.record_error(format!("Failed to save notes: {err}"), None);
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::filters::{self, BlockedCache, BlockedSet};
+use crate::prefs::{self, BlockEntry};
+use crate::yt::types::VideoDetails;
+
+use super::AppState;
+use super::channel_ops::ChannelMeta;
+
+/// Progress through the guarded "Block all visible channels" action in the help window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkBlockStep {
+    /// Showing how many channels would be blocked.
+    First,
+    /// Last chance to back out.
+    Final,
+}
+
+/// Channels of `videos` that are not blocked yet, once each and in list order, as the
+/// `(channel_id, label)` pair `block_channel` takes.
+fn unblocked_channels<'a>(
+    videos: impl Iterator<Item = &'a VideoDetails>,
+    blocked: &BlockedSet,
+) -> Vec<(String, String)> {
+    let mut seen = HashSet::new();
+    videos
+        .filter(|video| !filters::is_blocked(video, blocked))
+        .filter_map(|video| {
+            let id = video.channel_handle.trim();
+            let label = video
+                .channel_display_name
+                .as_deref()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .unwrap_or(video.channel_title.trim());
+            let key = if id.is_empty() { label } else { id };
+            (!key.is_empty() && seen.insert(key.to_ascii_lowercase()))
+                .then(|| (id.to_owned(), label.to_owned()))
+        })
+        .collect()
+}
+
+/// The `(raw_key, label)` block entry for a channel: keyed by ID when there is one,
+/// otherwise by title. `None` when the channel has neither.
+pub(super) fn block_entry_for(channel_id: &str, channel_title: &str) -> Option<(String, String)> {
+    let source = match channel_id.trim() {
+        "" => channel_title.trim(),
+        id => id,
+    };
+    if source.is_empty() {
+        return None;
+    }
+    let label = match channel_title.trim() {
+        "" => source,
+        title => title,
+    };
+    Some((source.trim_start_matches('@').to_owned(), label.to_owned()))
+}
+
+/// Add `entries` to the block list in a single edit; returns how many were new.
+pub(super) fn add_block_entries(
+    cache: &mut BlockedCache,
+    list: &mut Vec<String>,
+    entries: &[(String, String)],
+) -> usize {
+    cache.edit(list, |list| {
+        entries
+            .iter()
+            .filter(|(raw_key, label)| prefs::add_block_entry(list, raw_key, label))
+            .count()
+    })
+}
+
+/// Remove the entries `channel_key` names; whether any were.
+pub(super) fn remove_block_entry(
+    cache: &mut BlockedCache,
+    list: &mut Vec<String>,
+    channel_key: &str,
+) -> bool {
+    cache.edit(list, |list| prefs::remove_block_entries(list, channel_key))
+}
+
+/// Swap in `entries` wholesale, as restoring defaults does.
+pub(super) fn replace_block_list(
+    cache: &mut BlockedCache,
+    list: &mut Vec<String>,
+    entries: &[String],
+) {
+    cache.edit(list, |list| {
+        list.clear();
+        list.extend_from_slice(entries);
+    });
+}
+
+/// Update block entries in place from `known` (keyed by lowercased channel ID).
+pub(super) fn merge_block_metadata(
+    cache: &mut BlockedCache,
+    list: &mut Vec<String>,
+    known: &HashMap<String, ChannelMeta>,
+) -> bool {
+    cache.edit(list, |list| {
+        let mut changed = false;
+        for entry in list {
+            let mut parsed = BlockEntry::parse(entry);
+            if parsed.handle.is_some() {
+                continue;
+            }
+            let Some((id, title, handle)) = known.get(&parsed.key) else {
+                continue;
+            };
+            parsed.handle = handle.clone();
+            if parsed.label.eq_ignore_ascii_case(&parsed.raw_key) && !title.trim().is_empty() {
+                parsed.label = title.trim().to_owned();
+            }
+            // Older entries were stored lowercased; restore the real ID casing.
+            parsed.raw_key = id.clone();
+            let formatted = parsed.format();
+            if *entry != formatted {
+                *entry = formatted;
+                changed = true;
+            }
+        }
+        changed
+    })
+}
+
+/// How many of `videos` blocking this channel would hide, matched the way `block_channel`
+/// stores the entry.
+fn hidden_by_block<'a>(
+    videos: impl Iterator<Item = &'a VideoDetails>,
+    channel_id: &str,
+    channel_title: &str,
+) -> usize {
+    let Some((raw_key, label)) = block_entry_for(channel_id, channel_title) else {
+        return 0;
+    };
+    let single = BlockedSet::new(&[format!("{raw_key}|{label}")]);
+    videos
+        .filter(|video| filters::is_blocked(video, &single))
+        .count()
+}
+
+impl AppState {
+    /// Visible results that blocking this channel would hide.
+    pub fn block_impact(&self, channel_id: &str, channel_title: &str) -> usize {
+        hidden_by_block(
+            self.visible_rows.iter().map(|&row| self.result(row)),
+            channel_id,
+            channel_title,
+        )
+    }
+
+    /// Visible results from channels "Block all visible channels" would block.
+    pub fn bulk_block_impact(&self) -> usize {
+        self.visible_rows
+            .iter()
+            .filter(|&&row| !filters::is_blocked(self.result(row), self.blocked.set()))
+            .count()
+    }
+
+    /// Channels "Block all visible channels" would add to the block list.
+    pub fn visible_unblocked_channels(&self) -> Vec<(String, String)> {
+        unblocked_channels(
+            self.visible_rows.iter().map(|&row| self.result(row)),
+            self.blocked.set(),
+        )
+    }
+
+    /// Block every channel among the visible results in one edit, then report how many were
+    /// added.
+    pub fn block_all_visible_channels(&mut self) {
+        self.bulk_block_step = None;
+        let entries: Vec<(String, String)> = self
+            .visible_unblocked_channels()
+            .iter()
+            .filter_map(|(channel_id, label)| block_entry_for(channel_id, label))
+            .collect();
+        let added = add_block_entries(
+            &mut self.blocked,
+            &mut self.prefs.blocked_channels,
+            &entries,
+        );
+        self.debug_check_blocked_set();
+        if added == 0 {
+            self.status = "No visible channels left to block.".into();
+            return;
+        }
+        self.resolve_blocked_labels();
+        self.hide_blocked_results();
+        self.status = match prefs::save(&self.prefs) {
+            Ok(()) => format!("Blocked {added} channel(s) from the visible results."),
+            Err(err) => format!("Blocked {added} channel(s), but saving failed: {err}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(channel_id: &str, title: &str) -> VideoDetails {
+        VideoDetails {
+            channel_handle: channel_id.into(),
+            channel_title: title.into(),
+            ..VideoDetails::default()
+        }
+    }
+
+    #[test]
+    fn each_unblocked_channel_is_listed_once() {
+        let blocked = BlockedSet::new(&["UCspam".to_owned()]);
+        let videos = [
+            video("UCa", "Alpha"),
+            video("UCspam", "Spam"),
+            video("UCa", "Alpha"),
+            video("", "No ID"),
+            video("UCb", "Beta"),
+            video("", "no id"),
+            video("", ""),
+        ];
+        assert_eq!(
+            unblocked_channels(videos.iter(), &blocked),
+            vec![
+                ("UCa".to_owned(), "Alpha".to_owned()),
+                (String::new(), "No ID".to_owned()),
+                ("UCb".to_owned(), "Beta".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_impact_counts_every_video_from_the_channel() {
+        let videos = [
+            video("UCa", "Alpha"),
+            video("UCb", "Beta"),
+            video("UCa", "Alpha (reupload)"),
+            video("", "Alpha"),
+            video("", "Gamma"),
+        ];
+        assert_eq!(hidden_by_block(videos.iter(), "UCa", "Alpha"), 2);
+        assert_eq!(hidden_by_block(videos.iter(), "", "Gamma"), 1);
+        assert_eq!(hidden_by_block(videos.iter(), "UCzzz", "Nobody"), 0);
+        assert_eq!(hidden_by_block(videos.iter(), " ", ""), 0);
+    }
+
+    fn entry(raw_key: &str, label: &str) -> (String, String) {
+        (raw_key.to_owned(), label.to_owned())
+    }
+
+    #[test]
+    fn block_entries_fall_back_to_the_title() {
+        assert_eq!(
+            block_entry_for(" @UCa ", "Alpha"),
+            Some(entry("UCa", "Alpha"))
+        );
+        assert_eq!(block_entry_for("", " Beta "), Some(entry("Beta", "Beta")));
+        assert_eq!(block_entry_for("UCc", ""), Some(entry("UCc", "UCc")));
+        assert_eq!(block_entry_for(" ", ""), None);
+    }
+
+    #[test]
+    fn adding_recompiles_only_when_an_entry_is_new() {
+        let mut list = Vec::new();
+        let mut cache = BlockedCache::new(&list);
+        let added = add_block_entries(
+            &mut cache,
+            &mut list,
+            &[
+                entry("UCa", "Alpha"),
+                entry("UCb", "Beta"),
+                entry("UCA", "Alpha"),
+            ],
+        );
+        assert_eq!(added, 2);
+        assert_eq!(cache.rebuilds(), 1);
+        assert!(cache.set().matches("UCb", "Beta", None));
+
+        assert_eq!(
+            add_block_entries(&mut cache, &mut list, &[entry("UCa", "Alpha")]),
+            0
+        );
+        assert_eq!(cache.rebuilds(), 1);
+    }
+
+    #[test]
+    fn removing_recompiles_only_when_an_entry_goes() {
+        let mut list = vec!["UCa|Alpha".to_owned(), "UCb|Beta".to_owned()];
+        let mut cache = BlockedCache::new(&list);
+        assert!(!remove_block_entry(&mut cache, &mut list, "UCzzz"));
+        assert_eq!(cache.rebuilds(), 0);
+        assert!(remove_block_entry(&mut cache, &mut list, "beta"));
+        assert_eq!(cache.rebuilds(), 1);
+        assert!(!cache.set().matches("UCb", "Beta", None));
+    }
+
+    #[test]
+    fn replacing_with_the_same_list_keeps_the_compiled_set() {
+        let mut list = vec!["UCa|Alpha".to_owned()];
+        let mut cache = BlockedCache::new(&list);
+        replace_block_list(&mut cache, &mut list, &["UCa|Alpha".to_owned()]);
+        assert_eq!(cache.rebuilds(), 0);
+        replace_block_list(&mut cache, &mut list, &[]);
+        assert_eq!(cache.rebuilds(), 1);
+        assert!(!cache.set().matches("UCa", "Alpha", None));
+    }
+
+    #[test]
+    fn metadata_recompiles_once_then_settles() {
+        let mut list = vec!["uca|uca".to_owned(), "UCb|Beta|@beta".to_owned()];
+        let mut cache = BlockedCache::new(&list);
+        let known = HashMap::from([
+            (
+                "uca".to_owned(),
+                (
+                    "UCa".to_owned(),
+                    "Alpha".to_owned(),
+                    Some("@alpha".to_owned()),
+                ),
+            ),
+            (
+                "ucb".to_owned(),
+                (
+                    "UCb".to_owned(),
+                    "Other".to_owned(),
+                    Some("@other".to_owned()),
+                ),
+            ),
+        ]);
+        assert!(merge_block_metadata(&mut cache, &mut list, &known));
+        assert_eq!(list, ["UCa|Alpha|@alpha", "UCb|Beta|@beta"]);
+        assert_eq!(cache.rebuilds(), 1);
+        assert!(!merge_block_metadata(&mut cache, &mut list, &known));
+        assert_eq!(cache.rebuilds(), 1);
+    }
+}
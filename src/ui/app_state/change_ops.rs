@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use crate::search_runner::{self, RunDiff};
+
+use super::AppState;
+
+/// The "Changes" view's sections as drawn: `added` and `unchanged` hold rows into `results`
+/// that pass the current scoping and Length chips, in list order; `removed` holds indices
+/// into `previous_results`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChangeRows {
+    pub added: Vec<usize>,
+    pub removed: Vec<usize>,
+    pub unchanged: Vec<usize>,
+}
+
+/// Sort the shown rows into the diff's sections. Rows whose video the diff does not know
+/// are left out.
+fn project_changes(diff: &RunDiff, results: &[usize], visible_rows: &[usize]) -> ChangeRows {
+    let added: HashSet<usize> = diff.added.iter().copied().collect();
+    let unchanged: HashSet<usize> = diff.unchanged.iter().copied().collect();
+    let mut rows = ChangeRows {
+        removed: diff.removed.clone(),
+        ..ChangeRows::default()
+    };
+    for &row in visible_rows {
+        if added.contains(&results[row]) {
+            rows.added.push(row);
+        } else if unchanged.contains(&results[row]) {
+            rows.unchanged.push(row);
+        }
+    }
+    rows
+}
+
+impl AppState {
+    /// Compare the loaded results with the previous run's. Called when a run replaces the
+    /// results, so the "Changes" view never diffs while drawing.
+    pub(crate) fn refresh_run_diff(&mut self) {
+        self.run_diff = self
+            .previous_results
+            .as_deref()
+            .map(|previous| search_runner::diff_runs(previous, &self.results_all));
+    }
+
+    /// Re-split the shown rows after scoping, sorting, or the Length chips change.
+    pub(super) fn rebuild_change_rows(&mut self) {
+        self.change_rows = match (&self.run_diff, &self.previous_results) {
+            (Some(diff), Some(_)) => project_changes(diff, &self.results, &self.visible_rows),
+            _ => ChangeRows::default(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shown_rows_keep_list_order_within_each_section() {
+        let diff = RunDiff {
+            added: vec![0, 3],
+            removed: vec![1],
+            unchanged: vec![1, 2],
+        };
+        // Sorted so results_all index 3 comes first; row 2 (index 1) is hidden by a chip.
+        let results = [3, 2, 1, 0];
+        let visible_rows = [0, 1, 3];
+        let rows = project_changes(&diff, &results, &visible_rows);
+        assert_eq!(rows.added, [0, 3]);
+        assert_eq!(rows.unchanged, [1]);
+        assert_eq!(rows.removed, [1]);
+    }
+
+    #[test]
+    fn rows_the_diff_does_not_know_are_left_out() {
+        let diff = RunDiff {
+            added: vec![0],
+            ..RunDiff::default()
+        };
+        let rows = project_changes(&diff, &[0, 5], &[0, 1]);
+        assert_eq!(rows.added, [0]);
+        assert!(rows.unchanged.is_empty());
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
+
+use time::OffsetDateTime;
+
+use crate::channel_cache;
+use crate::prefs::{self, BlockEntry};
+use crate::quota;
+use crate::yt::{self, channels};
+
+use super::{AppState, block_ops};
+
+/// Outcome of resolving a preset's `@handle` entry to a channel ID.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChannelLookup {
+    Pending,
+    Resolved(String),
+    Failed(String),
+}
+
+/// Channel ID, title, and `@handle` as returned by `channels.list`.
+pub(super) type ChannelMeta = (String, String, Option<String>);
+
+/// How many `channels.list` calls a channel info refresh keeps in flight.
+const CHANNEL_REFRESH_WORKERS: usize = 3;
+
+/// Messages from the tasks refreshing channel info.
+enum RefreshMessage {
+    /// One `channels.list` chunk: the channels it returned, or why it failed.
+    Chunk(Result<Vec<ChannelMeta>, String>),
+    WorkerDone,
+}
+
+/// Progress of a channel info refresh, reported in the status line.
+struct RefreshProgress {
+    requested: usize,
+    chunks_done: usize,
+    chunks_total: usize,
+    updated: usize,
+    workers_left: usize,
+    errors: Vec<String>,
+}
+
+/// Handle-to-ID lookups shared by every preset editor session, plus the ID-to-handle
+/// lookups used to label the block list.
+pub struct ChannelLookups {
+    pub entries: HashMap<String, ChannelLookup>,
+    tx: mpsc::Sender<(String, ChannelLookup)>,
+    rx: mpsc::Receiver<(String, ChannelLookup)>,
+    blocked_requested: HashSet<String>,
+    blocked_tx: mpsc::Sender<Vec<ChannelMeta>>,
+    blocked_rx: mpsc::Receiver<Vec<ChannelMeta>>,
+    refresh: Option<RefreshProgress>,
+    refresh_tx: mpsc::Sender<RefreshMessage>,
+    refresh_rx: mpsc::Receiver<RefreshMessage>,
+}
+
+impl ChannelLookups {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let (blocked_tx, blocked_rx) = mpsc::channel();
+        let (refresh_tx, refresh_rx) = mpsc::channel();
+        Self {
+            entries: HashMap::new(),
+            tx,
+            rx,
+            blocked_requested: HashSet::new(),
+            blocked_tx,
+            blocked_rx,
+            refresh: None,
+            refresh_tx,
+            refresh_rx,
+        }
+    }
+
+    /// Whether a channel info refresh is still running.
+    pub fn refreshing(&self) -> bool {
+        self.refresh.is_some()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ChannelLookup> {
+        self.entries.get(key)
+    }
+
+    pub fn any_pending(&self) -> bool {
+        self.entries
+            .values()
+            .any(|lookup| *lookup == ChannelLookup::Pending)
+    }
+}
+
+impl AppState {
+    /// Fill in handles and display names for blocked channel IDs, first from loaded results
+    /// and then, for anything still unknown, with one background `channels.list` call.
+    pub fn resolve_blocked_labels(&mut self) {
+        let mut known: HashMap<String, ChannelMeta> = HashMap::new();
+        for video in &self.results_all {
+            let title = video
+                .channel_display_name
+                .clone()
+                .unwrap_or_else(|| video.channel_title.clone());
+            known.insert(
+                video.channel_handle.to_ascii_lowercase(),
+                (
+                    video.channel_handle.clone(),
+                    title,
+                    video.channel_custom_url.clone(),
+                ),
+            );
+        }
+        let changed = self.apply_blocked_metadata(&known);
+
+        let mut pending: Vec<String> = Vec::new();
+        for entry in &self.prefs.blocked_channels {
+            let parsed = BlockEntry::parse(entry);
+            if parsed.handle.is_none()
+                && prefs::looks_like_channel_id(&parsed.raw_key)
+                && self.channel_lookups.blocked_requested.insert(parsed.key)
+            {
+                pending.push(parsed.raw_key);
+            }
+        }
+        let api_key = self.prefs.api_key.trim().to_owned();
+        if !pending.is_empty() && !api_key.is_empty() {
+            let tx = self.channel_lookups.blocked_tx.clone();
+            self.runtime.spawn(async move {
+                let mut found = Vec::new();
+                for chunk in pending.chunks(50) {
+                    // Labels are cosmetic; a failed lookup just leaves the ID showing.
+                    if let Ok(resp) = channels::channels_list(&api_key, chunk).await {
+                        for item in resp.items {
+                            let handle = item.snippet.handle();
+                            found.push((item.id, item.snippet.title, handle));
+                        }
+                    }
+                }
+                let _ = tx.send(found);
+            });
+        }
+        if changed {
+            self.schedule_prefs_save();
+        }
+    }
+
+    /// Update block entries in place from `known` (keyed by lowercased channel ID).
+    fn apply_blocked_metadata(&mut self, known: &HashMap<String, ChannelMeta>) -> bool {
+        let changed = block_ops::merge_block_metadata(
+            &mut self.blocked,
+            &mut self.prefs.blocked_channels,
+            known,
+        );
+        self.debug_check_blocked_set();
+        changed
+    }
+
+    /// Fill in the ID on saved presets whose entry was saved before the lookup finished.
+    fn apply_resolved_channel(&mut self, key: &str, id: &str) {
+        let mut changed = false;
+        for search in &mut self.prefs.searches {
+            let refs = search
+                .query
+                .channel_allow
+                .iter_mut()
+                .chain(search.query.channel_deny.iter_mut());
+            for channel in refs {
+                if channel.unresolved_handle().as_deref() == Some(key) {
+                    channel.id = Some(id.to_owned());
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.schedule_prefs_save();
+        }
+    }
+
+    /// Store handle lookups made by a search run in the presets and the shared lookup table.
+    /// Returns the handles no channel owns.
+    pub(crate) fn apply_run_handle_lookups(
+        &mut self,
+        resolved: Vec<(String, Option<String>)>,
+    ) -> Vec<String> {
+        let mut missing = Vec::new();
+        for (key, found) in resolved {
+            let lookup = match found {
+                Some(id) => {
+                    self.apply_resolved_channel(&key, &id);
+                    ChannelLookup::Resolved(id)
+                }
+                None => {
+                    missing.push(key.clone());
+                    ChannelLookup::Failed("no channel has this handle".into())
+                }
+            };
+            self.channel_lookups.entries.insert(key, lookup);
+        }
+        missing
+    }
+
+    /// Refetch one channel's name and handle, ignoring the cache age.
+    pub fn refresh_channel(&mut self, channel_id: &str) {
+        self.start_channel_refresh(vec![channel_id.to_owned()]);
+    }
+
+    /// Refetch every cached channel older than the TTL, plus loaded channels never cached.
+    pub fn refresh_stale_channels(&mut self) {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let ids = self
+            .channel_cache
+            .refresh_candidates(&self.results_all, now);
+        if ids.is_empty() {
+            self.status = "Channel info is up to date.".into();
+            return;
+        }
+        self.start_channel_refresh(ids);
+    }
+
+    /// Fetch `ids` in chunks of 50, spread over at most [`CHANNEL_REFRESH_WORKERS`] tasks.
+    fn start_channel_refresh(&mut self, ids: Vec<String>) {
+        if self.channel_lookups.refreshing() {
+            self.status = "A channel info refresh is already running.".into();
+            return;
+        }
+        let api_key = self.prefs.api_key.trim().to_owned();
+        if api_key.is_empty() {
+            self.status = "Set your YouTube Data API key in Settings first.".into();
+            return;
+        }
+        let chunks: Vec<Vec<String>> = ids.chunks(50).map(<[String]>::to_vec).collect();
+        let workers = chunks.len().min(CHANNEL_REFRESH_WORKERS);
+        let mut shares: Vec<Vec<Vec<String>>> = vec![Vec::new(); workers];
+        for (i, chunk) in chunks.iter().enumerate() {
+            shares[i % workers].push(chunk.clone());
+        }
+        for share in shares {
+            let tx = self.channel_lookups.refresh_tx.clone();
+            let api_key = api_key.clone();
+            self.runtime.spawn(async move {
+                for chunk in share {
+                    let result = channels::channels_list(&api_key, &chunk)
+                        .await
+                        .map(|resp| {
+                            resp.items
+                                .into_iter()
+                                .map(|item| {
+                                    let handle = item.snippet.handle();
+                                    (item.id, item.snippet.title, handle)
+                                })
+                                .collect()
+                        })
+                        .map_err(|err| err.to_string());
+                    let _ = tx.send(RefreshMessage::Chunk(result));
+                }
+                let _ = tx.send(RefreshMessage::WorkerDone);
+            });
+        }
+        self.channel_lookups.refresh = Some(RefreshProgress {
+            requested: ids.len(),
+            chunks_done: 0,
+            chunks_total: chunks.len(),
+            updated: 0,
+            workers_left: workers,
+            errors: Vec::new(),
+        });
+        self.status = format!("Refreshing channel info for {} channel(s)…", ids.len());
+    }
+
+    /// Fold finished refresh chunks into the cache and the loaded videos.
+    fn poll_channel_refresh(&mut self) {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let mut quota_units = 0;
+        while let Ok(message) = self.channel_lookups.refresh_rx.try_recv() {
+            let Some(progress) = self.channel_lookups.refresh.as_mut() else {
+                continue;
+            };
+            match message {
+                RefreshMessage::Chunk(result) => {
+                    progress.chunks_done += 1;
+                    quota_units += quota::LIST_CALL_COST;
+                    match result {
+                        Ok(found) => {
+                            for (id, title, handle) in found {
+                                self.channel_cache.insert(&id, &title, handle, now);
+                                let info = &self.channel_cache.channels[&id];
+                                progress.updated += channel_cache::apply_to_videos(
+                                    &mut self.results_all,
+                                    &id,
+                                    info,
+                                );
+                            }
+                        }
+                        Err(err) => progress.errors.push(err),
+                    }
+                    self.status = format!(
+                        "Refreshing channel info: {}/{} request(s) done…",
+                        progress.chunks_done, progress.chunks_total
+                    );
+                }
+                RefreshMessage::WorkerDone => progress.workers_left -= 1,
+            }
+        }
+        self.record_quota_usage(quota_units);
+
+        let finished = self
+            .channel_lookups
+            .refresh
+            .as_ref()
+            .is_some_and(|progress| progress.workers_left == 0);
+        if !finished {
+            return;
+        }
+        let Some(progress) = self.channel_lookups.refresh.take() else {
+            return;
+        };
+        self.save_channel_cache();
+        if progress.updated > 0 {
+            if let Err(err) = self.resave_cached_results() {
+                eprintln!("Failed to save cached results: {err}");
+            }
+            self.reindex_results();
+        }
+        if let Some(first) = progress.errors.first() {
+            self.record_error(
+                format!(
+                    "Channel info refresh: {} of {} request(s) failed.",
+                    progress.errors.len(),
+                    progress.chunks_total
+                ),
+                Some(first.clone()),
+            );
+        } else {
+            self.status = format!(
+                "Refreshed {} channel(s); {} video(s) show a new name or handle.",
+                progress.requested, progress.updated
+            );
+        }
+    }
+
+    /// Write channels.json; the cache is cosmetic, so a failure is only logged.
+    pub(crate) fn save_channel_cache(&self) {
+        if let Err(err) = channel_cache::save(&self.channel_cache) {
+            eprintln!("Failed to save channel cache: {err}");
+        }
+    }
+
+    /// Start lookups for new `@handle` entries in the open editor and fold finished ones back in.
+    pub fn update_channel_lookups(&mut self) {
+        self.poll_channel_refresh();
+
+        while let Ok(found) = self.channel_lookups.blocked_rx.try_recv() {
+            let known: HashMap<String, ChannelMeta> = found
+                .into_iter()
+                .map(|meta| (meta.0.to_ascii_lowercase(), meta))
+                .collect();
+            if self.apply_blocked_metadata(&known) {
+                self.schedule_prefs_save();
+            }
+        }
+
+        while let Ok((key, lookup)) = self.channel_lookups.rx.try_recv() {
+            if let ChannelLookup::Resolved(id) = &lookup {
+                self.apply_resolved_channel(&key, id);
+            }
+            self.channel_lookups.entries.insert(key, lookup);
+        }
+
+        let Some(editor) = self.preset_editor.as_mut() else {
+            return;
+        };
+        for key in editor.unresolved_handles() {
+            match self.channel_lookups.entries.get(&key) {
+                Some(ChannelLookup::Resolved(id)) => {
+                    editor.known_channel_ids.insert(key, id.clone());
+                }
+                Some(_) => {}
+                None => {
+                    let api_key = self.prefs.api_key.trim().to_owned();
+                    if api_key.is_empty() {
+                        continue;
+                    }
+                    self.channel_lookups
+                        .entries
+                        .insert(key.clone(), ChannelLookup::Pending);
+                    let tx = self.channel_lookups.tx.clone();
+                    self.runtime.spawn(async move {
+                        let lookup = match yt::resolve_handle(&api_key, &key).await {
+                            Ok(Some(id)) => ChannelLookup::Resolved(id),
+                            Ok(None) => ChannelLookup::Failed("no channel has this handle".into()),
+                            Err(err) => ChannelLookup::Failed(err.to_string()),
+                        };
+                        let _ = tx.send((key, lookup));
+                    });
+                }
+            }
+        }
+    }
+}
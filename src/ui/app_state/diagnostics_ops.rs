@@ -0,0 +1,102 @@
+use std::sync::mpsc;
+
+use crate::diagnostics::{self, CheckFix, CheckResult};
+
+use super::AppState;
+
+/// The Diagnostics window: results so far, plus the channel still delivering them.
+#[derive(Default)]
+pub struct DiagnosticsState {
+    pub results: Vec<CheckResult>,
+    pub rx: Option<mpsc::Receiver<CheckResult>>,
+}
+
+impl DiagnosticsState {
+    pub fn running(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Replace the row with the same check name, or append a new one.
+    fn upsert(&mut self, result: CheckResult) {
+        match self.results.iter_mut().find(|row| row.name == result.name) {
+            Some(row) => *row = result,
+            None => self.results.push(result),
+        }
+    }
+}
+
+impl AppState {
+    /// Open the Diagnostics window and start every check.
+    pub fn open_diagnostics(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        let api_key = self.prefs.api_key.clone();
+        let defaults = self.prefs.search_defaults.clone();
+        let searches = self.prefs.searches.clone();
+        self.runtime
+            .spawn(diagnostics::run_all(api_key, defaults, searches, tx));
+        self.diagnostics = Some(DiagnosticsState {
+            results: Vec::new(),
+            rx: Some(rx),
+        });
+    }
+
+    pub fn close_diagnostics(&mut self) {
+        self.diagnostics = None;
+    }
+
+    /// Move finished checks into the window; called every frame.
+    pub fn poll_diagnostics(&mut self) {
+        let Some(state) = self.diagnostics.as_mut() else {
+            return;
+        };
+        let Some(rx) = state.rx.as_ref() else {
+            return;
+        };
+        let mut arrived = Vec::new();
+        let mut finished = false;
+        loop {
+            match rx.try_recv() {
+                Ok(result) => arrived.push(result),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        if finished {
+            state.rx = None;
+        }
+        let quota_units = arrived.iter().map(|result| result.quota_units).sum();
+        for result in arrived {
+            state.upsert(result);
+        }
+        self.record_quota_usage(quota_units);
+    }
+
+    pub fn apply_diagnostic_fix(&mut self, fix: CheckFix) {
+        match fix {
+            CheckFix::OpenSettings => self.open_settings(),
+            CheckFix::ClearThumbnails => {
+                let removed = self.thumbnail_cache.clear_disk();
+                self.sync_thumbnail_cache();
+                self.status = format!("Deleted {removed} cached thumbnail(s).");
+                self.open_diagnostics();
+            }
+            CheckFix::TestApiKey => {
+                let Some(state) = self.diagnostics.as_mut() else {
+                    return;
+                };
+                if state.running() {
+                    return;
+                }
+                let (tx, rx) = mpsc::channel();
+                let api_key = self.prefs.api_key.clone();
+                self.runtime.spawn(async move {
+                    let _ = tx.send(diagnostics::check_api_key(&api_key).await);
+                });
+                state.rx = Some(rx);
+            }
+        }
+    }
+}
@@ -1,7 +1,13 @@
-use crate::prefs::{self, MySearch, Prefs};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use time::OffsetDateTime;
+use time::macros::format_description;
+
+use crate::import_formats::{self, ChannelGrouping};
+use crate::prefs::{self, MySearch, Prefs, PresetDiff};
 
 use super::AppState;
-use serde_json;
 
 #[derive(Debug, Clone)]
 pub enum ImportMode {
@@ -9,27 +15,63 @@ pub enum ImportMode {
     File,
 }
 
-#[derive(Debug, Clone)]
-pub enum ExportMode {
-    Clipboard,
-    File,
+/// What the import text contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// A JSON preset array or prefs.json.
+    Presets,
+    /// OPML, CSV, or plain-text channel list turned into channel-allow presets.
+    Channels,
+}
+
+impl ImportFormat {
+    /// Guess from a file extension; anything unrecognised is treated as preset JSON.
+    fn from_path(path: &std::path::Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("opml" | "xml" | "csv" | "txt") => ImportFormat::Channels,
+            _ => ImportFormat::Presets,
+        }
+    }
 }
 
 pub struct ImportDialogState {
     pub raw_json: String,
     pub file_path: Option<String>,
-    pub manual_path: String,
     pub mode: ImportMode,
     pub error: Option<String>,
     pub replace_existing: bool,
     pub awaiting_clipboard: bool,
+    pub format: ImportFormat,
+    pub channel_grouping: ChannelGrouping,
+    pub channel_preset_name: String,
+    pub channel_query: String,
+    /// Set when Import is pressed with "Replace existing presets" checked; nothing is
+    /// replaced until the diff is confirmed.
+    pub replace_preview: Option<ReplacePreview>,
+}
+
+/// The validated replacement list and how it differs from the current presets.
+pub struct ReplacePreview {
+    pub diff: PresetDiff,
+    pub presets: Vec<MySearch>,
 }
 
 pub struct ExportDialogState {
     pub raw_json: String,
-    pub file_path: Option<String>,
-    pub manual_path: String,
-    pub mode: ExportMode,
+    /// Set when the chosen file already holds different content; the write waits for an answer.
+    pub overwrite_prompt: Option<OverwritePrompt>,
+}
+
+/// Pending "file already exists" question for an export.
+pub struct OverwritePrompt {
+    pub path: PathBuf,
+    /// e.g. "backup.json already contains 14 presets from May 3, 2024".
+    pub summary: String,
 }
 
 impl AppState {
@@ -37,11 +79,15 @@ impl AppState {
         self.import_dialog = Some(ImportDialogState {
             raw_json: String::new(),
             file_path: None,
-            manual_path: String::new(),
             mode: ImportMode::Clipboard,
             error: None,
             replace_existing: false,
             awaiting_clipboard: false,
+            format: ImportFormat::Presets,
+            channel_grouping: ChannelGrouping::default(),
+            channel_preset_name: String::new(),
+            channel_query: String::new(),
+            replace_preview: None,
         });
     }
 
@@ -50,9 +96,7 @@ impl AppState {
             Ok(raw_json) => {
                 self.export_dialog = Some(ExportDialogState {
                     raw_json,
-                    file_path: None,
-                    manual_path: String::new(),
-                    mode: ExportMode::Clipboard,
+                    overwrite_prompt: None,
                 });
             }
             Err(err) => {
@@ -72,19 +116,25 @@ impl AppState {
     pub fn import_from_file(&mut self) {
         match native_dialog::FileDialog::new()
             .add_filter("JSON files", &["json"])
+            .add_filter("Channel lists", &["opml", "xml", "csv", "txt"])
             .add_filter("All files", &["*"])
             .show_open_single_file()
         {
             Ok(Some(path)) => match std::fs::read_to_string(&path) {
                 Ok(content) => {
+                    let format = ImportFormat::from_path(&path);
                     self.import_dialog = Some(ImportDialogState {
                         raw_json: content,
                         file_path: Some(path.to_string_lossy().to_string()),
-                        manual_path: path.to_string_lossy().to_string(),
                         mode: ImportMode::File,
                         error: None,
-                        replace_existing: true,
+                        replace_existing: format == ImportFormat::Presets,
                         awaiting_clipboard: false,
+                        format,
+                        channel_grouping: ChannelGrouping::default(),
+                        channel_preset_name: String::new(),
+                        channel_query: String::new(),
+                        replace_preview: None,
                     });
                 }
                 Err(err) => {
@@ -105,14 +155,13 @@ impl AppState {
                 .set_filename("yts_search_presets.json")
                 .show_save_single_file()
             {
-                Ok(Some(path)) => match std::fs::write(&path, &dialog.raw_json) {
-                    Ok(_) => {
-                        self.status = format!("Presets saved to: {}", path.display());
-                        self.cancel_export_dialog();
-                    }
-                    Err(err) => {
-                        self.status = format!("Failed to save file: {err}");
+                Ok(Some(path)) => match overwrite_summary(&path, &dialog.raw_json) {
+                    Some(summary) => {
+                        if let Some(dialog) = self.export_dialog.as_mut() {
+                            dialog.overwrite_prompt = Some(OverwritePrompt { path, summary });
+                        }
                     }
+                    None => self.write_export(&path),
                 },
                 Ok(None) => {}
                 Err(err) => {
@@ -122,79 +171,156 @@ impl AppState {
         }
     }
 
-    /// Ingest presets from the import dialog and merge/replace as requested.
+    /// Replace the existing file named in the overwrite prompt.
+    pub fn confirm_export_overwrite(&mut self) {
+        let prompt = self
+            .export_dialog
+            .as_mut()
+            .and_then(|dialog| dialog.overwrite_prompt.take());
+        if let Some(prompt) = prompt {
+            self.write_export(&prompt.path);
+        }
+    }
+
+    /// Keep the existing file and write the export next to it under a free name.
+    pub fn export_as_copy(&mut self) {
+        let prompt = self
+            .export_dialog
+            .as_mut()
+            .and_then(|dialog| dialog.overwrite_prompt.take());
+        if let Some(prompt) = prompt {
+            let path = copy_path(&prompt.path, |candidate| candidate.exists());
+            self.write_export(&path);
+        }
+    }
+
+    pub fn cancel_export_overwrite(&mut self) {
+        if let Some(dialog) = self.export_dialog.as_mut()
+            && dialog.overwrite_prompt.take().is_some()
+        {
+            self.status = "Export cancelled; the existing file was left alone.".into();
+        }
+    }
+
+    fn write_export(&mut self, path: &Path) {
+        let Some(dialog) = self.export_dialog.as_ref() else {
+            return;
+        };
+        match fs::write(path, &dialog.raw_json) {
+            Ok(_) => {
+                self.status = format!("Presets saved to: {}", path.display());
+                self.cancel_export_dialog();
+            }
+            Err(err) => {
+                self.status = format!("Failed to save file: {err}");
+            }
+        }
+    }
+
+    /// Ingest presets from the import dialog. Appends right away; a replacement first
+    /// stores a [`ReplacePreview`] for the user to confirm.
     pub fn apply_import(&mut self) {
         let Some(mut dialog) = self.import_dialog.take() else {
             return;
         };
 
         dialog.error = None;
-        let mut presets = match serde_json::from_str::<Vec<MySearch>>(&dialog.raw_json) {
+        let presets = match self.prepare_import(&dialog) {
             Ok(list) => list,
-            Err(_) => match serde_json::from_str::<Prefs>(&dialog.raw_json) {
-                Ok(prefs_payload) => prefs_payload.searches,
-                Err(err) => {
-                    dialog.error = Some(format!("Import failed: {err}"));
-                    self.import_dialog = Some(dialog);
-                    return;
-                }
-            },
+            Err(err) => {
+                dialog.error = Some(err);
+                self.import_dialog = Some(dialog);
+                return;
+            }
         };
 
-        if presets.is_empty() {
-            dialog.error = Some("No presets found in import.".into());
+        if dialog.replace_existing {
+            dialog.replace_preview = Some(ReplacePreview {
+                diff: prefs::diff_presets(&self.prefs.searches, &presets),
+                presets,
+            });
             self.import_dialog = Some(dialog);
             return;
         }
+        self.finish_import(dialog, presets);
+    }
+
+    /// Replace the presets with the previewed list.
+    pub fn confirm_import_replace(&mut self) {
+        let Some(mut dialog) = self.import_dialog.take() else {
+            return;
+        };
+        match dialog.replace_preview.take() {
+            Some(preview) => self.finish_import(dialog, preview.presets),
+            None => self.import_dialog = Some(dialog),
+        }
+    }
+
+    /// Back out of the replace preview, keeping the dialog open.
+    pub fn cancel_import_replace(&mut self) {
+        if let Some(dialog) = self.import_dialog.as_mut() {
+            dialog.replace_preview = None;
+        }
+    }
+
+    /// Parse the dialog text into presets with trimmed, non-empty names; for a replacement
+    /// the ids are also made unique, so the list is exactly what will be stored.
+    fn prepare_import(&self, dialog: &ImportDialogState) -> Result<Vec<MySearch>, String> {
+        let mut presets = match dialog.format {
+            ImportFormat::Presets => parse_preset_payload(&dialog.raw_json),
+            ImportFormat::Channels => parse_channel_payload(dialog),
+        }?;
+
+        if presets.is_empty() {
+            return Err("No presets found in import.".into());
+        }
 
         for preset in &mut presets {
             preset.name = preset.name.trim().to_string();
         }
+        presets.retain(|preset| !preset.name.is_empty());
+        if presets.is_empty() {
+            return Err("No valid presets to import.".into());
+        }
 
-        let mut added = 0usize;
-        if dialog.replace_existing {
-            let mut new_list: Vec<MySearch> = Vec::new();
-            for mut preset in presets {
-                if preset.name.is_empty() {
-                    continue;
-                }
-                let trimmed_id = preset.id.trim();
-                if trimmed_id.is_empty() || new_list.iter().any(|s| s.id == trimmed_id) {
-                    preset.id = self.generate_unique_id_with(&preset.name, &new_list);
-                } else {
-                    preset.id = trimmed_id.to_string();
-                }
-                new_list.push(preset);
-            }
-            if new_list.is_empty() {
-                dialog.error = Some("No valid presets to import.".into());
-                self.import_dialog = Some(dialog);
-                return;
-            }
-            added = new_list.len();
-            self.prefs.searches = new_list;
-        } else {
-            for mut preset in presets {
-                if preset.name.is_empty() {
-                    continue;
-                }
-                if preset.id.trim().is_empty()
-                    || self.prefs.searches.iter().any(|s| s.id == preset.id)
-                {
-                    preset.id = self.generate_unique_id(&preset.name);
-                }
-                self.prefs.searches.push(preset);
-                added += 1;
+        if !dialog.replace_existing {
+            return Ok(presets);
+        }
+        let mut new_list: Vec<MySearch> = Vec::new();
+        for mut preset in presets {
+            let trimmed_id = preset.id.trim();
+            if trimmed_id.is_empty() || new_list.iter().any(|s| s.id == trimmed_id) {
+                preset.id = self.generate_unique_id_with(&preset.name, &new_list);
+            } else {
+                preset.id = trimmed_id.to_string();
             }
+            new_list.push(preset);
+        }
+        Ok(new_list)
+    }
 
-            if added == 0 {
-                dialog.error = Some("No valid presets to import.".into());
-                self.import_dialog = Some(dialog);
-                return;
+    /// Add presets after the existing ones, giving any with a blank or taken id a fresh one.
+    pub(super) fn append_presets(&mut self, presets: Vec<MySearch>) {
+        for mut preset in presets {
+            if preset.id.trim().is_empty() || self.prefs.searches.iter().any(|s| s.id == preset.id)
+            {
+                preset.id = self.generate_unique_id(&preset.name);
             }
+            self.prefs.searches.push(preset);
+        }
+    }
+
+    fn finish_import(&mut self, mut dialog: ImportDialogState, presets: Vec<MySearch>) {
+        let added = presets.len();
+        if dialog.replace_existing {
+            self.prefs.searches = presets;
+        } else {
+            self.append_presets(presets);
         }
 
         prefs::add_missing_defaults(&mut self.prefs);
+        prefs::mark_modified(&mut self.prefs);
+        self.previous_results = None;
 
         if let Err(err) = prefs::save(&self.prefs) {
             dialog.error = Some(format!("Failed to save prefs: {err}"));
@@ -207,3 +333,128 @@ impl AppState {
         self.refresh_visible_results();
     }
 }
+
+/// Describe `path` when writing `contents` there would replace something different, or
+/// `None` when the file is missing or already identical. Export paths that write user files
+/// should ask before clobbering one.
+pub(super) fn overwrite_summary(path: &Path, contents: &str) -> Option<String> {
+    let existing = fs::read_to_string(path).ok()?;
+    if existing == contents {
+        return None;
+    }
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+    let holds = match parse_preset_payload(&existing) {
+        Ok(presets) => format!(
+            "{} preset{}",
+            presets.len(),
+            if presets.len() == 1 { "" } else { "s" }
+        ),
+        Err(_) => "other data".to_owned(),
+    };
+    let modified = fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| {
+            OffsetDateTime::from(time)
+                .format(format_description!(
+                    "[month repr:short] [day padding:none], [year]"
+                ))
+                .ok()
+        });
+    Some(match modified {
+        Some(date) => format!("{name} already contains {holds} from {date}"),
+        None => format!("{name} already contains {holds}"),
+    })
+}
+
+/// First free "name-copy.json", "name-copy-2.json", … beside `path`.
+fn copy_path(path: &Path, exists: impl Fn(&Path) -> bool) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "presets".to_owned());
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| {
+            let suffix = if n == 1 {
+                "-copy".to_owned()
+            } else {
+                format!("-copy-{n}")
+            };
+            path.with_file_name(format!("{stem}{suffix}{extension}"))
+        })
+        .find(|candidate| !exists(candidate))
+        .expect("an unused copy name")
+}
+
+fn parse_preset_payload(raw: &str) -> Result<Vec<MySearch>, String> {
+    match serde_json::from_str::<Vec<MySearch>>(raw) {
+        Ok(list) => Ok(list),
+        Err(_) => serde_json::from_str::<Prefs>(raw)
+            .map(|prefs_payload| prefs_payload.searches)
+            .map_err(|err| format!("Import failed: {err}")),
+    }
+}
+
+fn parse_channel_payload(dialog: &ImportDialogState) -> Result<Vec<MySearch>, String> {
+    let channels = import_formats::parse_channels(&dialog.raw_json);
+    if channels.is_empty() {
+        return Err(
+            "No YouTube channels found. Expected OPML feeds, channel URLs, @handles, or UC… IDs."
+                .into(),
+        );
+    }
+    if dialog.channel_grouping == ChannelGrouping::Combined
+        && dialog.channel_query.trim().is_empty()
+    {
+        return Err("A combined channel preset needs query text to search with.".into());
+    }
+    Ok(import_formats::presets_from_channels(
+        &channels,
+        dialog.channel_grouping,
+        &dialog.channel_preset_name,
+        &dialog.channel_query,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_path_skips_names_already_taken() {
+        let target = Path::new("/exports/backup.json");
+        let taken = [
+            PathBuf::from("/exports/backup-copy.json"),
+            PathBuf::from("/exports/backup-copy-2.json"),
+        ];
+        let free = copy_path(target, |candidate| taken.iter().any(|t| t == candidate));
+        assert_eq!(free, PathBuf::from("/exports/backup-copy-3.json"));
+        assert_eq!(
+            copy_path(Path::new("/exports/notes"), |_| false),
+            PathBuf::from("/exports/notes-copy")
+        );
+    }
+
+    #[test]
+    fn overwrite_summary_counts_presets_and_ignores_identical_files() {
+        let dir = std::env::temp_dir().join(format!("yts-export-guard-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("backup.json");
+        let existing =
+            serde_json::to_string(&vec![MySearch::default(), MySearch::default()]).unwrap();
+        fs::write(&path, &existing).unwrap();
+
+        assert_eq!(overwrite_summary(&path, &existing), None);
+        let summary = overwrite_summary(&path, "[]").unwrap();
+        assert!(summary.starts_with("backup.json already contains 2 presets from "));
+        assert_eq!(overwrite_summary(&dir.join("missing.json"), "[]"), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
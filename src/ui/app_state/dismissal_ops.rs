@@ -0,0 +1,40 @@
+use crate::dismissals::{self, BlockSuggestion};
+
+use super::AppState;
+
+impl AppState {
+    /// "Not interested": hide the video from now on and count it against its channel.
+    pub fn dismiss_video(&mut self, video_id: &str, channel_id: &str, channel_title: &str) {
+        self.dismissals.record(video_id, channel_id, channel_title);
+        self.refresh_visible_results();
+        self.status = format!("Hid a video from {channel_title}.");
+        self.save_dismissals();
+    }
+
+    /// Channel to offer blocking in the results banner, if any crossed the threshold.
+    pub fn block_suggestion(&self) -> Option<BlockSuggestion> {
+        dismissals::block_suggestion(
+            &self.dismissals,
+            self.prefs.ui.block_suggestion_threshold,
+            |channel_id, channel_title| self.blocked.set().matches(channel_id, channel_title, None),
+        )
+    }
+
+    /// "Not now": ask again only after another threshold's worth of dismissals.
+    pub fn snooze_block_suggestion(&mut self, key: &str) {
+        self.dismissals.snooze(key);
+        self.save_dismissals();
+    }
+
+    /// "Never ask": stop suggesting this channel.
+    pub fn never_suggest_block(&mut self, key: &str) {
+        self.dismissals.never_ask(key);
+        self.save_dismissals();
+    }
+
+    fn save_dismissals(&mut self) {
+        if let Err(err) = dismissals::save(&self.dismissals) {
+            self.status = format!("Failed to save dismissed videos: {err}");
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::ui::find_index;
+use crate::ui::finder;
+
+use super::AppState;
+
+/// The "Search history" window: one query over everything the find index has seen.
+#[derive(Debug, Default)]
+pub struct HistorySearch {
+    pub open: bool,
+    pub query: String,
+    /// Positions in the find index, newest first.
+    pub hits: Vec<usize>,
+    /// How long the last lookup took.
+    pub took: Duration,
+}
+
+impl AppState {
+    /// Note that `results_all` changed and fold the new videos into the find index.
+    pub(crate) fn reindex_results(&mut self) {
+        self.results_generation += 1;
+        self.sync_find_index();
+    }
+
+    /// Index whatever arrived since the index last saw `results_generation`, loading it from
+    /// the config folder first. Drops it when indexed find is off.
+    fn sync_find_index(&mut self) {
+        if !self.prefs.ui.indexed_find {
+            self.find_index = None;
+            self.find_index_generation = None;
+            return;
+        }
+        if self.find_index.is_some() && self.find_index_generation == Some(self.results_generation)
+        {
+            return;
+        }
+        let index = self.find_index.get_or_insert_with(find_index::load);
+        if index.add(&self.results_all) > 0
+            && let Err(err) = find_index::save(index)
+        {
+            eprintln!("Failed to save find index: {err}");
+        }
+        self.find_index_generation = Some(self.results_generation);
+    }
+
+    /// "Rebuild index" next to the find box and in the history window.
+    pub fn rebuild_find_index(&mut self) {
+        if !self.prefs.ui.indexed_find {
+            return;
+        }
+        let index = self.find_index.get_or_insert_with(find_index::load);
+        index.rebuild();
+        index.add(&self.results_all);
+        let saved = find_index::save(index);
+        self.find_index_generation = Some(self.results_generation);
+        self.status = format!(
+            "Find index rebuilt: {} videos, {} words.",
+            index.video_count(),
+            index.word_count()
+        );
+        if let Err(err) = saved {
+            self.record_error(format!("Failed to save find index: {err}"), None);
+        }
+        self.refresh_visible_results();
+        self.run_history_search();
+    }
+
+    /// Indices into `results_all` the find box matches, in list order. With indexed find on,
+    /// the index narrows the list to videos whose words the query starts and
+    /// [`finder::relevance`] confirms them (a renamed video keeps its old words until the
+    /// next rebuild); otherwise every result is scanned.
+    pub(super) fn find_matches(&mut self) -> Vec<usize> {
+        self.sync_find_index();
+        let all = &self.results_all;
+        let query = &self.find_query;
+        let Some(index) = &self.find_index else {
+            return (0..all.len())
+                .filter(|&i| finder::relevance(query, &all[i]).is_some())
+                .collect();
+        };
+        let hits: HashSet<usize> = index.matches(query, false).into_iter().collect();
+        (0..all.len())
+            .filter(|&i| {
+                index
+                    .position(&all[i].id)
+                    .is_some_and(|position| hits.contains(&position))
+                    && finder::relevance(query, &all[i]).is_some()
+            })
+            .collect()
+    }
+
+    /// Look the history window's query up in the find index.
+    pub fn run_history_search(&mut self) {
+        self.sync_find_index();
+        let started = Instant::now();
+        let query = self.history_search.query.trim();
+        self.history_search.hits = match &self.find_index {
+            Some(index) if !query.is_empty() => index.search_history(query),
+            _ => Vec::new(),
+        };
+        self.history_search.took = started.elapsed();
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use crate::prefs;
+use crate::preset_packs::PresetPack;
+
+use super::AppState;
+
+/// The preset gallery window; every pack preset starts ticked.
+#[derive(Default)]
+pub struct PresetGalleryState {
+    /// Pack preset ids the user unticked.
+    pub unticked: HashSet<String>,
+}
+
+impl AppState {
+    pub fn open_preset_gallery(&mut self) {
+        self.preset_gallery = Some(PresetGalleryState::default());
+    }
+
+    pub fn close_preset_gallery(&mut self) {
+        self.preset_gallery = None;
+    }
+
+    /// Whether a preset with this id is already in the list.
+    pub fn has_preset(&self, id: &str) -> bool {
+        self.prefs.searches.iter().any(|search| search.id == id)
+    }
+
+    /// Add the pack's ticked presets that are not in the list yet.
+    pub fn add_preset_pack(&mut self, pack: &PresetPack) {
+        let presets = match pack.presets() {
+            Ok(presets) => presets,
+            Err(err) => {
+                self.record_error(err, None);
+                return;
+            }
+        };
+        let unticked = self
+            .preset_gallery
+            .as_ref()
+            .map(|gallery| gallery.unticked.clone())
+            .unwrap_or_default();
+        let fresh: Vec<_> = presets
+            .into_iter()
+            .filter(|preset| !unticked.contains(&preset.id) && !self.has_preset(&preset.id))
+            .collect();
+        if fresh.is_empty() {
+            self.status = format!("Nothing new to add from {}.", pack.name);
+            return;
+        }
+        let added = fresh.len();
+        self.append_presets(fresh);
+        prefs::mark_modified(&mut self.prefs);
+        self.previous_results = None;
+        if let Err(err) = prefs::save(&self.prefs) {
+            self.record_error(format!("Failed to save prefs: {err}"), None);
+        } else {
+            self.status = format!("Added {added} preset(s) from {}.", pack.name);
+        }
+        self.refresh_visible_results();
+    }
+}
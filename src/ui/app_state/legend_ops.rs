@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+use egui::Color32;
+
+use crate::ui::theme;
+
+use super::AppState;
+
+/// One row of the preset legend above the results.
+pub struct LegendEntry {
+    /// `None` when the preset was deleted after the run; such rows cannot be hidden.
+    pub id: Option<String>,
+    pub name: String,
+    pub color: Color32,
+    pub visible: usize,
+    pub hidden: bool,
+}
+
+impl AppState {
+    /// Color for a preset named in `source_presets`, keyed by its place in the preset list.
+    /// Presets that no longer exist share the next color after the list.
+    pub fn preset_color(&self, name: &str) -> Color32 {
+        let position = self
+            .prefs
+            .searches
+            .iter()
+            .position(|preset| preset.name == name)
+            .unwrap_or(self.prefs.searches.len());
+        theme::preset_color(position)
+    }
+
+    /// Presets that contributed to the loaded results, in preset-list order, with how many
+    /// of their videos are currently shown.
+    pub fn preset_legend(&self) -> Vec<LegendEntry> {
+        let mut names: Vec<&str> = Vec::new();
+        for video in &self.results_all {
+            for name in &video.source_presets {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+        }
+        names.sort_by_key(|name| {
+            self.prefs
+                .searches
+                .iter()
+                .position(|preset| preset.name == *name)
+                .unwrap_or(usize::MAX)
+        });
+        names
+            .into_iter()
+            .map(|name| {
+                let id = self
+                    .prefs
+                    .searches
+                    .iter()
+                    .find(|preset| preset.name == name)
+                    .map(|preset| preset.id.clone());
+                let visible = self
+                    .visible_rows
+                    .iter()
+                    .filter(|&&row| {
+                        self.result(row)
+                            .source_presets
+                            .iter()
+                            .any(|source| source == name)
+                    })
+                    .count();
+                LegendEntry {
+                    hidden: id
+                        .as_ref()
+                        .is_some_and(|id| self.view_hidden_presets.contains(id)),
+                    id,
+                    name: name.to_owned(),
+                    color: self.preset_color(name),
+                    visible,
+                }
+            })
+            .collect()
+    }
+
+    /// Hide or show one preset's results without touching its `enabled` flag.
+    pub fn set_preset_view_hidden(&mut self, id: &str, hidden: bool) {
+        if hidden {
+            self.view_hidden_presets.insert(id.to_owned());
+        } else {
+            self.view_hidden_presets.remove(id);
+        }
+        self.refresh_visible_results();
+    }
+
+    /// Names of the presets hidden from view, for filtering `source_presets`.
+    pub(super) fn view_hidden_names(&self) -> HashSet<&str> {
+        self.prefs
+            .searches
+            .iter()
+            .filter(|preset| self.view_hidden_presets.contains(&preset.id))
+            .map(|preset| preset.name.as_str())
+            .collect()
+    }
+}
+
+/// A video is hidden only when every preset that found it is hidden; videos from a hidden
+/// and a shown preset stay visible.
+pub(super) fn hidden_by_legend(source_presets: &[String], hidden: &HashSet<&str>) -> bool {
+    !source_presets.is_empty()
+        && source_presets
+            .iter()
+            .all(|name| hidden.contains(name.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn videos_stay_visible_while_any_source_preset_is_shown() {
+        let hidden: HashSet<&str> = ["Rust"].into_iter().collect();
+        let sources = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(hidden_by_legend(&sources(&["Rust"]), &hidden));
+        assert!(!hidden_by_legend(&sources(&["Rust", "Bevy"]), &hidden));
+        assert!(!hidden_by_legend(&sources(&["Bevy"]), &hidden));
+        assert!(!hidden_by_legend(&[], &hidden));
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::{BTreeSet, HashSet};
+
+use crate::prefs::{self, ChannelRef, MySearch};
+use crate::ui::preset_editor::PresetEditorState;
+
+use super::AppState;
+
+/// Combine presets into one. The highest-priority preset (first on ties) supplies the ID,
+/// free-text query and overrides; the others' queries join its any-terms. Term and channel
+/// lists are unioned.
+pub(super) fn merge_presets(presets: &[&MySearch]) -> Option<MySearch> {
+    let keep = presets.iter().copied().reduce(|best, next| {
+        if next.priority > best.priority {
+            next
+        } else {
+            best
+        }
+    })?;
+    let mut merged = keep.clone();
+    merged.name = presets
+        .iter()
+        .map(|preset| preset.name.trim())
+        .collect::<Vec<_>>()
+        .join(" + ");
+    merged.enabled = presets.iter().any(|preset| preset.enabled);
+    merged.system = false;
+
+    let query = &mut merged.query;
+    for preset in presets.iter().filter(|preset| preset.id != keep.id) {
+        let other = &preset.query;
+        if let Some(q) = other.q.as_deref()
+            && query.q.as_deref().map(str::trim) != Some(q.trim())
+        {
+            query.any_terms.push(q.to_owned());
+        }
+        query.any_terms.extend(other.any_terms.iter().cloned());
+        query.all_terms.extend(other.all_terms.iter().cloned());
+        query.not_terms.extend(other.not_terms.iter().cloned());
+        query
+            .channel_allow
+            .extend(other.channel_allow.iter().cloned());
+        query
+            .channel_deny
+            .extend(other.channel_deny.iter().cloned());
+        query.per_channel_search |= other.per_channel_search;
+    }
+    PresetEditorState::normalize_terms(&mut query.any_terms);
+    PresetEditorState::normalize_terms(&mut query.all_terms);
+    PresetEditorState::normalize_terms(&mut query.not_terms);
+    dedupe_channels(&mut query.channel_allow);
+    dedupe_channels(&mut query.channel_deny);
+    Some(merged)
+}
+
+/// Drop repeated channels by their displayed handle or ID, keeping the first (which may
+/// carry a resolved ID).
+fn dedupe_channels(channels: &mut Vec<ChannelRef>) {
+    let mut seen = HashSet::new();
+    channels.retain(|channel| {
+        let key = channel.display().trim().to_ascii_lowercase();
+        !key.is_empty() && seen.insert(key)
+    });
+}
+
+impl AppState {
+    pub fn toggle_merge_mode(&mut self) {
+        self.merge_mode = !self.merge_mode;
+        self.merge_selection.clear();
+        self.merge_confirm = false;
+    }
+
+    pub fn toggle_merge_selection(&mut self, id: &str) {
+        if !self.merge_selection.remove(id) {
+            self.merge_selection.insert(id.to_owned());
+        }
+    }
+
+    /// Names of the presets picked for merging, in list order.
+    pub fn merge_selection_names(&self) -> Vec<String> {
+        self.prefs
+            .searches
+            .iter()
+            .filter(|preset| self.merge_selection.contains(&preset.id))
+            .map(|preset| preset.name.clone())
+            .collect()
+    }
+
+    /// Replace the selected presets with their merge, placed where the kept preset was.
+    pub fn merge_selected_presets(&mut self) {
+        self.merge_confirm = false;
+        let selected: BTreeSet<String> = std::mem::take(&mut self.merge_selection);
+        let picked: Vec<&MySearch> = self
+            .prefs
+            .searches
+            .iter()
+            .filter(|preset| selected.contains(&preset.id) && !preset.system)
+            .collect();
+        if picked.len() < 2 {
+            self.status = "Pick at least two presets to merge.".into();
+            return;
+        }
+        let count = picked.len();
+        let Some(merged) = merge_presets(&picked) else {
+            return;
+        };
+        let Some(slot) = self.prefs.searches.iter().position(|p| p.id == merged.id) else {
+            return;
+        };
+        self.prefs.searches[slot] = merged.clone();
+        self.prefs
+            .searches
+            .retain(|preset| preset.id == merged.id || !selected.contains(&preset.id));
+        if self
+            .selected_search_id
+            .as_ref()
+            .is_some_and(|id| selected.contains(id))
+        {
+            self.selected_search_id = Some(merged.id.clone());
+        }
+        self.merge_mode = false;
+        prefs::mark_modified(&mut self.prefs);
+        self.previous_results = None;
+        self.status = match prefs::save(&self.prefs) {
+            Ok(()) => format!("Merged {count} presets into '{}'.", merged.name),
+            Err(err) => format!("Failed to save prefs: {err}"),
+        };
+        self.refresh_visible_results();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(id: &str, priority: i32, q: &str, any: &[&str], allow: &[&str]) -> MySearch {
+        let mut search = MySearch {
+            id: id.into(),
+            name: id.to_uppercase(),
+            priority,
+            ..MySearch::default()
+        };
+        search.query.q = Some(q.into()).filter(|q: &String| !q.is_empty());
+        search.query.any_terms = any.iter().map(|t| t.to_string()).collect();
+        search.query.channel_allow = allow.iter().map(|c| ChannelRef::parse(c)).collect();
+        search
+    }
+
+    #[test]
+    fn merge_keeps_the_higher_priority_preset_and_unions_lists() {
+        let low = preset("low", 1, "egui", &["rust", "gui"], &["@a", "@b"]);
+        let high = preset("high", 5, "rust", &["gui", "ui"], &["@B", "@c"]);
+        let merged = merge_presets(&[&low, &high]).unwrap();
+        assert_eq!(merged.id, "high");
+        assert_eq!(merged.priority, 5);
+        assert_eq!(merged.name, "LOW + HIGH");
+        assert_eq!(merged.query.q.as_deref(), Some("rust"));
+        assert_eq!(merged.query.any_terms, vec!["gui", "ui", "egui", "rust"]);
+        let channels: Vec<&str> = merged
+            .query
+            .channel_allow
+            .iter()
+            .map(|c| c.display())
+            .collect();
+        assert_eq!(channels, vec!["@B", "@c", "@a"]);
+        assert!(merge_presets(&[]).is_none());
+    }
+}
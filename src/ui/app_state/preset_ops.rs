@@ -1,24 +1,38 @@
 use anyhow::{Result as AnyResult, bail};
-use serde_json;
 use time::OffsetDateTime;
 
-use crate::prefs::{self, MySearch, Prefs};
+use std::sync::mpsc;
+
+use crate::prefs::{self, MySearch};
+use crate::search_runner::{self, RunMode};
+use crate::ui::pending_ops::PendingOp;
+use crate::ui::preset_editor::{self, PresetEstimateState, PresetTestState};
+use crate::ui::shortcuts::Shortcut;
+use crate::ui::utils::warning_suffix;
 
 use super::{AppState, PresetEditorMode, PresetEditorState};
 
+/// How many titles the editor's Test result lists.
+const PRESET_TEST_TITLES: usize = 5;
+
 impl AppState {
     /// Open the preset editor with a blank template.
     pub fn open_new_preset(&mut self) {
+        self.open_new_preset_from(MySearch::default());
+    }
+
+    /// Open the editor for a new preset pre-filled from `template`; it is added at the end.
+    pub fn open_new_preset_from(&mut self, template: MySearch) {
         let template = MySearch {
             priority: self.prefs.searches.len() as i32,
-            ..MySearch::default()
+            ..template
         };
         let editor = PresetEditorState::new(
             PresetEditorMode::New,
             &template,
-            self.prefs.global.english_only,
-            self.prefs.global.require_captions,
-            self.prefs.global.min_duration_secs,
+            self.prefs.search_defaults.english_only,
+            self.prefs.search_defaults.require_captions,
+            self.prefs.search_defaults.min_duration_secs,
         );
         self.preset_editor = Some(editor);
     }
@@ -33,9 +47,9 @@ impl AppState {
             let editor = PresetEditorState::new(
                 PresetEditorMode::Edit { index },
                 existing,
-                self.prefs.global.english_only,
-                self.prefs.global.require_captions,
-                self.prefs.global.min_duration_secs,
+                self.prefs.search_defaults.english_only,
+                self.prefs.search_defaults.require_captions,
+                self.prefs.search_defaults.min_duration_secs,
             );
             self.preset_editor = Some(editor);
         }
@@ -50,13 +64,11 @@ impl AppState {
             }
             duplicate.system = false;
             let mut editor = PresetEditorState::new(
-                PresetEditorMode::Duplicate {
-                    source_index: index,
-                },
+                PresetEditorMode::Duplicate,
                 &duplicate,
-                self.prefs.global.english_only,
-                self.prefs.global.require_captions,
-                self.prefs.global.min_duration_secs,
+                self.prefs.search_defaults.english_only,
+                self.prefs.search_defaults.require_captions,
+                self.prefs.search_defaults.min_duration_secs,
             );
             if editor.name.trim().is_empty() {
                 editor.name = "New preset".into();
@@ -75,6 +87,8 @@ impl AppState {
             return;
         }
         let removed = self.prefs.searches.remove(index);
+        prefs::mark_modified(&mut self.prefs);
+        self.previous_results = None;
         if self.prefs.searches.is_empty() {
             self.reset_to_defaults();
             self.status = format!(
@@ -90,17 +104,310 @@ impl AppState {
             self.status = format!("Removed preset '{}'.", removed.name);
         }
 
-        if let Some(selected) = self.selected_search_id.clone() {
-            if selected == removed.id {
-                let next_id = if index < self.prefs.searches.len() {
-                    Some(self.prefs.searches[index].id.clone())
-                } else {
-                    self.prefs.searches.last().map(|s| s.id.clone())
-                };
-                self.selected_search_id = next_id;
-                self.refresh_visible_results();
+        if let Some(selected) = self.selected_search_id.clone()
+            && selected == removed.id
+        {
+            let next_id = if index < self.prefs.searches.len() {
+                Some(self.prefs.searches[index].id.clone())
+            } else {
+                self.prefs.searches.last().map(|s| s.id.clone())
+            };
+            self.selected_search_id = next_id;
+            self.refresh_visible_results();
+        }
+    }
+
+    /// `E` edits, `D` duplicates the selected preset and `N` starts a new one, unless a text
+    /// field has focus or another window is already open.
+    pub fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input()
+            || self.preset_editor.is_some()
+            || self.import_dialog.is_some()
+            || self.export_dialog.is_some()
+            || self.settings_dialog.is_some()
+        {
+            return;
+        }
+        if ctx.input_mut(|i| Shortcut::OpenHelp.consume(i)) {
+            self.show_help_dialog = !self.show_help_dialog;
+            return;
+        }
+        if self.show_help_dialog {
+            return;
+        }
+        let (edit, duplicate, new, save_all) = ctx.input_mut(|i| {
+            (
+                Shortcut::EditPreset.consume(i),
+                Shortcut::DuplicatePreset.consume(i),
+                Shortcut::NewPreset.consume(i),
+                Shortcut::SaveAll.consume(i),
+            )
+        });
+        if save_all {
+            self.save_everything();
+            return;
+        }
+        if new {
+            self.open_new_preset();
+            return;
+        }
+        let selected = self.selected_search_id.as_deref().and_then(|id| {
+            self.prefs
+                .searches
+                .iter()
+                .position(|search| search.id == id)
+        });
+        let Some(index) = selected else {
+            return;
+        };
+        if edit && !self.prefs.searches[index].system {
+            self.open_edit_preset(index);
+        } else if duplicate {
+            self.open_duplicate_preset(index);
+        }
+    }
+
+    /// Add a video tag to the selected preset's any terms and report it in the status line.
+    pub fn add_tag_to_selected_preset(&mut self, tag: &str) {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return;
+        }
+        let selected = self.selected_search_id.as_deref().and_then(|id| {
+            self.prefs
+                .searches
+                .iter()
+                .position(|search| search.id == id)
+        });
+        let Some(index) = selected else {
+            self.status = "Select a preset to add tags to.".into();
+            return;
+        };
+        let preset = &mut self.prefs.searches[index];
+        if preset.system {
+            self.status = "System presets cannot be edited.".into();
+            return;
+        }
+        if preset
+            .query
+            .any_terms
+            .iter()
+            .any(|term| term.trim().eq_ignore_ascii_case(tag))
+        {
+            self.status = format!("'{}' is already an any term of '{}'.", tag, preset.name);
+            return;
+        }
+        preset.query.any_terms.push(tag.to_owned());
+        let name = preset.name.clone();
+
+        prefs::mark_modified(&mut self.prefs);
+        self.previous_results = None;
+        if let Err(err) = prefs::save(&self.prefs) {
+            self.status = format!("Failed to save prefs: {err}");
+        } else {
+            self.status = format!("Added '{}' to the any terms of '{}'.", tag, name);
+        }
+    }
+
+    /// Conflict warnings across the presets a run in `mode` would search.
+    pub(crate) fn run_conflict_count(&self, mode: &RunMode) -> usize {
+        let global = &self.prefs.search_defaults;
+        match mode {
+            RunMode::Any => self
+                .prefs
+                .searches
+                .iter()
+                .filter(|search| search.enabled)
+                .map(|search| prefs::check_preset_conflicts(global, search).len())
+                .sum(),
+            RunMode::Single(id) => self
+                .prefs
+                .searches
+                .iter()
+                .find(|search| &search.id == id)
+                .map_or(0, |search| {
+                    prefs::check_preset_conflicts(global, search).len()
+                }),
+            RunMode::AdHoc(search) => prefs::check_preset_conflicts(global, search).len(),
+        }
+    }
+
+    /// Number of presets currently enabled for Any mode.
+    pub fn enabled_preset_count(&self) -> usize {
+        self.prefs
+            .searches
+            .iter()
+            .filter(|preset| preset.enabled)
+            .count()
+    }
+
+    /// Enable or disable every preset in one pass.
+    pub fn set_all_presets_enabled(&mut self, enabled: bool) {
+        let mut changed = false;
+        for preset in &mut self.prefs.searches {
+            if preset.enabled != enabled {
+                preset.enabled = enabled;
+                changed = true;
+            }
+        }
+        if changed {
+            self.refresh_visible_results();
+            self.schedule_prefs_save();
+        }
+    }
+
+    /// Flip the enabled flag on every preset.
+    pub fn invert_preset_selection(&mut self) {
+        if self.prefs.searches.is_empty() {
+            return;
+        }
+        for preset in &mut self.prefs.searches {
+            preset.enabled = !preset.enabled;
+        }
+        self.refresh_visible_results();
+        self.schedule_prefs_save();
+    }
+
+    /// Run the editor's unsaved preset once, showing the outcome inside the editor.
+    pub fn test_editor_preset(&mut self) {
+        let Some(editor) = self.preset_editor.as_mut() else {
+            return;
+        };
+        let mut preset = editor.snapshot();
+        if let Some(err) = PresetEditorState::query_error(&preset) {
+            editor.test_state = Some(PresetTestState::Failed(err.into()));
+            return;
+        }
+        if preset.name.trim().is_empty() {
+            preset.name = "Test".into();
+        }
+        if !self.pending_ops.begin(PendingOp::PresetTest) {
+            return;
+        }
+        editor.test_state = Some(PresetTestState::Running);
+
+        if let Some(task) = self.preset_test_task.take() {
+            task.abort();
+        }
+        self.normalize_duration_selection();
+        let api_key = self.prefs.api_key.clone();
+        let defaults = self.prefs.search_defaults.clone();
+        let blocked_channels = self.prefs.blocked_channels.clone();
+        let (tx, rx) = mpsc::channel();
+        let task = self.runtime.spawn(async move {
+            let result = search_runner::run_searches(
+                api_key,
+                defaults,
+                Vec::new(),
+                blocked_channels,
+                RunMode::AdHoc(Box::new(preset)),
+                None,
+                None,
+            )
+            .await
+            .map_err(|err| err.to_string());
+            let _ = tx.send(result);
+        });
+        self.preset_test_task = Some(task);
+        self.preset_test_rx = Some(rx);
+    }
+
+    /// Fetch one search page for the editor's preset and report raw vs. post-filter counts.
+    pub fn estimate_editor_preset(&mut self) {
+        let now = OffsetDateTime::now_utc();
+        let Some(editor) = self.preset_editor.as_mut() else {
+            return;
+        };
+        if !editor.can_estimate(now) {
+            return;
+        }
+        let preset = editor.snapshot();
+        if let Some(err) = PresetEditorState::query_error(&preset) {
+            editor.estimate_state = Some(PresetEstimateState::Failed(err.into()));
+            return;
+        }
+        if !self.pending_ops.begin(PendingOp::PresetEstimate) {
+            return;
+        }
+        editor.estimate_state = Some(PresetEstimateState::Running);
+        editor.last_estimate = Some((preset.clone(), now));
+
+        let api_key = self.prefs.api_key.clone();
+        let defaults = self.prefs.search_defaults.clone();
+        let blocked_channels = self.prefs.blocked_channels.clone();
+        let (tx, rx) = mpsc::channel();
+        self.runtime.spawn(async move {
+            let result =
+                search_runner::estimate_preset(api_key, defaults, blocked_channels, preset)
+                    .await
+                    .map_err(|err| err.to_string());
+            let _ = tx.send(result);
+        });
+        self.preset_estimate_rx = Some(rx);
+    }
+
+    /// Deliver a finished test run to the editor, or drop it if the editor was closed.
+    pub fn poll_preset_test(&mut self) {
+        let Some(editor) = self.preset_editor.as_mut() else {
+            if let Some(task) = self.preset_test_task.take() {
+                task.abort();
+            }
+            self.preset_test_rx = None;
+            self.preset_estimate_rx = None;
+            self.pending_ops.finish(PendingOp::PresetTest);
+            self.pending_ops.finish(PendingOp::PresetEstimate);
+            return;
+        };
+        let mut quota_units = 0;
+        if let Some(rx) = self.preset_estimate_rx.as_ref() {
+            let state = match rx.try_recv() {
+                Ok(Ok(estimate)) => {
+                    quota_units += estimate.quota_units;
+                    Some(PresetEstimateState::Done {
+                        raw: estimate.raw_items,
+                        passed: estimate.passed_filters,
+                    })
+                }
+                Ok(Err(err)) => Some(PresetEstimateState::Failed(err)),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    Some(PresetEstimateState::Failed("Estimate cancelled.".into()))
+                }
+            };
+            if let Some(state) = state {
+                editor.estimate_state = Some(state);
+                self.preset_estimate_rx = None;
+                self.pending_ops.finish(PendingOp::PresetEstimate);
+            }
+        }
+        if let Some(rx) = self.preset_test_rx.as_ref() {
+            let state = match rx.try_recv() {
+                Ok(Ok(outcome)) => {
+                    quota_units += outcome.quota_units;
+                    Some(PresetTestState::Done {
+                        kept: outcome.videos.len(),
+                        titles: outcome
+                            .videos
+                            .iter()
+                            .take(PRESET_TEST_TITLES)
+                            .map(|video| video.title.clone())
+                            .collect(),
+                    })
+                }
+                Ok(Err(err)) => Some(PresetTestState::Failed(err)),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    Some(PresetTestState::Failed("Test run cancelled.".into()))
+                }
+            };
+            if let Some(state) = state {
+                editor.test_state = Some(state);
+                self.preset_test_rx = None;
+                self.preset_test_task = None;
+                self.pending_ops.finish(PendingOp::PresetTest);
             }
         }
+        self.record_quota_usage(quota_units);
     }
 
     pub fn cancel_editor(&mut self) {
@@ -121,18 +428,8 @@ impl AppState {
             return;
         }
 
-        let has_query_text = editor
-            .working
-            .query
-            .q
-            .as_ref()
-            .map(|q| !q.trim().is_empty())
-            .unwrap_or(false);
-        if !has_query_text
-            && editor.working.query.any_terms.is_empty()
-            && editor.working.query.all_terms.is_empty()
-        {
-            editor.error = Some("Configure at least one query term.".into());
+        if let Some(err) = PresetEditorState::query_error(&editor.working) {
+            editor.error = Some(err.into());
             self.preset_editor = Some(editor);
             return;
         }
@@ -162,7 +459,7 @@ impl AppState {
                     preset: editor.working.clone(),
                 }
             }
-            PresetEditorMode::Duplicate { .. } | PresetEditorMode::New => {
+            PresetEditorMode::Duplicate | PresetEditorMode::New => {
                 if editor.working.id.trim().is_empty()
                     || self
                         .prefs
@@ -178,22 +475,32 @@ impl AppState {
             }
         };
 
-        match action {
-            SaveAction::Update { index, id, preset } => {
-                if let Some(existing) = self.prefs.searches.get_mut(index) {
+        let changed = match action {
+            SaveAction::Update { index, id, preset } => match self.prefs.searches.get_mut(index) {
+                Some(existing) => {
+                    let before = existing.clone();
                     *existing = preset;
                     existing.id = id;
+                    *existing != before
                 }
-            }
+                None => false,
+            },
             SaveAction::Append { preset } => {
                 self.prefs.searches.push(preset);
+                true
             }
-        }
+        };
 
+        if changed {
+            prefs::mark_modified(&mut self.prefs);
+            self.previous_results = None;
+        }
         if let Err(err) = prefs::save(&self.prefs) {
             self.status = format!("Failed to save prefs: {err}");
         } else {
-            self.status = "Preset saved.".into();
+            let warnings =
+                prefs::check_preset_conflicts(&self.prefs.search_defaults, &editor.working).len();
+            self.status = format!("Preset saved{}.", warning_suffix(warnings));
             self.refresh_visible_results();
         }
 
@@ -238,44 +545,16 @@ impl AppState {
 
     /// Parse JSON text from the clipboard into a single preset structure.
     pub(crate) fn parse_clipboard_preset(&self, raw: &str) -> AnyResult<MySearch> {
-        let trimmed = raw.trim();
-
-        if trimmed.is_empty() {
+        if raw.trim().is_empty() {
             bail!("Clipboard is empty");
         }
-
-        if let Ok(preset) = serde_json::from_str::<MySearch>(trimmed) {
-            return Ok(preset);
-        }
-
-        if let Ok(presets) = serde_json::from_str::<Vec<MySearch>>(trimmed) {
-            if let Some(first) = presets.into_iter().next() {
-                return Ok(first);
-            }
-        }
-
-        if let Ok(payload) = serde_json::from_str::<Prefs>(trimmed) {
-            if let Some(first) = payload.searches.into_iter().next() {
-                return Ok(first);
-            }
-        }
-
-        bail!("Clipboard JSON did not contain a preset.");
+        preset_editor::parse_preset_json(raw)
     }
 
     /// Apply clipboard-derived preset contents into the active editor session.
-    pub(crate) fn apply_clipboard_preset(&mut self, mut preset: MySearch) {
+    pub(crate) fn apply_clipboard_preset(&mut self, preset: MySearch) {
         if let Some(editor) = self.preset_editor.as_mut() {
-            match editor.mode {
-                PresetEditorMode::Edit { .. } => {
-                    preset.id = editor.working.id.clone();
-                }
-                PresetEditorMode::Duplicate { .. } | PresetEditorMode::New => {
-                    preset.id.clear();
-                    preset.enabled = true;
-                }
-            }
-            editor.apply_source(&preset);
+            editor.load_preset(preset);
         }
     }
 }
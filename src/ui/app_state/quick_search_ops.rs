@@ -0,0 +1,60 @@
+use crate::prefs::{self, MySearch, QuerySpec};
+use crate::ui::pending_ops::PendingOp;
+
+use super::AppState;
+
+/// Unsaved preset for a quick search: free-text `q` only, everything else from globals.
+fn quick_preset(query: &str) -> MySearch {
+    MySearch {
+        id: "quick-search".into(),
+        name: format!("Quick: {query}"),
+        enabled: true,
+        query: QuerySpec {
+            q: Some(query.to_owned()),
+            ..QuerySpec::default()
+        },
+        ..MySearch::default()
+    }
+}
+
+impl AppState {
+    /// Run the quick-search box text as a one-off search without touching saved presets.
+    pub fn launch_quick_search(&mut self) {
+        if self.pending_ops.is_pending(PendingOp::Search) {
+            return;
+        }
+        let query = self.quick_query.trim().to_owned();
+        if query.is_empty() {
+            self.status = "Type something to quick-search.".into();
+            return;
+        }
+        prefs::remember_query(&mut self.prefs.recent_queries, &query);
+        self.schedule_prefs_save();
+        self.quick_search = Some(quick_preset(&query));
+        self.launch_run();
+    }
+
+    /// Open the preset editor pre-filled with the quick-search query so it can be saved.
+    pub fn promote_quick_search(&mut self) {
+        let query = match &self.quick_search {
+            Some(search) => search.query.q.clone().unwrap_or_default(),
+            None => self.quick_query.trim().to_owned(),
+        };
+        if query.is_empty() {
+            return;
+        }
+        self.open_new_preset_from(MySearch {
+            name: query.clone(),
+            query: QuerySpec {
+                q: Some(query),
+                ..QuerySpec::default()
+            },
+            ..MySearch::default()
+        });
+    }
+
+    pub fn clear_recent_queries(&mut self) {
+        self.prefs.recent_queries.clear();
+        self.schedule_prefs_save();
+    }
+}
@@ -0,0 +1,84 @@
+use time::OffsetDateTime;
+
+use crate::quota::{self, QuotaCheck};
+use crate::search_runner;
+
+use super::AppState;
+
+/// Pending confirmation for a run that would overshoot the daily quota budget.
+pub struct QuotaPrompt {
+    /// Page sources in the run; per-channel presets count once per channel.
+    pub scopes: quota::RunScopes,
+    pub max_pages: usize,
+    pub estimated: u32,
+    pub used_today: u32,
+    pub budget: u32,
+    pub remaining: u32,
+    /// Largest page cap that fits the remaining budget, if any.
+    pub fewer_pages: Option<usize>,
+}
+
+impl AppState {
+    /// Units spent today according to the persisted usage counter.
+    pub fn quota_used_today(&self) -> u32 {
+        let today = quota::quota_day(OffsetDateTime::now_utc());
+        self.prefs.quota_usage.used_on(&today)
+    }
+
+    pub(crate) fn record_quota_usage(&mut self, units: u32) {
+        if units == 0 {
+            return;
+        }
+        let today = quota::quota_day(OffsetDateTime::now_utc());
+        self.prefs.quota_usage.record(&today, units);
+        self.schedule_prefs_save();
+    }
+
+    /// Check the upcoming run against the remaining budget. A budget of 0 disables the guard.
+    pub(super) fn quota_prompt_for_run(&self) -> Option<QuotaPrompt> {
+        let budget = self.prefs.ui.daily_quota_budget;
+        if budget == 0 {
+            return None;
+        }
+        let mode = self.determine_run_mode().ok()?;
+        let scopes = search_runner::run_scope_count(&self.prefs.searches, &mode);
+        let max_pages = search_runner::max_search_pages();
+        let used_today = self.quota_used_today();
+        match quota::check_run(scopes, max_pages, budget, used_today) {
+            QuotaCheck::Fits => None,
+            QuotaCheck::Exceeds {
+                estimated,
+                remaining,
+                fewer_pages,
+            } => Some(QuotaPrompt {
+                scopes,
+                max_pages,
+                estimated,
+                used_today,
+                budget,
+                remaining,
+                fewer_pages,
+            }),
+        }
+    }
+
+    /// Start the blocked run at full page depth.
+    pub fn quota_run_anyway(&mut self) {
+        if self.quota_prompt.take().is_some() {
+            self.start_search(None);
+        }
+    }
+
+    /// Start the blocked run with pages clamped to fit the remaining budget.
+    pub fn quota_run_fewer_pages(&mut self) {
+        if let Some(pages) = self.quota_prompt.take().and_then(|p| p.fewer_pages) {
+            self.start_search(Some(pages));
+        }
+    }
+
+    pub fn cancel_quota_prompt(&mut self) {
+        if self.quota_prompt.take().is_some() {
+            self.status = "Search cancelled to stay within the daily quota budget.".into();
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::debug_report;
+
+use super::AppState;
+
+/// Status lines the debug report can include, newest last.
+#[derive(Default)]
+pub struct StatusHistory {
+    entries: VecDeque<String>,
+    last: String,
+}
+
+impl StatusHistory {
+    /// Note `status` if it changed since the last call.
+    fn observe(&mut self, status: &str) {
+        if status == self.last {
+            return;
+        }
+        self.last = status.to_owned();
+        if status.is_empty() {
+            return;
+        }
+        let now = OffsetDateTime::now_utc();
+        let when = now.format(&Rfc3339).unwrap_or_else(|_| now.to_string());
+        self.entries.push_back(format!("{when} {status}"));
+        if self.entries.len() > debug_report::STATUS_HISTORY_LIMIT {
+            self.entries.pop_front();
+        }
+    }
+}
+
+impl AppState {
+    /// Called once per frame, after the panels had their chance to change the status.
+    pub(in crate::ui) fn record_status_history(&mut self) {
+        self.status_history.observe(&self.status);
+    }
+
+    /// Ask where to save the debug report, then write it.
+    pub fn save_debug_report(&mut self) {
+        let titles = self.debug_report_titles.then(|| {
+            self.visible_rows
+                .iter()
+                .map(|&row| self.result(row).title.clone())
+                .collect()
+        });
+        let report = debug_report::build(
+            &self.prefs,
+            self.last_run_stats.as_ref(),
+            self.last_error.as_ref().map(|error| error.message.as_str()),
+            &self.status_history.entries,
+            titles,
+        );
+        let json = match debug_report::to_json(report, debug_report::MAX_REPORT_BYTES) {
+            Ok(json) => json,
+            Err(err) => {
+                self.status = format!("Failed to build debug report: {err}");
+                return;
+            }
+        };
+        match native_dialog::FileDialog::new()
+            .add_filter("JSON files", &["json"])
+            .set_filename("ytsearch-debug-report.json")
+            .show_save_single_file()
+        {
+            Ok(Some(path)) => {
+                self.status = match std::fs::write(&path, json) {
+                    Ok(()) => format!("Saved debug report to {}.", path.display()),
+                    Err(err) => format!("Failed to save debug report: {err}"),
+                };
+            }
+            Ok(None) => {}
+            Err(err) => {
+                self.status = format!("Failed to open save dialog: {err}");
+            }
+        }
+    }
+}
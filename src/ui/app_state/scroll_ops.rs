@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use super::{AppState, CardKey};
+
+/// The card to keep at the top after the list is rebuilt: `top` itself when it survived,
+/// otherwise the closest survivor below it in the old order, then the closest above it.
+pub(super) fn scroll_anchor(
+    old_order: &[CardKey],
+    top: &CardKey,
+    survivors: &HashSet<&CardKey>,
+) -> Option<CardKey> {
+    let position = old_order.iter().position(|key| key == top)?;
+    let (above, below) = old_order.split_at(position);
+    below
+        .iter()
+        .chain(above.iter().rev())
+        .find(|key| survivors.contains(key))
+        .cloned()
+}
+
+impl AppState {
+    /// Card keys in the order the list currently shows them.
+    pub(super) fn visible_order(&self) -> Vec<CardKey> {
+        self.visible_rows
+            .iter()
+            .map(|&index| self.card_key(index))
+            .collect()
+    }
+
+    /// After a rebuild, queue a scroll that puts the previously topmost video (or its nearest
+    /// surviving neighbor) back at the top of the list.
+    pub(super) fn restore_scroll_anchor(&mut self, old_order: &[CardKey]) {
+        let Some(top) = self.top_visible_id.take() else {
+            return;
+        };
+        let keys = self.visible_order();
+        let survivors: HashSet<&CardKey> = keys.iter().collect();
+        self.pending_scroll_to = scroll_anchor(old_order, &top, &survivors);
+    }
+
+    /// Start the list from the first card, dropping any anchor kept from the previous results
+    /// and every preset's remembered position.
+    pub fn scroll_results_to_top(&mut self) {
+        self.top_visible_id = None;
+        self.pending_scroll_to = None;
+        self.pending_scroll_offset = Some(0.0);
+        self.preset_scroll_offsets.clear();
+    }
+
+    /// Change the selected preset. In Single mode the list position is remembered for the
+    /// preset being left and restored for the one being shown (the top on first visit).
+    pub fn select_search(&mut self, id: Option<String>) {
+        let single = !self.run_any_mode;
+        if single {
+            let key = self.selected_search_id.clone().unwrap_or_default();
+            self.preset_scroll_offsets
+                .insert(key, self.results_scroll_offset);
+        }
+        self.selected_search_id = id;
+        self.refresh_visible_results();
+        if single {
+            let key = self.selected_search_id.as_deref().unwrap_or_default();
+            self.top_visible_id = None;
+            self.pending_scroll_to = None;
+            self.pending_scroll_offset =
+                Some(self.preset_scroll_offsets.get(key).copied().unwrap_or(0.0));
+        }
+    }
+
+    /// Offset queued by [`Self::scroll_results_to_top`] or [`Self::select_search`].
+    pub fn take_pending_scroll_offset(&mut self) -> Option<f32> {
+        self.pending_scroll_offset.take()
+    }
+
+    /// Row to bring to the top for a queued scroll; consumed by the results list.
+    pub fn take_pending_scroll(&mut self) -> Option<usize> {
+        let target = self.pending_scroll_to.take()?;
+        self.visible_rows
+            .iter()
+            .position(|&index| self.card_key(index) == target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(ids: &[&str]) -> Vec<CardKey> {
+        ids.iter()
+            .enumerate()
+            .map(|(index, id)| (id.to_string(), index))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_the_top_video_or_the_nearest_survivor() {
+        let old = order(&["a", "b", "c", "d", "e"]);
+        let survivors: HashSet<&CardKey> = [&old[0], &old[2], &old[4]].into_iter().collect();
+        assert_eq!(
+            scroll_anchor(&old, &old[2], &survivors),
+            Some(old[2].clone())
+        );
+        assert_eq!(
+            scroll_anchor(&old, &old[1], &survivors),
+            Some(old[2].clone())
+        );
+        assert_eq!(
+            scroll_anchor(&old, &old[3], &survivors),
+            Some(old[4].clone())
+        );
+
+        let only_above: HashSet<&CardKey> = [&old[0]].into_iter().collect();
+        assert_eq!(
+            scroll_anchor(&old, &old[3], &only_above),
+            Some(old[0].clone())
+        );
+        assert_eq!(scroll_anchor(&old, &old[3], &HashSet::new()), None);
+        assert_eq!(scroll_anchor(&old, &("zz".into(), 9), &survivors), None);
+    }
+
+    #[test]
+    fn copies_of_one_video_anchor_separately() {
+        // `DuplicatePolicy::KeepAll` can leave the same ID in several cards.
+        let old = order(&["a", "v", "b", "v", "c"]);
+        assert_ne!(old[1], old[3]);
+        let survivors: HashSet<&CardKey> = [&old[0], &old[1], &old[4]].into_iter().collect();
+        assert_eq!(
+            scroll_anchor(&old, &old[3], &survivors),
+            Some(old[4].clone())
+        );
+        assert_eq!(
+            scroll_anchor(&old, &old[1], &survivors),
+            Some(old[1].clone())
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use egui::Context;
+
+use crate::prefs;
+use crate::ui::duration_filters::DurationFilterState;
+use crate::ui::settings::SettingsState;
+
+use super::AppState;
+
+impl AppState {
+    /// Open the settings window with a draft copy of the current prefs.
+    pub fn open_settings(&mut self) {
+        self.settings_dialog = Some(SettingsState::new(
+            &self.prefs.api_key,
+            &self.prefs.search_defaults,
+            &self.prefs.ui,
+        ));
+    }
+
+    /// Drop the draft without touching prefs.
+    pub fn cancel_settings(&mut self) {
+        self.settings_dialog = None;
+    }
+
+    /// Validate the draft, copy it into prefs, and persist.
+    pub fn apply_settings(&mut self, ctx: &Context) {
+        let Some(mut draft) = self.settings_dialog.take() else {
+            return;
+        };
+
+        draft.error = None;
+        if let Err(msg) = draft.validate() {
+            draft.error = Some(msg);
+            self.settings_dialog = Some(draft);
+            return;
+        }
+
+        let (api_key, mut defaults, mut ui) = draft.merged_into(
+            &self.prefs.api_key,
+            &self.prefs.search_defaults,
+            &self.prefs.ui,
+        );
+        let theme = ui.theme;
+        ui.theme = self.prefs.ui.theme;
+        prefs::normalize_duration_filters(&mut defaults);
+
+        let search_changed = prefs::search_defaults_differ(&self.prefs.search_defaults, &defaults);
+        self.prefs.api_key = api_key;
+        self.prefs.search_defaults = defaults;
+        self.prefs.ui = ui;
+        crate::yt::http::configure(&self.prefs.search_defaults);
+        self.duration_filter = DurationFilterState::from_defaults(&self.prefs.search_defaults);
+        self.set_theme_choice(ctx, theme);
+        self.prefs_save_due = None;
+        if search_changed {
+            prefs::mark_modified(&mut self.prefs);
+        }
+
+        if let Err(err) = prefs::save(&self.prefs) {
+            self.record_error(format!("Failed to save settings: {err}"), None);
+        } else {
+            self.status = "Settings saved.".into();
+        }
+        self.refresh_visible_results();
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{HashSet, VecDeque};
+
+use time::{Duration, OffsetDateTime};
+
+use crate::ui::utils::open_in_browser;
+
+use super::AppState;
+
+/// Pause between browser launches in a batch so tabs open in list order.
+const OPEN_STAGGER_MS: i64 = 400;
+
+/// A video waiting its turn in an "Open next" batch.
+pub struct QueuedOpen {
+    pub title: String,
+    pub url: String,
+}
+
+/// Progress of the running "Open next" batch.
+#[derive(Default)]
+pub struct OpenBatch {
+    pub queue: VecDeque<QueuedOpen>,
+    pub opened: usize,
+    pub failures: Vec<String>,
+    pub next_at: Option<OffsetDateTime>,
+}
+
+/// Positions of the first `count` IDs in list order that were not opened yet.
+fn next_unopened(order: &[&str], opened: &HashSet<String>, count: usize) -> Vec<usize> {
+    order
+        .iter()
+        .enumerate()
+        .filter(|(_, id)| !opened.contains(**id))
+        .map(|(position, _)| position)
+        .take(count)
+        .collect()
+}
+
+impl AppState {
+    /// Visible videos not opened this session.
+    pub fn unopened_visible_count(&self) -> usize {
+        self.visible_rows
+            .iter()
+            .filter(|&&index| !self.opened_videos.contains(&self.result(index).id))
+            .count()
+    }
+
+    pub fn open_batch_running(&self) -> bool {
+        self.open_batch.is_some()
+    }
+
+    /// Queue the next unopened visible videos; they open one per stagger interval.
+    pub fn open_next_batch(&mut self) {
+        if self.open_batch.is_some() {
+            return;
+        }
+        let order: Vec<&str> = self
+            .visible_rows
+            .iter()
+            .map(|&index| self.result(index).id.as_str())
+            .collect();
+        let count = self.prefs.ui.open_batch_size.max(1) as usize;
+        let picked = next_unopened(&order, &self.opened_videos, count);
+        if picked.is_empty() {
+            self.status = "Every visible video was already opened this session.".into();
+            return;
+        }
+        let mut batch = OpenBatch::default();
+        for position in picked {
+            let video = &self.results_all[self.results[self.visible_rows[position]]];
+            self.opened_videos.insert(video.id.clone());
+            batch.queue.push_back(QueuedOpen {
+                title: video.title.clone(),
+                url: video.watch_url(),
+            });
+        }
+        self.open_batch = Some(batch);
+    }
+
+    /// Open the next queued video once its turn comes; reports the batch when it drains.
+    pub(crate) fn advance_open_batch(&mut self) {
+        let Some(batch) = self.open_batch.as_mut() else {
+            return;
+        };
+        let now = OffsetDateTime::now_utc();
+        if batch.next_at.is_some_and(|due| now < due) {
+            return;
+        }
+        if let Some(next) = batch.queue.pop_front() {
+            match open_in_browser(&next.url) {
+                Ok(()) => batch.opened += 1,
+                Err(err) => batch.failures.push(format!("{}: {err}", next.title)),
+            }
+            batch.next_at = Some(now + Duration::milliseconds(OPEN_STAGGER_MS));
+        }
+        if !batch.queue.is_empty() {
+            return;
+        }
+        let Some(batch) = self.open_batch.take() else {
+            return;
+        };
+        let remaining = self.unopened_visible_count();
+        self.status = if batch.failures.is_empty() {
+            format!("Opened {} ({remaining} remaining)", batch.opened)
+        } else {
+            format!(
+                "Opened {} ({remaining} remaining); failed: {}",
+                batch.opened,
+                batch.failures.join("; ")
+            )
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_opened_videos_and_continues_down_the_list() {
+        let order = ["a", "b", "c", "d", "e", "f"];
+        let mut opened: HashSet<String> = HashSet::new();
+        opened.insert("b".into());
+        assert_eq!(next_unopened(&order, &opened, 3), vec![0, 2, 3]);
+
+        for id in ["a", "c", "d"] {
+            opened.insert(id.into());
+        }
+        assert_eq!(next_unopened(&order, &opened, 3), vec![4, 5]);
+        opened.extend(["e".to_string(), "f".to_string()]);
+        assert!(next_unopened(&order, &opened, 3).is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::prefs::{DurationBucketConfig, GlobalPrefs};
+use crate::prefs::{DurationBucketConfig, SearchDefaults};
 use crate::yt::types::VideoDetails;
 
 #[derive(Clone)]
@@ -16,10 +16,10 @@ pub struct DurationFilterState {
 }
 
 impl DurationFilterState {
-    pub fn from_global(global: &GlobalPrefs) -> Self {
+    pub fn from_defaults(defaults: &SearchDefaults) -> Self {
         let mut state = Self {
-            allow_multiple: global.duration_filters.allow_multiple,
-            buckets: global
+            allow_multiple: defaults.duration_filters.allow_multiple,
+            buckets: defaults
                 .duration_filters
                 .buckets
                 .iter()
@@ -30,7 +30,7 @@ impl DurationFilterState {
                 })
                 .collect(),
         };
-        state.sync_from_ids(&global.active_duration_bucket_ids);
+        state.sync_from_ids(&defaults.active_duration_bucket_ids);
         state
     }
 
@@ -98,6 +98,19 @@ impl DurationFilterState {
         changed
     }
 
+    /// Select only the catch-all bucket so no length filtering applies.
+    pub fn reset_to_catch_all(&mut self) -> bool {
+        self.activate_catch_all()
+    }
+
+    /// True when the only active bucket is the catch-all (or nothing filters at all).
+    pub fn is_unfiltered(&self) -> bool {
+        self.buckets
+            .iter()
+            .filter(|bucket| bucket.selected)
+            .all(|bucket| bucket.config.is_catch_all())
+    }
+
     pub fn selected_ids(&self) -> Vec<String> {
         self.buckets
             .iter()
@@ -191,11 +204,11 @@ impl DurationFilterState {
             return changed;
         }
 
-        if let Some(first) = self.buckets.first_mut() {
-            if !first.selected {
-                first.selected = true;
-                return true;
-            }
+        if let Some(first) = self.buckets.first_mut()
+            && !first.selected
+        {
+            first.selected = true;
+            return true;
         }
         changed
     }
@@ -239,3 +252,116 @@ pub fn channel_sort_key(video: &VideoDetails) -> String {
         })
         .unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(id: &str, min: u32, max: Option<u32>, default_selected: bool) -> DurationBucketState {
+        DurationBucketState {
+            config: DurationBucketConfig {
+                id: id.into(),
+                label: id.into(),
+                min_seconds: min,
+                max_seconds: max,
+                default_selected,
+            },
+            selected: false,
+        }
+    }
+
+    fn state(allow_multiple: bool) -> DurationFilterState {
+        DurationFilterState {
+            allow_multiple,
+            buckets: vec![
+                bucket("any", 0, None, false),
+                bucket("short", 0, Some(240), false),
+                bucket("medium", 240, Some(1200), false),
+                bucket("long", 1200, None, false),
+            ],
+        }
+    }
+
+    #[test]
+    fn catch_all_deselects_others() {
+        let mut filter = state(true);
+        filter.sync_from_ids(&["short".into(), "medium".into()]);
+        assert!(filter.toggle("any"));
+        assert_eq!(filter.selected_ids(), vec!["any".to_string()]);
+    }
+
+    #[test]
+    fn specific_bucket_deselects_catch_all_in_multi_mode() {
+        let mut filter = state(true);
+        filter.sync_from_ids(&["any".into()]);
+        assert!(filter.toggle("short"));
+        assert_eq!(filter.selected_ids(), vec!["short".to_string()]);
+        assert!(filter.toggle("long"));
+        assert_eq!(
+            filter.selected_ids(),
+            vec!["short".to_string(), "long".to_string()]
+        );
+    }
+
+    #[test]
+    fn untoggling_last_bucket_falls_back_to_catch_all() {
+        let mut filter = state(true);
+        filter.sync_from_ids(&["short".into()]);
+        assert!(filter.toggle("short"));
+        assert_eq!(filter.selected_ids(), vec!["any".to_string()]);
+    }
+
+    #[test]
+    fn single_mode_keeps_one_selection() {
+        let mut filter = state(false);
+        filter.sync_from_ids(&["short".into(), "medium".into()]);
+        assert_eq!(filter.selected_ids(), vec!["short".to_string()]);
+        assert!(filter.toggle("long"));
+        assert_eq!(filter.selected_ids(), vec!["long".to_string()]);
+    }
+
+    #[test]
+    fn minimum_selection_prefers_default_bucket() {
+        let mut filter = state(true);
+        filter.buckets[2].config.default_selected = true;
+        assert!(filter.ensure_minimum_selection());
+        assert_eq!(filter.selected_ids(), vec!["medium".to_string()]);
+        assert!(!filter.ensure_minimum_selection());
+    }
+
+    #[test]
+    fn minimum_selection_falls_back_to_catch_all_then_first() {
+        let mut filter = state(true);
+        assert!(filter.ensure_minimum_selection());
+        assert_eq!(filter.selected_ids(), vec!["any".to_string()]);
+
+        let mut no_catch_all = state(true);
+        no_catch_all.buckets.remove(0);
+        assert!(no_catch_all.ensure_minimum_selection());
+        assert_eq!(no_catch_all.selected_ids(), vec!["short".to_string()]);
+    }
+
+    #[test]
+    fn reset_to_catch_all_clears_specific_buckets() {
+        let mut filter = state(true);
+        filter.sync_from_ids(&["short".into(), "long".into()]);
+        assert!(!filter.is_unfiltered());
+        assert!(filter.reset_to_catch_all());
+        assert_eq!(filter.selected_ids(), vec!["any".to_string()]);
+        assert!(filter.is_unfiltered());
+        assert!(!filter.reset_to_catch_all());
+    }
+
+    #[test]
+    fn sync_from_ids_round_trips_selected_ids() {
+        let mut filter = state(true);
+        let ids = vec!["medium".to_string(), "long".to_string()];
+        assert!(filter.sync_from_ids(&ids));
+        assert_eq!(filter.selected_ids(), ids);
+
+        let mut copy = state(true);
+        copy.sync_from_ids(&filter.selected_ids());
+        assert_eq!(copy.selected_ids(), ids);
+        assert!(!copy.sync_from_ids(&ids));
+    }
+}
@@ -0,0 +1,314 @@
+//! Word index over every video the app has seen, kept in the config folder. Optional: with
+//! `UiPrefs::indexed_find` off the find box scans every loaded result with
+//! [`finder::relevance`], which is fine for a few hundred videos but slows down once the
+//! cache holds thousands, and the "Search history" window is unavailable.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config_dir;
+use crate::yt::types::VideoDetails;
+
+use super::finder;
+
+/// What the index remembers about a video after it has left the result list.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SeenVideo {
+    pub id: String,
+    pub title: String,
+    /// [`finder::channel_text`] when the video was last indexed.
+    pub channel: String,
+    #[serde(default)]
+    pub description: String,
+    pub published_at: String,
+    pub url: String,
+}
+
+impl SeenVideo {
+    fn from_details(video: &VideoDetails) -> Self {
+        Self {
+            id: video.id.clone(),
+            title: video.title.clone(),
+            channel: finder::channel_text(video),
+            description: video.description.clone(),
+            published_at: video.published_at.clone(),
+            url: video.url.clone(),
+        }
+    }
+}
+
+/// Lowercased word to ascending positions in [`FindIndex::videos`].
+type Postings = BTreeMap<String, Vec<usize>>;
+
+/// Every video seen so far, with the words of its title and channel, and separately of its
+/// description, mapped back to it.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct FindIndex {
+    videos: Vec<SeenVideo>,
+    words: Postings,
+    /// Kept apart from `words` so the results find box matches exactly what
+    /// [`finder::relevance`] does, which ignores descriptions.
+    description_words: Postings,
+    #[serde(skip)]
+    positions: HashMap<String, usize>,
+}
+
+impl FindIndex {
+    #[cfg(test)]
+    pub fn build(videos: &[VideoDetails]) -> Self {
+        let mut index = Self::default();
+        index.add(videos);
+        index
+    }
+
+    /// Index videos not seen before, and words a known video has gained since: a new title
+    /// or channel name, or a description the cached copy did not carry. Returns how many
+    /// videos were new or changed.
+    pub fn add(&mut self, videos: &[VideoDetails]) -> usize {
+        let mut changed = 0;
+        for video in videos {
+            let seen = SeenVideo::from_details(video);
+            let position = match self.positions.get(&seen.id) {
+                Some(&position) => {
+                    let known = &mut self.videos[position];
+                    let fresh_text = known.title != seen.title || known.channel != seen.channel;
+                    let fresh_description =
+                        !seen.description.is_empty() && known.description != seen.description;
+                    if !fresh_text && !fresh_description {
+                        continue;
+                    }
+                    if fresh_text {
+                        known.title = seen.title;
+                        known.channel = seen.channel;
+                    }
+                    if fresh_description {
+                        known.description = seen.description;
+                    }
+                    position
+                }
+                None => {
+                    self.positions.insert(seen.id.clone(), self.videos.len());
+                    self.videos.push(seen);
+                    self.videos.len() - 1
+                }
+            };
+            self.post(position);
+            changed += 1;
+        }
+        changed
+    }
+
+    /// Derive the word lists again from the stored videos, dropping words they no longer
+    /// contain.
+    pub fn rebuild(&mut self) {
+        self.words.clear();
+        self.description_words.clear();
+        self.restore_positions();
+        for position in 0..self.videos.len() {
+            self.post(position);
+        }
+    }
+
+    /// Map IDs back to positions, which are not stored.
+    fn restore_positions(&mut self) {
+        self.positions = self
+            .videos
+            .iter()
+            .enumerate()
+            .map(|(position, video)| (video.id.clone(), position))
+            .collect();
+    }
+
+    fn post(&mut self, position: usize) {
+        let video = &self.videos[position];
+        let text = format!("{} {}", video.title, video.channel);
+        for (postings, text) in [
+            (&mut self.words, text.as_str()),
+            (&mut self.description_words, video.description.as_str()),
+        ] {
+            for word in finder::words(text) {
+                let docs = postings.entry(word).or_default();
+                // Positions only grow, except when a known video gains words.
+                if let Err(at) = docs.binary_search(&position) {
+                    docs.insert(at, position);
+                }
+            }
+        }
+    }
+
+    pub fn video_count(&self) -> usize {
+        self.videos.len()
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.words.len() + self.description_words.len()
+    }
+
+    pub fn video(&self, position: usize) -> &SeenVideo {
+        &self.videos[position]
+    }
+
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.positions.get(id).copied()
+    }
+
+    /// Positions of the videos in which every query word starts some word, ascending. Only
+    /// title and channel words count unless `descriptions` is set. Each query word is one
+    /// ordered range lookup: its exact entry and the words it is a prefix of.
+    pub fn matches(&self, query: &str, descriptions: bool) -> Vec<usize> {
+        let mut found: Option<Vec<usize>> = None;
+        for token in finder::words(query) {
+            let mut docs: Vec<usize> = prefixed(&self.words, &token).collect();
+            if descriptions {
+                docs.extend(prefixed(&self.description_words, &token));
+            }
+            docs.sort_unstable();
+            docs.dedup();
+            if let Some(previous) = found {
+                docs.retain(|position| previous.binary_search(position).is_ok());
+            }
+            if docs.is_empty() {
+                return docs;
+            }
+            found = Some(docs);
+        }
+        found.unwrap_or_default()
+    }
+
+    /// Everything seen that matches `query` in its title, channel, or description, newest
+    /// first.
+    pub fn search_history(&self, query: &str) -> Vec<usize> {
+        let mut found = self.matches(query, true);
+        found.sort_by(|&a, &b| {
+            self.videos[b]
+                .published_at
+                .cmp(&self.videos[a].published_at)
+        });
+        found
+    }
+}
+
+fn prefixed<'a>(postings: &'a Postings, token: &'a str) -> impl Iterator<Item = usize> + 'a {
+    postings
+        .range::<str, _>((Bound::Included(token), Bound::Unbounded))
+        .take_while(move |(word, _)| word.starts_with(token))
+        .flat_map(|(_, docs)| docs.iter().copied())
+}
+
+fn find_index_path() -> PathBuf {
+    config_dir::get().find_index_path()
+}
+
+pub fn load() -> FindIndex {
+    let mut index: FindIndex = fs::read(find_index_path())
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default();
+    index.restore_positions();
+    index
+}
+
+pub fn save(index: &FindIndex) -> std::io::Result<()> {
+    if !config_dir::get().writable {
+        return Ok(());
+    }
+    let path = find_index_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Not pretty-printed: with descriptions the file runs to megabytes.
+    fs::write(path, serde_json::to_vec(index)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(title: &str, channel: &str) -> VideoDetails {
+        VideoDetails {
+            id: title.into(),
+            title: title.into(),
+            channel_title: channel.into(),
+            ..VideoDetails::default()
+        }
+    }
+
+    #[test]
+    fn matches_the_same_videos_as_the_linear_scan() {
+        let videos = [
+            video("Async Rust in 2026", "fasterthanlime"),
+            video("Bevy 0.15 release", "Bevy Engine"),
+            video("Game jam recap", "Some Dev"),
+            video("Writing a game engine", "Rust Gamedev"),
+            video("Engine for my game", "Bevy Engine"),
+        ];
+        let index = FindIndex::build(&videos);
+        assert_eq!(index.video_count(), 5);
+        for query in [
+            "rust",
+            "game engine",
+            "gam",
+            "bevy engine",
+            "rust python",
+            "FASTER",
+            "lime",
+            "  ",
+            "0.15",
+        ] {
+            let linear: Vec<usize> = (0..videos.len())
+                .filter(|&i| finder::relevance(query, &videos[i]).is_some())
+                .collect();
+            assert_eq!(index.matches(query, false), linear, "query {query:?}");
+        }
+    }
+
+    #[test]
+    fn history_keeps_earlier_runs_and_searches_descriptions() {
+        let mut index = FindIndex::build(&[video("Old run", "Chan")]);
+        let mut described = video("New run", "Chan");
+        described.description = "A deep dive into borrowck".into();
+        described.published_at = "2026-01-02T00:00:00Z".into();
+        assert_eq!(index.add(&[described.clone(), video("Old run", "Chan")]), 1);
+        assert_eq!(index.video_count(), 2);
+
+        assert!(index.matches("borrow", false).is_empty());
+        let found = index.search_history("borrow");
+        assert_eq!(found.len(), 1);
+        assert_eq!(index.video(found[0]).title, "New run");
+
+        let run = index.search_history("run");
+        assert_eq!(index.video(run[0]).title, "New run");
+        assert_eq!(index.video(run[1]).title, "Old run");
+
+        // A copy from the results cache carries no description; the stored one stays.
+        described.description.clear();
+        assert_eq!(index.add(&[described]), 0);
+        assert_eq!(index.search_history("deep").len(), 1);
+    }
+
+    #[test]
+    fn rebuild_drops_words_a_renamed_video_lost() {
+        let mut index = FindIndex::build(&[video("Clip", "Old Name")]);
+        let mut renamed = video("Clip", "New Name");
+        renamed.id = "Clip".into();
+        assert_eq!(index.add(&[renamed]), 1);
+        assert_eq!(index.matches("old", false), [0]);
+        index.rebuild();
+        assert!(index.matches("old", false).is_empty());
+        assert_eq!(index.matches("new", false), [0]);
+        assert_eq!(index.position("Clip"), Some(0));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let index = FindIndex::build(&[video("Async Rust", "Chan")]);
+        let mut loaded: FindIndex =
+            serde_json::from_slice(&serde_json::to_vec(&index).unwrap()).unwrap();
+        assert_eq!(loaded.matches("async", false), [0]);
+        loaded.restore_positions();
+        assert_eq!(loaded.position("Async Rust"), Some(0));
+    }
+}
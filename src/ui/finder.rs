@@ -0,0 +1,114 @@
+use crate::yt::types::VideoDetails;
+
+/// Bonus when the whole query appears in the title.
+const TITLE_PHRASE: u32 = 50;
+/// Bonus when the whole query appears in the channel label.
+const CHANNEL_PHRASE: u32 = 20;
+const TITLE_WORD: u32 = 10;
+const TITLE_PARTIAL: u32 = 5;
+const CHANNEL_WORD: u32 = 4;
+const CHANNEL_PARTIAL: u32 = 2;
+
+/// Relevance of a video for the find box, or `None` when some query word starts no word of
+/// the title or the channel. Whole-word hits outrank prefixes, so a half-typed word still
+/// finds the video while finished words rank it higher.
+pub fn relevance(query: &str, video: &VideoDetails) -> Option<u32> {
+    score(query, &video.title, &channel_text(video))
+}
+
+/// Every name the find box matches a video's channel by.
+pub fn channel_text(video: &VideoDetails) -> String {
+    [
+        video.channel_display_name.as_deref(),
+        Some(video.channel_title.as_str()),
+        video.channel_custom_url.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+pub fn score(query: &str, title: &str, channel: &str) -> Option<u32> {
+    let tokens = words(query);
+    if tokens.is_empty() {
+        return None;
+    }
+    let title_words = words(title);
+    let channel_words = words(channel);
+
+    let mut total = 0;
+    for token in &tokens {
+        let in_title = word_score(token, &title_words, TITLE_WORD, TITLE_PARTIAL);
+        let in_channel = word_score(token, &channel_words, CHANNEL_WORD, CHANNEL_PARTIAL);
+        if in_title + in_channel == 0 {
+            return None;
+        }
+        total += in_title + in_channel;
+    }
+
+    let phrase = tokens.join(" ");
+    if tokens.len() > 1 {
+        if title_words.join(" ").contains(&phrase) {
+            total += TITLE_PHRASE;
+        }
+        if channel_words.join(" ").contains(&phrase) {
+            total += CHANNEL_PHRASE;
+        }
+    }
+    Some(total)
+}
+
+fn word_score(token: &str, haystack: &[String], whole: u32, partial: u32) -> u32 {
+    if haystack.iter().any(|word| word == token) {
+        whole
+    } else if haystack.iter().any(|word| word.starts_with(token)) {
+        partial
+    } else {
+        0
+    }
+}
+
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_word_must_match_somewhere() {
+        assert!(score("rust async", "Async Rust in 2026", "").is_some());
+        assert!(score("rust python", "Async Rust in 2026", "").is_none());
+        assert!(score("rust fasterthanlime", "Async Rust", "fasterthanlime").is_some());
+        assert!(score("   ", "anything", "").is_none());
+    }
+
+    #[test]
+    fn partial_hits_must_start_a_word() {
+        assert!(score("faster", "Async Rust", "fasterthanlime").is_some());
+        assert!(score("lime", "Async Rust", "fasterthanlime").is_none());
+    }
+
+    #[test]
+    fn title_outranks_channel_and_whole_words_outrank_prefixes() {
+        let in_title = score("bevy", "Bevy 0.15 release", "Some Dev").unwrap();
+        let in_channel = score("bevy", "Release notes", "Bevy Engine").unwrap();
+        assert!(in_title > in_channel);
+
+        let whole = score("game", "Game jam recap", "").unwrap();
+        let prefix = score("gam", "Game jam recap", "").unwrap();
+        assert!(whole > prefix);
+    }
+
+    #[test]
+    fn exact_phrase_gets_a_bonus() {
+        let phrase = score("game engine", "Writing a game engine", "").unwrap();
+        let scattered = score("game engine", "Engine for my game", "").unwrap();
+        assert!(phrase > scattered);
+    }
+}
@@ -0,0 +1,98 @@
+/// Points for every query character found in order.
+const MATCHED_CHAR: i32 = 1;
+/// Bonus when a character starts a word, so initials like "gdev" find "Game Development".
+const WORD_START: i32 = 8;
+/// Bonus when a character directly follows the previous match.
+const CONSECUTIVE: i32 = 5;
+/// Bonus when the first character matches the start of the candidate.
+const LEADING: i32 = 10;
+/// Cost of each skipped character between matches.
+const GAP: i32 = 1;
+
+/// Subsequence score of `query` in `candidate`, case-insensitively, or `None` when some
+/// query character cannot be matched in order. Higher is better; an empty query scores 0.
+/// Each query character prefers the next word start when one is ahead, otherwise the next
+/// occurrence.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let chars: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let is_word_start =
+        |i: usize| i == 0 || (!chars[i - 1].is_alphanumeric() && chars[i].is_alphanumeric());
+
+    let mut score = 0i32;
+    let mut next = 0usize;
+    let mut previous: Option<usize> = None;
+    for wanted in query {
+        let mut candidates = (next..chars.len()).filter(|&i| chars[i] == wanted);
+        let first = candidates.next()?;
+        let found = if previous == Some(first.wrapping_sub(1)) || is_word_start(first) {
+            first
+        } else {
+            candidates.find(|&i| is_word_start(i)).unwrap_or(first)
+        };
+        score += MATCHED_CHAR;
+        if is_word_start(found) {
+            score += WORD_START;
+        }
+        match previous {
+            Some(prev) if found == prev + 1 => score += CONSECUTIVE,
+            Some(prev) => score -= GAP * (found - prev - 1) as i32,
+            None if found == 0 => score += LEADING,
+            None => score -= GAP * found as i32,
+        }
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score.max(0) as u32)
+}
+
+/// Indices of `names` matching `query`, best score first and list order on ties. An empty
+/// query keeps every index in order.
+pub fn rank<'a>(query: &str, names: impl IntoIterator<Item = &'a str>) -> Vec<usize> {
+    let mut scored: Vec<(usize, u32)> = names
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, name)| Some((index, fuzzy_score(query, name)?)))
+        .collect();
+    if !query.trim().is_empty() {
+        scored.sort_by_key(|&(index, score)| (std::cmp::Reverse(score), index));
+    }
+    scored.into_iter().map(|(index, _)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_subsequences_and_rejects_out_of_order() {
+        assert!(fuzzy_score("gdev", "Game Development").is_some());
+        assert!(fuzzy_score("GDEV", "game development").is_some());
+        assert!(fuzzy_score("game dev", "Game Development").is_some());
+        assert_eq!(fuzzy_score("veg", "Game Development"), None);
+        assert_eq!(fuzzy_score("x", "Game Development"), None);
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn word_starts_and_runs_outrank_scattered_letters() {
+        let initials = fuzzy_score("gd", "Game Development").unwrap();
+        let scattered = fuzzy_score("gd", "big wooden").unwrap();
+        assert!(initials > scattered);
+
+        let prefix = fuzzy_score("rust", "Rust tutorials").unwrap();
+        let spread = fuzzy_score("rust", "Random user stories today").unwrap();
+        assert!(prefix > spread);
+    }
+
+    #[test]
+    fn rank_sorts_by_score_and_keeps_order_for_empty_queries() {
+        let names = ["Big wooden desk", "News", "Game Development"];
+        assert_eq!(rank("gd", names), vec![2, 0]);
+        assert_eq!(rank("  ", names), vec![0, 1, 2]);
+    }
+}
@@ -1,18 +1,30 @@
 mod app_state;
 mod duration_filters;
+mod find_index;
+mod finder;
+mod fuzzy;
+mod notify;
 mod panels;
+mod pending_ops;
 mod preset_editor;
+mod settings;
+mod share;
+mod shortcuts;
 mod theme;
 mod thumbnails;
+mod ui_state;
 mod utils;
 
 pub use app_state::AppState;
 use app_state::SearchResult;
-pub use duration_filters::DurationFilterState;
-pub use preset_editor::{PresetEditorMode, PresetEditorState};
+use pending_ops::PendingOp;
 
+use crate::debug_report;
 use crate::filters;
-use crate::prefs;
+use crate::prefs::TimeWindowPreset;
+use crate::preset_stats;
+use crate::search_runner;
+use crate::yt::http;
 use egui::Context;
 use std::sync::mpsc::TryRecvError;
 use time::OffsetDateTime;
@@ -23,14 +35,20 @@ impl eframe::App for AppState {
             "YTSearch v{}",
             env!("CARGO_PKG_VERSION")
         )));
+        if let Some(rx) = self.search_progress_rx.as_ref()
+            && let Some(event) = rx.try_iter().last()
+        {
+            self.search_stage = Some(event.to_string());
+        }
         // Handle incoming search results
         let incoming = if let Some(rx) = self.search_rx.as_mut() {
             match rx.try_recv() {
                 Ok(msg) => Some(msg),
                 Err(TryRecvError::Empty) => None,
-                Err(TryRecvError::Disconnected) => {
-                    Some(SearchResult::Error("Search cancelled.".into()))
-                }
+                Err(TryRecvError::Disconnected) => Some(SearchResult::Error {
+                    message: "Search cancelled.".into(),
+                    details: None,
+                }),
             }
         } else {
             None
@@ -39,6 +57,7 @@ impl eframe::App for AppState {
         if let Some(message) = incoming {
             match message {
                 SearchResult::Success(outcome) => {
+                    self.last_run_stats = Some(debug_report::RunStats::from_outcome(&outcome));
                     let skipped_duplicates =
                         outcome.duplicates_within_presets + outcome.duplicates_across_presets;
                     let presets = outcome.presets_ran;
@@ -46,49 +65,143 @@ impl eframe::App for AppState {
                     let raw = outcome.raw_items;
                     let unique = outcome.unique_ids;
                     let passed = outcome.passed_filters;
-                    let blocked_keys = prefs::blocked_keys(&self.prefs.blocked_channels);
-                    self.results_all = outcome
+                    let quota_units = outcome.quota_units;
+                    let dropped_title_mismatch = outcome.dropped_title_mismatch;
+                    let dropped_missing_terms = outcome.dropped_missing_terms;
+                    let mut dropped_over_cap = outcome.dropped_over_cap;
+                    let clock_warning = outcome.clock_skew_secs.and_then(http::clock_skew_warning);
+                    let missing_handles = self.apply_run_handle_lookups(outcome.resolved_handles);
+                    self.preset_overlaps = outcome.preset_overlaps;
+                    self.raw_pages = outcome.raw_pages;
+                    let region = outcome.region_code.as_deref().unwrap_or("none").to_owned();
+                    let blocked = self.blocked.set();
+                    let mut fresh: Vec<_> = outcome
                         .videos
                         .into_iter()
-                        .filter(|v| {
-                            !filters::matches_channel(
-                                &v.channel_handle,
-                                &v.channel_title,
-                                &blocked_keys,
-                            )
-                        })
+                        .filter(|v| !filters::is_blocked(v, blocked))
                         .collect();
+                    let now = OffsetDateTime::now_utc().unix_timestamp();
+                    self.channel_cache.record_videos(&fresh, now);
+                    self.save_channel_cache();
+                    self.preset_stats.record(&outcome.preset_runs, now);
+                    if let Err(err) = preset_stats::save(&self.preset_stats) {
+                        eprintln!("Failed to save preset stats: {err}");
+                    }
+                    if !self.results_all.is_empty() {
+                        self.previous_results = Some(self.results_all.clone());
+                    }
+                    let since_last_run =
+                        self.prefs.search_defaults.default_window == TimeWindowPreset::SinceLastRun;
+                    if since_last_run || (self.prefs.ui.dedup_across_presets && !self.run_any_mode)
+                    {
+                        // Fold single-preset runs into what is already loaded so a video
+                        // tagged by several presets stays one card across mode switches.
+                        // "Since last search" runs add to the loaded list the same way, so
+                        // videos in the overlap before the last search are not doubled.
+                        search_runner::merge_with_policy(
+                            self.prefs.search_defaults.duplicate_policy,
+                            &mut self.results_all,
+                            fresh,
+                        );
+                        dropped_over_cap += search_runner::cap_newest(
+                            &mut self.results_all,
+                            self.prefs.search_defaults.max_total_results,
+                        );
+                    } else {
+                        if !self.run_any_mode {
+                            // Keep the other presets already known to match each video, so
+                            // Single mode still shows a video's full match set as chips.
+                            search_runner::carry_source_presets(&mut fresh, &self.results_all);
+                        }
+                        self.results_all = fresh;
+                    }
                     self.sync_thumbnail_cache();
+                    self.reindex_results();
+                    self.refresh_run_diff();
                     self.refresh_visible_results();
+                    self.scroll_results_to_top();
+                    self.resolve_blocked_labels();
                     let kept = self.results.len();
                     self.status = format!(
-                        "Ran {presets} preset(s) across {pages} page(s); raw {raw}, unique {unique}, passed {passed}, kept {kept} (skipped {skipped_duplicates} duplicates)."
+                        "Ran {presets} preset(s) across {pages} page(s) in region {region}; raw {raw}, unique {unique}, passed {passed}, kept {kept} (skipped {skipped_duplicates} duplicates)."
                     );
+                    if !missing_handles.is_empty() {
+                        self.status.push_str(&format!(
+                            " No channel found for {}; matching by name instead.",
+                            missing_handles.join(", ")
+                        ));
+                    }
+                    if dropped_title_mismatch > 0 {
+                        self.status.push_str(&format!(
+                            " Dropped {dropped_title_mismatch} without the terms in the title."
+                        ));
+                    }
+                    if dropped_missing_terms > 0 {
+                        self.status.push_str(&format!(
+                            " Dropped {dropped_missing_terms} missing a required (all) term."
+                        ));
+                    }
+                    if dropped_over_cap > 0 {
+                        let cap = self.prefs.search_defaults.max_total_results;
+                        self.status.push_str(&format!(
+                            " ⚠ Kept the newest {cap}; dropped {dropped_over_cap} older result(s) over the cap."
+                        ));
+                    }
+                    if let Some(warning) = clock_warning {
+                        self.status.push_str(&format!(" ⚠ {warning}"));
+                    }
                     self.is_searching = false;
                     self.cached_banner_until = None;
+                    self.results_are_cached = false;
                     self.persist_cached_results();
+                    self.record_quota_usage(quota_units);
+                    notify::search_completed(ctx, self.prefs.ui.notify_on_completion);
                 }
-                SearchResult::Error(err) => {
-                    self.status = format!("Search failed: {err}");
+                SearchResult::Error { message, details } => {
+                    self.record_error(format!("Search failed: {message}"), details);
                     self.is_searching = false;
                     self.cached_banner_until = None;
+                    notify::search_completed(ctx, self.prefs.ui.notify_on_completion);
                 }
             }
             self.search_rx = None;
+            self.search_progress_rx = None;
+            self.search_stage = None;
             self.pending_task = None;
+            self.pending_ops.finish(PendingOp::Search);
         }
 
-        if let Some(until) = self.cached_banner_until {
-            if OffsetDateTime::now_utc() >= until {
-                self.cached_banner_until = None;
-                if self.status.starts_with("Cached ") {
-                    self.status = "Ready.".into();
-                }
+        if let Some(until) = self.cached_banner_until
+            && OffsetDateTime::now_utc() >= until
+        {
+            self.cached_banner_until = None;
+            if self.status.starts_with("Cached ") {
+                self.status = "Ready.".into();
             }
         }
 
+        self.sync_system_theme(ctx);
+        self.flush_scheduled_prefs_save();
+        self.flush_annotations_save(false);
+        if self.prefs_save_due.is_some() || self.annotations_save_due.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
+
         self.thumbnail_cache.update(ctx);
 
+        self.update_channel_lookups();
+        self.poll_preset_test();
+        self.poll_diagnostics();
+        self.advance_open_batch();
+        if self.channel_lookups.any_pending()
+            || self.channel_lookups.refreshing()
+            || self.open_batch.is_some()
+            || self.preset_test_rx.is_some()
+            || self.preset_estimate_rx.is_some()
+        {
+            ctx.request_repaint_after(std::time::Duration::from_millis(250));
+        }
+
         // Validate selected search
         if let Some(selected) = self.selected_search_id.clone() {
             if !self.prefs.searches.iter().any(|s| s.id == selected) {
@@ -98,16 +211,33 @@ impl eframe::App for AppState {
             self.selected_search_id = Some(first.id.clone());
         }
 
+        self.handle_shortcuts(ctx);
+
         // Render panels
         let search_requested = self.render_top_panel(ctx);
+        self.render_footer(ctx);
         self.render_left_panel(ctx);
         self.render_central_panel(ctx);
         self.render_editor_window(ctx);
         self.render_import_export_windows(ctx);
+        self.render_settings_window(ctx);
+        self.render_quota_prompt(ctx);
+        self.render_raw_responses_window(ctx);
+        self.render_history_window(ctx);
         self.render_help_window(ctx);
+        self.render_diagnostics_window(ctx);
+        self.render_preset_gallery(ctx);
+        self.render_welcome_window(ctx);
 
         if search_requested {
             self.launch_search();
         }
+        self.record_status_history();
+    }
+
+    // eframe's `save` hook needs its `persistence` feature and only hands over its own
+    // key-value storage, so everything is written to our files on exit instead.
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.shutdown();
     }
 }
@@ -0,0 +1,50 @@
+use egui::Context;
+
+use crate::prefs::CompletionNotice;
+
+/// Let the user know a search finished, but only when the window is not focused.
+pub fn search_completed(ctx: &Context, notice: CompletionNotice) {
+    if notice == CompletionNotice::None {
+        return;
+    }
+    let focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+    if focused {
+        return;
+    }
+    if notice.flashes() {
+        ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+            egui::UserAttentionType::Informational,
+        ));
+    }
+    if notice.plays_sound() {
+        play_chime();
+    }
+}
+
+/// Two short synthesized tones on a background thread; any audio error is ignored.
+#[cfg(feature = "sound")]
+fn play_chime() {
+    use rodio::Source;
+    use rodio::source::SineWave;
+    use std::time::Duration;
+
+    std::thread::spawn(|| {
+        let Ok((_stream, handle)) = rodio::OutputStream::try_default() else {
+            return;
+        };
+        let Ok(sink) = rodio::Sink::try_new(&handle) else {
+            return;
+        };
+        for freq in [660.0, 880.0] {
+            sink.append(
+                SineWave::new(freq)
+                    .take_duration(Duration::from_millis(140))
+                    .amplify(0.2),
+            );
+        }
+        sink.sleep_until_end();
+    });
+}
+
+#[cfg(not(feature = "sound"))]
+fn play_chime() {}
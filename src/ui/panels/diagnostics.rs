@@ -0,0 +1,75 @@
+use egui::{Color32, Context, RichText};
+
+use crate::diagnostics::{CheckFix, CheckStatus};
+use crate::ui::theme::{ACCENT_SAVE, ACCENT_SINGLE};
+
+use super::AppState;
+
+fn status_badge(status: CheckStatus) -> RichText {
+    match status {
+        CheckStatus::Pass => RichText::new("✔ pass").color(ACCENT_SAVE),
+        CheckStatus::Warn => RichText::new("⚠ warn").color(ACCENT_SINGLE),
+        CheckStatus::Fail => RichText::new("✖ fail").color(Color32::from_rgb(239, 68, 68)),
+    }
+    .strong()
+}
+
+/// One row per check, filled in as results arrive.
+pub(super) fn render(state: &mut AppState, ctx: &Context) {
+    let Some(diagnostics) = state.diagnostics.as_ref() else {
+        return;
+    };
+    let mut open = true;
+    let mut fix: Option<CheckFix> = None;
+    let mut rerun = false;
+    egui::Window::new("Diagnostics")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(520.0)
+        .show(ctx, |ui| {
+            egui::Grid::new("diagnostics_rows")
+                .num_columns(4)
+                .spacing([12.0, 6.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for result in &diagnostics.results {
+                        ui.label(status_badge(result.status));
+                        ui.label(RichText::new(result.name).strong());
+                        ui.add(egui::Label::new(&result.details).wrap());
+                        match result.fix {
+                            Some(action)
+                                if result.status != CheckStatus::Pass
+                                    && ui.small_button(action.label()).clicked() =>
+                            {
+                                fix = Some(action);
+                            }
+                            _ => {
+                                ui.label("");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if diagnostics.running() {
+                    ui.spinner();
+                    ui.label("Running checks…");
+                } else if ui.button("Run again").clicked() {
+                    rerun = true;
+                }
+            });
+        });
+    if diagnostics.running() {
+        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+    }
+
+    if let Some(action) = fix {
+        state.apply_diagnostic_fix(action);
+    } else if rerun {
+        state.open_diagnostics();
+    }
+    if !open {
+        state.close_diagnostics();
+    }
+}
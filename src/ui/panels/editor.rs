@@ -1,8 +1,14 @@
-use egui::{Align, Color32, Context, Layout, RichText};
+use egui::{Align, Color32, Context, Layout, RichText, Sense};
 
-use crate::prefs::MySearch;
-use crate::ui::preset_editor::PresetEditorMode;
-use crate::ui::theme::ACCENT_SAVE;
+use crate::prefs::{self, BroadcastEvent, ChannelRef, MySearch};
+use crate::search_runner;
+use crate::ui::app_state::{ChannelLookup, ChannelLookups};
+use crate::ui::preset_editor::{
+    PresetEditorMode, PresetEditorState, PresetEstimateState, PresetTestState,
+};
+use crate::ui::shortcuts::Shortcut;
+use crate::ui::theme::{ACCENT_SAVE, ACCENT_SINGLE};
+use crate::ui::utils::broadcast_event_label;
 
 use super::AppState;
 use super::helpers::render_token_editor;
@@ -10,6 +16,9 @@ use super::helpers::render_token_editor;
 pub(super) fn render(state: &mut AppState, ctx: &Context) {
     let mut wants_save = false;
     let mut wants_cancel = false;
+    let mut wants_test = false;
+    let mut wants_estimate = false;
+    let now = time::OffsetDateTime::now_utc();
 
     let pasted_text = ctx.input(|i| {
         i.events.iter().rev().find_map(|event| match event {
@@ -25,18 +34,21 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
     let mut confirm_replace = false;
     let mut cancel_replace = false;
 
+    let lookups = &state.channel_lookups;
+    let global = &state.prefs.search_defaults;
+    let has_api_key = !state.prefs.api_key.trim().is_empty();
     if let Some(editor) = state.preset_editor.as_mut() {
-        if editor.awaiting_clipboard {
-            if let Some(text) = pasted_text.clone() {
-                editor.awaiting_clipboard = false;
-                pending_clipboard_text = Some(text);
-            }
+        if editor.awaiting_clipboard
+            && let Some(text) = pasted_text.clone()
+        {
+            editor.awaiting_clipboard = false;
+            pending_clipboard_text = Some(text);
         }
 
         let title = match editor.mode {
             PresetEditorMode::New => "New preset",
             PresetEditorMode::Edit { .. } => "Edit preset",
-            PresetEditorMode::Duplicate { .. } => "Duplicate preset",
+            PresetEditorMode::Duplicate => "Duplicate preset",
         };
 
         let mut open = true;
@@ -52,11 +64,25 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                     .show(ui, |ui| {
                         ui.checkbox(&mut editor.enabled, "Enabled");
                         ui.label("Name");
-                        ui.text_edit_singleline(&mut editor.name);
+                        let name_response = ui.text_edit_singleline(&mut editor.name);
+                        if editor.focus_name {
+                            name_response.request_focus();
+                            editor.focus_name = false;
+                        }
+                        ui.label("Description");
+                        ui.add(
+                            egui::TextEdit::multiline(&mut editor.description)
+                                .hint_text("What this preset is for (shown when hovering it in the sidebar)")
+                                .desired_rows(2)
+                                .desired_width(f32::INFINITY),
+                        );
 
                         ui.add_space(6.0);
                         ui.horizontal(|ui| {
-                            if ui.button("📋 Copy preset").clicked() {
+                            if ui
+                                .add(egui::Button::new("📋 Copy preset").sense(Sense::CLICK))
+                                .clicked()
+                            {
                                 let snapshot = editor.snapshot();
                                 match serde_json::to_string_pretty(&snapshot) {
                                     Ok(json) => copy_payload = Some(json),
@@ -66,7 +92,10 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                                     }
                                 }
                             }
-                            if ui.button("📥 Paste preset").clicked() {
+                            if ui
+                                .add(egui::Button::new("📥 Paste preset").sense(Sense::CLICK))
+                                .clicked()
+                            {
                                 editor.awaiting_clipboard = true;
                                 editor.error = None;
                                 editor.pending_clipboard = None;
@@ -99,6 +128,25 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                             &mut editor.new_all_term,
                             "Add required term",
                         );
+                        ui.checkbox(
+                            &mut editor.require_terms_in_title,
+                            "Require terms in title",
+                        )
+                        .on_hover_text(
+                            "Drop results whose title lacks one of the any terms or any of the all terms; \
+                             YouTube otherwise matches descriptions and tags too",
+                        );
+                        ui.checkbox(&mut editor.match_tags, "Match terms in tags")
+                            .on_hover_text(
+                                "Let any, all, and not terms also match the video's tags \
+                                 when filtering results",
+                            );
+                        ui.checkbox(&mut editor.split_long_query, "Split long queries")
+                            .on_hover_text(format!(
+                                "When the query is over ~{} characters, search the any terms \
+                                 in several parts and merge the results (one search per part)",
+                                search_runner::QUERY_LENGTH_LIMIT
+                            ));
 
                         ui.add_space(6.0);
                         render_token_editor(
@@ -109,6 +157,36 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                             "Add excluded term",
                         );
 
+                        ui.add_space(6.0);
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    editor.can_estimate(now),
+                                    egui::Button::new("Estimate").sense(Sense::CLICK),
+                                )
+                                .on_hover_text(
+                                    "Fetch one page of results to see how broad this query is",
+                                )
+                                .clicked()
+                            {
+                                wants_estimate = true;
+                            }
+                            match editor.estimate_state.as_ref() {
+                                Some(PresetEstimateState::Running) => {
+                                    ui.spinner();
+                                }
+                                Some(PresetEstimateState::Done { raw, passed }) => {
+                                    ui.label(format!(
+                                        "First page: {raw} raw, {passed} after filters"
+                                    ));
+                                }
+                                Some(PresetEstimateState::Failed(err)) => {
+                                    ui.colored_label(Color32::from_rgb(239, 68, 68), err);
+                                }
+                                None => {}
+                            }
+                        });
+
                         ui.add_space(6.0);
                         render_token_editor(
                             ui,
@@ -117,6 +195,43 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                             &mut editor.new_allow_entry,
                             "Add allowed channel",
                         );
+                        render_channel_lookup_notes(
+                            ui,
+                            &editor.channel_allow,
+                            lookups,
+                            has_api_key,
+                        );
+                        if editor.channel_allow.len() > 1 || editor.per_channel_search {
+                            let calls = editor.channel_allow.len();
+                            ui.checkbox(
+                                &mut editor.per_channel_search,
+                                "Search each allowed channel separately",
+                            )
+                            .on_hover_text(format!(
+                                "Runs one channel-scoped search per channel with a known ID \
+                                 (about {calls} searches, each costing quota) instead of \
+                                 filtering one general search. @handles without an ID fall \
+                                 back to filtering."
+                            ));
+                        }
+                        if !editor.channel_allow.is_empty() || editor.uploads_mode {
+                            ui.checkbox(&mut editor.uploads_mode, "Read uploads playlists (cheap)")
+                                .on_hover_text(
+                                    "With no terms or category, list each allowed channel's new \
+                                     uploads at 1 quota unit per page instead of 100 per search. \
+                                     Region, language, and other search-side options do not apply.",
+                                );
+                            if editor.uploads_mode
+                                && !search_runner::uses_uploads_playlist(&editor.snapshot())
+                            {
+                                ui.small(
+                                    RichText::new(
+                                        "Has terms or a category, so runs use search.list.",
+                                    )
+                                    .weak(),
+                                );
+                            }
+                        }
 
                         ui.add_space(6.0);
                         render_token_editor(
@@ -126,6 +241,7 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                             &mut editor.new_deny_entry,
                             "Add blocked channel",
                         );
+                        render_channel_lookup_notes(ui, &editor.channel_deny, lookups, has_api_key);
 
                         ui.separator();
                         if ui
@@ -134,11 +250,10 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                                 "Override time window (RFC3339)",
                             )
                             .clicked()
+                            && !editor.window_override_enabled
                         {
-                            if !editor.window_override_enabled {
-                                editor.window_start.clear();
-                                editor.window_end.clear();
-                            }
+                            editor.window_start.clear();
+                            editor.window_end.clear();
                         }
                         if editor.window_override_enabled {
                             ui.label("Start");
@@ -199,11 +314,34 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                             });
                         });
 
+                        ui.horizontal(|ui| {
+                            ui.checkbox(
+                                &mut editor.event_type_override_enabled,
+                                "Override broadcasts",
+                            );
+                            ui.add_enabled_ui(editor.event_type_override_enabled, |ui| {
+                                for event in [
+                                    BroadcastEvent::Live,
+                                    BroadcastEvent::Upcoming,
+                                    BroadcastEvent::Completed,
+                                ] {
+                                    ui.selectable_value(
+                                        &mut editor.event_type_override_value,
+                                        event,
+                                        broadcast_event_label(Some(event)),
+                                    );
+                                }
+                            });
+                        });
+
                         ui.add_space(6.0);
                         ui.horizontal(|ui| {
-                            ui.label("Priority (Any mode sort, higher first)");
+                            ui.label("Priority (Sort → Priority, higher first)");
                             ui.add(egui::DragValue::new(&mut editor.priority).speed(1));
                         });
+
+                        ui.add_space(6.0);
+                        render_raw_json(ui, editor);
                     });
 
                 if let Some(err) = editor.error.as_ref() {
@@ -211,6 +349,39 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                     ui.colored_label(Color32::from_rgb(239, 68, 68), err);
                 }
 
+                let conflicts = prefs::check_preset_conflicts(global, &editor.snapshot());
+                if !conflicts.is_empty() {
+                    ui.add_space(6.0);
+                    for conflict in &conflicts {
+                        ui.colored_label(ACCENT_SINGLE, format!("⚠ {}", conflict.message()));
+                    }
+                }
+
+                match editor.test_state.as_ref() {
+                    Some(PresetTestState::Running) => {
+                        ui.add_space(6.0);
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Testing preset…");
+                        });
+                    }
+                    Some(PresetTestState::Done { kept, titles }) => {
+                        ui.add_space(6.0);
+                        ui.label(format!("Test run kept {kept} video(s)."));
+                        for title in titles {
+                            ui.small(format!("• {title}"));
+                        }
+                    }
+                    Some(PresetTestState::Failed(err)) => {
+                        ui.add_space(6.0);
+                        ui.colored_label(
+                            Color32::from_rgb(239, 68, 68),
+                            format!("Test failed: {err}"),
+                        );
+                    }
+                    None => {}
+                }
+
                 ui.add_space(10.0);
                 ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                     if ui
@@ -218,19 +389,42 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                             egui::Button::new(RichText::new("Save preset").color(Color32::WHITE))
                                 .fill(ACCENT_SAVE),
                         )
+                        .on_hover_text("Ctrl+Enter")
                         .clicked()
                     {
                         wants_save = true;
                     }
-                    if ui.button("Cancel").clicked() {
+                    if ui.button("Cancel").on_hover_text("Esc").clicked() {
                         wants_cancel = true;
                     }
+                    let testing = matches!(editor.test_state, Some(PresetTestState::Running));
+                    if ui
+                        .add_enabled(!testing, egui::Button::new("Test"))
+                        .on_hover_text("Run this preset once without saving it")
+                        .clicked()
+                    {
+                        wants_test = true;
+                    }
                 });
             });
 
         if !open {
             wants_cancel = true;
         }
+
+        // Shortcuts are read after the window so Enter still commits a half-typed token.
+        if !editor.show_dirty_warning && !editor.confirm_discard {
+            if ctx.input_mut(|i| Shortcut::SavePreset.consume(i)) {
+                wants_save = true;
+            }
+            if ctx.input_mut(|i| Shortcut::CloseEditor.consume(i)) {
+                wants_cancel = true;
+            }
+        }
+        if wants_cancel && !wants_save && editor.is_dirty() {
+            editor.confirm_discard = true;
+            wants_cancel = false;
+        }
     }
 
     if let Some(text) = pending_clipboard_text {
@@ -254,50 +448,42 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
         }
     }
 
-    if let Some(editor) = state.preset_editor.as_mut() {
-        if editor.show_dirty_warning {
-            let mut open_confirm = true;
-            egui::Window::new("Unsaved changes")
-                .open(&mut open_confirm)
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::new(0.0, -40.0))
-                .show(ctx, |ui| {
-                    ui.label("You have unsaved edits. Replace them with the clipboard preset?");
-                    ui.add_space(10.0);
-                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                        if ui
-                            .add(
-                                egui::Button::new(RichText::new("Replace").color(Color32::WHITE))
-                                    .fill(ACCENT_SAVE),
-                            )
-                            .clicked()
-                        {
-                            confirm_replace = true;
-                        }
-                        if ui.button("Keep current").clicked() {
-                            cancel_replace = true;
-                        }
-                    });
-                });
-            if !open_confirm {
-                cancel_replace = true;
-            }
+    if let Some(editor) = state.preset_editor.as_mut()
+        && editor.show_dirty_warning
+    {
+        match unsaved_changes_prompt(
+            ctx,
+            "You have unsaved edits. Replace them with the clipboard preset?",
+            "Replace",
+            "Keep current",
+        ) {
+            Some(true) => confirm_replace = true,
+            Some(false) => cancel_replace = true,
+            None => {}
         }
     }
 
-    if confirm_replace {
-        if let Some(editor) = state.preset_editor.as_mut() {
-            if let Some(preset) = editor.pending_clipboard.take() {
-                editor.show_dirty_warning = false;
-                apply_from_clipboard = Some(preset);
-            }
+    let mut discard = false;
+    if let Some(editor) = state.preset_editor.as_mut()
+        && editor.confirm_discard
+    {
+        match unsaved_changes_prompt(ctx, "Discard changes?", "Discard", "Keep editing") {
+            Some(true) => discard = true,
+            Some(false) => editor.confirm_discard = false,
+            None => {}
         }
-    } else if cancel_replace {
-        if let Some(editor) = state.preset_editor.as_mut() {
-            editor.pending_clipboard = None;
+    }
+
+    if confirm_replace {
+        if let Some(editor) = state.preset_editor.as_mut()
+            && let Some(preset) = editor.pending_clipboard.take()
+        {
             editor.show_dirty_warning = false;
+            apply_from_clipboard = Some(preset);
         }
+    } else if cancel_replace && let Some(editor) = state.preset_editor.as_mut() {
+        editor.pending_clipboard = None;
+        editor.show_dirty_warning = false;
     }
 
     if let Some(json) = copy_payload {
@@ -312,9 +498,123 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
         state.status = "Preset loaded from clipboard.".into();
     }
 
+    if wants_test {
+        state.test_editor_preset();
+    }
+    if wants_estimate {
+        state.estimate_editor_preset();
+    }
+
     if wants_save {
         state.try_save_editor();
-    } else if wants_cancel {
+    } else if wants_cancel || discard {
         state.cancel_editor();
     }
 }
+
+/// The "Unsaved changes" confirmation: `Some(true)` to go ahead, `Some(false)` to keep the
+/// current edits (also on Esc or closing the window), `None` while undecided.
+fn unsaved_changes_prompt(
+    ctx: &Context,
+    message: &str,
+    confirm_label: &str,
+    keep_label: &str,
+) -> Option<bool> {
+    let mut decision = None;
+    let mut open_confirm = true;
+    egui::Window::new("Unsaved changes")
+        .open(&mut open_confirm)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::new(0.0, -40.0))
+        .show(ctx, |ui| {
+            ui.label(message);
+            ui.add_space(10.0);
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ui
+                    .add(
+                        egui::Button::new(RichText::new(confirm_label).color(Color32::WHITE))
+                            .fill(ACCENT_SAVE),
+                    )
+                    .clicked()
+                {
+                    decision = Some(true);
+                }
+                if ui.button(keep_label).clicked() {
+                    decision = Some(false);
+                }
+            });
+        });
+    if !open_confirm || ctx.input_mut(|i| Shortcut::CloseEditor.consume(i)) {
+        decision = Some(false);
+    }
+    decision
+}
+
+/// Show which `@handle` entries are still resolving or could not be resolved to a channel ID.
+fn render_channel_lookup_notes(
+    ui: &mut egui::Ui,
+    entries: &[String],
+    lookups: &ChannelLookups,
+    has_api_key: bool,
+) {
+    for entry in entries {
+        let Some(key) = ChannelRef::parse(entry).unresolved_handle() else {
+            continue;
+        };
+        match lookups.get(&key) {
+            Some(ChannelLookup::Pending) => {
+                ui.small(format!("Resolving {entry}…"));
+            }
+            Some(ChannelLookup::Failed(reason)) => {
+                ui.colored_label(
+                    Color32::from_rgb(239, 68, 68),
+                    format!("Could not resolve {entry}: {reason}"),
+                );
+            }
+            Some(ChannelLookup::Resolved(_)) => {}
+            None if !has_api_key => {
+                ui.small(format!(
+                    "{entry} matches by name only until an API key is set."
+                ));
+            }
+            None => {}
+        }
+    }
+}
+
+/// Collapsible JSON view of the preset. Edits only reach the fields when Apply is clicked.
+fn render_raw_json(ui: &mut egui::Ui, editor: &mut PresetEditorState) {
+    egui::CollapsingHeader::new("Raw JSON")
+        .id_salt("preset_raw_json")
+        .show(ui, |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut editor.raw_json)
+                    .code_editor()
+                    .desired_rows(12)
+                    .desired_width(f32::INFINITY),
+            );
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Apply JSON")
+                    .on_hover_text("Replace the fields above with this JSON")
+                    .clicked()
+                {
+                    match editor.apply_raw_json() {
+                        Ok(()) => editor.raw_json_error = None,
+                        Err(err) => editor.raw_json_error = Some(err.to_string()),
+                    }
+                }
+                if ui
+                    .button("Reload from fields")
+                    .on_hover_text("Discard JSON edits and show the current fields")
+                    .clicked()
+                {
+                    editor.reload_raw_json();
+                }
+            });
+            if let Some(err) = editor.raw_json_error.as_ref() {
+                ui.colored_label(Color32::from_rgb(239, 68, 68), err);
+            }
+        });
+}
@@ -0,0 +1,111 @@
+use egui::{Context, RichText, Sense};
+use time::OffsetDateTime;
+
+use crate::ui::settings::SettingsTab;
+use crate::ui::theme::palette;
+use crate::ui::utils::humanize_elapsed;
+
+use super::AppState;
+
+/// Where clicking a footer segment takes you.
+#[derive(Clone, Copy)]
+enum Jump {
+    ResultsTop,
+    BlockList,
+    QuotaSettings,
+}
+
+pub(super) fn render(state: &mut AppState, ctx: &Context) {
+    let downloaded = state.results_all.len();
+    let visible = state.visible_result_count();
+    let blocked = state.prefs.blocked_channels.len();
+    let enabled = state.enabled_preset_count();
+    let total = state.prefs.searches.len();
+    let quota_used = state.quota_used_today();
+    let quota_budget = state.prefs.ui.daily_quota_budget;
+    let last_search = match state.last_search_unix {
+        Some(unix) => {
+            let elapsed = OffsetDateTime::now_utc().unix_timestamp() - unix;
+            match humanize_elapsed(elapsed) {
+                age if age == "now" => "just now".to_owned(),
+                age => format!("{age} ago"),
+            }
+        }
+        None => "never".to_owned(),
+    };
+
+    let mut jump: Option<Jump> = None;
+    egui::TopBottomPanel::bottom("footer")
+        .resizable(false)
+        .frame(
+            egui::Frame::default()
+                .fill(palette(ctx).panel_fill)
+                .inner_margin(egui::Margin::symmetric(14, 4)),
+        )
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let stats = [
+                    (
+                        format!("Downloaded {downloaded}"),
+                        "Videos loaded from the last search or the cache; click to scroll to the top",
+                        Some(Jump::ResultsTop),
+                    ),
+                    (
+                        format!("Visible {visible}"),
+                        "Videos left after presets, Length chips, and dismissals; click to scroll to the top",
+                        Some(Jump::ResultsTop),
+                    ),
+                    (
+                        format!("Blocked channels {blocked}"),
+                        "Click to show the block list in the sidebar",
+                        Some(Jump::BlockList),
+                    ),
+                    (
+                        format!("Presets enabled {enabled}/{total}"),
+                        "Presets Any mode runs",
+                        None,
+                    ),
+                    (
+                        if quota_budget == 0 {
+                            format!("Quota today {quota_used}")
+                        } else {
+                            format!("Quota today {quota_used}/{quota_budget}")
+                        },
+                        "API units spent today (Pacific time); click to change the daily budget",
+                        Some(Jump::QuotaSettings),
+                    ),
+                    (
+                        format!("Last search {last_search}"),
+                        "When the last successful search finished",
+                        None,
+                    ),
+                ];
+                for (idx, (stat, hover, target)) in stats.into_iter().enumerate() {
+                    if idx > 0 {
+                        ui.separator();
+                    }
+                    let label = egui::Label::new(RichText::new(stat).small());
+                    let response = match target {
+                        Some(_) => ui.add(label.sense(Sense::click())),
+                        None => ui.add(label),
+                    }
+                    .on_hover_text(hover);
+                    if response.clicked() {
+                        jump = target;
+                    }
+                }
+            });
+        });
+
+    match jump {
+        Some(Jump::ResultsTop) => state.scroll_results_to_top(),
+        Some(Jump::BlockList) => state.scroll_to_block_list = true,
+        Some(Jump::QuotaSettings) => {
+            state.open_settings();
+            if let Some(settings) = state.settings_dialog.as_mut() {
+                settings.tab = SettingsTab::Network;
+            }
+        }
+        None => {}
+    }
+}
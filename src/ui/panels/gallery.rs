@@ -0,0 +1,69 @@
+use egui::{Context, RichText};
+
+use crate::preset_packs::{PACKS, PresetPack};
+
+use super::AppState;
+
+/// Bundled preset packs with their presets; "Add pack" appends the ticked ones.
+pub(super) fn render(state: &mut AppState, ctx: &Context) {
+    if state.preset_gallery.is_none() {
+        return;
+    }
+    let mut open = true;
+    let mut add: Option<&'static PresetPack> = None;
+    egui::Window::new("Preset gallery")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            ui.label("Curated presets to start from. Added presets are grouped under the pack name in the sidebar.");
+            ui.add_space(6.0);
+            egui::ScrollArea::vertical()
+                .max_height(420.0)
+                .show(ui, |ui| {
+                    for pack in PACKS {
+                        let presets = pack.presets().unwrap_or_default();
+                        egui::CollapsingHeader::new(RichText::new(pack.name).strong())
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                ui.small(pack.description);
+                                for preset in &presets {
+                                    let present = state.has_preset(&preset.id);
+                                    let Some(gallery) = state.preset_gallery.as_mut() else {
+                                        return;
+                                    };
+                                    let mut ticked =
+                                        !present && !gallery.unticked.contains(&preset.id);
+                                    let response = ui
+                                        .add_enabled(
+                                            !present,
+                                            egui::Checkbox::new(&mut ticked, &preset.name),
+                                        )
+                                        .on_disabled_hover_text("Already in your presets");
+                                    if response.changed() {
+                                        if ticked {
+                                            gallery.unticked.remove(&preset.id);
+                                        } else {
+                                            gallery.unticked.insert(preset.id.clone());
+                                        }
+                                    }
+                                    if let Some(text) = preset.description.as_deref() {
+                                        ui.indent(&preset.id, |ui| {
+                                            ui.small(text);
+                                        });
+                                    }
+                                }
+                                if ui.button("Add pack").clicked() {
+                                    add = Some(pack);
+                                }
+                            });
+                    }
+                });
+        });
+    if let Some(pack) = add {
+        state.add_preset_pack(pack);
+    }
+    if !open {
+        state.close_preset_gallery();
+    }
+}
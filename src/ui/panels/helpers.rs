@@ -1,6 +1,7 @@
-use egui::{Frame, Key, Margin, RichText, Stroke, TextEdit};
+use egui::{Frame, Margin, RichText, Sense, Stroke, TextEdit};
 
 use crate::ui::preset_editor::PresetEditorState;
+use crate::ui::shortcuts::Shortcut;
 use crate::ui::theme::PRESET_COLORS;
 use crate::yt::types::VideoDetails;
 
@@ -28,7 +29,10 @@ pub(super) fn render_token_editor(
                     ui.horizontal(|ui| {
                         ui.label(RichText::new(token).color(color));
                         ui.add_space(6.0);
-                        if ui.small_button("×").clicked() {
+                        if ui
+                            .add(egui::Button::new("×").small().sense(Sense::CLICK))
+                            .clicked()
+                        {
                             removals.push(idx);
                         }
                     });
@@ -48,8 +52,13 @@ pub(super) fn render_token_editor(
 
     ui.horizontal(|ui| {
         let response = ui.add(TextEdit::singleline(new_token).hint_text(hint));
-        let mut commit = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
-        if ui.button("Add").clicked() {
+        let entered = response.lost_focus() && ui.input(|i| Shortcut::AddTerm.pressed(i));
+        let mut commit = entered;
+        // Tab skips the buttons so it moves straight between token fields.
+        if ui
+            .add(egui::Button::new("Add").sense(Sense::CLICK))
+            .clicked()
+        {
             commit = true;
         }
 
@@ -64,6 +73,10 @@ pub(super) fn render_token_editor(
                 PresetEditorState::normalize_terms(tokens);
             }
             new_token.clear();
+            if entered {
+                // Stay in the field so several tokens can be typed in a row.
+                response.request_focus();
+            }
         }
     });
 }
@@ -0,0 +1,90 @@
+use egui::{Context, RichText, TextStyle};
+
+use super::AppState;
+
+/// Hits listed at most; a one-letter query can match most of the history.
+const MAX_LISTED: usize = 500;
+
+/// "Search everything I've ever seen": titles, channels, and descriptions of every video the
+/// find index has taken in, not just the loaded results.
+pub(super) fn render(state: &mut AppState, ctx: &Context) {
+    if !state.history_search.open {
+        return;
+    }
+    let mut open = true;
+    egui::Window::new("Search history")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(560.0)
+        .show(ctx, |ui| {
+            if !state.prefs.ui.indexed_find {
+                ui.label(
+                    "Turn on \"Index results for the find box\" in Settings to keep a \
+                     searchable history.",
+                );
+                return;
+            }
+            ui.horizontal(|ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut state.history_search.query)
+                        .hint_text("Search everything I've ever seen")
+                        .desired_width(320.0),
+                );
+                if response.changed() {
+                    state.run_history_search();
+                }
+                if ui
+                    .small_button("⟳ Rebuild index")
+                    .on_hover_text("Rebuild the word lists from every stored video")
+                    .clicked()
+                {
+                    state.rebuild_find_index();
+                }
+            });
+            let Some(index) = &state.find_index else {
+                return;
+            };
+            let search = &state.history_search;
+            if search.query.trim().is_empty() {
+                ui.small(format!(
+                    "{} videos seen. Titles, channels, and descriptions are searched.",
+                    index.video_count()
+                ));
+                return;
+            }
+            ui.small(format!(
+                "{} match(es) in {:.1} ms",
+                search.hits.len(),
+                search.took.as_secs_f64() * 1000.0
+            ));
+            ui.add_space(4.0);
+            let listed = &search.hits[..search.hits.len().min(MAX_LISTED)];
+            let row_height = ui.text_style_height(&TextStyle::Body);
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .max_height(480.0)
+                .show_rows(ui, row_height, listed.len(), |ui, rows| {
+                    for &position in &listed[rows] {
+                        let video = index.video(position);
+                        ui.horizontal(|ui| {
+                            ui.hyperlink_to(&video.title, &video.url);
+                            ui.label(
+                                RichText::new(format!(
+                                    "{} · {}",
+                                    video.channel,
+                                    video.published_at.get(..10).unwrap_or_default()
+                                ))
+                                .small()
+                                .weak(),
+                            );
+                        });
+                    }
+                });
+            if search.hits.len() > MAX_LISTED {
+                ui.small(format!("Showing the newest {MAX_LISTED}."));
+            }
+        });
+    if !open {
+        state.history_search.open = false;
+    }
+}
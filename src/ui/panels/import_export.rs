@@ -1,12 +1,14 @@
 use egui::{Align, Color32, Context, RichText, TextEdit, TextStyle};
 
-use crate::ui::app_state::ImportMode;
+use crate::import_formats::ChannelGrouping;
+use crate::ui::app_state::{ImportFormat, ImportMode};
 use crate::ui::theme::ACCENT_SAVE;
 
 use super::AppState;
 
 pub(super) fn render(state: &mut AppState, ctx: &Context) {
     render_import_dialog(state, ctx);
+    render_replace_preview(state, ctx);
     render_export_dialog(state, ctx);
 }
 
@@ -52,7 +54,45 @@ fn render_import_dialog(state: &mut AppState, ctx: &Context) {
                 }
 
                 ui.add_space(6.0);
-                ui.label("Paste a JSON array of presets or a prefs.json snippet.");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut dialog.format, ImportFormat::Presets, "Presets JSON");
+                    ui.selectable_value(
+                        &mut dialog.format,
+                        ImportFormat::Channels,
+                        "Channel list (OPML / CSV / text)",
+                    );
+                });
+                match dialog.format {
+                    ImportFormat::Presets => {
+                        ui.label("Paste a JSON array of presets or a prefs.json snippet.");
+                    }
+                    ImportFormat::Channels => {
+                        ui.label("Paste an OPML export or one channel URL, @handle, or UC… ID per line.");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut dialog.channel_grouping,
+                                ChannelGrouping::Combined,
+                                "One preset for all channels",
+                            );
+                            ui.radio_value(
+                                &mut dialog.channel_grouping,
+                                ChannelGrouping::PerChannel,
+                                "One preset per channel",
+                            );
+                        });
+                        if dialog.channel_grouping == ChannelGrouping::Combined {
+                            ui.horizontal(|ui| {
+                                ui.label("Preset name");
+                                ui.text_edit_singleline(&mut dialog.channel_preset_name);
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label("Query text");
+                            ui.text_edit_singleline(&mut dialog.channel_query);
+                        });
+                        ui.small("Per-channel presets without query text list each channel's newest uploads once its ID is known.");
+                    }
+                }
                 ui.checkbox(&mut dialog.replace_existing, "Replace existing presets");
                 ui.small("Checked: imported presets overwrite the current list. Unchecked: imported presets are added alongside existing ones.");
                 egui::ScrollArea::both()
@@ -71,9 +111,11 @@ fn render_import_dialog(state: &mut AppState, ctx: &Context) {
                     ui.colored_label(Color32::from_rgb(239, 68, 68), err);
                 }
                 ui.add_space(10.0);
+                let previewing = dialog.replace_preview.is_some();
                 ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
                     if ui
-                        .add(
+                        .add_enabled(
+                            !previewing,
                             egui::Button::new(RichText::new("Import").color(Color32::WHITE))
                                 .fill(ACCENT_SAVE),
                         )
@@ -97,34 +139,100 @@ fn render_import_dialog(state: &mut AppState, ctx: &Context) {
         state.cancel_import_dialog();
     } else if wants_switch_to_file {
         state.import_from_file();
-    } else if wants_switch_to_clipboard {
-        if let Some(dialog) = state.import_dialog.as_mut() {
-            dialog.mode = ImportMode::Clipboard;
-            dialog.file_path = None;
-            dialog.replace_existing = false;
-            dialog.awaiting_clipboard = true;
-            dialog.raw_json.clear();
-            dialog.error = None;
-            ctx.send_viewport_cmd(egui::ViewportCommand::RequestPaste);
-        }
+    } else if wants_switch_to_clipboard && let Some(dialog) = state.import_dialog.as_mut() {
+        dialog.mode = ImportMode::Clipboard;
+        dialog.file_path = None;
+        dialog.replace_existing = false;
+        dialog.awaiting_clipboard = true;
+        dialog.raw_json.clear();
+        dialog.error = None;
+        ctx.send_viewport_cmd(egui::ViewportCommand::RequestPaste);
     }
 
-    if let Some(dialog) = state.import_dialog.as_mut() {
-        if dialog.awaiting_clipboard {
-            if let Some(text) = pasted_text {
-                dialog.raw_json = text;
-                dialog.file_path = None;
-                dialog.awaiting_clipboard = false;
-                dialog.mode = ImportMode::Clipboard;
+    if let Some(dialog) = state.import_dialog.as_mut()
+        && dialog.awaiting_clipboard
+        && let Some(text) = pasted_text
+    {
+        dialog.raw_json = text;
+        dialog.file_path = None;
+        dialog.awaiting_clipboard = false;
+        dialog.mode = ImportMode::Clipboard;
+    }
+}
+
+/// Confirmation shown before "Replace existing presets" overwrites the list.
+fn render_replace_preview(state: &mut AppState, ctx: &Context) {
+    let Some(preview) = state
+        .import_dialog
+        .as_ref()
+        .and_then(|dialog| dialog.replace_preview.as_ref())
+    else {
+        return;
+    };
+
+    let mut confirm = false;
+    let mut back = false;
+    let mut open = true;
+    egui::Window::new("Replace presets?")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.set_min_width(360.0);
+            ui.label(preview.diff.summary());
+            if !preview.diff.changed.is_empty() {
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical()
+                    .max_height(220.0)
+                    .show(ui, |ui| {
+                        for changed in &preview.diff.changed {
+                            egui::CollapsingHeader::new(&changed.name)
+                                .id_salt(("replace-preview", &changed.name))
+                                .default_open(false)
+                                .show(ui, |ui| {
+                                    for field in &changed.fields {
+                                        ui.small(format!("• {field}"));
+                                    }
+                                });
+                        }
+                    });
             }
-        }
+            ui.add_space(10.0);
+            ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                if ui
+                    .add(
+                        egui::Button::new(RichText::new("Replace").color(Color32::WHITE))
+                            .fill(ACCENT_SAVE),
+                    )
+                    .clicked()
+                {
+                    confirm = true;
+                }
+                if ui.button("Back").clicked() {
+                    back = true;
+                }
+            });
+        });
+
+    if confirm {
+        state.confirm_import_replace();
+    } else if back || !open {
+        state.cancel_import_replace();
     }
 }
 
+/// Answer to the "file already exists" question in the export dialog.
+enum OverwriteChoice {
+    Overwrite,
+    Copy,
+    Cancel,
+}
+
 fn render_export_dialog(state: &mut AppState, ctx: &Context) {
     let mut wants_close_export = false;
     let mut wants_switch_to_file_export = false;
     let mut wants_copy_to_clipboard = false;
+    let mut overwrite_choice: Option<OverwriteChoice> = None;
 
     if let Some(dialog) = state.export_dialog.as_mut() {
         let mut open = true;
@@ -144,6 +252,30 @@ fn render_export_dialog(state: &mut AppState, ctx: &Context) {
                     }
                 });
 
+                if let Some(prompt) = dialog.overwrite_prompt.as_ref() {
+                    ui.add_space(6.0);
+                    ui.colored_label(
+                        Color32::from_rgb(234, 179, 8),
+                        format!("⚠ {} — overwrite?", prompt.summary),
+                    )
+                    .on_hover_text(prompt.path.display().to_string());
+                    ui.horizontal(|ui| {
+                        if ui.button("Overwrite").clicked() {
+                            overwrite_choice = Some(OverwriteChoice::Overwrite);
+                        }
+                        if ui
+                            .button("Save as copy")
+                            .on_hover_text("Write beside it with a -copy suffix")
+                            .clicked()
+                        {
+                            overwrite_choice = Some(OverwriteChoice::Copy);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            overwrite_choice = Some(OverwriteChoice::Cancel);
+                        }
+                    });
+                }
+
                 ui.add_space(6.0);
                 ui.label("Copy this JSON to share or back up your presets.");
                 egui::ScrollArea::both()
@@ -170,14 +302,18 @@ fn render_export_dialog(state: &mut AppState, ctx: &Context) {
         }
     }
 
-    if wants_close_export {
+    if let Some(choice) = overwrite_choice {
+        match choice {
+            OverwriteChoice::Overwrite => state.confirm_export_overwrite(),
+            OverwriteChoice::Copy => state.export_as_copy(),
+            OverwriteChoice::Cancel => state.cancel_export_overwrite(),
+        }
+    } else if wants_close_export {
         state.cancel_export_dialog();
     } else if wants_switch_to_file_export {
         state.export_to_file();
-    } else if wants_copy_to_clipboard {
-        if let Some(dialog) = state.export_dialog.as_ref() {
-            ctx.copy_text(dialog.raw_json.clone());
-            state.status = "Export JSON copied.".into();
-        }
+    } else if wants_copy_to_clipboard && let Some(dialog) = state.export_dialog.as_ref() {
+        ctx.copy_text(dialog.raw_json.clone());
+        state.status = "Export JSON copied.".into();
     }
 }
@@ -1,7 +1,10 @@
 use egui::{Color32, Context, Frame, Margin, RichText};
+use time::OffsetDateTime;
 
 use crate::prefs;
-use crate::ui::theme::{ACCENT_EXTRA, ACCENT_OPEN, ACCENT_SAVE, PANEL_FILL};
+use crate::ui::fuzzy;
+use crate::ui::theme::{ACCENT_EXTRA, ACCENT_OPEN, ACCENT_SAVE, palette};
+use crate::ui::utils::{humanize_elapsed, warning_suffix};
 
 use super::AppState;
 
@@ -16,7 +19,7 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
         .resizable(true)
         .show(ctx, |ui| {
             Frame::default()
-                .fill(PANEL_FILL)
+                .fill(palette(ctx).panel_fill)
                 .inner_margin(Margin::symmetric(14, 12))
                 .show(ui, |ui| {
                     let mut pending_action: Option<PresetAction> = None;
@@ -26,9 +29,6 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                         .show(ui, |scroll_ui| {
                             scroll_ui.heading("My Searches");
                             scroll_ui.separator();
-                            scroll_ui.label("API key:");
-                            scroll_ui.text_edit_singleline(&mut state.prefs.api_key);
-                            scroll_ui.add_space(8.0);
                             scroll_ui.horizontal(|ui| {
                                 let new_button = egui::Button::new(
                                     RichText::new("New preset").strong().color(Color32::WHITE),
@@ -72,26 +72,111 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                                 }
                             });
                             scroll_ui.add_space(8.0);
-                            let reset_button = egui::Button::new(
-                                RichText::new("Reset defaults")
-                                    .strong()
-                                    .color(Color32::WHITE),
-                            )
-                            .fill(Color32::from_rgb(200, 60, 60))
-                            .min_size(egui::vec2(140.0, 28.0));
-                            let reset_response = scroll_ui.add(reset_button).on_hover_text(
-                                "Restore built-in presets, clear blocks, and reset filters",
+                            let enabled_count = state.enabled_preset_count();
+                            let total_count = state.prefs.searches.len();
+                            scroll_ui.horizontal(|ui| {
+                                ui.label("Presets (enable/disable):");
+                                ui.label(
+                                    RichText::new(format!(
+                                        "{} / {} enabled",
+                                        enabled_count, total_count
+                                    ))
+                                    .small(),
+                                );
+                            });
+                            scroll_ui.horizontal(|ui| {
+                                if ui
+                                    .small_button("Enable all")
+                                    .on_hover_text("Turn on every preset")
+                                    .clicked()
+                                {
+                                    state.set_all_presets_enabled(true);
+                                }
+                                if ui
+                                    .small_button("Disable all")
+                                    .on_hover_text("Turn off every preset")
+                                    .clicked()
+                                {
+                                    state.set_all_presets_enabled(false);
+                                }
+                                if ui
+                                    .small_button("Invert")
+                                    .on_hover_text("Flip which presets are enabled")
+                                    .clicked()
+                                {
+                                    state.invert_preset_selection();
+                                }
+                                let merge_label = if state.merge_mode {
+                                    "Stop merging"
+                                } else {
+                                    "Merge…"
+                                };
+                                if ui
+                                    .small_button(merge_label)
+                                    .on_hover_text("Pick presets to combine into one")
+                                    .clicked()
+                                {
+                                    state.toggle_merge_mode();
+                                }
+                                if ui
+                                    .small_button("Gallery…")
+                                    .on_hover_text("Browse bundled preset packs")
+                                    .clicked()
+                                {
+                                    state.open_preset_gallery();
+                                }
+                            });
+
+                            scroll_ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut state.preset_filter)
+                                        .hint_text("Filter presets (fuzzy)")
+                                        .desired_width(180.0),
+                                );
+                                if !state.preset_filter.is_empty()
+                                    && ui.small_button("✕").on_hover_text("Clear filter").clicked()
+                                {
+                                    state.preset_filter.clear();
+                                }
+                            });
+                            let mut order = fuzzy::rank(
+                                &state.preset_filter,
+                                state
+                                    .prefs
+                                    .searches
+                                    .iter()
+                                    .map(|search| search.name.as_str()),
                             );
-                            if reset_response.clicked() {
-                                state.reset_to_defaults();
+                            // Without a filter, cluster grouped presets (e.g. from a pack)
+                            // under their group heading, after the ungrouped ones.
+                            let grouped = state.preset_filter.trim().is_empty();
+                            if grouped {
+                                let searches = &state.prefs.searches;
+                                order.sort_by(|&a, &b| searches[a].group.cmp(&searches[b].group));
+                            }
+                            let mut current_group: Option<String> = None;
+                            if order.is_empty() {
+                                scroll_ui.weak("No presets match the filter.");
                             }
-                            scroll_ui.add_space(8.0);
-                            scroll_ui.label("Presets (enable/disable):");
-
-                            let len = state.prefs.searches.len();
                             let mut any_enabled_changed = false;
-                            for index in 0..len {
+                            let mut merge_toggle: Option<String> = None;
+                            let now = OffsetDateTime::now_utc().unix_timestamp();
+                            for index in order {
                                 if let Some(search) = state.prefs.searches.get_mut(index) {
+                                    if grouped
+                                        && search.group.is_some()
+                                        && search.group != current_group
+                                    {
+                                        current_group.clone_from(&search.group);
+                                        scroll_ui.add_space(4.0);
+                                        scroll_ui.label(
+                                            RichText::new(
+                                                search.group.as_deref().unwrap_or_default(),
+                                            )
+                                            .small()
+                                            .strong(),
+                                        );
+                                    }
                                     let mut select_id: Option<String> = None;
                                     let mut row_action: Option<PresetAction> = None;
                                     scroll_ui.horizontal(|ui| {
@@ -100,12 +185,56 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                                         if old_enabled != search.enabled {
                                             any_enabled_changed = true;
                                         }
+                                        if state.merge_mode {
+                                            let mut picked =
+                                                state.merge_selection.contains(&search.id);
+                                            if ui
+                                                .add_enabled(
+                                                    !search.system,
+                                                    egui::Checkbox::new(&mut picked, "⊕"),
+                                                )
+                                                .on_hover_text("Include in the merge")
+                                                .on_disabled_hover_text(
+                                                    "System presets cannot be merged",
+                                                )
+                                                .changed()
+                                            {
+                                                merge_toggle = Some(search.id.clone());
+                                            }
+                                        }
                                         let selected = state
                                             .selected_search_id
                                             .as_deref()
                                             .map(|id| id == search.id)
                                             .unwrap_or(false);
-                                        if ui.selectable_label(selected, &search.name).clicked() {
+                                        let conflicts = prefs::check_preset_conflicts(
+                                            &state.prefs.search_defaults,
+                                            search,
+                                        );
+                                        let mut label = ui.selectable_label(selected, &search.name);
+                                        let description = search
+                                            .description
+                                            .as_deref()
+                                            .map(str::trim)
+                                            .filter(|text| !text.is_empty());
+                                        if !conflicts.is_empty() {
+                                            let lines: Vec<String> = conflicts
+                                                .iter()
+                                                .map(|conflict| format!("⚠ {}", conflict.message()))
+                                                .collect();
+                                            let about = description
+                                                .map(|text| format!("\n{text}"))
+                                                .unwrap_or_default();
+                                            label = label.on_hover_text(format!(
+                                                "{}{}{about}\n{}",
+                                                search.name,
+                                                warning_suffix(conflicts.len()),
+                                                lines.join("\n")
+                                            ));
+                                        } else if let Some(text) = description {
+                                            label = label.on_hover_text(text);
+                                        }
+                                        if label.clicked() {
                                             if selected {
                                                 select_id = Some(String::new());
                                             } else {
@@ -113,15 +242,14 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                                             }
                                         }
                                         ui.menu_button("⋮", |menu_ui| {
-                                            if !search.system {
-                                                if menu_ui
+                                            if !search.system
+                                                && menu_ui
                                                     .button("Edit")
                                                     .on_hover_text("Edit this preset")
                                                     .clicked()
-                                                {
-                                                    row_action = Some(PresetAction::Edit(index));
-                                                    menu_ui.close_menu();
-                                                }
+                                            {
+                                                row_action = Some(PresetAction::Edit(index));
+                                                menu_ui.close_menu();
                                             }
                                             if menu_ui
                                                 .button("Duplicate")
@@ -131,36 +259,62 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                                                 row_action = Some(PresetAction::Duplicate(index));
                                                 menu_ui.close_menu();
                                             }
-                                            if !search.system {
-                                                if menu_ui
+                                            if !search.system
+                                                && menu_ui
                                                     .button("Delete")
                                                     .on_hover_text("Remove this preset")
                                                     .clicked()
-                                                {
-                                                    row_action = Some(PresetAction::Delete(index));
-                                                    menu_ui.close_menu();
-                                                }
+                                            {
+                                                row_action = Some(PresetAction::Delete(index));
+                                                menu_ui.close_menu();
                                             }
                                         });
                                     });
+                                    if let Some(last) = state.preset_stats.last_run(&search.id) {
+                                        let ago = match humanize_elapsed(now - last.ran_at_unix) {
+                                            age if age == "now" => "ran just now".to_owned(),
+                                            age => format!("ran {age} ago"),
+                                        };
+                                        scroll_ui.horizontal(|ui| {
+                                            ui.add_space(28.0);
+                                            ui.label(RichText::new(ago).small().weak())
+                                                .on_hover_text(last.summary());
+                                        });
+                                    }
                                     if let Some(id) = select_id {
-                                        if id.is_empty() {
-                                            state.selected_search_id = None;
-                                        } else {
-                                            state.selected_search_id = Some(id);
-                                        }
-                                        state.refresh_visible_results();
+                                        state.select_search(Some(id).filter(|id| !id.is_empty()));
                                     }
-                                    if pending_action.is_none() {
-                                        if let Some(action) = row_action {
-                                            pending_action = Some(action);
-                                        }
+                                    if pending_action.is_none()
+                                        && let Some(action) = row_action
+                                    {
+                                        pending_action = Some(action);
                                     }
                                 }
                             }
                             if any_enabled_changed {
                                 state.refresh_visible_results();
                             }
+                            if let Some(id) = merge_toggle {
+                                state.toggle_merge_selection(&id);
+                            }
+                            if state.merge_mode {
+                                scroll_ui.horizontal(|ui| {
+                                    let picked = state.merge_selection.len();
+                                    if ui
+                                        .add_enabled(
+                                            picked >= 2,
+                                            egui::Button::new(format!("Merge {picked} presets")),
+                                        )
+                                        .on_disabled_hover_text("Tick at least two presets")
+                                        .clicked()
+                                    {
+                                        state.merge_confirm = true;
+                                    }
+                                    if ui.button("Cancel").clicked() {
+                                        state.toggle_merge_mode();
+                                    }
+                                });
+                            }
 
                             scroll_ui.add_space(8.0);
                             let save_button = egui::Button::new(
@@ -183,18 +337,31 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                             scroll_ui.add_space(12.0);
                             scroll_ui.separator();
                             scroll_ui.add_space(12.0);
-                            scroll_ui.label("Blocked channels:");
+                            let heading = scroll_ui.label("Blocked channels:");
+                            if std::mem::take(&mut state.scroll_to_block_list) {
+                                heading.scroll_to_me(Some(egui::Align::TOP));
+                            }
                             if state.prefs.blocked_channels.is_empty() {
                                 scroll_ui.label("(none)");
                             } else {
                                 let blocked_snapshot = state.prefs.blocked_channels.clone();
                                 for entry in blocked_snapshot {
-                                    let (key, label) = prefs::parse_block_entry(&entry);
-                                    if key.is_empty() {
+                                    let parsed = prefs::BlockEntry::parse(&entry);
+                                    if parsed.key.is_empty() {
                                         continue;
                                     }
+                                    let key = parsed.key.clone();
                                     scroll_ui.horizontal(|ui| {
-                                        ui.label(label);
+                                        ui.label(parsed.display_label())
+                                            .on_hover_text(&parsed.raw_key);
+                                        if parsed.is_name_only() {
+                                            ui.weak("(exact name)").on_hover_text(
+                                                "Hides only channels named exactly this. \
+                                                 Earlier versions also hid channels whose name \
+                                                 contained it; block from a result card to \
+                                                 match the channel ID instead.",
+                                            );
+                                        }
                                         if ui
                                             .button("Unblock")
                                             .on_hover_text("Allow videos from this channel again")
@@ -216,4 +383,41 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                     }
                 });
         });
+    render_merge_confirm(state, ctx);
+}
+
+/// Ask before the picked presets are replaced by their merge.
+fn render_merge_confirm(state: &mut AppState, ctx: &Context) {
+    if !state.merge_confirm {
+        return;
+    }
+    let names = state.merge_selection_names();
+    let mut open = true;
+    let mut merge = false;
+    let mut cancel = false;
+    egui::Window::new("Merge presets?")
+        .collapsible(false)
+        .resizable(false)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.label(format!("Combine {} into one preset?", names.join(", ")));
+            ui.label(
+                "Terms and channels are combined; the highest-priority preset keeps its query \
+                 and overrides. The originals are removed.",
+            );
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button(RichText::new("Merge").strong()).clicked() {
+                    merge = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+    if merge {
+        state.merge_selected_presets();
+    } else if cancel || !open {
+        state.merge_confirm = false;
+    }
 }
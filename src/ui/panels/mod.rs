@@ -2,18 +2,30 @@ use egui::Context;
 
 use super::app_state::AppState;
 
+mod diagnostics;
 mod editor;
+mod footer;
+mod gallery;
 mod helpers;
+mod history;
 mod import_export;
 mod left;
+mod quota;
+mod raw_responses;
 mod results;
+mod settings;
 mod top;
+mod welcome;
 
 impl AppState {
     pub fn render_top_panel(&mut self, ctx: &Context) -> bool {
         top::render(self, ctx)
     }
 
+    pub fn render_footer(&mut self, ctx: &Context) {
+        footer::render(self, ctx);
+    }
+
     pub fn render_left_panel(&mut self, ctx: &Context) {
         left::render(self, ctx);
     }
@@ -29,4 +41,32 @@ impl AppState {
     pub fn render_import_export_windows(&mut self, ctx: &Context) {
         import_export::render(self, ctx);
     }
+
+    pub fn render_settings_window(&mut self, ctx: &Context) {
+        settings::render(self, ctx);
+    }
+
+    pub fn render_quota_prompt(&mut self, ctx: &Context) {
+        quota::render(self, ctx);
+    }
+
+    pub fn render_raw_responses_window(&mut self, ctx: &Context) {
+        raw_responses::render(self, ctx);
+    }
+
+    pub fn render_history_window(&mut self, ctx: &Context) {
+        history::render(self, ctx);
+    }
+
+    pub fn render_diagnostics_window(&mut self, ctx: &Context) {
+        diagnostics::render(self, ctx);
+    }
+
+    pub fn render_preset_gallery(&mut self, ctx: &Context) {
+        gallery::render(self, ctx);
+    }
+
+    pub fn render_welcome_window(&mut self, ctx: &Context) {
+        welcome::render(self, ctx);
+    }
 }
@@ -0,0 +1,74 @@
+use egui::{Align, Color32, Context, Layout, RichText};
+
+use crate::ui::theme::ACCENT_SAVE;
+
+use super::AppState;
+
+pub(super) fn render(state: &mut AppState, ctx: &Context) {
+    let Some(prompt) = state.quota_prompt.as_ref() else {
+        return;
+    };
+
+    let mut wants_anyway = false;
+    let mut wants_fewer = false;
+    let mut wants_cancel = false;
+    let mut open = true;
+
+    egui::Window::new("Daily quota budget")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.set_max_width(420.0);
+            let sources = if prompt.scopes.uploads > 0 {
+                format!(
+                    "{} search(es) and {} uploads playlist(s)",
+                    prompt.scopes.searches, prompt.scopes.uploads
+                )
+            } else {
+                format!("{} search(es)", prompt.scopes.searches)
+            };
+            ui.label(format!(
+                "{sources} × up to {} page(s) could use about {} units.",
+                prompt.max_pages, prompt.estimated
+            ));
+            ui.label(format!(
+                "Budget {} − used today {} = {} remaining.",
+                prompt.budget, prompt.used_today, prompt.remaining
+            ));
+            ui.small("Each search page costs 100 units plus 1 for its video lookup; channel details add 1 per search. Per-channel presets run one search per channel. Uploads playlist pages cost 1 unit plus 1 for the lookup.");
+
+            ui.add_space(10.0);
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ui.button("Cancel").clicked() {
+                    wants_cancel = true;
+                }
+                let fewer = match prompt.fewer_pages {
+                    Some(pages) => ui.add(
+                        egui::Button::new(
+                            RichText::new(format!("Run {pages} page(s)")).color(Color32::WHITE),
+                        )
+                        .fill(ACCENT_SAVE),
+                    ),
+                    None => ui
+                        .add_enabled(false, egui::Button::new("Run fewer pages"))
+                        .on_disabled_hover_text("Not even one page per search fits what is left."),
+                };
+                if fewer.clicked() {
+                    wants_fewer = true;
+                }
+                if ui.button("Run anyway").clicked() {
+                    wants_anyway = true;
+                }
+            });
+        });
+
+    if wants_anyway {
+        state.quota_run_anyway();
+    } else if wants_fewer {
+        state.quota_run_fewer_pages();
+    } else if wants_cancel || !open {
+        state.cancel_quota_prompt();
+    }
+}
@@ -0,0 +1,90 @@
+use egui::{Color32, Context, RichText};
+
+use crate::search_runner::{RawFate, RawPage};
+use crate::ui::theme::{ACCENT_EXTRA, ACCENT_SAVE, ACCENT_SINGLE};
+
+use super::AppState;
+
+fn fate_color(fate: RawFate) -> Color32 {
+    match fate {
+        RawFate::Kept => ACCENT_SAVE,
+        RawFate::Duplicate => Color32::from_gray(150),
+        RawFate::MissingFromVideos => ACCENT_SINGLE,
+        RawFate::Filtered | RawFate::TitleMismatch | RawFate::MissingAllTerms => ACCENT_EXTRA,
+    }
+}
+
+/// Per-preset, per-page listing of what search.list returned and where each item was dropped.
+pub(super) fn render(state: &mut AppState, ctx: &Context) {
+    if !state.show_raw_responses {
+        return;
+    }
+    let mut open = true;
+    egui::Window::new("Raw search responses")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(520.0)
+        .show(ctx, |ui| {
+            ui.small("From the last run; replaced when the next search starts.");
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .max_height(480.0)
+                .show(ui, |ui| {
+                    let mut presets: Vec<&str> = Vec::new();
+                    for page in &state.raw_pages {
+                        if !presets.contains(&page.preset.as_str()) {
+                            presets.push(&page.preset);
+                        }
+                    }
+                    for preset in presets {
+                        let pages: Vec<&RawPage> = state
+                            .raw_pages
+                            .iter()
+                            .filter(|page| page.preset == preset)
+                            .collect();
+                        egui::CollapsingHeader::new(format!("{preset} ({} page(s))", pages.len()))
+                            .id_salt(("raw_preset", preset))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for page in pages {
+                                    render_page(ui, page);
+                                }
+                            });
+                    }
+                });
+        });
+    if !open {
+        state.show_raw_responses = false;
+    }
+}
+
+fn render_page(ui: &mut egui::Ui, page: &RawPage) {
+    let kept = page
+        .items
+        .iter()
+        .filter(|item| item.fate == RawFate::Kept)
+        .count();
+    let title = match &page.channel_scope {
+        Some(channel) => format!(
+            "{channel} · page {} — kept {kept}/{}",
+            page.page,
+            page.items.len()
+        ),
+        None => format!("Page {} — kept {kept}/{}", page.page, page.items.len()),
+    };
+    egui::CollapsingHeader::new(title)
+        .id_salt(("raw_page", &page.preset, &page.channel_scope, page.page))
+        .show(ui, |ui| {
+            egui::Grid::new(("raw_grid", &page.preset, &page.channel_scope, page.page))
+                .striped(true)
+                .show(ui, |ui| {
+                    for item in &page.items {
+                        ui.monospace(&item.video_id);
+                        ui.label(&item.published_at);
+                        ui.label(RichText::new(item.fate.label()).color(fate_color(item.fate)));
+                        ui.end_row();
+                    }
+                });
+        });
+}
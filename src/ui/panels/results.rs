@@ -1,24 +1,73 @@
+use egui::text::{LayoutJob, TextFormat};
 use egui::{
-    Align2, Color32, Context, CornerRadius, FontId, Frame, Image, Margin, RichText, Sense, Stroke,
-    StrokeKind,
+    Align2, Color32, Context, CornerRadius, FontId, Frame, Image, Margin, Rect, RichText, Sense,
+    Stroke, StrokeKind, TextStyle, UiBuilder, vec2,
 };
 
+use crate::config_dir;
 use crate::ui::panels::helpers::channel_display_label;
-use crate::ui::theme::{ACCENT_EXTRA, ACCENT_OPEN, CARD_BG, CARD_BORDER, PRESET_COLORS};
-use crate::ui::utils::{format_duration, open_in_browser};
+use crate::ui::theme::{
+    ACCENT_EXTRA, ACCENT_OPEN, ACCENT_SAVE, ACCENT_SEARCH, ACCENT_SINGLE, RECENCY_FRESH,
+    RECENCY_FRESH_HOURS, RECENCY_OLD, RECENCY_RECENT, RECENCY_RECENT_HOURS, palette,
+};
+use crate::ui::utils::{
+    format_duration, humanize_elapsed, link_at_time, open_in_browser, parse_timestamp,
+    truncate_middle,
+};
 use crate::yt::types::VideoDetails;
 
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
 use super::AppState;
-use crate::ui::app_state::ResultSort;
+
+/// Titles wrap to this many rows before they are cut with an ellipsis.
+const TITLE_MAX_ROWS: usize = 2;
+/// Channel labels longer than this are shortened in the middle.
+const CHANNEL_LABEL_MAX_CHARS: usize = 40;
+/// Height assumed for result cards that have not been laid out yet.
+const CARD_HEIGHT_ESTIMATE: f32 = 120.0;
+/// Gap between result cards.
+const CARD_SPACING: f32 = 6.0;
+/// Tags shown as chips when a card's tag row is expanded.
+const TAG_CHIP_LIMIT: usize = 8;
+use crate::ui::app_state::{CardKey, ResultSort};
+use crate::ui::share::{self, ShareStyle};
 use crate::ui::thumbnails::{MAX_THUMB_HEIGHT, MAX_THUMB_WIDTH, ThumbnailRef};
 
 pub(super) fn render(state: &mut AppState, ctx: &Context) {
     egui::CentralPanel::default().show(ctx, |ui| {
+        let mut clear_requested = false;
         ui.horizontal(|ui| {
             ui.heading("Results");
             ui.add_space(8.0);
             let previous_sort = state.result_sort;
-            egui::ComboBox::from_label("Sort")
+            let find_response = ui.add(
+                egui::TextEdit::singleline(&mut state.find_query)
+                    .hint_text("Find in titles and channels")
+                    .desired_width(200.0),
+            );
+            if find_response.changed() {
+                state.refresh_visible_results();
+            }
+            if state.find_active() && ui.small_button("✕").on_hover_text("Clear find").clicked() {
+                state.find_query.clear();
+                state.refresh_visible_results();
+            }
+            if state.prefs.ui.indexed_find
+                && ui
+                    .small_button("⟳")
+                    .on_hover_text("Rebuild the find index from every stored video")
+                    .clicked()
+            {
+                state.rebuild_find_index();
+            }
+            ui.add_space(8.0);
+            egui::ComboBox::from_label(if state.find_active() {
+                "Sort (ranked by match)"
+            } else {
+                "Sort"
+            })
                 .selected_text(state.result_sort.label())
                 .show_ui(ui, |ui| {
                     ui.selectable_value(&mut state.result_sort, ResultSort::Newest, "Newest");
@@ -26,56 +75,498 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) {
                     ui.selectable_value(&mut state.result_sort, ResultSort::Shortest, "Shortest");
                     ui.selectable_value(&mut state.result_sort, ResultSort::Longest, "Longest");
                     ui.selectable_value(&mut state.result_sort, ResultSort::Channel, "Channel");
+                    ui.selectable_value(&mut state.result_sort, ResultSort::Priority, "Priority")
+                        .on_hover_text("Highest preset priority first, then newest");
                 });
             if state.result_sort != previous_sort {
                 state.apply_result_sort();
             }
+            ui.add_space(8.0);
+            ui.add_enabled(
+                state.previous_results.is_some(),
+                egui::Checkbox::new(&mut state.show_changes, "Changes"),
+            )
+            .on_hover_text("Compare with the previous run")
+            .on_disabled_hover_text("Available after a second run with the same presets");
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .add_enabled(
+                        !state.results_all.is_empty() && !state.is_searching,
+                        egui::Button::new("Clear results"),
+                    )
+                    .on_hover_text("Empty the list and delete the cached results file")
+                    .clicked()
+                {
+                    clear_requested = true;
+                }
+                let visible = state.visible_result_count();
+                if ui
+                    .add_enabled(
+                        visible > 0,
+                        egui::Button::new(format!("Copy {visible} as Markdown list")),
+                    )
+                    .on_hover_text("Copy the visible results as a Markdown bullet list")
+                    .clicked()
+                {
+                    let list = share::markdown_list(
+                        &state.prefs.ui.share_template,
+                        state
+                            .visible_rows
+                            .iter()
+                            .map(|&row| state.result(row)),
+                    );
+                    ctx.copy_text(list);
+                    state.status = format!("Copied {visible} result(s) as a Markdown list.");
+                }
+                let batch_size = state.prefs.ui.open_batch_size.max(1);
+                if ui
+                    .add_enabled(
+                        state.unopened_visible_count() > 0 && !state.open_batch_running(),
+                        egui::Button::new(format!("Open next {batch_size}")),
+                    )
+                    .on_hover_text("Open the next videos you have not opened this session")
+                    .clicked()
+                {
+                    state.open_next_batch();
+                }
                 ui.label(format!(
                     "Results: {}/{}",
-                    state.results.len(),
+                    state.visible_result_count(),
                     state.results_all.len()
                 ));
+                if !state.results_all.is_empty() {
+                    render_source_badge(state, ui);
+                }
             });
         });
+        if !config_dir::get().writable {
+            ui.colored_label(
+                Color32::from_rgb(239, 68, 68),
+                format!(
+                    "⚠ No writable config folder: presets, settings, and results will not be saved. \
+                     Restart with --config-dir <path> or set {}.",
+                    config_dir::CONFIG_DIR_ENV
+                ),
+            );
+        }
+        if state.cached_results_are_stale() && !state.is_searching {
+            ui.horizontal(|ui| {
+                ui.colored_label(
+                    ACCENT_SINGLE,
+                    "These cached results predate your current presets.",
+                );
+                if ui.small_button("Clear them").clicked() {
+                    clear_requested = true;
+                }
+            });
+        }
+        if clear_requested {
+            state.clear_results();
+        }
+        render_block_suggestion(state, ui);
+        if !state.is_searching && !state.show_changes {
+            render_preset_legend(state, ui);
+            render_overlap_report(state, ui);
+        }
         if state.is_searching {
-            ui.label("Searching...");
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Searching...");
+            });
+            if let Some(stage) = &state.search_stage {
+                ui.label(RichText::new(stage).small().weak());
+            }
+        } else if state.show_changes && state.previous_results.is_some() {
+            render_changes(state, ui);
         } else if state.results.is_empty() {
             ui.label("No results yet. Enter your API key and click Search.");
         } else {
-            let mut block_requests: Vec<(String, String)> = Vec::new();
-            let results_snapshot = state.results.clone();
-            let filtered_results: Vec<VideoDetails> = results_snapshot
-                .into_iter()
-                .filter(|video| state.duration_filter.allows(video.duration_secs))
-                .collect();
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                for video in &filtered_results {
-                    render_video_card(state, ui, video, &mut block_requests);
-                    ui.add_space(6.0);
+            render_result_list(state, ui);
+        }
+    });
+}
+
+/// "cached" until a live search replaces the startup results, then "live"; hover shows when.
+fn render_source_badge(state: &AppState, ui: &mut egui::Ui) {
+    let (text, color) = if state.results_are_cached {
+        ("cached", ACCENT_SINGLE)
+    } else {
+        ("live", ACCENT_SAVE)
+    };
+    let saved_at = state
+        .cache_saved_at
+        .and_then(|unix| OffsetDateTime::from_unix_timestamp(unix).ok());
+    let when = saved_at.map(|at| {
+        let age = match humanize_elapsed((OffsetDateTime::now_utc() - at).whole_seconds()) {
+            age if age == "now" => "just now".to_owned(),
+            age => format!("{age} ago"),
+        };
+        format!("at {} ({age})", at.format(&Rfc3339).unwrap_or_default())
+    });
+    let hover = match (state.results_are_cached, when) {
+        (true, Some(when)) => {
+            format!("Loaded from the results cache saved {when}. Search to refresh.")
+        }
+        (true, None) => "Loaded from the results cache. Search to refresh.".to_owned(),
+        (false, Some(when)) => format!("Fetched by a search this session, saved {when}."),
+        (false, None) => "Fetched by a search this session.".to_owned(),
+    };
+    Frame::default()
+        .stroke(Stroke::new(1.0, color))
+        .corner_radius(CornerRadius::same(6))
+        .inner_margin(Margin::symmetric(5, 1))
+        .show(ui, |ui| {
+            ui.label(RichText::new(text).small().color(color));
+        })
+        .response
+        .on_hover_text(hover);
+}
+
+/// Collapsible strip naming each preset in the results with its chip color and shown count;
+/// the checkbox hides a preset's videos from view without disabling the preset.
+fn render_preset_legend(state: &mut AppState, ui: &mut egui::Ui) {
+    let legend = state.preset_legend();
+    if legend.is_empty() {
+        return;
+    }
+    let mut toggled: Option<(String, bool)> = None;
+    egui::CollapsingHeader::new("Presets in these results")
+        .id_salt("results_preset_legend")
+        .default_open(legend.len() > 1)
+        .show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for entry in &legend {
+                    let mut shown = !entry.hidden;
+                    let label = RichText::new(format!("{} ({})", entry.name, entry.visible))
+                        .color(entry.color);
+                    let response =
+                        ui.add_enabled(entry.id.is_some(), egui::Checkbox::new(&mut shown, label));
+                    if response.changed()
+                        && let Some(id) = entry.id.as_ref()
+                    {
+                        toggled = Some((id.clone(), !shown));
+                    }
+                    response
+                        .on_hover_text("Uncheck to hide this preset's videos until the next search")
+                        .on_disabled_hover_text("This preset no longer exists");
+                    ui.add_space(8.0);
                 }
             });
-            for (channel_id, channel_title) in block_requests {
-                state.block_channel(&channel_id, &channel_title);
+        });
+    if let Some((id, hidden)) = toggled {
+        state.set_preset_view_hidden(&id, hidden);
+    }
+}
+
+/// Preset pairs from the last run that returned the same videos; heavy overlap suggests the
+/// presets could be merged.
+fn render_overlap_report(state: &AppState, ui: &mut egui::Ui) {
+    if state.preset_overlaps.is_empty() {
+        return;
+    }
+    egui::CollapsingHeader::new(format!("Preset overlap ({})", state.preset_overlaps.len()))
+        .id_salt("results_overlap_report")
+        .default_open(false)
+        .show(ui, |ui| {
+            for overlap in &state.preset_overlaps {
+                ui.horizontal(|ui| {
+                    ui.colored_label(state.preset_color(&overlap.first), &overlap.first);
+                    ui.label("+");
+                    ui.colored_label(state.preset_color(&overlap.second), &overlap.second);
+                    ui.label(format!(
+                        "share {} video{}",
+                        overlap.shared,
+                        if overlap.shared == 1 { "" } else { "s" }
+                    ));
+                });
             }
+        });
+}
+
+/// Non-modal offer to block a channel the user keeps dismissing videos from.
+fn render_block_suggestion(state: &mut AppState, ui: &mut egui::Ui) {
+    let Some(suggestion) = state.block_suggestion() else {
+        return;
+    };
+    let hides = state.block_impact(&suggestion.channel_id, &suggestion.channel_title);
+    let mut block = false;
+    let mut not_now = false;
+    let mut never = false;
+    ui.horizontal_wrapped(|ui| {
+        ui.colored_label(
+            ACCENT_SINGLE,
+            format!(
+                "You've dismissed {} videos from '{}' — block this channel?",
+                suggestion.count, suggestion.channel_title
+            ),
+        );
+        block = ui
+            .small_button("Block")
+            .on_hover_text(format!("This will hide {hides} videos"))
+            .clicked();
+        not_now = ui.small_button("Not now").clicked();
+        never = ui.small_button("Never ask").clicked();
+    });
+    if block {
+        state.block_channel(&suggestion.channel_id, &suggestion.channel_title);
+    } else if not_now {
+        state.snooze_block_suggestion(&suggestion.key);
+    } else if never {
+        state.never_suggest_block(&suggestion.key);
+    }
+}
+
+/// Lay out only the cards that intersect the viewport. `ScrollArea::show_rows` needs a single
+/// row height, but cards grow with expanded titles and wrapped preset chips, so each card's
+/// measured height is remembered and cards not seen yet use an estimate.
+fn render_result_list(state: &mut AppState, ui: &mut egui::Ui) {
+    let row_heights: Vec<f32> = state
+        .visible_rows
+        .iter()
+        .map(|&index| {
+            state
+                .card_heights
+                .get(&state.card_key(index))
+                .copied()
+                .unwrap_or(CARD_HEIGHT_ESTIMATE)
+                + CARD_SPACING
+        })
+        .collect();
+    let now = OffsetDateTime::now_utc();
+    let mut card_actions: Vec<CardAction> = Vec::new();
+    let mut scroll_area = egui::ScrollArea::vertical();
+    let pending_offset = match state.take_pending_scroll_offset() {
+        Some(offset) => {
+            state.pending_scroll_to = None;
+            Some(offset)
+        }
+        None => state
+            .take_pending_scroll()
+            .map(|row| row_heights[..row].iter().sum()),
+    };
+    if let Some(offset) = pending_offset {
+        scroll_area = scroll_area.vertical_scroll_offset(offset);
+    }
+    let mut top_visible: Option<CardKey> = None;
+
+    let output = show_virtual_rows(scroll_area, ui, &row_heights, |ui, row, rect| {
+        let index = state.visible_rows[row];
+        let video = state.result(index).clone();
+        let key = state.card_key(index);
+        if top_visible.is_none() {
+            top_visible = Some(key.clone());
         }
+        let mut card_ui = ui.new_child(
+            UiBuilder::new()
+                .id_salt(&key)
+                .max_rect(rect)
+                .layout(*ui.layout()),
+        );
+        render_video_card(state, &mut card_ui, &video, &key, now, &mut card_actions);
+        state.card_heights.insert(key, card_ui.min_rect().height());
     });
+
+    state.top_visible_id = top_visible;
+    state.results_scroll_offset = output.state.offset.y;
+    apply_card_actions(state, card_actions);
+}
+
+/// Draw only the rows of a tall list that intersect the viewport, handing `draw` each one's
+/// index and rect. `row_heights` includes the spacing below each row.
+fn show_virtual_rows(
+    scroll_area: egui::ScrollArea,
+    ui: &mut egui::Ui,
+    row_heights: &[f32],
+    mut draw: impl FnMut(&mut egui::Ui, usize, Rect),
+) -> egui::scroll_area::ScrollAreaOutput<()> {
+    let total_height: f32 = row_heights.iter().sum();
+    scroll_area.show_viewport(ui, |ui, viewport| {
+        ui.set_height(total_height);
+        let origin = ui.min_rect().min;
+        let width = ui.available_width();
+        let mut top = 0.0;
+        for (row, height) in row_heights.iter().enumerate() {
+            if top > viewport.max.y {
+                break;
+            }
+            let bottom = top + height;
+            if bottom >= viewport.min.y {
+                draw(
+                    ui,
+                    row,
+                    Rect::from_min_size(origin + vec2(0.0, top), vec2(width, *height)),
+                );
+            }
+            top = bottom;
+        }
+    })
+}
+
+/// Titles of the "Changes" sections, in the order of `AppState::change_sections_open`.
+const CHANGE_SECTIONS: [&str; 3] = ["New since last run", "No longer returned", "Still present"];
+const CHANGE_HEADER_HEIGHT: f32 = 28.0;
+
+/// One line of the "Changes" view.
+enum ChangeRow {
+    Header(usize),
+    Empty,
+    /// A section and the entry's value in it: a row into `results` for new and still-present
+    /// videos, an index into `previous_results` for the ones no longer returned.
+    Card(usize, usize),
+}
+
+/// The shown results split into new / unchanged, plus what the previous run had extra. The
+/// split is worked out once per run (see `AppState::change_rows`); this only lays it out,
+/// through the same virtualized list as the plain results.
+fn render_changes(state: &mut AppState, ui: &mut egui::Ui) {
+    let sections = [
+        &state.change_rows.added,
+        &state.change_rows.removed,
+        &state.change_rows.unchanged,
+    ];
+    let mut rows: Vec<ChangeRow> = Vec::new();
+    for (section, entries) in sections.into_iter().enumerate() {
+        rows.push(ChangeRow::Header(section));
+        if !state.change_sections_open[section] {
+            continue;
+        }
+        if entries.is_empty() {
+            rows.push(ChangeRow::Empty);
+        }
+        rows.extend(entries.iter().map(|&entry| ChangeRow::Card(section, entry)));
+    }
+    let row_heights: Vec<f32> = rows
+        .iter()
+        .map(|row| match *row {
+            ChangeRow::Header(_) | ChangeRow::Empty => CHANGE_HEADER_HEIGHT,
+            ChangeRow::Card(section, entry) => {
+                state
+                    .card_heights
+                    .get(&change_card_key(state, section, entry))
+                    .copied()
+                    .unwrap_or(CARD_HEIGHT_ESTIMATE)
+                    + CARD_SPACING
+            }
+        })
+        .collect();
+
+    let now = OffsetDateTime::now_utc();
+    let mut card_actions: Vec<CardAction> = Vec::new();
+    let scroll_area = egui::ScrollArea::vertical().id_salt("changes");
+    show_virtual_rows(scroll_area, ui, &row_heights, |ui, row, rect| {
+        let mut row_ui = ui.new_child(
+            UiBuilder::new()
+                .id_salt(("change row", row))
+                .max_rect(rect)
+                .layout(*ui.layout()),
+        );
+        match rows[row] {
+            ChangeRow::Header(section) => {
+                let count = [
+                    state.change_rows.added.len(),
+                    state.change_rows.removed.len(),
+                    state.change_rows.unchanged.len(),
+                ][section];
+                row_ui
+                    .toggle_value(
+                        &mut state.change_sections_open[section],
+                        RichText::new(format!("{} ({count})", CHANGE_SECTIONS[section])).strong(),
+                    )
+                    .on_hover_text("Show or hide this section");
+            }
+            ChangeRow::Empty => {
+                row_ui.label("(none)");
+            }
+            ChangeRow::Card(section, entry) => {
+                let video = if section == 1 {
+                    state
+                        .previous_results
+                        .as_ref()
+                        .map(|previous| previous[entry].clone())
+                        .unwrap_or_default()
+                } else {
+                    state.result(entry).clone()
+                };
+                let key = change_card_key(state, section, entry);
+                render_video_card(state, &mut row_ui, &video, &key, now, &mut card_actions);
+                state.card_heights.insert(key, row_ui.min_rect().height());
+            }
+        }
+    });
+    apply_card_actions(state, card_actions);
+}
+
+/// Card key for a "Changes" entry. Cards still in `results_all` share the plain list's key;
+/// removed ones are keyed past its end so they never collide with a current card.
+fn change_card_key(state: &AppState, section: usize, entry: usize) -> CardKey {
+    match &state.previous_results {
+        Some(previous) if section == 1 => {
+            (previous[entry].id.clone(), state.results_all.len() + entry)
+        }
+        _ => state.card_key(entry),
+    }
+}
+
+/// Card buttons that change the result list, applied once the list has been drawn.
+enum CardAction {
+    Block {
+        channel_id: String,
+        channel_title: String,
+    },
+    Dismiss {
+        video_id: String,
+        channel_id: String,
+        channel_title: String,
+    },
+    AddTag(String),
+    RefreshChannel(String),
+}
+
+fn apply_card_actions(state: &mut AppState, actions: Vec<CardAction>) {
+    for action in actions {
+        match action {
+            CardAction::Block {
+                channel_id,
+                channel_title,
+            } => state.block_channel(&channel_id, &channel_title),
+            CardAction::Dismiss {
+                video_id,
+                channel_id,
+                channel_title,
+            } => state.dismiss_video(&video_id, &channel_id, &channel_title),
+            CardAction::AddTag(tag) => state.add_tag_to_selected_preset(&tag),
+            CardAction::RefreshChannel(channel_id) => state.refresh_channel(&channel_id),
+        }
+    }
 }
 
 fn render_video_card(
     state: &mut AppState,
     ui: &mut egui::Ui,
     video: &VideoDetails,
-    block_requests: &mut Vec<(String, String)>,
+    key: &CardKey,
+    now: OffsetDateTime,
+    card_actions: &mut Vec<CardAction>,
 ) {
     let ctx = ui.ctx();
     let thumbnail = state.thumbnail_for_video(ctx, video);
     let thumb_loading = state.thumbnail_cache.is_loading(&video.id);
     let thumb_failed = state.thumbnail_cache.is_failed(&video.id);
+    // In Single mode the selected preset's chip leads; the rest are its other matches.
+    let current_preset = (!state.run_any_mode)
+        .then(|| state.selected_search_name())
+        .flatten();
+    let mut chips: Vec<(&String, Color32)> = video
+        .source_presets
+        .iter()
+        .map(|name| (name, state.preset_color(name)))
+        .collect();
+    chips.sort_by_key(|(name, _)| Some(name.as_str()) != current_preset.as_deref());
+    let palette = palette(ctx);
 
     Frame::default()
-        .fill(CARD_BG)
-        .stroke(Stroke::new(1.0, CARD_BORDER))
+        .fill(palette.card_bg)
+        .stroke(Stroke::new(1.0, palette.card_border))
         .corner_radius(egui::CornerRadius::same(8))
         .inner_margin(Margin::symmetric(12, 10))
         .show(ui, |ui| {
@@ -88,11 +579,38 @@ fn render_video_card(
                 });
                 ui.add_space(12.0);
                 ui.vertical(|ui| {
-                    render_title_row(ui, video);
+                    let expanded = state.expanded_titles.get(key).copied().unwrap_or(false);
+                    if render_title_row(ui, video, expanded) {
+                        state.expanded_titles.insert(key.clone(), !expanded);
+                    }
                     ui.add_space(4.0);
                     ui.horizontal(|ui| {
                         let channel_label = channel_display_label(video);
-                        ui.label(format!("Channel: {}", channel_label));
+                        let short_label = truncate_middle(&channel_label, CHANNEL_LABEL_MAX_CHARS);
+                        let mut response = ui.add(
+                            egui::Label::new(format!("Channel: {}", short_label))
+                                .sense(Sense::click()),
+                        );
+                        if short_label != channel_label {
+                            response = response.on_hover_text(&channel_label);
+                        }
+                        let channel_id = video.channel_handle.trim();
+                        response.context_menu(|ui| {
+                            if ui.button("Refresh channel info").clicked() {
+                                card_actions
+                                    .push(CardAction::RefreshChannel(channel_id.to_owned()));
+                                ui.close_menu();
+                            }
+                        });
+                        if state
+                            .channel_cache
+                            .is_stale(channel_id, now.unix_timestamp())
+                        {
+                            ui.label(RichText::new("●").small().weak()).on_hover_text(
+                                "Channel name and handle were fetched over a week ago. \
+                                 Right-click the channel to refresh them.",
+                            );
+                        }
                         if state.is_channel_blocked(video) {
                             ui.label(RichText::new("Blocked").color(ACCENT_EXTRA).strong());
                         } else {
@@ -105,31 +623,110 @@ fn render_video_card(
                             .min_size(egui::vec2(140.0, 24.0));
                             if ui
                                 .add(block_button)
-                                .on_hover_text("Hide this channel in future results")
+                                .on_hover_ui(|ui| {
+                                    ui.label("Hide this channel in future results");
+                                    let hides = state.block_impact(channel_id, &channel_label);
+                                    ui.label(format!("This will hide {hides} videos"));
+                                })
                                 .clicked()
                             {
-                                block_requests.push((
-                                    video.channel_handle.trim().to_owned(),
-                                    channel_label.clone(),
-                                ));
+                                card_actions.push(CardAction::Block {
+                                    channel_id: video.channel_handle.trim().to_owned(),
+                                    channel_title: channel_label.clone(),
+                                });
                             }
                         }
+                        let has_note = state.annotations.has_note(&video.id);
+                        let note_label = if has_note {
+                            RichText::new("📝 Note").color(ACCENT_OPEN).strong()
+                        } else {
+                            RichText::new("📝 Add note")
+                        };
+                        if ui
+                            .small_button(note_label)
+                            .on_hover_text(
+                                state
+                                    .annotations
+                                    .note(&video.id)
+                                    .unwrap_or("Jot a note on this video"),
+                            )
+                            .clicked()
+                        {
+                            state.toggle_note_editor(key);
+                        }
+                        render_share_menu(state, ui, video);
+                        if ui
+                            .small_button("Not interested")
+                            .on_hover_text("Hide this video and count it against the channel")
+                            .clicked()
+                        {
+                            card_actions.push(CardAction::Dismiss {
+                                video_id: video.id.clone(),
+                                channel_id: video.channel_handle.trim().to_owned(),
+                                channel_title: channel_label.clone(),
+                            });
+                        }
                     });
-                    ui.label(format!("Published: {}", video.published_at));
-                    ui.label(format!(
-                        "Duration: {}",
-                        format_duration(video.duration_secs)
-                    ));
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Published: {}", video.published_at));
+                        render_age_badge(ui, video, now);
+                    });
+                    ui.horizontal(|ui| {
+                        if video.duration_is_known() {
+                            ui.label(format!(
+                                "Duration: {}",
+                                format_duration(video.duration_secs)
+                            ));
+                        } else {
+                            ui.label("Duration:");
+                            render_duration_badge(ui, video);
+                        }
+                        if video.caption == Some(true) {
+                            render_flag_badge(ui, "CC", ACCENT_OPEN, "Captions available");
+                        }
+                        if video.licensed_content == Some(true) {
+                            render_flag_badge(
+                                ui,
+                                "Licensed",
+                                ACCENT_SAVE,
+                                "Licensed content from the rights holder",
+                            );
+                        }
+                        if video.is_3d() {
+                            render_flag_badge(ui, "3D", ACCENT_EXTRA, "3D video");
+                        }
+                        if video.age_restricted {
+                            render_flag_badge(
+                                ui,
+                                "⚠ 18+",
+                                ACCENT_EXTRA,
+                                "Age-restricted: YouTube asks viewers to sign in",
+                            );
+                        }
+                        if video.blocked_in_region == Some(true) {
+                            render_flag_badge(
+                                ui,
+                                "⚠ Region",
+                                ACCENT_EXTRA,
+                                "Not available in your configured region",
+                            );
+                        }
+                    });
+                    if state.open_notes.contains(key) {
+                        render_note_editor(state, ui, &video.id);
+                    }
+                    if !video.tags.is_empty() {
+                        render_tag_row(ui, video, card_actions);
+                    }
                     if !video.source_presets.is_empty() {
                         ui.add_space(6.0);
                         ui.horizontal_wrapped(|ui| {
                             ui.label("Presets:");
-                            for (idx, preset_name) in video.source_presets.iter().enumerate() {
-                                let color = PRESET_COLORS[idx % PRESET_COLORS.len()];
+                            for (preset_name, color) in &chips {
                                 let fill = color.linear_multiply(0.18);
-                                let stroke = Stroke::new(1.0, color);
-                                let text = RichText::new(preset_name).color(color);
-                                Frame::default()
+                                let stroke = Stroke::new(1.0, *color);
+                                let text = RichText::new(*preset_name).color(*color);
+                                let chip = Frame::default()
                                     .fill(fill)
                                     .stroke(stroke)
                                     .corner_radius(egui::CornerRadius::same(6))
@@ -137,6 +734,13 @@ fn render_video_card(
                                     .show(ui, |ui| {
                                         ui.label(text.clone());
                                     });
+                                if current_preset
+                                    .as_deref()
+                                    .is_some_and(|current| current != preset_name.as_str())
+                                {
+                                    chip.response
+                                        .on_hover_text(format!("Also matched by {preset_name}"));
+                                }
                             }
                         });
                     }
@@ -145,12 +749,137 @@ fn render_video_card(
         });
 }
 
-fn render_title_row(ui: &mut egui::Ui, video: &VideoDetails) {
-    let title = RichText::new(&video.title)
-        .heading()
-        .color(Color32::from_rgb(229, 231, 235));
-    let label = egui::Label::new(title).wrap();
-    ui.add(label);
+/// Collapsed row with the first few tags; clicking one adds it to the selected preset.
+fn render_tag_row(ui: &mut egui::Ui, video: &VideoDetails, card_actions: &mut Vec<CardAction>) {
+    egui::CollapsingHeader::new(
+        RichText::new(format!("Tags ({})", video.tags.len()))
+            .small()
+            .weak(),
+    )
+    .id_salt("tags")
+    .show(ui, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            for tag in video.tags.iter().take(TAG_CHIP_LIMIT) {
+                if ui
+                    .small_button(RichText::new(tag).weak())
+                    .on_hover_text("Add as an any term to the selected preset")
+                    .clicked()
+                {
+                    card_actions.push(CardAction::AddTag(tag.clone()));
+                }
+            }
+            let hidden = video.tags.len().saturating_sub(TAG_CHIP_LIMIT);
+            if hidden > 0 {
+                ui.label(RichText::new(format!("+{hidden} more")).small().weak())
+                    .on_hover_text(video.tags[TAG_CHIP_LIMIT..].join(", "));
+            }
+        });
+    });
+}
+
+/// Multi-line note for a video; saved to annotations.json shortly after typing stops.
+fn render_note_editor(state: &mut AppState, ui: &mut egui::Ui, video_id: &str) {
+    let mut text = state
+        .annotations
+        .note(video_id)
+        .unwrap_or_default()
+        .to_owned();
+    ui.add_space(4.0);
+    let response = ui.add(
+        egui::TextEdit::multiline(&mut text)
+            .hint_text("Note, e.g. \"good intro, use at 4:30\"")
+            .desired_rows(2)
+            .desired_width(f32::INFINITY),
+    );
+    if response.changed() {
+        state.set_video_note(video_id, &text);
+    }
+}
+
+/// Small outlined chip for a yes/no video property.
+fn render_flag_badge(ui: &mut egui::Ui, text: &str, color: Color32, hover: &str) {
+    Frame::default()
+        .stroke(Stroke::new(1.0, color))
+        .corner_radius(egui::CornerRadius::same(4))
+        .inner_margin(Margin::symmetric(4, 1))
+        .show(ui, |ui| {
+            ui.label(RichText::new(text).small().strong().color(color));
+        })
+        .response
+        .on_hover_text(hover);
+}
+
+fn render_duration_badge(ui: &mut egui::Ui, video: &VideoDetails) {
+    let (text, color) = match video.live_broadcast_content.as_deref() {
+        Some("live") => ("LIVE", ACCENT_SEARCH),
+        Some("upcoming") => ("Upcoming", ACCENT_OPEN),
+        _ => ("Unknown", Color32::from_gray(150)),
+    };
+    Frame::default()
+        .fill(color.linear_multiply(0.18))
+        .stroke(Stroke::new(1.0, color))
+        .corner_radius(egui::CornerRadius::same(6))
+        .inner_margin(Margin::symmetric(6, 2))
+        .show(ui, |ui| {
+            ui.label(RichText::new(text).color(color).strong());
+        });
+}
+
+fn render_age_badge(ui: &mut egui::Ui, video: &VideoDetails, now: OffsetDateTime) {
+    let Ok(published) = OffsetDateTime::parse(&video.published_at, &Rfc3339) else {
+        return;
+    };
+    let elapsed = (now - published).whole_seconds();
+    let hours = elapsed / 3600;
+    let color = if hours < RECENCY_FRESH_HOURS {
+        RECENCY_FRESH
+    } else if hours < RECENCY_RECENT_HOURS {
+        RECENCY_RECENT
+    } else {
+        RECENCY_OLD
+    };
+    Frame::default()
+        .fill(color.linear_multiply(0.18))
+        .stroke(Stroke::new(1.0, color))
+        .corner_radius(egui::CornerRadius::same(6))
+        .inner_margin(Margin::symmetric(6, 2))
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new(humanize_elapsed(elapsed))
+                    .color(color)
+                    .strong(),
+            );
+        });
+}
+
+/// Title capped at two rows unless expanded; returns true when clicked to toggle.
+fn render_title_row(ui: &mut egui::Ui, video: &VideoDetails, expanded: bool) -> bool {
+    let format = TextFormat {
+        font_id: TextStyle::Heading.resolve(ui.style()),
+        color: palette(ui.ctx()).title_text,
+        ..Default::default()
+    };
+    let mut job = LayoutJob::single_section(video.title.clone(), format);
+    if !expanded {
+        job.wrap.max_rows = TITLE_MAX_ROWS;
+        job.wrap.overflow_character = Some('…');
+    }
+    let galley = ui.fonts(|fonts| {
+        let mut measure = job.clone();
+        measure.wrap.max_width = ui.available_width();
+        fonts.layout_job(measure)
+    });
+    let label = egui::Label::new(job).wrap().sense(Sense::click());
+    let response = ui.add(label);
+    if galley.elided || expanded {
+        let hint = if expanded {
+            "Click to collapse"
+        } else {
+            "Click to show the full title"
+        };
+        return response.on_hover_text(hint).clicked();
+    }
+    false
 }
 
 fn render_thumbnail(
@@ -169,12 +898,13 @@ fn render_thumbnail(
     } else {
         let (rect, _) = ui.allocate_exact_size(desired, Sense::hover());
         let rounding = CornerRadius::same(6);
-        let bg = Color32::from_rgb(30, 34, 42);
-        ui.painter().rect_filled(rect, rounding, bg);
+        let palette = palette(ui.ctx());
+        ui.painter()
+            .rect_filled(rect, rounding, palette.placeholder_bg);
         ui.painter().rect_stroke(
             rect,
             rounding,
-            Stroke::new(1.0, CARD_BORDER),
+            Stroke::new(1.0, palette.card_border),
             StrokeKind::Inside,
         );
 
@@ -200,6 +930,64 @@ fn render_thumbnail(
     }
 }
 
+/// Copy the video ID, the link, or the link at a typed position.
+fn render_share_menu(state: &mut AppState, ui: &mut egui::Ui, video: &VideoDetails) {
+    ui.menu_button("🔗 Share", |ui| {
+        if ui.button("Copy video ID").clicked() {
+            ui.ctx().copy_text(video.id.clone());
+            state.status = format!("Copied video ID {}.", video.id);
+            ui.close_menu();
+        }
+        if ui.button("Copy link").clicked() {
+            ui.ctx().copy_text(video.watch_url());
+            state.status = "Copied video link.".into();
+            ui.close_menu();
+        }
+        if ui
+            .button("Copy as Markdown")
+            .on_hover_text("Uses the share template from Settings → General")
+            .clicked()
+        {
+            let text = share::render(&state.prefs.ui.share_template, video, ShareStyle::Markdown);
+            ui.ctx().copy_text(text);
+            state.status = "Copied video as Markdown.".into();
+            ui.close_menu();
+        }
+        if ui.button("Copy as plain text").clicked() {
+            ui.ctx().copy_text(share::plain_text(video));
+            state.status = "Copied video as plain text.".into();
+            ui.close_menu();
+        }
+        ui.separator();
+        let timestamp = parse_timestamp(&state.share_timestamp);
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut state.share_timestamp)
+                    .hint_text("mm:ss")
+                    .desired_width(64.0),
+            );
+            if ui
+                .add_enabled(timestamp.is_ok(), egui::Button::new("Copy link at time"))
+                .clicked()
+                && let Ok(secs) = timestamp
+            {
+                ui.ctx().copy_text(link_at_time(&video.watch_url(), secs));
+                state.status = format!("Copied video link at {}.", state.share_timestamp.trim());
+                ui.close_menu();
+            }
+        });
+        if !state.share_timestamp.trim().is_empty()
+            && let Err(err) = &timestamp
+        {
+            ui.label(
+                RichText::new(err)
+                    .small()
+                    .color(ui.visuals().error_fg_color),
+            );
+        }
+    });
+}
+
 fn render_open_button(state: &mut AppState, ui: &mut egui::Ui, video: &VideoDetails) {
     let open_button = egui::Button::new(RichText::new("Open").strong().color(Color32::WHITE))
         .fill(ACCENT_OPEN)
@@ -208,8 +996,9 @@ fn render_open_button(state: &mut AppState, ui: &mut egui::Ui, video: &VideoDeta
         .add_sized(egui::vec2(MAX_THUMB_WIDTH, 30.0), open_button)
         .on_hover_text("Open video in your browser");
     if response.clicked() {
-        match open_in_browser(&video.url) {
+        match open_in_browser(&video.watch_url()) {
             Ok(()) => {
+                state.opened_videos.insert(video.id.clone());
                 state.status = "Opened video in browser.".into();
             }
             Err(err) => {
@@ -0,0 +1,614 @@
+use egui::{Align, Color32, Context, Layout, RichText};
+use time::OffsetDateTime;
+
+use crate::channel_cache;
+use crate::config_dir;
+use crate::prefs::{
+    self, BroadcastEvent, COMMON_REGION_CODES, CompletionNotice, DEFAULT_SHARE_TEMPLATE,
+    DuplicatePolicy, DurationBucketConfig, ResultSort, ThemeChoice, TimeWindowPreset,
+};
+use crate::search_runner;
+use crate::ui::settings::{SettingsState, SettingsTab};
+use crate::ui::theme::ACCENT_SAVE;
+use crate::ui::utils::{
+    broadcast_event_label, completion_notice_label, duplicate_policy_label, theme_choice_label,
+    time_window_label,
+};
+use crate::yt::http;
+
+use super::AppState;
+
+pub(super) fn render(state: &mut AppState, ctx: &Context) {
+    let mut wants_apply = false;
+    let mut wants_cancel = false;
+    let mut wants_reset = false;
+    let mut wants_channel_refresh = false;
+    if state.settings_dialog.is_none() {
+        return;
+    }
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let channels = ChannelSummary {
+        cached: state.channel_cache.channels.len(),
+        stale: state
+            .channel_cache
+            .refresh_candidates(&state.results_all, now)
+            .len(),
+        refreshing: state.channel_lookups.refreshing(),
+    };
+    let losses = prefs::ResetLosses::of(&state.prefs, state.results_all.len());
+    let mut backup_before_reset = true;
+
+    if let Some(settings) = state.settings_dialog.as_mut() {
+        let mut open = true;
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.set_min_width(520.0);
+                ui.horizontal(|ui| {
+                    for tab in SettingsTab::ALL {
+                        ui.selectable_value(&mut settings.tab, tab, tab.label());
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(420.0)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| match settings.tab {
+                        SettingsTab::General => render_general(ui, settings),
+                        SettingsTab::SearchDefaults => render_search_defaults(ui, settings),
+                        SettingsTab::Network => render_network(ui, settings),
+                        SettingsTab::Appearance => render_appearance(ui, settings),
+                        SettingsTab::Data => match render_data(ui, settings, &channels, &losses) {
+                            Some(DataAction::Reset) => wants_reset = true,
+                            Some(DataAction::RefreshChannels) => wants_channel_refresh = true,
+                            None => {}
+                        },
+                    });
+
+                if let Some(err) = settings.error.as_ref() {
+                    ui.add_space(6.0);
+                    ui.colored_label(Color32::from_rgb(239, 68, 68), err);
+                }
+
+                ui.add_space(10.0);
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui
+                        .add(
+                            egui::Button::new(RichText::new("Apply").color(Color32::WHITE))
+                                .fill(ACCENT_SAVE),
+                        )
+                        .clicked()
+                    {
+                        wants_apply = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        wants_cancel = true;
+                    }
+                });
+            });
+
+        if !open {
+            wants_cancel = true;
+        }
+        backup_before_reset = settings.backup_before_reset;
+    }
+
+    if wants_channel_refresh {
+        state.refresh_stale_channels();
+    }
+    if wants_reset {
+        state.cancel_settings();
+        state.reset_with_backup(backup_before_reset);
+    } else if wants_apply {
+        state.apply_settings(ctx);
+    } else if wants_cancel {
+        state.cancel_settings();
+    }
+}
+
+fn render_general(ui: &mut egui::Ui, settings: &mut SettingsState) {
+    ui.label("YouTube Data API key:");
+    ui.add(egui::TextEdit::singleline(&mut settings.api_key).password(true));
+    ui.small("Saved to prefs.json in your YTSearch config directory.");
+
+    ui.add_space(8.0);
+    egui::ComboBox::from_label("Default date window")
+        .selected_text(time_window_label(settings.search_defaults.default_window))
+        .show_ui(ui, |ui| {
+            for preset in [
+                TimeWindowPreset::Today,
+                TimeWindowPreset::H48,
+                TimeWindowPreset::D7,
+                TimeWindowPreset::AllTime,
+                TimeWindowPreset::SinceLastRun,
+            ] {
+                ui.selectable_value(
+                    &mut settings.search_defaults.default_window,
+                    preset,
+                    time_window_label(preset),
+                );
+            }
+        });
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("Suggest blocking a channel after");
+        ui.add(
+            egui::DragValue::new(&mut settings.ui.block_suggestion_threshold)
+                .range(0..=100)
+                .suffix(" dismissed videos"),
+        );
+    })
+    .response
+    .on_hover_text("Counts \"Not interested\" clicks per channel. 0 turns suggestions off.");
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("\"Open next\" opens");
+        ui.add(
+            egui::DragValue::new(&mut settings.ui.open_batch_size)
+                .range(1..=20)
+                .suffix(" videos"),
+        );
+    })
+    .response
+    .on_hover_text("How many unopened videos each click of the results header button opens.");
+
+    ui.add_space(8.0);
+    egui::ComboBox::from_label("Sort results on launch by")
+        .selected_text(
+            settings
+                .ui
+                .default_result_sort
+                .map_or("Last used", ResultSort::label),
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut settings.ui.default_result_sort, None, "Last used");
+            for sort in ResultSort::ALL {
+                ui.selectable_value(
+                    &mut settings.ui.default_result_sort,
+                    Some(sort),
+                    sort.label(),
+                );
+            }
+        });
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("Markdown share template");
+        ui.add(
+            egui::TextEdit::singleline(&mut settings.ui.share_template)
+                .hint_text(DEFAULT_SHARE_TEMPLATE)
+                .desired_width(320.0),
+        );
+        if ui.small_button("Reset").clicked() {
+            settings.ui.share_template = DEFAULT_SHARE_TEMPLATE.to_owned();
+        }
+    })
+    .response
+    .on_hover_text(
+        "Used by \"Copy as Markdown\" on cards and the results list. Placeholders: {title}, \
+         {url}, {channel}, {duration}, {published}.",
+    );
+
+    ui.add_space(8.0);
+    ui.checkbox(
+        &mut settings.ui.indexed_find,
+        "Index results for the find box",
+    )
+    .on_hover_text(
+        "Keeps a word index of every video seen (titles, channels, and descriptions) in the \
+             config folder, so find stays quick with thousands of cached results and \
+             \"History\" can search past runs. Updated whenever results arrive.",
+    );
+}
+
+fn render_search_defaults(ui: &mut egui::Ui, settings: &mut SettingsState) {
+    let defaults = &mut settings.search_defaults;
+    ui.checkbox(&mut defaults.english_only, "English only");
+    ui.checkbox(&mut defaults.require_captions, "Require captions");
+    ui.checkbox(
+        &mut defaults.require_captions_strict,
+        "Require captions, checked per video",
+    )
+    .on_hover_text("Drop results whose video details report no captions (no extra API calls)");
+    ui.checkbox(
+        &mut defaults.verify_captions_with_oauth,
+        "Verify captions with OAuth (not yet used)",
+    );
+    ui.checkbox(&mut defaults.only_licensed, "Only licensed content")
+        .on_hover_text(
+            "Drop videos not marked as licensed content, which filters out most reuploads",
+        );
+    ui.checkbox(&mut defaults.exclude_3d, "Exclude 3D videos");
+    ui.checkbox(
+        &mut defaults.hide_restricted,
+        "Hide age-restricted and region-blocked videos",
+    )
+    .on_hover_text("Region blocks are checked against the configured region code");
+    ui.checkbox(
+        &mut defaults.strict_all_terms,
+        "Require every \"all\" term in the title",
+    )
+    .on_hover_text(
+        "YouTube's search matches loosely; drop results whose title (or tags, for presets that \
+         match tags) is missing one of the preset's required terms",
+    );
+    ui.horizontal(|ui| {
+        ui.label("Min duration (s):");
+        ui.add(egui::DragValue::new(&mut defaults.min_duration_secs).range(0..=7200));
+    });
+    ui.horizontal(|ui| {
+        ui.label("Max results:");
+        ui.add(egui::DragValue::new(&mut defaults.max_total_results).range(0..=100_000))
+            .on_hover_text("Keep at most this many results per run, newest first, so huge runs stay responsive and the cache stays small. 0 keeps everything.");
+    });
+    ui.checkbox(
+        &mut defaults.include_unknown_duration,
+        "Keep videos whose duration is unknown",
+    )
+    .on_hover_text("Live and upcoming broadcasts are always kept; this covers videos whose length could not be read.");
+    ui.checkbox(
+        &mut settings.ui.dedup_across_presets,
+        "Merge Single-mode runs into loaded results",
+    )
+    .on_hover_text("Keep one card per video with every matching preset tagged, even as you switch presets or modes.");
+    egui::ComboBox::from_label("Duplicates")
+        .selected_text(duplicate_policy_label(defaults.duplicate_policy))
+        .show_ui(ui, |ui| {
+            for policy in DuplicatePolicy::ALL {
+                ui.selectable_value(
+                    &mut defaults.duplicate_policy,
+                    policy,
+                    duplicate_policy_label(policy),
+                );
+            }
+        })
+        .response
+        .on_hover_text("How a run folds videos that several presets returned. \"Re-uploads\" also merges videos from one channel with the same title.");
+    egui::ComboBox::from_label("Broadcasts")
+        .selected_text(broadcast_event_label(defaults.event_type))
+        .show_ui(ui, |ui| {
+            for event in [
+                None,
+                Some(BroadcastEvent::Live),
+                Some(BroadcastEvent::Upcoming),
+                Some(BroadcastEvent::Completed),
+            ] {
+                ui.selectable_value(
+                    &mut defaults.event_type,
+                    event,
+                    broadcast_event_label(event),
+                );
+            }
+        });
+    ui.horizontal(|ui| {
+        ui.label("Region:");
+        let current = settings.region_text.trim().to_ascii_uppercase();
+        let selected_text = if current.is_empty() {
+            "None".to_string()
+        } else {
+            COMMON_REGION_CODES
+                .iter()
+                .find(|(code, _)| *code == current)
+                .map(|(code, name)| format!("{code} · {name}"))
+                .unwrap_or(current)
+        };
+        egui::ComboBox::from_id_salt("settings_region")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                if ui.selectable_label(false, "None").clicked() {
+                    settings.region_text.clear();
+                }
+                for (code, name) in COMMON_REGION_CODES {
+                    if ui
+                        .selectable_label(false, format!("{code} · {name}"))
+                        .clicked()
+                    {
+                        settings.region_text = (*code).to_string();
+                    }
+                }
+            });
+        ui.add(
+            egui::TextEdit::singleline(&mut settings.region_text)
+                .hint_text("US")
+                .desired_width(48.0)
+                .char_limit(4),
+        );
+    });
+    match prefs::parse_region_code(&settings.region_text) {
+        Ok(_) => {
+            ui.small("Pick a region or type any two-letter code; None searches without one.");
+        }
+        Err(msg) => {
+            ui.colored_label(Color32::from_rgb(239, 68, 68), msg);
+        }
+    }
+
+    ui.add_space(8.0);
+    ui.separator();
+    ui.label("Duration buckets");
+    let filters = &mut settings.search_defaults.duration_filters;
+    ui.checkbox(
+        &mut filters.allow_multiple,
+        "Allow selecting several buckets at once",
+    )
+    .on_hover_text(
+        "When off, the Length row works like radio buttons: picking one clears the rest",
+    );
+
+    let mut removals: Vec<usize> = Vec::new();
+    egui::Grid::new("settings_duration_buckets")
+        .num_columns(7)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Id");
+            ui.label("Label");
+            ui.label("Min (s)");
+            ui.label("Max (s)");
+            ui.label("Default");
+            ui.label("Active");
+            ui.label("");
+            ui.end_row();
+
+            for (idx, bucket) in filters.buckets.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(&mut bucket.id).desired_width(80.0));
+                ui.add(egui::TextEdit::singleline(&mut bucket.label).desired_width(140.0));
+                ui.add(egui::DragValue::new(&mut bucket.min_seconds).range(0..=86_400));
+                ui.horizontal(|ui| {
+                    let mut has_max = bucket.max_seconds.is_some();
+                    if ui.checkbox(&mut has_max, "").changed() {
+                        bucket.max_seconds = if has_max {
+                            Some(bucket.min_seconds + 60)
+                        } else {
+                            None
+                        };
+                    }
+                    if let Some(max) = bucket.max_seconds.as_mut() {
+                        ui.add(egui::DragValue::new(max).range(0..=86_400));
+                    } else {
+                        ui.label("none");
+                    }
+                });
+                ui.checkbox(&mut bucket.default_selected, "");
+                let mut active =
+                    global_active(&settings.search_defaults.active_duration_bucket_ids, bucket);
+                if ui.checkbox(&mut active, "").changed() {
+                    let ids = &mut settings.search_defaults.active_duration_bucket_ids;
+                    ids.retain(|id| id != &bucket.id);
+                    if active {
+                        ids.push(bucket.id.clone());
+                    }
+                }
+                if ui.small_button("Remove").clicked() {
+                    removals.push(idx);
+                }
+                ui.end_row();
+            }
+        });
+
+    for idx in removals.into_iter().rev() {
+        let removed = filters.buckets.remove(idx);
+        settings
+            .search_defaults
+            .active_duration_bucket_ids
+            .retain(|id| id != &removed.id);
+    }
+
+    if ui.button("Add bucket").clicked() {
+        let next = filters.buckets.len() + 1;
+        filters.buckets.push(DurationBucketConfig {
+            id: format!("bucket-{next}"),
+            label: format!("Bucket {next}"),
+            min_seconds: 0,
+            max_seconds: None,
+            default_selected: false,
+        });
+    }
+}
+
+fn global_active(active_ids: &[String], bucket: &DurationBucketConfig) -> bool {
+    active_ids.iter().any(|id| id == &bucket.id)
+}
+
+fn render_network(ui: &mut egui::Ui, settings: &mut SettingsState) {
+    ui.label(format!(
+        "Search pages per preset: {} (set YTSEARCH_MAX_SEARCH_PAGES to change, 1-10).",
+        search_runner::max_search_pages()
+    ));
+    ui.add_space(6.0);
+    ui.horizontal(|ui| {
+        ui.label("Daily quota budget (units):");
+        ui.add(egui::DragValue::new(&mut settings.ui.daily_quota_budget).range(0..=1_000_000));
+    });
+    ui.small("Runs estimated to overshoot what is left of this today ask before starting. 0 turns the check off.");
+    ui.add_space(6.0);
+    ui.horizontal(|ui| {
+        ui.label("Proxy:");
+        ui.add_enabled(
+            !settings.search_defaults.no_proxy,
+            egui::TextEdit::singleline(&mut settings.proxy_text)
+                .hint_text("http://10.0.0.1:3128")
+                .desired_width(220.0),
+        );
+    })
+    .response
+    .on_hover_text(format!(
+        "Used for API calls and thumbnails. Precedence: \"No proxy\" first, then this URL, \
+         then {}, otherwise a direct connection.",
+        http::ENV_PROXY_VARS.join(" / ")
+    ));
+    if !settings.proxy_text.trim().is_empty()
+        && let Err(err) = http::validate_proxy_url(&settings.proxy_text)
+    {
+        ui.colored_label(Color32::from_rgb(239, 68, 68), err);
+    }
+    ui.checkbox(
+        &mut settings.search_defaults.no_proxy,
+        "No proxy (ignore proxy environment variables)",
+    );
+    ui.add_space(6.0);
+    ui.checkbox(
+        &mut settings.search_defaults.keep_raw_responses,
+        "Keep raw search responses (debug)",
+    )
+    .on_hover_text(
+        "Remember every search.list page of the last run and what happened to each item; \
+         open it with \"🔎 Raw\" next to the status line. Replaced on the next run.",
+    );
+    ui.add_space(6.0);
+    ui.label("Fallback API keys:");
+    ui.small(
+        "When a request fails for quota or key reasons, YT_API_private.alt, YT_API_private,old, and YT_API_private in the working directory are tried in order.",
+    );
+}
+
+fn render_appearance(ui: &mut egui::Ui, settings: &mut SettingsState) {
+    egui::ComboBox::from_label("Theme")
+        .selected_text(theme_choice_label(settings.ui.theme))
+        .show_ui(ui, |ui| {
+            for choice in [ThemeChoice::System, ThemeChoice::Dark, ThemeChoice::Light] {
+                ui.selectable_value(&mut settings.ui.theme, choice, theme_choice_label(choice));
+            }
+        });
+    ui.small("Follow system re-applies the palette when your OS theme changes.");
+
+    ui.add_space(8.0);
+    egui::ComboBox::from_label("When a search finishes in the background")
+        .selected_text(completion_notice_label(settings.ui.notify_on_completion))
+        .show_ui(ui, |ui| {
+            for notice in [
+                CompletionNotice::None,
+                CompletionNotice::Flash,
+                CompletionNotice::Sound,
+                CompletionNotice::Both,
+            ] {
+                ui.selectable_value(
+                    &mut settings.ui.notify_on_completion,
+                    notice,
+                    completion_notice_label(notice),
+                );
+            }
+        });
+    if !cfg!(feature = "sound") && settings.ui.notify_on_completion.plays_sound() {
+        ui.small("This build was compiled without audio support, so no sound will play.");
+    }
+}
+
+/// Channel cache counts shown on the Data tab.
+struct ChannelSummary {
+    cached: usize,
+    /// Cached channels past the TTL plus loaded channels never cached.
+    stale: usize,
+    refreshing: bool,
+}
+
+enum DataAction {
+    Reset,
+    RefreshChannels,
+}
+
+fn render_data(
+    ui: &mut egui::Ui,
+    settings: &mut SettingsState,
+    channels: &ChannelSummary,
+    losses: &prefs::ResetLosses,
+) -> Option<DataAction> {
+    let config = config_dir::get();
+    ui.label("Presets, block list, and settings are stored in prefs.json.");
+    ui.small("Search results are cached in last_results.json next to it.");
+    ui.small("Videos hidden with \"Not interested\" are listed in dismissals.json.");
+    ui.small(format!("Folder: {}", config.path.display()));
+    if !config.writable {
+        ui.colored_label(
+            Color32::from_rgb(239, 68, 68),
+            "This folder is not writable, so nothing will be saved.",
+        );
+    }
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label("Delete cached thumbnails older than");
+        ui.add(
+            egui::DragValue::new(&mut settings.ui.thumbnail_max_age_days)
+                .range(0..=3650)
+                .suffix(" days"),
+        );
+    })
+    .response
+    .on_hover_text("Checked on startup. 0 keeps thumbnails forever.");
+    ui.add_space(8.0);
+
+    let mut action = None;
+    ui.small(format!(
+        "Channel names and handles for {} channel(s) are cached in channels.json; {} need refreshing.",
+        channels.cached, channels.stale
+    ));
+    ui.horizontal(|ui| {
+        let refresh = ui
+            .add_enabled(
+                !channels.refreshing && channels.stale > 0,
+                egui::Button::new("Refresh all channel info"),
+            )
+            .on_hover_text(format!(
+                "Refetch channels cached over {} days ago, 50 per request (1 quota unit each).",
+                channel_cache::CHANNEL_TTL_SECS / 86_400
+            ));
+        if refresh.clicked() {
+            action = Some(DataAction::RefreshChannels);
+        }
+        if channels.refreshing {
+            ui.spinner();
+        }
+    });
+    ui.add_space(8.0);
+
+    if settings.confirm_reset {
+        ui.label("Restore built-in presets, clear blocks, and reset filters?");
+        ui.label(format!(
+            "This removes {}. The API key and min duration are kept.",
+            losses.summary()
+        ));
+        ui.checkbox(
+            &mut settings.backup_before_reset,
+            "Back up the current prefs first",
+        )
+        .on_hover_text(format!(
+            "Saved as a timestamped file in {}; the newest {} backups are kept.",
+            config_dir::get().backups_dir().display(),
+            prefs::PREFS_BACKUP_KEEP
+        ));
+        ui.horizontal(|ui| {
+            if ui
+                .add(
+                    egui::Button::new(RichText::new("Reset").color(Color32::WHITE))
+                        .fill(Color32::from_rgb(200, 60, 60)),
+                )
+                .clicked()
+            {
+                action = Some(DataAction::Reset);
+            }
+            if ui.button("Keep current").clicked() {
+                settings.confirm_reset = false;
+            }
+        });
+    } else {
+        let reset_button = egui::Button::new(
+            RichText::new("Reset defaults")
+                .strong()
+                .color(Color32::WHITE),
+        )
+        .fill(Color32::from_rgb(200, 60, 60))
+        .min_size(egui::vec2(140.0, 28.0));
+        if ui
+            .add(reset_button)
+            .on_hover_text("Restore built-in presets, clear blocks, and reset filters")
+            .clicked()
+        {
+            settings.confirm_reset = true;
+        }
+    }
+    action
+}
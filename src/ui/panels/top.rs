@@ -1,31 +1,34 @@
-use egui::{Align, Color32, Context, Frame, Layout, Margin, RichText};
+use egui::{Align, Color32, Context, Frame, Layout, Margin, RichText, Sense, TextStyle};
 
 use crate::prefs::TimeWindowPreset;
+use crate::ui::pending_ops::PendingOp;
+use crate::ui::shortcuts::Shortcut;
 use crate::ui::theme::{
-    ACCENT_ANY, ACCENT_SEARCH, ACCENT_SINGLE, PANEL_FILL, PRESET_COLORS, STATUS_ACCENT,
+    ACCENT_ANY, ACCENT_SEARCH, ACCENT_SINGLE, PRESET_COLORS, STATUS_ACCENT, palette,
     tinted_toggle_button,
 };
-use crate::ui::utils::time_window_label;
+use crate::ui::utils::{elide_middle_to_width, time_window_label};
 
 use super::AppState;
 
+/// Seconds the "Copied" note stays next to a clicked status line.
+const STATUS_COPIED_SECS: f64 = 1.5;
+
 pub(super) fn render(state: &mut AppState, ctx: &Context) -> bool {
     let mut search_requested = false;
+    let mut quick_requested = false;
 
     egui::TopBottomPanel::top("top")
         .resizable(false)
         .show(ctx, |ui| {
             Frame::default()
-                .fill(PANEL_FILL)
+                .fill(palette(ctx).panel_fill)
                 .inner_margin(Margin::symmetric(16, 12))
                 .show(ui, |ui| {
                     ui.vertical(|ui| {
                         ui.horizontal(|ui| {
-                            ui.heading(
-                                RichText::new("YTSearch").color(Color32::from_rgb(229, 231, 235)),
-                            );
+                            ui.heading(RichText::new("YTSearch").color(palette(ctx).title_text));
                             ui.add_space(12.0);
-                            ui.colored_label(STATUS_ACCENT, RichText::new(&state.status).strong());
                             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                                 if ui
                                     .button("Help")
@@ -34,21 +37,72 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) -> bool {
                                 {
                                     state.show_help_dialog = true;
                                 }
+                                if ui
+                                    .button("Settings")
+                                    .on_hover_text("API key, search defaults, theme, and data")
+                                    .clicked()
+                                {
+                                    state.open_settings();
+                                }
+                                if ui
+                                    .button("💾 Save all")
+                                    .on_hover_text(format!(
+                                        "Save prefs, loaded results, notes, and view state ({})",
+                                        Shortcut::SaveAll.label(ctx)
+                                    ))
+                                    .clicked()
+                                {
+                                    state.save_everything();
+                                }
                                 ui.add_space(6.0);
                                 let search_button = egui::Button::new(
                                     RichText::new("Search").strong().color(Color32::WHITE),
                                 )
                                 .fill(ACCENT_SEARCH)
                                 .min_size(egui::vec2(120.0, 32.0));
+                                let searching = state.pending_ops.is_pending(PendingOp::Search);
                                 if ui
-                                    .add(search_button)
+                                    .add_enabled(!searching, search_button)
                                     .on_hover_text(
                                         "Fetch results from YouTube with current filters",
                                     )
+                                    .on_disabled_hover_text("A search is already running")
                                     .clicked()
                                 {
                                     search_requested = true;
                                 }
+                                if searching {
+                                    ui.spinner();
+                                }
+                                if state.prefs.ui.indexed_find
+                                    && ui
+                                        .small_button("🕘 History")
+                                        .on_hover_text("Search everything you've ever seen")
+                                        .clicked()
+                                {
+                                    state.history_search.open = true;
+                                }
+                                if !state.raw_pages.is_empty()
+                                    && ui
+                                        .small_button("🔎 Raw")
+                                        .on_hover_text("Inspect the raw search.list pages of the last run")
+                                        .clicked()
+                                {
+                                    state.show_raw_responses = true;
+                                }
+                                if state.last_error.is_some()
+                                    && ui
+                                        .small_button("⚠ Details")
+                                        .on_hover_text("Show the last error in full")
+                                        .clicked()
+                                {
+                                    state.show_help_dialog = true;
+                                }
+                                ui.add_space(6.0);
+                                ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                                    let max_width = ui.available_width();
+                                    render_status_label(ui, &state.status, max_width);
+                                });
                             });
                         });
                         ui.add_space(8.0);
@@ -86,12 +140,7 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) -> bool {
                                 state.refresh_visible_results();
                             }
                             if state.run_any_mode {
-                                let enabled = state
-                                    .prefs
-                                    .searches
-                                    .iter()
-                                    .filter(|preset| preset.enabled)
-                                    .count();
+                                let enabled = state.enabled_preset_count();
                                 ui.add_space(8.0);
                                 ui.label(format!(
                                     "{} preset{} enabled",
@@ -104,48 +153,41 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) -> bool {
                             }
                             ui.add_space(12.0);
                             egui::ComboBox::from_label("Date window")
-                                .selected_text(time_window_label(state.prefs.global.default_window))
+                                .selected_text(time_window_label(state.prefs.search_defaults.default_window))
                                 .show_ui(ui, |ui| {
                                     ui.selectable_value(
-                                        &mut state.prefs.global.default_window,
+                                        &mut state.prefs.search_defaults.default_window,
                                         TimeWindowPreset::Today,
                                         "Today",
                                     );
                                     ui.selectable_value(
-                                        &mut state.prefs.global.default_window,
+                                        &mut state.prefs.search_defaults.default_window,
                                         TimeWindowPreset::H48,
                                         "48h",
                                     );
                                     ui.selectable_value(
-                                        &mut state.prefs.global.default_window,
+                                        &mut state.prefs.search_defaults.default_window,
                                         TimeWindowPreset::D7,
                                         "7d",
                                     );
                                     ui.selectable_value(
-                                        &mut state.prefs.global.default_window,
+                                        &mut state.prefs.search_defaults.default_window,
                                         TimeWindowPreset::AllTime,
                                         "Any date",
                                     );
+                                    ui.selectable_value(
+                                        &mut state.prefs.search_defaults.default_window,
+                                        TimeWindowPreset::SinceLastRun,
+                                        "Since last search",
+                                    )
+                                    .on_hover_text(
+                                        "Videos published since your last successful search, with a 30 minute overlap",
+                                    );
                                 });
-                            ui.add_space(12.0);
-                            let old_english_only = state.prefs.global.english_only;
-                            ui.checkbox(&mut state.prefs.global.english_only, "English only");
-                            if old_english_only != state.prefs.global.english_only {
-                                state.refresh_visible_results();
-                            }
-                            let old_require_captions = state.prefs.global.require_captions;
-                            ui.checkbox(
-                                &mut state.prefs.global.require_captions,
-                                "Require captions",
-                            );
-                            if old_require_captions != state.prefs.global.require_captions {
-                                state.refresh_visible_results();
-                            }
-                            ui.label("Min duration (s):");
-                            ui.add(
-                                egui::DragValue::new(&mut state.prefs.global.min_duration_secs)
-                                    .range(0..=7200),
-                            );
+                        });
+                        ui.add_space(6.0);
+                        ui.horizontal(|ui| {
+                            quick_requested = render_quick_search(ui, state);
                         });
                         ui.add_space(6.0);
                         let length_buttons: Vec<(String, String, bool, Color32)> = state
@@ -155,7 +197,7 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) -> bool {
                             .enumerate()
                             .map(|(idx, bucket)| {
                                 let color = PRESET_COLORS[idx % PRESET_COLORS.len()];
-                                let label = if bucket.selected {
+                                let label = if bucket.selected && state.duration_filter.allow_multiple {
                                     format!("● {}", bucket.config.label)
                                 } else {
                                     bucket.config.label.clone()
@@ -164,23 +206,162 @@ pub(super) fn render(state: &mut AppState, ctx: &Context) -> bool {
                             })
                             .collect();
                         if !length_buttons.is_empty() {
+                            let unfiltered = state.duration_filter.is_unfiltered();
+                            let single_select = !state.duration_filter.allow_multiple;
+                            let mut reset_lengths = false;
                             ui.horizontal_wrapped(|ui| {
-                                ui.label("Length:");
+                                if single_select {
+                                    ui.label("Length (pick one):")
+                                        .on_hover_text("Single-select mode; change it in Settings → Search defaults");
+                                } else {
+                                    ui.label("Length:");
+                                }
+                                ui.add_space(4.0);
+                                if ui
+                                    .add_enabled(!unfiltered, egui::Button::new("All lengths"))
+                                    .on_hover_text("Stop filtering by length")
+                                    .clicked()
+                                {
+                                    reset_lengths = true;
+                                }
                                 ui.add_space(4.0);
                                 for (id, label, selected, color) in length_buttons {
-                                    if tinted_toggle_button(ui, selected, label.as_str(), color)
-                                        && state.duration_filter.toggle(&id)
-                                    {
+                                    let clicked = if single_select {
+                                        ui.radio(selected, label.as_str()).clicked()
+                                    } else {
+                                        tinted_toggle_button(ui, selected, label.as_str(), color)
+                                    };
+                                    if clicked && state.duration_filter.toggle(&id) {
                                         state.normalize_duration_selection();
                                         state.refresh_visible_results();
                                     }
                                     ui.add_space(4.0);
                                 }
+                                if !unfiltered
+                                    && ui
+                                        .small_button("✕ Clear")
+                                        .on_hover_text("Clear length filters")
+                                        .clicked()
+                                {
+                                    reset_lengths = true;
+                                }
                             });
+                            if reset_lengths && state.duration_filter.reset_to_catch_all() {
+                                state.normalize_duration_selection();
+                                state.refresh_visible_results();
+                            }
                         }
                     });
                 });
         });
 
+    if quick_requested {
+        state.launch_quick_search();
+    }
     search_requested
 }
+
+/// Quick-search field with its recent-query dropdown. Returns true when a run was requested.
+fn render_quick_search(ui: &mut egui::Ui, state: &mut AppState) -> bool {
+    let mut requested = false;
+    ui.label("Quick search:");
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut state.quick_query)
+            .hint_text("One-off query, not saved as a preset")
+            .desired_width(260.0),
+    );
+    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+        requested = true;
+    }
+    let searching = state.pending_ops.is_pending(PendingOp::Search);
+    let has_text = !state.quick_query.trim().is_empty();
+    if ui
+        .add_enabled(!searching && has_text, egui::Button::new("Go"))
+        .on_hover_text("Search this text once using the global filters")
+        .clicked()
+    {
+        requested = true;
+    }
+    if ui
+        .add_enabled(
+            has_text || state.quick_search.is_some(),
+            egui::Button::new("Save as preset…"),
+        )
+        .on_hover_text("Open the preset editor pre-filled with this query")
+        .clicked()
+    {
+        state.promote_quick_search();
+    }
+    let mut picked: Option<String> = None;
+    let mut clear_history = false;
+    ui.add_enabled_ui(!state.prefs.recent_queries.is_empty(), |ui| {
+        ui.menu_button("Recent ▾", |ui| {
+            for query in &state.prefs.recent_queries {
+                if ui.button(query).clicked() {
+                    picked = Some(query.clone());
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+            if ui.button("Clear history").clicked() {
+                clear_history = true;
+                ui.close_menu();
+            }
+        })
+        .response
+        .on_hover_text("Re-run a recent quick search");
+    });
+    if let Some(query) = picked {
+        state.quick_query = query;
+        requested = true;
+    }
+    if clear_history {
+        state.clear_recent_queries();
+    }
+    if let Some(search) = &state.quick_search {
+        ui.add_space(8.0);
+        ui.weak(format!("Showing {}", search.name));
+    }
+    requested && !searching
+}
+
+/// Status text elided in the middle to fit `max_width`, with the full text on hover.
+/// Clicking copies the full text and briefly shows "Copied" beside it.
+pub(super) fn render_status_label(ui: &mut egui::Ui, text: &str, max_width: f32) -> egui::Response {
+    let font = TextStyle::Body.resolve(ui.style());
+    let copied_id = ui.id().with("status_copied_at");
+    let now = ui.input(|i| i.time);
+    let copied_at: Option<f64> = ui.ctx().data(|d| d.get_temp(copied_id));
+    let show_copied = copied_at.is_some_and(|at| now - at < STATUS_COPIED_SECS);
+    let note_width = if show_copied { 70.0 } else { 0.0 };
+
+    let shown = elide_middle_to_width(text, (max_width - note_width).max(0.0), |candidate| {
+        ui.fonts(|fonts| {
+            fonts
+                .layout_no_wrap(candidate.to_owned(), font.clone(), STATUS_ACCENT)
+                .size()
+                .x
+        })
+    });
+    let hover = if shown == text {
+        "Click to copy".to_owned()
+    } else {
+        format!("{text}\n\nClick to copy")
+    };
+    let response = ui
+        .add(
+            egui::Label::new(RichText::new(shown).strong().color(STATUS_ACCENT))
+                .sense(Sense::click()),
+        )
+        .on_hover_text(hover);
+    if response.clicked() {
+        ui.ctx().copy_text(text.to_owned());
+        ui.ctx().data_mut(|d| d.insert_temp(copied_id, now));
+    }
+    if show_copied {
+        ui.small("✓ Copied");
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_secs_f64(STATUS_COPIED_SECS));
+    }
+    response
+}
@@ -0,0 +1,61 @@
+use egui::{Context, RichText};
+
+use super::AppState;
+
+/// First-run note shown when no prefs.json existed at startup.
+pub(super) fn render(state: &mut AppState, ctx: &Context) {
+    if !state.show_welcome {
+        return;
+    }
+    let mut open = true;
+    let mut dismiss = false;
+    let mut open_settings = false;
+    let examples: Vec<(String, Option<String>)> = state
+        .prefs
+        .searches
+        .iter()
+        .filter(|search| search.description.is_some())
+        .map(|search| (search.name.clone(), search.description.clone()))
+        .collect();
+    egui::Window::new("Welcome to YTSearch")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_max_width(420.0);
+            ui.label("YTSearch runs your saved searches (presets) against the YouTube Data API and lists what is new.");
+            ui.add_space(6.0);
+            if !examples.is_empty() {
+                ui.label("The sidebar starts with example presets; hover one to see what it does:");
+                for (name, description) in &examples {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(RichText::new(name).strong());
+                        if let Some(text) = description {
+                            ui.small(text);
+                        }
+                    });
+                }
+                ui.add_space(6.0);
+            }
+            ui.label("Add your API key in Settings → General, then press Search.");
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("Open Settings").clicked() {
+                    open_settings = true;
+                }
+                if ui.button("Got it").clicked() {
+                    dismiss = true;
+                }
+            });
+        });
+    if open_settings {
+        state.open_settings();
+        dismiss = true;
+    }
+    if dismiss || !open {
+        state.show_welcome = false;
+        // Writing prefs.json keeps the welcome from coming back on the next launch.
+        state.schedule_prefs_save();
+    }
+}
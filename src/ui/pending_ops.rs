@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+/// Async operations that buttons can start; each may only be in flight once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PendingOp {
+    Search,
+    PresetTest,
+    PresetEstimate,
+}
+
+/// Operations currently running. Buttons check [`PendingOps::is_pending`] to disable
+/// themselves, and every completion path (success, error, or a dropped task) calls
+/// [`PendingOps::finish`].
+#[derive(Debug, Default)]
+pub struct PendingOps {
+    running: HashSet<PendingOp>,
+}
+
+impl PendingOps {
+    /// Mark `op` as started. Returns false if it was already running, in which case the
+    /// caller should not start it again.
+    pub fn begin(&mut self, op: PendingOp) -> bool {
+        self.running.insert(op)
+    }
+
+    pub fn finish(&mut self, op: PendingOp) {
+        self.running.remove(&op);
+    }
+
+    pub fn is_pending(&self, op: PendingOp) -> bool {
+        self.running.contains(&op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_op_runs_once_until_finished() {
+        let mut ops = PendingOps::default();
+        assert!(ops.begin(PendingOp::Search));
+        assert!(!ops.begin(PendingOp::Search));
+        assert!(ops.begin(PendingOp::PresetTest));
+        assert!(ops.is_pending(PendingOp::Search));
+
+        ops.finish(PendingOp::Search);
+        assert!(!ops.is_pending(PendingOp::Search));
+        assert!(ops.is_pending(PendingOp::PresetTest));
+        assert!(ops.begin(PendingOp::Search));
+    }
+}
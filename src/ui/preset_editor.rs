@@ -1,12 +1,34 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::prefs::{MySearch, TimeWindow};
+use anyhow::{Result as AnyResult, bail};
+use time::{Duration, OffsetDateTime};
+
+use crate::prefs::{self, BroadcastEvent, ChannelRef, MySearch, Prefs, TimeWindow};
+
+/// Minimum gap between estimates of an unchanged preset.
+const ESTIMATE_COOLDOWN: Duration = Duration::seconds(10);
 
 #[derive(Clone)]
 pub enum PresetEditorMode {
     New,
     Edit { index: usize },
-    Duplicate { source_index: usize },
+    Duplicate,
+}
+
+/// Progress of the editor's Test button run.
+#[derive(Clone)]
+pub enum PresetTestState {
+    Running,
+    Done { kept: usize, titles: Vec<String> },
+    Failed(String),
+}
+
+/// Result of the editor's one-page Estimate button.
+#[derive(Clone)]
+pub enum PresetEstimateState {
+    Running,
+    Done { raw: usize, passed: usize },
+    Failed(String),
 }
 
 #[derive(Clone)]
@@ -15,6 +37,7 @@ pub struct PresetEditorState {
     pub working: MySearch,
     pub enabled: bool,
     pub name: String,
+    pub description: String,
     pub query_text: String,
     pub any_terms: Vec<String>,
     pub new_any_term: String,
@@ -24,8 +47,11 @@ pub struct PresetEditorState {
     pub new_not_term: String,
     pub channel_allow: Vec<String>,
     pub new_allow_entry: String,
+    pub per_channel_search: bool,
     pub channel_deny: Vec<String>,
     pub new_deny_entry: String,
+    /// Channel IDs already known for `@handle` entries, keyed by `prefs::handle_key`.
+    pub known_channel_ids: HashMap<String, String>,
     pub window_override_enabled: bool,
     pub window_start: String,
     pub window_end: String,
@@ -35,7 +61,13 @@ pub struct PresetEditorState {
     pub captions_override_value: bool,
     pub min_duration_override_enabled: bool,
     pub min_duration_override_value: u32,
+    pub event_type_override_enabled: bool,
+    pub event_type_override_value: BroadcastEvent,
     pub priority: i32,
+    pub require_terms_in_title: bool,
+    pub match_tags: bool,
+    pub split_long_query: bool,
+    pub uploads_mode: bool,
     pub error: Option<String>,
     pub default_english: bool,
     pub default_captions: bool,
@@ -44,6 +76,17 @@ pub struct PresetEditorState {
     pub awaiting_clipboard: bool,
     pub pending_clipboard: Option<MySearch>,
     pub show_dirty_warning: bool,
+    /// Set when the user asked to close the editor with unsaved edits.
+    pub confirm_discard: bool,
+    /// Give the Name field keyboard focus on the next frame.
+    pub focus_name: bool,
+    pub test_state: Option<PresetTestState>,
+    pub estimate_state: Option<PresetEstimateState>,
+    /// Preset and time of the last estimate, used to debounce repeat requests.
+    pub last_estimate: Option<(MySearch, OffsetDateTime)>,
+    /// Text of the Raw JSON section; only read back into the fields on Apply.
+    pub raw_json: String,
+    pub raw_json_error: Option<String>,
 }
 
 type TermBuckets = (
@@ -62,11 +105,13 @@ impl PresetEditorState {
         default_captions: bool,
         default_min_duration: u32,
     ) -> Self {
+        let focus_name = matches!(mode, PresetEditorMode::New);
         let mut state = Self {
             mode,
             working: MySearch::default(),
             enabled: true,
             name: String::new(),
+            description: String::new(),
             query_text: String::new(),
             any_terms: Vec::new(),
             new_any_term: String::new(),
@@ -76,8 +121,10 @@ impl PresetEditorState {
             new_not_term: String::new(),
             channel_allow: Vec::new(),
             new_allow_entry: String::new(),
+            per_channel_search: false,
             channel_deny: Vec::new(),
             new_deny_entry: String::new(),
+            known_channel_ids: HashMap::new(),
             window_override_enabled: false,
             window_start: String::new(),
             window_end: String::new(),
@@ -87,7 +134,13 @@ impl PresetEditorState {
             captions_override_value: default_captions,
             min_duration_override_enabled: false,
             min_duration_override_value: default_min_duration,
+            event_type_override_enabled: false,
+            event_type_override_value: BroadcastEvent::Live,
             priority: 0,
+            require_terms_in_title: false,
+            match_tags: false,
+            split_long_query: false,
+            uploads_mode: false,
             error: None,
             default_english,
             default_captions,
@@ -96,10 +149,18 @@ impl PresetEditorState {
             awaiting_clipboard: false,
             pending_clipboard: None,
             show_dirty_warning: false,
+            confirm_discard: false,
+            focus_name,
+            test_state: None,
+            estimate_state: None,
+            last_estimate: None,
+            raw_json: String::new(),
+            raw_json_error: None,
         };
         state.apply_source(source);
         state.initial = state.snapshot();
         state.working = state.initial.clone();
+        state.reload_raw_json();
         state
     }
 
@@ -154,6 +215,8 @@ impl PresetEditorState {
         channel_deny: &[String],
     ) {
         target.name = self.name.trim().to_string();
+        let description = self.description.trim();
+        target.description = (!description.is_empty()).then(|| description.to_owned());
         target.enabled = self.enabled;
         let trimmed_query = self.query_text.trim();
         target.query.q = if trimmed_query.is_empty() {
@@ -164,8 +227,9 @@ impl PresetEditorState {
         target.query.any_terms = any_terms.to_vec();
         target.query.all_terms = all_terms.to_vec();
         target.query.not_terms = not_terms.to_vec();
-        target.query.channel_allow = channel_allow.to_vec();
-        target.query.channel_deny = channel_deny.to_vec();
+        target.query.channel_allow = self.channel_refs(channel_allow);
+        target.query.per_channel_search = self.per_channel_search;
+        target.query.channel_deny = self.channel_refs(channel_deny);
 
         if self.window_override_enabled
             && !self.window_start.trim().is_empty()
@@ -197,7 +261,68 @@ impl PresetEditorState {
             None
         };
 
+        target.event_type_override = if self.event_type_override_enabled {
+            Some(self.event_type_override_value)
+        } else {
+            None
+        };
+
         target.priority = self.priority;
+        target.require_terms_in_title = self.require_terms_in_title;
+        target.match_tags = self.match_tags;
+        target.split_long_query = self.split_long_query;
+        target.uploads_mode = self.uploads_mode;
+    }
+
+    fn channel_refs(&self, entries: &[String]) -> Vec<ChannelRef> {
+        entries
+            .iter()
+            .map(|entry| {
+                let mut channel = ChannelRef::parse(entry);
+                if let Some(key) = channel.unresolved_handle() {
+                    channel.id = self.known_channel_ids.get(&key).cloned();
+                }
+                channel
+            })
+            .collect()
+    }
+
+    /// Handle keys for `@handle` entries that have no channel ID yet.
+    pub fn unresolved_handles(&self) -> Vec<String> {
+        self.channel_allow
+            .iter()
+            .chain(self.channel_deny.iter())
+            .filter_map(|entry| ChannelRef::parse(entry).unresolved_handle())
+            .filter(|key| !self.known_channel_ids.contains_key(key))
+            .collect()
+    }
+
+    /// Whether the Estimate button may fire: not already running, and the preset changed or
+    /// the cooldown passed since the last estimate.
+    pub fn can_estimate(&self, now: OffsetDateTime) -> bool {
+        if matches!(self.estimate_state, Some(PresetEstimateState::Running)) {
+            return false;
+        }
+        match self.last_estimate.as_ref() {
+            Some((preset, at)) => *preset != self.snapshot() || now - *at >= ESTIMATE_COOLDOWN,
+            None => true,
+        }
+    }
+
+    /// Why `search` cannot run, if it has no query text or terms at all.
+    pub fn query_error(search: &MySearch) -> Option<&'static str> {
+        let has_query_text = search
+            .query
+            .q
+            .as_ref()
+            .map(|q| !q.trim().is_empty())
+            .unwrap_or(false);
+        if !has_query_text && search.query.any_terms.is_empty() && search.query.all_terms.is_empty()
+        {
+            Some("Configure at least one query term.")
+        } else {
+            None
+        }
     }
 
     pub fn hydrate_working(&mut self) {
@@ -225,9 +350,33 @@ impl PresetEditorState {
         self.snapshot() != self.initial
     }
 
-    pub fn reset_dirty_baseline(&mut self) {
-        self.hydrate_working();
-        self.initial = self.working.clone();
+    /// Replace the Raw JSON text with the pretty-printed current fields.
+    pub fn reload_raw_json(&mut self) {
+        self.raw_json = serde_json::to_string_pretty(&self.snapshot()).unwrap_or_default();
+        self.raw_json_error = None;
+    }
+
+    /// Parse the Raw JSON text into the fields. On failure the text is left as typed.
+    pub fn apply_raw_json(&mut self) -> AnyResult<()> {
+        let preset = parse_preset_json(&self.raw_json)?;
+        self.load_preset(preset);
+        Ok(())
+    }
+
+    /// Load a preset from outside the editor (clipboard or Raw JSON). Edits keep the preset's
+    /// own ID; new and duplicated presets get a fresh one on save.
+    pub fn load_preset(&mut self, mut preset: MySearch) {
+        match self.mode {
+            PresetEditorMode::Edit { .. } => {
+                preset.id = self.working.id.clone();
+            }
+            PresetEditorMode::Duplicate | PresetEditorMode::New => {
+                preset.id.clear();
+                preset.enabled = true;
+            }
+        }
+        self.apply_source(&preset);
+        self.reload_raw_json();
     }
 
     pub fn apply_source(&mut self, source: &MySearch) {
@@ -240,6 +389,7 @@ impl PresetEditorState {
         let working = &self.working;
         self.enabled = working.enabled;
         self.name = working.name.clone();
+        self.description = working.description.clone().unwrap_or_default();
         self.query_text = working.query.q.clone().unwrap_or_default();
 
         self.any_terms = working.query.any_terms.clone();
@@ -248,9 +398,31 @@ impl PresetEditorState {
         self.new_all_term.clear();
         self.not_terms = working.query.not_terms.clone();
         self.new_not_term.clear();
-        self.channel_allow = working.query.channel_allow.clone();
+        self.known_channel_ids = working
+            .query
+            .channel_allow
+            .iter()
+            .chain(working.query.channel_deny.iter())
+            .filter_map(|channel| {
+                let handle = channel.handle.as_deref()?;
+                let id = channel.id.clone()?;
+                Some((prefs::handle_key(handle), id))
+            })
+            .collect();
+        self.channel_allow = working
+            .query
+            .channel_allow
+            .iter()
+            .map(|channel| channel.display().to_owned())
+            .collect();
         self.new_allow_entry.clear();
-        self.channel_deny = working.query.channel_deny.clone();
+        self.per_channel_search = working.query.per_channel_search;
+        self.channel_deny = working
+            .query
+            .channel_deny
+            .iter()
+            .map(|channel| channel.display().to_owned())
+            .collect();
         self.new_deny_entry.clear();
 
         Self::normalize_terms(&mut self.any_terms);
@@ -284,10 +456,153 @@ impl PresetEditorState {
             .min_duration_override
             .unwrap_or(self.default_min_duration);
 
+        self.event_type_override_enabled = working.event_type_override.is_some();
+        self.event_type_override_value =
+            working.event_type_override.unwrap_or(BroadcastEvent::Live);
+
         self.priority = working.priority;
+        self.require_terms_in_title = working.require_terms_in_title;
+        self.match_tags = working.match_tags;
+        self.split_long_query = working.split_long_query;
+        self.uploads_mode = working.uploads_mode;
         self.error = None;
         self.awaiting_clipboard = false;
         self.pending_clipboard = None;
         self.show_dirty_warning = false;
     }
 }
+
+/// Read a preset from JSON: a single preset, a list of presets, or a full prefs export. Lists
+/// and exports yield their first preset.
+pub fn parse_preset_json(raw: &str) -> AnyResult<MySearch> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        bail!("No JSON to read.");
+    }
+
+    let single_err = match serde_json::from_str::<MySearch>(trimmed) {
+        Ok(preset) => return Ok(preset),
+        Err(err) => err,
+    };
+
+    if let Ok(presets) = serde_json::from_str::<Vec<MySearch>>(trimmed)
+        && let Some(first) = presets.into_iter().next()
+    {
+        return Ok(first);
+    }
+
+    if let Ok(payload) = serde_json::from_str::<Prefs>(trimmed)
+        && let Some(first) = payload.searches.into_iter().next()
+    {
+        return Ok(first);
+    }
+
+    bail!("JSON did not contain a preset ({single_err}).");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prefs::QuerySpec;
+
+    fn full_preset() -> MySearch {
+        MySearch {
+            id: "rust-news".into(),
+            name: "Rust news".into(),
+            enabled: false,
+            query: QuerySpec {
+                q: Some("rust".into()),
+                any_terms: vec!["async".into(), "tokio".into()],
+                all_terms: vec!["release".into()],
+                not_terms: vec!["reaction".into()],
+                channel_allow: vec![
+                    ChannelRef {
+                        handle: Some("@rustlang".into()),
+                        id: Some("UCaYhcUwRBNscFNUKTjgPFiA".into()),
+                    },
+                    ChannelRef {
+                        handle: None,
+                        id: Some("UC1234567890123456789012".into()),
+                    },
+                ],
+                channel_deny: vec![ChannelRef {
+                    handle: Some("@spam".into()),
+                    id: None,
+                }],
+                category_id: Some(28),
+                per_channel_search: true,
+            },
+            window_override: Some(TimeWindow {
+                start_rfc3339: "2026-01-01T00:00:00Z".into(),
+                end_rfc3339: "2026-02-01T00:00:00Z".into(),
+            }),
+            english_only_override: Some(false),
+            require_captions_override: Some(true),
+            min_duration_override: Some(240),
+            event_type_override: Some(BroadcastEvent::Upcoming),
+            priority: 7,
+            system: true,
+            require_terms_in_title: true,
+            match_tags: true,
+            split_long_query: true,
+            uploads_mode: true,
+            description: Some("Release notes and talks".into()),
+            group: Some("Rust".into()),
+        }
+    }
+
+    fn edit_editor(source: &MySearch) -> PresetEditorState {
+        PresetEditorState::new(PresetEditorMode::Edit { index: 0 }, source, true, false, 60)
+    }
+
+    #[test]
+    fn raw_json_round_trip_preserves_every_field() {
+        let preset = full_preset();
+        let mut editor = edit_editor(&MySearch {
+            id: preset.id.clone(),
+            ..MySearch::default()
+        });
+        editor.raw_json = serde_json::to_string_pretty(&preset).unwrap();
+        editor.apply_raw_json().unwrap();
+        assert_eq!(editor.snapshot(), preset);
+
+        let shown = editor.raw_json.clone();
+        editor.apply_raw_json().unwrap();
+        assert_eq!(editor.snapshot(), preset);
+        assert_eq!(editor.raw_json, shown);
+    }
+
+    #[test]
+    fn blank_description_is_stored_as_none() {
+        let mut editor = edit_editor(&full_preset());
+        editor.description = "  Weekly roundup \n".into();
+        assert_eq!(
+            editor.snapshot().description.as_deref(),
+            Some("Weekly roundup")
+        );
+        editor.description = "   ".into();
+        assert_eq!(editor.snapshot().description, None);
+    }
+
+    #[test]
+    fn bad_raw_json_keeps_text_and_fields() {
+        let preset = full_preset();
+        let mut editor = edit_editor(&preset);
+        editor.raw_json = "{ \"name\": ".into();
+        assert!(editor.apply_raw_json().is_err());
+        assert_eq!(editor.raw_json, "{ \"name\": ");
+        assert_eq!(editor.snapshot(), preset);
+    }
+
+    #[test]
+    fn new_preset_from_json_drops_the_id() {
+        let mut editor =
+            PresetEditorState::new(PresetEditorMode::New, &MySearch::default(), true, false, 60);
+        editor.raw_json = serde_json::to_string(&vec![full_preset()]).unwrap();
+        editor.apply_raw_json().unwrap();
+        let snapshot = editor.snapshot();
+        assert!(snapshot.id.is_empty());
+        assert!(snapshot.enabled);
+        assert_eq!(snapshot.name, "Rust news");
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::prefs::{self, DurationBucketConfig, SearchDefaults, UiPrefs};
+use crate::yt::http;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingsTab {
+    General,
+    SearchDefaults,
+    Network,
+    Appearance,
+    Data,
+}
+
+impl SettingsTab {
+    pub const ALL: [SettingsTab; 5] = [
+        SettingsTab::General,
+        SettingsTab::SearchDefaults,
+        SettingsTab::Network,
+        SettingsTab::Appearance,
+        SettingsTab::Data,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsTab::General => "General",
+            SettingsTab::SearchDefaults => "Search defaults",
+            SettingsTab::Network => "Network",
+            SettingsTab::Appearance => "Appearance",
+            SettingsTab::Data => "Data",
+        }
+    }
+}
+
+/// Draft copy of the global settings; nothing touches prefs until Apply.
+#[derive(Clone)]
+pub struct SettingsState {
+    pub tab: SettingsTab,
+    pub api_key: String,
+    pub search_defaults: SearchDefaults,
+    pub ui: UiPrefs,
+    /// Prefs as they were when the window opened, so Apply writes back only what the draft
+    /// changed and keeps edits made elsewhere meanwhile (e.g. the Length chips).
+    original_api_key: String,
+    original_search_defaults: SearchDefaults,
+    original_ui: UiPrefs,
+    pub region_text: String,
+    pub proxy_text: String,
+    pub error: Option<String>,
+    pub confirm_reset: bool,
+    /// Write a timestamped prefs backup before "Reset defaults" runs.
+    pub backup_before_reset: bool,
+}
+
+impl SettingsState {
+    pub fn new(api_key: &str, defaults: &SearchDefaults, ui: &UiPrefs) -> Self {
+        Self {
+            tab: SettingsTab::General,
+            api_key: api_key.to_owned(),
+            search_defaults: defaults.clone(),
+            ui: ui.clone(),
+            original_api_key: api_key.to_owned(),
+            original_search_defaults: defaults.clone(),
+            original_ui: ui.clone(),
+            region_text: defaults.region_code.clone().unwrap_or_default(),
+            proxy_text: defaults.proxy_url.clone().unwrap_or_default(),
+            error: None,
+            confirm_reset: false,
+            backup_before_reset: true,
+        }
+    }
+
+    /// Check the draft and fold free-text fields back into it.
+    pub fn validate(&mut self) -> Result<(), String> {
+        self.search_defaults.region_code = prefs::parse_region_code(&self.region_text)?;
+        self.region_text = self.search_defaults.region_code.clone().unwrap_or_default();
+
+        if self.search_defaults.min_duration_secs > 7200 {
+            return Err("Minimum duration must be 7200 seconds or less.".into());
+        }
+
+        self.search_defaults.proxy_url = match self.proxy_text.trim() {
+            "" => None,
+            raw => Some(http::validate_proxy_url(raw)?),
+        };
+        self.proxy_text = self.search_defaults.proxy_url.clone().unwrap_or_default();
+
+        validate_buckets(&self.search_defaults.duration_filters.buckets)?;
+        self.api_key = self.api_key.trim().to_owned();
+        Ok(())
+    }
+
+    /// The draft's edits laid over the live prefs: each field the draft changed since the
+    /// window opened replaces the live value; every other field keeps the live one.
+    pub fn merged_into(
+        &self,
+        api_key: &str,
+        defaults: &SearchDefaults,
+        ui: &UiPrefs,
+    ) -> (String, SearchDefaults, UiPrefs) {
+        let api_key = if self.api_key != self.original_api_key.trim() {
+            self.api_key.clone()
+        } else {
+            api_key.to_owned()
+        };
+        let mut merged_defaults = merge_edits(
+            defaults,
+            &self.original_search_defaults,
+            &self.search_defaults,
+        );
+        merged_defaults.last_search_unix = defaults.last_search_unix;
+        let merged_ui = merge_edits(ui, &self.original_ui, &self.ui);
+        (api_key, merged_defaults, merged_ui)
+    }
+}
+
+/// `live` with every top-level field that differs between `original` and `draft` taken from
+/// `draft`. Falls back to `draft` if the values do not serialize as objects.
+fn merge_edits<T: Serialize + DeserializeOwned + Clone>(live: &T, original: &T, draft: &T) -> T {
+    let (
+        Ok(serde_json::Value::Object(mut merged)),
+        Ok(serde_json::Value::Object(original)),
+        Ok(serde_json::Value::Object(edited)),
+    ) = (
+        serde_json::to_value(live),
+        serde_json::to_value(original),
+        serde_json::to_value(draft),
+    )
+    else {
+        return draft.clone();
+    };
+    for (key, value) in edited {
+        if original.get(&key) != Some(&value) {
+            merged.insert(key, value);
+        }
+    }
+    serde_json::from_value(serde_json::Value::Object(merged)).unwrap_or_else(|_| draft.clone())
+}
+
+fn validate_buckets(buckets: &[DurationBucketConfig]) -> Result<(), String> {
+    if buckets.is_empty() {
+        return Err("Configure at least one duration bucket.".into());
+    }
+    let mut seen = HashSet::new();
+    for bucket in buckets {
+        let id = bucket.id.trim();
+        if id.is_empty() {
+            return Err("Every duration bucket needs an id.".into());
+        }
+        if !seen.insert(id) {
+            return Err(format!("Duration bucket id '{id}' is used twice."));
+        }
+        if bucket.label.trim().is_empty() {
+            return Err(format!("Duration bucket '{id}' needs a label."));
+        }
+        if let Some(max) = bucket.max_seconds
+            && max <= bucket.min_seconds
+        {
+            return Err(format!(
+                "Duration bucket '{id}' must have a max greater than its min."
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prefs::ThemeChoice;
+
+    #[test]
+    fn apply_keeps_live_edits_to_fields_the_draft_left_alone() {
+        let defaults = SearchDefaults::default();
+        let ui = UiPrefs::default();
+        let mut draft = SettingsState::new("key", &defaults, &ui);
+        draft.ui.theme = ThemeChoice::Dark;
+        draft.search_defaults.exclude_3d = true;
+
+        let mut live_defaults = defaults.clone();
+        live_defaults.active_duration_bucket_ids = vec!["long".into()];
+        live_defaults.default_window = prefs::TimeWindowPreset::AllTime;
+        let mut live_ui = ui.clone();
+        live_ui.daily_quota_budget = 1234;
+
+        let (api_key, merged_defaults, merged_ui) =
+            draft.merged_into("live-key", &live_defaults, &live_ui);
+        assert_eq!(api_key, "live-key");
+        assert!(merged_defaults.exclude_3d);
+        assert_eq!(merged_defaults.active_duration_bucket_ids, vec!["long"]);
+        assert_eq!(
+            merged_defaults.default_window,
+            prefs::TimeWindowPreset::AllTime
+        );
+        assert_eq!(merged_ui.theme, ThemeChoice::Dark);
+        assert_eq!(merged_ui.daily_quota_budget, 1234);
+
+        draft.api_key = "new-key".into();
+        assert_eq!(
+            draft.merged_into("live-key", &live_defaults, &live_ui).0,
+            "new-key"
+        );
+    }
+}
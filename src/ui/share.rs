@@ -0,0 +1,173 @@
+//! Text copied when sharing videos: Markdown from the `share_template` pref, or a plain line.
+
+use crate::yt::types::VideoDetails;
+
+use super::utils::format_duration;
+
+/// Plain-text share line; values are inserted as they are.
+const PLAIN_TEMPLATE: &str = "{title} — {channel}, {duration} {url}";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShareStyle {
+    /// Escape values so titles with brackets or asterisks cannot break the link.
+    Markdown,
+    Plain,
+}
+
+/// Fill `{title}`, `{url}`, `{channel}`, `{duration}`, and `{published}` in `template`.
+/// Substitution is a single pass, so placeholder-like text inside a value stays literal, and
+/// unknown placeholders are kept as typed.
+pub fn render(template: &str, video: &VideoDetails, style: ShareStyle) -> String {
+    let mut out = String::with_capacity(template.len() + video.title.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after
+            .find('}')
+            .and_then(|close| Some((close, placeholder(&after[..close], video, style)?)))
+        {
+            Some((close, value)) => {
+                out.push_str(&value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+pub fn plain_text(video: &VideoDetails) -> String {
+    render(PLAIN_TEMPLATE, video, ShareStyle::Plain)
+}
+
+/// One Markdown bullet per video, rendered with `template`.
+pub fn markdown_list<'a>(
+    template: &str,
+    videos: impl IntoIterator<Item = &'a VideoDetails>,
+) -> String {
+    videos
+        .into_iter()
+        .map(|video| format!("- {}", render(template, video, ShareStyle::Markdown)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn placeholder(name: &str, video: &VideoDetails, style: ShareStyle) -> Option<String> {
+    let value = match name {
+        "title" => video.title.trim().to_owned(),
+        "url" => {
+            let url = video.watch_url();
+            return Some(match style {
+                ShareStyle::Markdown => escape_url(&url),
+                ShareStyle::Plain => url,
+            });
+        }
+        "channel" => video
+            .channel_display_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(video.channel_title.trim())
+            .to_owned(),
+        "duration" if video.duration_is_known() => {
+            format_duration(video.duration_secs).replace(' ', "")
+        }
+        "duration" if video.is_live_or_upcoming() => "live".to_owned(),
+        "duration" => "unknown length".to_owned(),
+        "published" => video
+            .published_at
+            .get(..10)
+            .unwrap_or(&video.published_at)
+            .to_owned(),
+        _ => return None,
+    };
+    Some(match style {
+        ShareStyle::Markdown => escape_markdown(&value),
+        ShareStyle::Plain => value,
+    })
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(
+            ch,
+            '\\' | '[' | ']' | '(' | ')' | '*' | '_' | '`' | '<' | '>'
+        ) {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Percent-encode the characters that would end a Markdown link target early.
+fn escape_url(url: &str) -> String {
+    url.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prefs::DEFAULT_SHARE_TEMPLATE;
+
+    fn video(title: &str) -> VideoDetails {
+        VideoDetails {
+            id: "abc".into(),
+            title: title.into(),
+            url: "https://www.youtube.com/watch?v=abc".into(),
+            channel_title: "Chan".into(),
+            published_at: "2024-05-06T07:08:09Z".into(),
+            duration_secs: 754,
+            ..VideoDetails::default()
+        }
+    }
+
+    #[test]
+    fn markdown_share_escapes_brackets_in_titles() {
+        assert_eq!(
+            render(
+                DEFAULT_SHARE_TEMPLATE,
+                &video("Rust 2024"),
+                ShareStyle::Markdown
+            ),
+            "[Rust 2024](https://www.youtube.com/watch?v=abc) — Chan, 12m34s"
+        );
+        assert_eq!(
+            render(
+                DEFAULT_SHARE_TEMPLATE,
+                &video("[LIVE] *Big* news (part 1)"),
+                ShareStyle::Markdown
+            ),
+            r"[\[LIVE\] \*Big\* news \(part 1\)](https://www.youtube.com/watch?v=abc) — Chan, 12m34s"
+        );
+        assert_eq!(
+            plain_text(&video("[LIVE] news")),
+            "[LIVE] news — Chan, 12m34s https://www.youtube.com/watch?v=abc"
+        );
+    }
+
+    #[test]
+    fn substitution_is_single_pass_and_keeps_unknown_placeholders() {
+        let sneaky = video("{url} {channel}");
+        assert_eq!(
+            render(
+                "{title} on {published} {nope} {",
+                &sneaky,
+                ShareStyle::Plain
+            ),
+            "{url} {channel} on 2024-05-06 {nope} {"
+        );
+        assert_eq!(
+            markdown_list("{title}", [&video("a_b"), &video("c")]),
+            "- a\\_b\n- c"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Every keyboard shortcut in one table; handlers and the Help window both read from it,
+//! so the listed keys are always the bound ones.
+
+use egui::{Context, InputState, Key, KeyboardShortcut, Modifiers};
+
+/// Where a shortcut applies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShortcutScope {
+    /// The main window, while no text field is focused and no dialog is open.
+    Main,
+    /// The preset editor window.
+    Editor,
+}
+
+impl ShortcutScope {
+    pub const ALL: [ShortcutScope; 2] = [ShortcutScope::Main, ShortcutScope::Editor];
+
+    pub fn heading(self) -> &'static str {
+        match self {
+            ShortcutScope::Main => "Main window (when no text field is focused)",
+            ShortcutScope::Editor => "Preset editor",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shortcut {
+    OpenHelp,
+    EditPreset,
+    DuplicatePreset,
+    NewPreset,
+    SaveAll,
+    AddTerm,
+    SavePreset,
+    CloseEditor,
+}
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 8] = [
+        Shortcut::OpenHelp,
+        Shortcut::EditPreset,
+        Shortcut::DuplicatePreset,
+        Shortcut::NewPreset,
+        Shortcut::SaveAll,
+        Shortcut::AddTerm,
+        Shortcut::SavePreset,
+        Shortcut::CloseEditor,
+    ];
+
+    /// Key combinations bound to this shortcut; any one of them triggers it.
+    pub fn keys(self) -> &'static [KeyboardShortcut] {
+        const fn plain(key: Key) -> KeyboardShortcut {
+            KeyboardShortcut::new(Modifiers::NONE, key)
+        }
+        match self {
+            Shortcut::OpenHelp => const { &[plain(Key::Questionmark), plain(Key::F1)] },
+            Shortcut::EditPreset => const { &[plain(Key::E)] },
+            Shortcut::DuplicatePreset => const { &[plain(Key::D)] },
+            Shortcut::NewPreset => const { &[plain(Key::N)] },
+            Shortcut::SaveAll => const { &[KeyboardShortcut::new(Modifiers::COMMAND, Key::S)] },
+            Shortcut::AddTerm => const { &[plain(Key::Enter)] },
+            Shortcut::SavePreset => {
+                const { &[KeyboardShortcut::new(Modifiers::COMMAND, Key::Enter)] }
+            }
+            Shortcut::CloseEditor => const { &[plain(Key::Escape)] },
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Shortcut::OpenHelp => "open or close this help",
+            Shortcut::EditPreset => "edit the selected preset",
+            Shortcut::DuplicatePreset => "duplicate the selected preset",
+            Shortcut::NewPreset => "new preset",
+            Shortcut::SaveAll => "save prefs, results, and view state",
+            Shortcut::AddTerm => "add the typed term",
+            Shortcut::SavePreset => "save",
+            Shortcut::CloseEditor => "close",
+        }
+    }
+
+    pub fn scope(self) -> ShortcutScope {
+        match self {
+            Shortcut::OpenHelp
+            | Shortcut::EditPreset
+            | Shortcut::DuplicatePreset
+            | Shortcut::NewPreset
+            | Shortcut::SaveAll => ShortcutScope::Main,
+            Shortcut::AddTerm | Shortcut::SavePreset | Shortcut::CloseEditor => {
+                ShortcutScope::Editor
+            }
+        }
+    }
+
+    /// Consume a press of any bound key, so it fires once.
+    pub fn consume(self, input: &mut InputState) -> bool {
+        self.keys().iter().any(|keys| input.consume_shortcut(keys))
+    }
+
+    /// Whether a bound key was pressed this frame, with any modifiers and without consuming
+    /// it; for keys a focused text field has already seen, so Ctrl+Enter still adds a term.
+    pub fn pressed(self, input: &InputState) -> bool {
+        self.keys()
+            .iter()
+            .any(|keys| input.key_pressed(keys.logical_key))
+    }
+
+    /// Platform-formatted keys, e.g. "Ctrl+Enter" or "? or F1".
+    pub fn label(self, ctx: &Context) -> String {
+        self.keys()
+            .iter()
+            .map(|keys| ctx.format_shortcut(keys))
+            .collect::<Vec<_>>()
+            .join(" or ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_two_shortcuts_in_a_scope_share_keys() {
+        for (i, a) in Shortcut::ALL.iter().enumerate() {
+            assert!(!a.keys().is_empty(), "{a:?} has no keys");
+            for b in &Shortcut::ALL[i + 1..] {
+                if a.scope() != b.scope() {
+                    continue;
+                }
+                for keys in a.keys() {
+                    assert!(!b.keys().contains(keys), "{a:?} and {b:?} share {keys:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_scope_lists_a_shortcut() {
+        for scope in ShortcutScope::ALL {
+            assert!(
+                Shortcut::ALL
+                    .iter()
+                    .any(|shortcut| shortcut.scope() == scope)
+            );
+        }
+    }
+}
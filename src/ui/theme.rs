@@ -1,4 +1,6 @@
-use egui::{Color32, FontFamily, FontId, Margin, RichText, Stroke, TextStyle};
+use egui::{Color32, FontFamily, FontId, Margin, RichText, Stroke, TextStyle, Theme};
+
+use crate::prefs::ThemeChoice;
 
 pub const PRESET_COLORS: &[egui::Color32] = &[
     egui::Color32::from_rgb(0x4F, 0x90, 0xD9),
@@ -8,6 +10,12 @@ pub const PRESET_COLORS: &[egui::Color32] = &[
     egui::Color32::from_rgb(0x9A, 0x59, 0xD1),
 ];
 
+/// Chip color for the preset at `position` in the preset list, so a preset keeps the same
+/// color on every card and in the legend.
+pub fn preset_color(position: usize) -> Color32 {
+    PRESET_COLORS[position % PRESET_COLORS.len()]
+}
+
 pub const PANEL_FILL: Color32 = Color32::from_rgb(22, 22, 28);
 pub const WINDOW_FILL: Color32 = Color32::from_rgb(15, 15, 20);
 pub const CARD_BG: Color32 = Color32::from_rgb(32, 32, 40);
@@ -20,17 +28,96 @@ pub const ACCENT_SAVE: Color32 = Color32::from_rgb(34, 197, 94); // green
 pub const ACCENT_OPEN: Color32 = Color32::from_rgb(59, 130, 246); // blue
 pub const ACCENT_EXTRA: Color32 = Color32::from_rgb(168, 85, 247); // purple
 
-pub fn apply_gfv_theme(ctx: &egui::Context) {
-    let mut visuals = egui::Visuals::dark();
-    visuals.window_fill = WINDOW_FILL;
-    visuals.panel_fill = PANEL_FILL;
-    visuals.faint_bg_color = Color32::from_rgb(32, 32, 40);
-    visuals.extreme_bg_color = Color32::from_rgb(42, 42, 50);
+/// Videos newer than this many hours get the green recency badge.
+pub const RECENCY_FRESH_HOURS: i64 = 24;
+/// Videos newer than this many hours (but past fresh) get the yellow badge.
+pub const RECENCY_RECENT_HOURS: i64 = 72;
+pub const RECENCY_FRESH: Color32 = ACCENT_SAVE;
+pub const RECENCY_RECENT: Color32 = ACCENT_SINGLE;
+pub const RECENCY_OLD: Color32 = Color32::from_gray(150);
+
+pub const LIGHT_PANEL_FILL: Color32 = Color32::from_rgb(243, 244, 246);
+pub const LIGHT_WINDOW_FILL: Color32 = Color32::from_rgb(249, 250, 251);
+pub const LIGHT_CARD_BG: Color32 = Color32::from_rgb(255, 255, 255);
+pub const LIGHT_CARD_BORDER: Color32 = Color32::from_rgb(209, 213, 219);
+
+/// Surface colors that differ between the dark and light palettes.
+pub struct Palette {
+    pub panel_fill: Color32,
+    pub window_fill: Color32,
+    pub card_bg: Color32,
+    pub card_border: Color32,
+    pub title_text: Color32,
+    pub placeholder_bg: Color32,
+}
+
+pub const DARK_PALETTE: Palette = Palette {
+    panel_fill: PANEL_FILL,
+    window_fill: WINDOW_FILL,
+    card_bg: CARD_BG,
+    card_border: CARD_BORDER,
+    title_text: Color32::from_rgb(229, 231, 235),
+    placeholder_bg: Color32::from_rgb(30, 34, 42),
+};
+
+pub const LIGHT_PALETTE: Palette = Palette {
+    panel_fill: LIGHT_PANEL_FILL,
+    window_fill: LIGHT_WINDOW_FILL,
+    card_bg: LIGHT_CARD_BG,
+    card_border: LIGHT_CARD_BORDER,
+    title_text: Color32::from_rgb(17, 24, 39),
+    placeholder_bg: Color32::from_rgb(229, 231, 235),
+};
+
+/// Palette matching whichever visuals are currently installed on the context.
+pub fn palette(ctx: &egui::Context) -> &'static Palette {
+    if ctx.style().visuals.dark_mode {
+        &DARK_PALETTE
+    } else {
+        &LIGHT_PALETTE
+    }
+}
+
+/// Resolve a theme choice against the OS preference.
+pub fn resolve_dark(choice: ThemeChoice, system_is_dark: bool) -> bool {
+    match choice {
+        ThemeChoice::System => system_is_dark,
+        ThemeChoice::Dark => true,
+        ThemeChoice::Light => false,
+    }
+}
+
+/// Read the OS dark/light signal, assuming dark when the platform does not report one.
+pub fn system_is_dark(ctx: &egui::Context) -> bool {
+    ctx.system_theme()
+        .map(|theme| theme == Theme::Dark)
+        .unwrap_or(true)
+}
+
+/// Install the gfv styling using the palette selected by `choice`.
+pub fn apply_theme(ctx: &egui::Context, choice: ThemeChoice, system_is_dark: bool) {
+    let dark = resolve_dark(choice, system_is_dark);
+    let palette = if dark { &DARK_PALETTE } else { &LIGHT_PALETTE };
+
+    let mut visuals = if dark {
+        egui::Visuals::dark()
+    } else {
+        egui::Visuals::light()
+    };
+    visuals.window_fill = palette.window_fill;
+    visuals.panel_fill = palette.panel_fill;
+    visuals.faint_bg_color = palette.card_bg;
+    visuals.extreme_bg_color = if dark {
+        Color32::from_rgb(42, 42, 50)
+    } else {
+        Color32::from_rgb(255, 255, 255)
+    };
     visuals.selection.bg_fill = STATUS_ACCENT;
     visuals.hyperlink_color = STATUS_ACCENT;
     visuals.button_frame = true;
-    visuals.window_stroke = Stroke::new(1.0, CARD_BORDER);
+    visuals.window_stroke = Stroke::new(1.0, palette.card_border);
 
+    ctx.set_theme(if dark { Theme::Dark } else { Theme::Light });
     let mut style = (*ctx.style()).clone();
     style.spacing.item_spacing = egui::vec2(12.0, 8.0);
     style.spacing.button_padding = egui::vec2(14.0, 8.0);
@@ -2,17 +2,21 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, SystemTime};
 
-use directories::ProjectDirs;
 use egui::{self, ColorImage, Context, ImageData, TextureHandle, TextureOptions, Vec2};
 use tokio::runtime::Runtime;
 
+use crate::config_dir;
+use crate::yt::http;
+
 pub const MAX_THUMB_WIDTH: f32 = 160.0;
 pub const MAX_THUMB_HEIGHT: f32 = 90.0;
+/// Cached results whose thumbnails are read from disk right after startup.
+pub const WARM_UP_COUNT: usize = 24;
 
 pub struct ThumbnailCache {
     entries: HashMap<String, ThumbnailEntry>,
-    client: reqwest::Client,
     tx: Sender<ThumbnailMessage>,
     rx: Receiver<ThumbnailMessage>,
     disk_dir: PathBuf,
@@ -33,7 +37,6 @@ enum ThumbnailState {
 
 pub struct ThumbnailRef {
     pub texture: TextureHandle,
-    pub original_size: Vec2,
     pub display_size: Vec2,
 }
 
@@ -41,6 +44,9 @@ struct ThumbnailMessage {
     video_id: String,
     url: String,
     payload: Result<ThumbnailPayload, String>,
+    /// Decoded from the disk cache, so there is nothing to persist; a disk miss during
+    /// warm-up resets the entry so the card fetches it normally.
+    from_disk: bool,
 }
 
 struct ThumbnailPayload {
@@ -51,21 +57,33 @@ struct ThumbnailPayload {
 impl ThumbnailCache {
     pub fn new() -> Self {
         let (tx, rx) = mpsc::channel();
-        let disk_dir = ProjectDirs::from("com", "yourname", "YTSearch")
-            .map(|proj| proj.config_dir().join("thumbnails"))
-            .unwrap_or_else(|| PathBuf::from("thumbnails"));
-        if let Err(err) = fs::create_dir_all(&disk_dir) {
+        let config = config_dir::get();
+        let disk_dir = config.thumbnails_dir();
+        if config.writable
+            && let Err(err) = fs::create_dir_all(&disk_dir)
+        {
             eprintln!("Failed to create thumbnail cache dir: {err}");
         }
         Self {
             entries: HashMap::new(),
-            client: reqwest::Client::new(),
             tx,
             rx,
             disk_dir,
         }
     }
 
+    /// Delete on-disk thumbnails whose files have not been written within `max_age`.
+    pub fn prune_old(&self, max_age: Duration) -> usize {
+        let removed = prune_dir(&self.disk_dir, max_age, SystemTime::now());
+        if removed > 0 {
+            eprintln!(
+                "Pruned {removed} cached thumbnail(s) older than {} day(s)",
+                max_age.as_secs() / 86_400
+            );
+        }
+        removed
+    }
+
     pub fn retain_ids<'a, I>(&mut self, ids: I)
     where
         I: IntoIterator<Item = &'a str>,
@@ -78,6 +96,17 @@ impl ThumbnailCache {
         self.entries.clear();
     }
 
+    /// Drop every thumbnail from memory and disk; returns how many were deleted from disk.
+    pub fn clear_disk(&mut self) -> usize {
+        self.entries.clear();
+        // Pruning from a minute ahead makes every file, even one written just now, stale.
+        prune_dir(
+            &self.disk_dir,
+            Duration::ZERO,
+            SystemTime::now() + Duration::from_secs(60),
+        )
+    }
+
     pub fn request(&mut self, video_id: &str, url: Option<&str>, ctx: &Context, runtime: &Runtime) {
         let entry = self
             .entries
@@ -94,40 +123,12 @@ impl ThumbnailCache {
                     entry.state,
                     ThumbnailState::Idle | ThumbnailState::Failed | ThumbnailState::Missing
                 );
-                if matches!(entry.state, ThumbnailState::Idle) {
-                    if let Some(cached) = load_from_disk(&self.disk_dir, video_id, actual) {
-                        let [w, h] = cached.size;
-                        let original = Vec2::new(w as f32, h as f32);
-                        let texture = ctx.load_texture(
-                            format!("thumbnail://{}", video_id),
-                            ImageData::from(cached),
-                            TextureOptions::LINEAR,
-                        );
-                        entry.url = Some(actual.to_owned());
-                        entry.state = ThumbnailState::Ready {
-                            texture,
-                            size: original,
-                        };
-                        return;
-                    }
-                }
                 if url_has_changed || needs_fetch {
+                    let try_disk = matches!(entry.state, ThumbnailState::Idle);
                     entry.url = Some(actual.to_owned());
                     entry.state = ThumbnailState::Loading;
                     ctx.request_repaint();
-
-                    let tx = self.tx.clone();
-                    let client = self.client.clone();
-                    let video_id_owned = video_id.to_owned();
-                    let url_owned = actual.to_owned();
-                    runtime.spawn(async move {
-                        let payload = fetch_thumbnail(client, &url_owned).await;
-                        let _ = tx.send(ThumbnailMessage {
-                            video_id: video_id_owned,
-                            url: url_owned,
-                            payload,
-                        });
-                    });
+                    self.spawn_load(video_id, actual, ctx, runtime, try_disk, true);
                 }
             }
             _ => {
@@ -137,6 +138,74 @@ impl ThumbnailCache {
         }
     }
 
+    /// Start decoding the disk-cached thumbnails for `videos` in the background so the first
+    /// screen of cached results shows images without per-card pop-in.
+    pub fn warm_up<'a, I>(&mut self, videos: I, ctx: &Context, runtime: &Runtime)
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        for (video_id, url) in videos {
+            if url.is_empty() || self.entries.contains_key(video_id) {
+                continue;
+            }
+            self.entries.insert(
+                video_id.to_owned(),
+                ThumbnailEntry {
+                    url: Some(url.to_owned()),
+                    state: ThumbnailState::Loading,
+                },
+            );
+            self.spawn_load(video_id, url, ctx, runtime, true, false);
+        }
+    }
+
+    /// Load a thumbnail off the UI thread: from the disk cache when `try_disk`, then from the
+    /// network when `fetch` (and the disk had nothing usable).
+    fn spawn_load(
+        &self,
+        video_id: &str,
+        url: &str,
+        ctx: &Context,
+        runtime: &Runtime,
+        try_disk: bool,
+        fetch: bool,
+    ) {
+        let tx = self.tx.clone();
+        let ctx = ctx.clone();
+        let disk_dir = self.disk_dir.clone();
+        let video_id = video_id.to_owned();
+        let url = url.to_owned();
+        runtime.spawn(async move {
+            let cached = if try_disk {
+                let (dir, id, wanted) = (disk_dir, video_id.clone(), url.clone());
+                tokio::task::spawn_blocking(move || load_from_disk(&dir, &id, &wanted))
+                    .await
+                    .ok()
+                    .flatten()
+            } else {
+                None
+            };
+            let (payload, from_disk) = match cached {
+                Some(image) => (
+                    Ok(ThumbnailPayload {
+                        image,
+                        bytes: Vec::new(),
+                    }),
+                    true,
+                ),
+                None if fetch => (fetch_thumbnail(http::client(), &url).await, false),
+                None => (Err("not in the disk cache".to_owned()), true),
+            };
+            let _ = tx.send(ThumbnailMessage {
+                video_id,
+                url,
+                payload,
+                from_disk,
+            });
+            ctx.request_repaint();
+        });
+    }
+
     pub fn update(&mut self, ctx: &Context) {
         while let Ok(message) = self.rx.try_recv() {
             if let Some(entry) = self.entries.get_mut(&message.video_id) {
@@ -165,15 +234,21 @@ impl ThumbnailCache {
                                 };
                             }
                         }
-                        if let Err(err) = persist_to_disk(
-                            &self.disk_dir,
-                            &message.video_id,
-                            &message.url,
-                            &payload.bytes,
-                        ) {
+                        if !message.from_disk
+                            && config_dir::get().writable
+                            && let Err(err) = persist_to_disk(
+                                &self.disk_dir,
+                                &message.video_id,
+                                &message.url,
+                                &payload.bytes,
+                            )
+                        {
                             eprintln!("Failed to persist thumbnail: {err}");
                         }
                     }
+                    Err(_) if message.from_disk => {
+                        entry.state = ThumbnailState::Idle;
+                    }
                     Err(_) => {
                         entry.state = ThumbnailState::Failed;
                     }
@@ -189,7 +264,6 @@ impl ThumbnailCache {
             let display = scaled_size(*size);
             Some(ThumbnailRef {
                 texture: texture.clone(),
-                original_size: *size,
                 display_size: display,
             })
         } else {
@@ -255,20 +329,76 @@ fn load_from_disk(base: &Path, video_id: &str, url: &str) -> Option<ColorImage>
     let (image_path, url_path) = cache_paths(base, video_id);
     let stored_url = fs::read_to_string(url_path).ok()?;
     if stored_url.trim() != url {
+        remove_stale_pair(base, video_id, url);
         return None;
     }
     let bytes = fs::read(image_path).ok()?;
     decode_image(&bytes).ok()
 }
 
+/// Delete the cached pair for `video_id` when it was stored for a URL other than `url`, e.g.
+/// another thumbnail resolution, so superseded images do not linger until pruned.
+fn remove_stale_pair(base: &Path, video_id: &str, url: &str) {
+    let (image_path, url_path) = cache_paths(base, video_id);
+    let Ok(stored_url) = fs::read_to_string(&url_path) else {
+        return;
+    };
+    if stored_url.trim() != url {
+        let _ = fs::remove_file(image_path);
+        let _ = fs::remove_file(url_path);
+    }
+}
+
 fn persist_to_disk(base: &Path, video_id: &str, url: &str, bytes: &[u8]) -> std::io::Result<()> {
     fs::create_dir_all(base)?;
+    remove_stale_pair(base, video_id, url);
     let (image_path, url_path) = cache_paths(base, video_id);
     fs::write(&image_path, bytes)?;
     fs::write(&url_path, url)?;
     Ok(())
 }
 
+/// Remove `.bin`/`.url` pairs whose newest file is older than `max_age`.
+/// Returns the number of thumbnails removed.
+fn prune_dir(base: &Path, max_age: Duration, now: SystemTime) -> usize {
+    let Ok(read_dir) = fs::read_dir(base) else {
+        return 0;
+    };
+    let mut newest: HashMap<String, SystemTime> = HashMap::new();
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        let is_cache_file = matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("bin" | "url")
+        );
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if !is_cache_file {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) else {
+            continue;
+        };
+        let slot = newest.entry(stem.to_owned()).or_insert(modified);
+        if modified > *slot {
+            *slot = modified;
+        }
+    }
+
+    let mut removed = 0;
+    for (stem, modified) in newest {
+        let age = now.duration_since(modified).unwrap_or_default();
+        if age <= max_age {
+            continue;
+        }
+        let _ = fs::remove_file(base.join(format!("{stem}.bin")));
+        let _ = fs::remove_file(base.join(format!("{stem}.url")));
+        removed += 1;
+    }
+    removed
+}
+
 fn sanitize_id(raw: &str) -> String {
     raw.chars()
         .map(|ch| match ch {
@@ -277,3 +407,67 @@ fn sanitize_id(raw: &str) -> String {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_dir_removes_only_stale_pairs() {
+        let dir = std::env::temp_dir().join(format!("ytsearch-thumbs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        persist_to_disk(&dir, "old", "https://i.ytimg.com/old.jpg", b"old").unwrap();
+        persist_to_disk(&dir, "new", "https://i.ytimg.com/new.jpg", b"new").unwrap();
+
+        let stale = SystemTime::now() - Duration::from_secs(40 * 86_400);
+        for name in ["old.bin", "old.url"] {
+            fs::File::options()
+                .write(true)
+                .open(dir.join(name))
+                .unwrap()
+                .set_modified(stale)
+                .unwrap();
+        }
+
+        let removed = prune_dir(&dir, Duration::from_secs(30 * 86_400), SystemTime::now());
+        assert_eq!(removed, 1);
+        assert!(!dir.join("old.bin").exists());
+        assert!(!dir.join("old.url").exists());
+        assert!(dir.join("new.bin").exists());
+        assert!(dir.join("new.url").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_new_url_replaces_the_stored_pair() {
+        let dir = std::env::temp_dir().join(format!("ytsearch-thumbs-url-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        persist_to_disk(
+            &dir,
+            "vid",
+            "https://i.ytimg.com/vi/vid/default.jpg",
+            b"low",
+        )
+        .unwrap();
+
+        assert!(load_from_disk(&dir, "vid", "https://i.ytimg.com/vi/vid/hqdefault.jpg").is_none());
+        assert!(!dir.join("vid.bin").exists());
+        assert!(!dir.join("vid.url").exists());
+
+        persist_to_disk(
+            &dir,
+            "vid",
+            "https://i.ytimg.com/vi/vid/hqdefault.jpg",
+            b"high",
+        )
+        .unwrap();
+        assert_eq!(fs::read(dir.join("vid.bin")).unwrap(), b"high");
+        assert_eq!(
+            fs::read_to_string(dir.join("vid.url")).unwrap(),
+            "https://i.ytimg.com/vi/vid/hqdefault.jpg"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,75 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config_dir;
+
+use super::app_state::ResultSort;
+
+/// View state restored on the next launch, persisted as ui_state.json. The Length chips are
+/// not here: they are saved in prefs as `active_duration_bucket_ids`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct UiState {
+    pub result_sort: ResultSort,
+    pub run_any_mode: bool,
+    pub selected_search_id: Option<String>,
+    /// Whether the one-time notice that name-only block entries now match exact channel names
+    /// was shown.
+    pub name_block_notice_shown: bool,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            result_sort: ResultSort::Newest,
+            run_any_mode: true,
+            selected_search_id: None,
+            name_block_notice_shown: false,
+        }
+    }
+}
+
+fn ui_state_path() -> PathBuf {
+    config_dir::get().ui_state_path()
+}
+
+pub fn load() -> UiState {
+    fs::read(ui_state_path())
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(state: &UiState) -> std::io::Result<()> {
+    if !config_dir::get().writable {
+        return Ok(());
+    }
+    let path = ui_state_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(state)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let partial: UiState = serde_json::from_str(r#"{ "result_sort": "Longest" }"#).unwrap();
+        assert_eq!(partial.result_sort, ResultSort::Longest);
+        assert!(partial.run_any_mode);
+        assert_eq!(partial.selected_search_id, None);
+
+        let state = UiState {
+            result_sort: ResultSort::Channel,
+            run_any_mode: false,
+            selected_search_id: Some("rust".into()),
+            name_block_notice_shown: true,
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<UiState>(&json).unwrap(), state);
+    }
+}
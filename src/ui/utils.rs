@@ -1,4 +1,6 @@
-use crate::prefs::TimeWindowPreset;
+use crate::prefs::{
+    BroadcastEvent, CompletionNotice, DuplicatePolicy, ThemeChoice, TimeWindowPreset,
+};
 
 pub fn time_window_label(preset: TimeWindowPreset) -> &'static str {
     match preset {
@@ -6,6 +8,41 @@ pub fn time_window_label(preset: TimeWindowPreset) -> &'static str {
         TimeWindowPreset::H48 => "48h",
         TimeWindowPreset::D7 => "7d",
         TimeWindowPreset::AllTime => "Any date",
+        TimeWindowPreset::SinceLastRun => "Since last search",
+    }
+}
+
+pub fn broadcast_event_label(event: Option<BroadcastEvent>) -> &'static str {
+    match event {
+        None => "Regular videos",
+        Some(BroadcastEvent::Live) => "Live now",
+        Some(BroadcastEvent::Upcoming) => "Upcoming",
+        Some(BroadcastEvent::Completed) => "Past streams",
+    }
+}
+
+pub fn duplicate_policy_label(policy: DuplicatePolicy) -> &'static str {
+    match policy {
+        DuplicatePolicy::DedupById => "Merge by video",
+        DuplicatePolicy::DedupByIdAndChannel => "Merge by video and re-uploads",
+        DuplicatePolicy::KeepAll => "Keep every copy",
+    }
+}
+
+pub fn theme_choice_label(choice: ThemeChoice) -> &'static str {
+    match choice {
+        ThemeChoice::System => "Follow system",
+        ThemeChoice::Dark => "Dark",
+        ThemeChoice::Light => "Light",
+    }
+}
+
+pub fn completion_notice_label(notice: CompletionNotice) -> &'static str {
+    match notice {
+        CompletionNotice::None => "Off",
+        CompletionNotice::Flash => "Flash taskbar",
+        CompletionNotice::Sound => "Play sound",
+        CompletionNotice::Both => "Flash and sound",
     }
 }
 
@@ -26,6 +63,105 @@ pub fn format_duration(total_secs: u64) -> String {
     parts.join(" ")
 }
 
+/// Shorten `text` to at most `max_chars` characters by replacing its middle with "…".
+///
+/// Works on `char`s, so a multi-byte code point is never split.
+pub fn truncate_middle(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    let keep = max_chars - 1;
+    let head = keep.div_ceil(2);
+    let tail = keep - head;
+    let mut out: String = text.chars().take(head).collect();
+    out.push('…');
+    out.extend(text.chars().skip(count - tail));
+    out
+}
+
+/// Cut the middle of `text` with "…" until `measure` reports it fits in `max_width`.
+///
+/// `measure` returns the rendered width of a candidate string; the longest cut that fits wins.
+pub fn elide_middle_to_width(text: &str, max_width: f32, measure: impl Fn(&str) -> f32) -> String {
+    if measure(text) <= max_width {
+        return text.to_string();
+    }
+    let (mut low, mut high) = (0, text.chars().count());
+    while low + 1 < high {
+        let mid = (low + high) / 2;
+        if measure(&truncate_middle(text, mid)) <= max_width {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    truncate_middle(text, low)
+}
+
+/// Compact age label such as "5m", "2h", "3d", or "4w"; anything under a minute is "now".
+pub fn humanize_elapsed(elapsed_secs: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const WEEK: i64 = 7 * DAY;
+
+    match elapsed_secs.max(0) {
+        secs if secs < MINUTE => "now".into(),
+        secs if secs < HOUR => format!("{}m", secs / MINUTE),
+        secs if secs < DAY => format!("{}h", secs / HOUR),
+        secs if secs < WEEK => format!("{}d", secs / DAY),
+        secs => format!("{}w", secs / WEEK),
+    }
+}
+
+/// " (2 warnings)" style suffix for preset conflict counts; empty when there are none.
+pub fn warning_suffix(count: usize) -> String {
+    match count {
+        0 => String::new(),
+        1 => " (1 warning)".into(),
+        n => format!(" ({n} warnings)"),
+    }
+}
+
+/// Parse a video position typed as "mm:ss", "h:mm:ss", or plain seconds into seconds.
+pub fn parse_timestamp(input: &str) -> Result<u64, String> {
+    let trimmed = input.trim();
+    let invalid = || format!("'{trimmed}' is not a time; use mm:ss or h:mm:ss.");
+    if trimmed.is_empty() {
+        return Err("Enter a time such as 1:30.".into());
+    }
+    let parts: Vec<&str> = trimmed.split(':').collect();
+    if parts.len() > 3 {
+        return Err(invalid());
+    }
+    let mut total = 0u64;
+    for (idx, part) in parts.iter().enumerate() {
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let value: u64 = part.parse().map_err(|_| invalid())?;
+        // Every field after the first is minutes or seconds and must stay under 60.
+        if idx > 0 && value >= 60 {
+            return Err(invalid());
+        }
+        total = total
+            .checked_mul(60)
+            .and_then(|total| total.checked_add(value))
+            .ok_or_else(invalid)?;
+    }
+    Ok(total)
+}
+
+/// `url` with a `t=` parameter that starts playback `secs` in.
+pub fn link_at_time(url: &str, secs: u64) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}t={secs}s")
+}
+
 pub fn open_in_browser(url: &str) -> Result<(), String> {
     #[cfg(all(unix, not(target_os = "macos")))]
     {
@@ -68,3 +204,104 @@ fn try_launch_new_window(url: &str) -> std::io::Result<()> {
         "no supported browser command found",
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        elide_middle_to_width, humanize_elapsed, link_at_time, parse_timestamp, truncate_middle,
+    };
+
+    #[test]
+    fn short_text_is_untouched() {
+        assert_eq!(truncate_middle("Rust", 10), "Rust");
+        assert_eq!(truncate_middle("Rustacean", 9), "Rustacean");
+    }
+
+    #[test]
+    fn long_text_keeps_both_ends() {
+        assert_eq!(truncate_middle("abcdefghijkl", 7), "abc…jkl");
+        assert_eq!(truncate_middle("abcdefghijkl", 6), "abc…kl");
+        assert_eq!(truncate_middle("abcdefghijkl", 1), "…");
+        assert_eq!(truncate_middle("abcdefghijkl", 0), "");
+    }
+
+    #[test]
+    fn multibyte_text_is_cut_on_char_boundaries() {
+        let text = "日本語のチャンネル名です";
+        let cut = truncate_middle(text, 5);
+        assert_eq!(cut, "日本…です");
+        assert_eq!(cut.chars().count(), 5);
+        assert_eq!(truncate_middle("🎮🎮🎮🎮🎮🎮", 4), "🎮🎮…🎮");
+    }
+
+    /// Wide (non-ASCII) characters count double, roughly like CJK glyphs on screen.
+    fn cells(text: &str) -> f32 {
+        text.chars()
+            .map(|ch| if ch.is_ascii() { 1.0 } else { 2.0 })
+            .sum()
+    }
+
+    #[test]
+    fn width_elision_keeps_text_that_fits() {
+        assert_eq!(
+            elide_middle_to_width("Searching...", 12.0, cells),
+            "Searching..."
+        );
+        assert_eq!(elide_middle_to_width("日本語", 6.0, cells), "日本語");
+    }
+
+    #[test]
+    fn width_elision_cuts_multibyte_text_in_the_middle() {
+        let text = "エラー: https://www.googleapis.com/youtube/v3/search の応答";
+        let cut = elide_middle_to_width(text, 20.0, cells);
+        assert!(cells(&cut) <= 20.0);
+        assert!(cut.starts_with("エラー"));
+        assert!(cut.ends_with("応答"));
+        assert!(cut.contains('…'));
+
+        assert_eq!(elide_middle_to_width("🎮🎮🎮🎮🎮🎮", 7.0, cells), "🎮…🎮");
+        assert_eq!(elide_middle_to_width("日本語", 1.0, cells), "");
+    }
+
+    #[test]
+    fn under_a_minute_is_now() {
+        assert_eq!(humanize_elapsed(0), "now");
+        assert_eq!(humanize_elapsed(59), "now");
+        assert_eq!(humanize_elapsed(-30), "now");
+    }
+
+    #[test]
+    fn minute_and_hour_boundaries() {
+        assert_eq!(humanize_elapsed(60), "1m");
+        assert_eq!(humanize_elapsed(3599), "59m");
+        assert_eq!(humanize_elapsed(3600), "1h");
+        assert_eq!(humanize_elapsed(86_399), "23h");
+    }
+
+    #[test]
+    fn day_and_week_boundaries() {
+        assert_eq!(humanize_elapsed(86_400), "1d");
+        assert_eq!(humanize_elapsed(604_799), "6d");
+        assert_eq!(humanize_elapsed(604_800), "1w");
+        assert_eq!(humanize_elapsed(3 * 604_800 + 86_400), "3w");
+    }
+
+    #[test]
+    fn timestamps_parse_and_append_to_links() {
+        assert_eq!(parse_timestamp("1:30"), Ok(90));
+        assert_eq!(parse_timestamp(" 1:02:03 "), Ok(3_723));
+        assert_eq!(parse_timestamp("75"), Ok(75));
+        assert_eq!(parse_timestamp("0:05"), Ok(5));
+        for bad in ["", "1:60", "1::2", "-1:00", "a:bc", "1:2:3:4", "1.5"] {
+            assert!(parse_timestamp(bad).is_err(), "{bad:?} should be rejected");
+        }
+        assert_eq!(
+            link_at_time("https://www.youtube.com/watch?v=abc", 90),
+            "https://www.youtube.com/watch?v=abc&t=90s"
+        );
+        assert_eq!(
+            link_at_time("https://youtu.be/abc", 5),
+            "https://youtu.be/abc?t=5s"
+        );
+    }
+}
@@ -1,7 +1,12 @@
 use super::types::ChannelsListResponse;
-use anyhow::{Context, bail};
+use anyhow::{Context, anyhow};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
+use std::sync::Mutex;
+
+/// Uploads playlist ID by channel ID, learned from any `channels.list` response this session.
+static UPLOADS_PLAYLISTS: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
 
 #[derive(Deserialize, Debug)]
 struct GoogleApiErrorResponse {
@@ -28,7 +33,11 @@ struct GoogleApiErrorDetail {
     domain: Option<String>,
 }
 
-fn format_youtube_error(status: reqwest::StatusCode, body: &str, endpoint: &str) -> String {
+pub(super) fn format_youtube_error(
+    status: reqwest::StatusCode,
+    body: &str,
+    endpoint: &str,
+) -> String {
     if let Ok(parsed) = serde_json::from_str::<GoogleApiErrorResponse>(body) {
         let reason = parsed
             .error
@@ -92,15 +101,74 @@ pub async fn channels_list(api_key: &str, ids: &[String]) -> anyhow::Result<Chan
     if ids.is_empty() {
         return Ok(ChannelsListResponse { items: vec![] });
     }
+    let filter = format!("&id={}", ids.join(","));
+    let resp = fetch_channels(api_key, &filter).await?;
+    remember_uploads(&resp);
+    Ok(resp)
+}
+
+/// Remember the uploads playlists a `channels.list` response carried.
+fn remember_uploads(resp: &ChannelsListResponse) {
+    let Ok(mut cache) = UPLOADS_PLAYLISTS.lock() else {
+        return;
+    };
+    let cache = cache.get_or_insert_with(HashMap::new);
+    for item in &resp.items {
+        if let Some(playlist) = item.uploads_playlist() {
+            cache.insert(item.id.clone(), playlist.to_owned());
+        }
+    }
+}
+
+/// Uploads playlist ID for each of `ids` that has one. Channels not seen this session are
+/// looked up 50 per `channels.list` call; returns the map and how many calls were made.
+pub async fn uploads_playlists(
+    api_key: &str,
+    ids: &[String],
+) -> anyhow::Result<(HashMap<String, String>, u32)> {
+    let known = |id: &String| {
+        UPLOADS_PLAYLISTS
+            .lock()
+            .ok()
+            .and_then(|cache| cache.as_ref()?.get(id).cloned())
+    };
+    let missing: Vec<String> = ids
+        .iter()
+        .filter(|id| known(id).is_none())
+        .cloned()
+        .collect();
+    let mut calls = 0u32;
+    for chunk in missing.chunks(50) {
+        channels_list(api_key, chunk).await?;
+        calls += 1;
+    }
+    let found = ids
+        .iter()
+        .filter_map(|id| Some((id.clone(), known(id)?)))
+        .collect();
+    Ok((found, calls))
+}
+
+/// Resolve an `@handle` to its `UC…` channel ID; `Ok(None)` when no channel has that handle.
+pub async fn resolve_handle(api_key: &str, handle: &str) -> anyhow::Result<Option<String>> {
+    let handle = handle.trim();
+    if handle.trim_start_matches('@').is_empty() {
+        return Ok(None);
+    }
+    let filter = format!("&forHandle={}", urlencoding::encode(handle));
+    let resp = fetch_channels(api_key, &filter).await?;
+    Ok(resp.items.into_iter().next().map(|item| item.id))
+}
 
-    let mut url = "https://www.googleapis.com/youtube/v3/channels?part=snippet".to_string();
-    url.push_str("&id=");
-    url.push_str(&ids.join(","));
+async fn fetch_channels(api_key: &str, filter: &str) -> anyhow::Result<ChannelsListResponse> {
+    let mut url =
+        "https://www.googleapis.com/youtube/v3/channels?part=snippet,contentDetails".to_string();
+    url.push_str(filter);
     url.push_str("&key=");
     url.push_str(api_key.trim());
 
-    let client = reqwest::Client::new();
-    let mut resp = client.get(&url).send().await?;
+    let client = super::http::client();
+    let mut resp = super::http::send(client.get(&url)).await?;
     let mut status = resp.status();
     let mut bytes = resp.bytes().await?;
     if !status.is_success() {
@@ -117,15 +185,13 @@ pub async fn channels_list(api_key: &str, ids: &[String]) -> anyhow::Result<Chan
             let alt_keys = load_alt_keys(api_key);
             for alt_key in alt_keys {
                 let mut alt_url =
-                    "https://www.googleapis.com/youtube/v3/channels?part=snippet".to_string();
-                alt_url.push_str("&id=");
-                alt_url.push_str(&ids.join(","));
+                    "https://www.googleapis.com/youtube/v3/channels?part=snippet,contentDetails"
+                        .to_string();
+                alt_url.push_str(filter);
                 alt_url.push_str("&key=");
                 alt_url.push_str(alt_key.trim());
 
-                resp = client
-                    .get(&alt_url)
-                    .send()
+                resp = super::http::send(client.get(&alt_url))
                     .await
                     .with_context(|| "retry with alternate API key failed to send request")?;
                 status = resp.status();
@@ -137,7 +203,9 @@ pub async fn channels_list(api_key: &str, ids: &[String]) -> anyhow::Result<Chan
             }
         }
         body_string = String::from_utf8_lossy(&bytes).to_string();
-        bail!(format_youtube_error(status, &body_string, "channels.list"));
+        let summary = format_youtube_error(status, &body_string, "channels.list");
+        // Keep the raw body as the underlying cause so callers can surface it.
+        return Err(anyhow!(body_string.trim().to_owned()).context(summary));
     }
     let parsed = serde_json::from_slice::<ChannelsListResponse>(&bytes)?;
     Ok(parsed)
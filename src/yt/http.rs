@@ -0,0 +1,310 @@
+//! The HTTP client shared by API calls and thumbnail downloads, built with the proxy
+//! settings so every request takes the same route.
+
+use std::sync::{Mutex, OnceLock, RwLock};
+
+use anyhow::anyhow;
+use reqwest::{Client, Proxy, RequestBuilder, Response, Url};
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::prefs::SearchDefaults;
+
+/// Environment variables consulted when no proxy is set in prefs, in this order.
+pub const ENV_PROXY_VARS: [&str; 4] = ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"];
+
+/// Where requests go: straight out, or through a proxy from prefs or the environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProxyChoice {
+    Direct,
+    Proxy {
+        url: String,
+        from_env: Option<&'static str>,
+    },
+}
+
+struct Current {
+    choice: ProxyChoice,
+    client: Client,
+}
+
+static CURRENT: OnceLock<RwLock<Current>> = OnceLock::new();
+
+/// Clock differences up to this many seconds are ordinary drift and not worth a warning.
+pub const CLOCK_SKEW_WARN_SECS: i64 = 5 * 60;
+
+/// Local clock minus the server's `Date` header, from the most recent API response.
+static LAST_CLOCK_SKEW: Mutex<Option<i64>> = Mutex::new(None);
+
+/// Pick the proxy: the "no proxy" toggle wins, then the prefs URL, then the first of
+/// `ENV_PROXY_VARS` that is set, otherwise a direct connection.
+pub fn proxy_choice(
+    proxy_url: Option<&str>,
+    no_proxy: bool,
+    env: impl Fn(&str) -> Option<String>,
+) -> ProxyChoice {
+    if no_proxy {
+        return ProxyChoice::Direct;
+    }
+    if let Some(url) = proxy_url.map(str::trim).filter(|url| !url.is_empty()) {
+        return ProxyChoice::Proxy {
+            url: url.to_owned(),
+            from_env: None,
+        };
+    }
+    ENV_PROXY_VARS
+        .iter()
+        .find_map(|var| {
+            let url = env(var)?.trim().to_owned();
+            (!url.is_empty()).then_some(ProxyChoice::Proxy {
+                url,
+                from_env: Some(*var),
+            })
+        })
+        .unwrap_or(ProxyChoice::Direct)
+}
+
+/// Check a proxy URL typed in settings and return it normalized; a bare `host:port` is
+/// taken as an `http://` proxy.
+pub fn validate_proxy_url(raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim();
+    let with_scheme = if trimmed.contains("://") {
+        trimmed.to_owned()
+    } else {
+        format!("http://{trimmed}")
+    };
+    let url = Url::parse(&with_scheme).map_err(|err| format!("Proxy URL '{trimmed}': {err}."))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!(
+            "Proxy URL '{trimmed}' must use http:// or https://."
+        ));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(format!("Proxy URL '{trimmed}' needs a host."));
+    }
+    Ok(with_scheme)
+}
+
+/// Build a client that follows `choice`; `Direct` also ignores proxies from the environment.
+pub fn build_client(choice: &ProxyChoice) -> reqwest::Result<Client> {
+    let builder = Client::builder();
+    let builder = match choice {
+        ProxyChoice::Direct => builder.no_proxy(),
+        ProxyChoice::Proxy { url, .. } => builder.proxy(Proxy::all(url.as_str())?),
+    };
+    builder.build()
+}
+
+/// Rebuild the shared client when the proxy settings changed.
+pub fn configure(defaults: &SearchDefaults) {
+    let choice = proxy_choice(defaults.proxy_url.as_deref(), defaults.no_proxy, |var| {
+        std::env::var(var).ok()
+    });
+    set_choice(choice);
+}
+
+fn set_choice(choice: ProxyChoice) {
+    let lock = current_lock();
+    if lock.read().is_ok_and(|current| current.choice == choice) {
+        return;
+    }
+    let client = build_client(&choice).unwrap_or_else(|err| {
+        eprintln!("Invalid proxy settings ({err}); connecting directly.");
+        build_client(&ProxyChoice::Direct).unwrap_or_default()
+    });
+    if let Ok(mut current) = lock.write() {
+        *current = Current { choice, client };
+    }
+}
+
+fn current_lock() -> &'static RwLock<Current> {
+    CURRENT.get_or_init(|| {
+        let choice = proxy_choice(None, false, |var| std::env::var(var).ok());
+        let client = build_client(&choice).unwrap_or_default();
+        RwLock::new(Current { choice, client })
+    })
+}
+
+/// The shared client; cheap to clone.
+pub fn client() -> Client {
+    current_lock()
+        .read()
+        .map(|current| current.client.clone())
+        .unwrap_or_default()
+}
+
+/// `host:port` of the proxy in use, if any.
+pub fn proxy_label() -> Option<String> {
+    let current = current_lock().read().ok()?;
+    let ProxyChoice::Proxy { url, .. } = &current.choice else {
+        return None;
+    };
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed.host_str()?;
+    Some(match parsed.port_or_known_default() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_owned(),
+    })
+}
+
+/// Send `request` and note the response's `Date` header for [`last_clock_skew`].
+pub async fn send(request: RequestBuilder) -> anyhow::Result<Response> {
+    let resp = request.send().await.map_err(send_error)?;
+    let server_date = resp
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_http_date);
+    if let (Some(server), Ok(mut last)) = (server_date, LAST_CLOCK_SKEW.lock()) {
+        *last = Some((OffsetDateTime::now_utc() - server).whole_seconds());
+    }
+    Ok(resp)
+}
+
+/// Parse an HTTP `Date` header such as "Sun, 06 Nov 1994 08:49:37 GMT".
+pub fn parse_http_date(value: &str) -> Option<OffsetDateTime> {
+    let format = format_description!(
+        "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+    );
+    PrimitiveDateTime::parse(value.trim(), format)
+        .ok()
+        .map(PrimitiveDateTime::assume_utc)
+}
+
+/// Seconds the local clock was ahead of the server (negative when behind) at the last API
+/// response that carried a `Date` header.
+pub fn last_clock_skew() -> Option<i64> {
+    LAST_CLOCK_SKEW.lock().ok().and_then(|last| *last)
+}
+
+/// Forget the measured skew, so a run that makes no API calls does not report an old one.
+pub fn reset_clock_skew() {
+    if let Ok(mut last) = LAST_CLOCK_SKEW.lock() {
+        *last = None;
+    }
+}
+
+/// A warning when `skew_secs` is past [`CLOCK_SKEW_WARN_SECS`] either way.
+pub fn clock_skew_warning(skew_secs: i64) -> Option<String> {
+    if skew_secs.abs() <= CLOCK_SKEW_WARN_SECS {
+        return None;
+    }
+    let direction = if skew_secs > 0 { "ahead" } else { "behind" };
+    let secs = skew_secs.unsigned_abs();
+    let amount = match secs {
+        0..3_600 => format!("{} min", secs.div_ceil(60)),
+        3_600..172_800 => format!("{} h", (secs + 1_800) / 3_600),
+        _ => format!("{} days", (secs + 43_200) / 86_400),
+    };
+    Some(format!(
+        "Your system clock appears to be {amount} {direction} — date-window searches may return nothing."
+    ))
+}
+
+/// Turn a failed send into an error that names the proxy when it could not be reached.
+pub fn send_error(err: reqwest::Error) -> anyhow::Error {
+    match proxy_label() {
+        Some(proxy) if err.is_connect() => {
+            anyhow!("Proxy {proxy} refused the connection: {err}")
+        }
+        _ => err.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_with(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn prefs_beat_env_and_no_proxy_beats_both() {
+        let env = env_with(&[
+            ("HTTP_PROXY", "http://env:8080"),
+            ("HTTPS_PROXY", "http://secure:3128"),
+        ]);
+        assert_eq!(
+            proxy_choice(Some("http://10.0.0.1:3128"), false, &env),
+            ProxyChoice::Proxy {
+                url: "http://10.0.0.1:3128".into(),
+                from_env: None,
+            }
+        );
+        assert_eq!(
+            proxy_choice(Some("  "), false, &env),
+            ProxyChoice::Proxy {
+                url: "http://secure:3128".into(),
+                from_env: Some("HTTPS_PROXY"),
+            }
+        );
+        assert_eq!(
+            proxy_choice(None, false, env_with(&[("http_proxy", "http://lower:80")])),
+            ProxyChoice::Proxy {
+                url: "http://lower:80".into(),
+                from_env: Some("http_proxy"),
+            }
+        );
+        assert_eq!(
+            proxy_choice(Some("http://p:1"), true, &env),
+            ProxyChoice::Direct
+        );
+        assert_eq!(
+            proxy_choice(None, false, env_with(&[])),
+            ProxyChoice::Direct
+        );
+    }
+
+    #[test]
+    fn validates_and_normalizes_proxy_urls() {
+        assert_eq!(
+            validate_proxy_url("10.0.0.1:3128").unwrap(),
+            "http://10.0.0.1:3128"
+        );
+        assert!(validate_proxy_url("https://proxy.corp:443").is_ok());
+        assert!(validate_proxy_url("ftp://proxy.corp").is_err());
+        assert!(validate_proxy_url("http://").is_err());
+    }
+
+    #[test]
+    fn parses_date_headers_and_warns_only_past_the_threshold() {
+        let date = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(date.unix_timestamp(), 784_111_777);
+        assert!(parse_http_date("yesterday").is_none());
+
+        assert_eq!(clock_skew_warning(0), None);
+        assert_eq!(clock_skew_warning(-CLOCK_SKEW_WARN_SECS), None);
+        assert_eq!(
+            clock_skew_warning(2 * 3_600 + 40),
+            Some(
+                "Your system clock appears to be 2 h ahead — date-window searches may return nothing."
+                    .into()
+            )
+        );
+        assert!(
+            clock_skew_warning(-(CLOCK_SKEW_WARN_SECS + 1))
+                .unwrap()
+                .contains("6 min behind")
+        );
+        assert!(
+            clock_skew_warning(3 * 86_400)
+                .unwrap()
+                .contains("3 days ahead")
+        );
+    }
+
+    #[test]
+    fn builds_clients_for_each_choice() {
+        assert!(build_client(&ProxyChoice::Direct).is_ok());
+        let proxied = ProxyChoice::Proxy {
+            url: "http://10.0.0.1:3128".into(),
+            from_env: None,
+        };
+        assert!(build_client(&proxied).is_ok());
+    }
+}
@@ -0,0 +1,137 @@
+//! Canonical watch links, so opened and copied URLs never carry tracking parameters.
+
+use reqwest::Url;
+
+const WATCH_URL: &str = "https://www.youtube.com/watch?v=";
+
+/// Reduce any YouTube video URL (watch, youtu.be, shorts, live, embed, mobile, music) to
+/// `https://www.youtube.com/watch?v=<id>`, dropping `si`, `feature`, `pp`, and every other
+/// parameter. With `keep_time`, a `t` (or embed `start`) position is carried over. Returns
+/// `None` for URLs that are not a YouTube video.
+pub fn normalize_youtube_url(url: &str, keep_time: bool) -> Option<String> {
+    let trimmed = url.trim();
+    let parsed = if trimmed.contains("://") {
+        Url::parse(trimmed)
+    } else {
+        Url::parse(&format!("https://{trimmed}"))
+    }
+    .ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = parsed.host_str()?.to_ascii_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let mut segments = parsed.path_segments()?.filter(|s| !s.is_empty());
+    let query = |name: &str| {
+        parsed
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    let id = match host {
+        "youtu.be" => segments.next().map(str::to_owned),
+        "youtube.com" | "m.youtube.com" | "music.youtube.com" | "youtube-nocookie.com" => {
+            match segments.next()? {
+                "watch" => query("v"),
+                "shorts" | "live" | "embed" | "v" => segments.next().map(str::to_owned),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+    .filter(|id| is_video_id(id))?;
+
+    let mut canonical = format!("{WATCH_URL}{id}");
+    if keep_time
+        && let Some(time) = query("t")
+            .or_else(|| query("start"))
+            .filter(|time| is_timestamp(time))
+    {
+        canonical.push_str("&t=");
+        canonical.push_str(&time);
+    }
+    Some(canonical)
+}
+
+fn is_video_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+/// `90`, `90s`, or `1h2m3s`: digits and unit letters only, starting with a digit.
+fn is_timestamp(time: &str) -> bool {
+    time.bytes().next().is_some_and(|b| b.is_ascii_digit())
+        && time
+            .bytes()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'h' | b'm' | b's'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANONICAL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+    #[test]
+    fn every_youtube_form_reduces_to_the_watch_url() {
+        for url in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://www.youtube.com/watch?feature=share&v=dQw4w9WgXcQ&si=abc123&pp=ygU",
+            "http://m.youtube.com/watch?v=dQw4w9WgXcQ&list=PL123&index=4",
+            "https://youtu.be/dQw4w9WgXcQ?si=tracking",
+            "https://www.youtube.com/shorts/dQw4w9WgXcQ?feature=share",
+            "https://www.youtube.com/live/dQw4w9WgXcQ?si=x",
+            "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ?start=30",
+            "https://music.youtube.com/watch?v=dQw4w9WgXcQ&si=x",
+            "  youtube.com/watch?v=dQw4w9WgXcQ#comments ",
+        ] {
+            assert_eq!(
+                normalize_youtube_url(url, false).as_deref(),
+                Some(CANONICAL),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn timestamps_survive_only_when_asked_and_well_formed() {
+        assert_eq!(
+            normalize_youtube_url("https://youtu.be/dQw4w9WgXcQ?si=x&t=42", true),
+            Some(format!("{CANONICAL}&t=42"))
+        );
+        assert_eq!(
+            normalize_youtube_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=1m30s", true),
+            Some(format!("{CANONICAL}&t=1m30s"))
+        );
+        assert_eq!(
+            normalize_youtube_url("https://www.youtube.com/embed/dQw4w9WgXcQ?start=30", true),
+            Some(format!("{CANONICAL}&t=30"))
+        );
+        assert_eq!(
+            normalize_youtube_url("https://youtu.be/dQw4w9WgXcQ?t=42", false).as_deref(),
+            Some(CANONICAL)
+        );
+        assert_eq!(
+            normalize_youtube_url("https://youtu.be/dQw4w9WgXcQ?t=evil%26x", true).as_deref(),
+            Some(CANONICAL)
+        );
+    }
+
+    #[test]
+    fn non_video_urls_are_rejected() {
+        for url in [
+            "https://example.com/watch?v=dQw4w9WgXcQ",
+            "https://www.youtube.com/@handle",
+            "https://www.youtube.com/channel/UC123",
+            "https://www.youtube.com/watch",
+            "https://www.youtube.com/watch?v=bad%20id",
+            "javascript:alert(1)",
+            "",
+        ] {
+            assert_eq!(normalize_youtube_url(url, true), None, "{url}");
+        }
+    }
+}
@@ -1,4 +1,10 @@
 pub mod channels;
+pub mod http;
+pub mod links;
+pub mod playlists;
 pub mod search;
 pub mod types;
 pub mod videos;
+
+pub use channels::resolve_handle;
+pub use links::normalize_youtube_url;
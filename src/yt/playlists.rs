@@ -0,0 +1,31 @@
+use super::channels::format_youtube_error;
+use super::types::PlaylistItemsResponse;
+use anyhow::anyhow;
+
+/// One page of a playlist's items (1 quota unit), newest first for uploads playlists.
+pub async fn playlist_items(
+    api_key: &str,
+    playlist_id: &str,
+    page_token: Option<&str>,
+) -> anyhow::Result<PlaylistItemsResponse> {
+    let mut url = format!(
+        "https://www.googleapis.com/youtube/v3/playlistItems?part=contentDetails&maxResults=50&playlistId={}",
+        urlencoding::encode(playlist_id)
+    );
+    if let Some(token) = page_token {
+        url.push_str("&pageToken=");
+        url.push_str(&urlencoding::encode(token));
+    }
+    url.push_str("&key=");
+    url.push_str(api_key.trim());
+
+    let resp = super::http::send(super::http::client().get(&url)).await?;
+    let status = resp.status();
+    let bytes = resp.bytes().await?;
+    if !status.is_success() {
+        let body = String::from_utf8_lossy(&bytes);
+        let summary = format_youtube_error(status, &body, "playlistItems.list");
+        return Err(anyhow!(body.trim().to_owned()).context(summary));
+    }
+    Ok(serde_json::from_slice(&bytes)?)
+}
@@ -1,5 +1,5 @@
 use super::types::SearchListResponse;
-use anyhow::{Context, bail};
+use anyhow::{Context, anyhow};
 use serde::Deserialize;
 use std::fs;
 
@@ -105,8 +105,8 @@ pub async fn search_list(
     url.push_str("&key=");
     url.push_str(api_key.trim());
 
-    let client = reqwest::Client::new();
-    let mut resp = client.get(&url).send().await?;
+    let client = super::http::client();
+    let mut resp = super::http::send(client.get(&url)).await?;
     let mut status = resp.status();
     let mut bytes = resp.bytes().await?;
     if !status.is_success() {
@@ -134,9 +134,11 @@ pub async fn search_list(
                 alt_url.push_str("&key=");
                 alt_url.push_str(alt_key.trim());
 
-                resp = client.get(&alt_url).send().await.with_context(|| {
-                    "retry with alternate API key failed to send request".to_string()
-                })?;
+                resp = super::http::send(client.get(&alt_url))
+                    .await
+                    .with_context(|| {
+                        "retry with alternate API key failed to send request".to_string()
+                    })?;
                 status = resp.status();
                 bytes = resp.bytes().await?;
                 if status.is_success() {
@@ -147,7 +149,9 @@ pub async fn search_list(
             }
         }
         body_string = String::from_utf8_lossy(&bytes).to_string();
-        bail!(format_youtube_error(status, &body_string, "search.list"));
+        let summary = format_youtube_error(status, &body_string, "search.list");
+        // Keep the raw body as the underlying cause so callers can surface it.
+        return Err(anyhow!(body_string.trim().to_owned()).context(summary));
     }
     let parsed = serde_json::from_slice::<SearchListResponse>(&bytes)?;
     Ok(parsed)
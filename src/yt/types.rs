@@ -2,13 +2,19 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VideoDetails {
     pub id: String,
     pub title: String,
     pub title_lower: String,
     pub channel_title: String,
     pub channel_handle: String,
+    /// Lowercased `channel_title` and `channel_handle`, for channel matching without
+    /// allocating per check.
+    #[serde(default)]
+    pub channel_title_lower: String,
+    #[serde(default)]
+    pub channel_handle_lower: String,
     pub channel_display_name: Option<String>,
     pub channel_custom_url: Option<String>,
     pub published_at: String,
@@ -18,7 +24,79 @@ pub struct VideoDetails {
     pub thumbnail_url: Option<String>,
     pub url: String,
     pub has_caption_lang_en: Option<bool>,
+    /// Captions available, from videos.list `contentDetails.caption`; `None` when unknown
+    /// (e.g. results cached by older versions).
+    #[serde(default)]
+    pub caption: Option<bool>,
+    /// From `contentDetails.licensedContent`: uploaded by a content partner and claimed;
+    /// `None` when unknown.
+    #[serde(default)]
+    pub licensed_content: Option<bool>,
+    /// From `contentDetails.dimension`: "2d" or "3d".
+    #[serde(default)]
+    pub dimension: Option<String>,
+    /// Uploader tags from the videos.list snippet; empty when none were set or for results
+    /// cached by older versions.
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub source_presets: Vec<String>,
+    #[serde(default)]
+    pub live_broadcast_content: Option<String>,
+    /// Set when contentDetails.duration was missing or could not be parsed.
+    #[serde(default)]
+    pub duration_unknown: bool,
+    /// From `contentDetails.contentRating.ytRating`; results cached without it load as
+    /// unrestricted.
+    #[serde(default)]
+    pub age_restricted: bool,
+    /// Whether `contentDetails.regionRestriction` blocks the configured region; `None` when
+    /// no region is configured or for results cached by older versions.
+    #[serde(default)]
+    pub blocked_in_region: Option<bool>,
+    /// The videos.list description, handed to the find index as results arrive. Not written
+    /// out, so the results cache stays small; cached results load with it empty.
+    #[serde(default, skip_serializing)]
+    pub description: String,
+}
+
+impl VideoDetails {
+    /// Live and upcoming broadcasts report no duration until they finish.
+    pub fn is_live_or_upcoming(&self) -> bool {
+        matches!(
+            self.live_broadcast_content.as_deref(),
+            Some("live") | Some("upcoming")
+        )
+    }
+
+    /// Fill the lowercased fields, which results cached by older versions lack.
+    pub fn fill_lowercase(&mut self) {
+        self.title_lower = self.title.to_ascii_lowercase();
+        self.channel_title_lower = self.channel_title.to_ascii_lowercase();
+        self.channel_handle_lower = self.channel_handle.to_ascii_lowercase();
+    }
+
+    /// The canonical watch link for opening and copying: tracking parameters that cached or
+    /// imported data may carry are dropped. Falls back to `url` when it is not a YouTube link.
+    pub fn watch_url(&self) -> String {
+        super::links::normalize_youtube_url(&self.url, false).unwrap_or_else(|| self.url.clone())
+    }
+
+    /// Age-restricted, or unavailable in the configured region.
+    pub fn is_restricted(&self) -> bool {
+        self.age_restricted || self.blocked_in_region == Some(true)
+    }
+
+    /// Whether videos.list reported the video as 3D.
+    pub fn is_3d(&self) -> bool {
+        self.dimension
+            .as_deref()
+            .is_some_and(|dimension| dimension.eq_ignore_ascii_case("3d"))
+    }
+
+    /// True when `duration_secs` reflects a real measured length.
+    pub fn duration_is_known(&self) -> bool {
+        !self.duration_unknown && !self.is_live_or_upcoming()
+    }
 }
 
 #[derive(Deserialize)]
@@ -68,6 +146,12 @@ pub struct VideoSnippet {
     #[serde(rename = "defaultLanguage")]
     pub default_language: Option<String>,
     pub thumbnails: Option<Thumbs>,
+    #[serde(rename = "liveBroadcastContent")]
+    pub live_broadcast_content: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub description: String,
 }
 #[derive(Deserialize)]
 pub struct Thumbs {
@@ -80,11 +164,60 @@ pub struct Thumb {
 }
 #[derive(Deserialize)]
 pub struct ContentDetails {
+    /// Empty when videos.list leaves it out; the video is then kept with an unknown length.
+    #[serde(default)]
     pub duration: String,
+    /// "true" or "false": whether the video has captions.
+    #[serde(default)]
+    pub caption: Option<String>,
+    #[serde(rename = "licensedContent", default)]
+    pub licensed_content: Option<bool>,
+    /// "2d" or "3d".
+    #[serde(default)]
+    pub dimension: Option<String>,
+    #[serde(rename = "contentRating", default)]
+    pub content_rating: ContentRating,
+    #[serde(rename = "regionRestriction", default)]
+    pub region_restriction: Option<RegionRestriction>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ContentRating {
+    /// "ytAgeRestricted" for age-restricted videos; absent otherwise.
+    #[serde(rename = "ytRating", default)]
+    pub yt_rating: Option<String>,
+}
+
+impl ContentRating {
+    pub fn is_age_restricted(&self) -> bool {
+        self.yt_rating.as_deref() == Some("ytAgeRestricted")
+    }
+}
+
+/// Region codes a video is limited to or blocked in. When `allowed` is present, every
+/// region missing from it is blocked.
+#[derive(Deserialize, Default)]
+pub struct RegionRestriction {
+    #[serde(default)]
+    pub allowed: Option<Vec<String>>,
+    #[serde(default)]
+    pub blocked: Vec<String>,
+}
+
+impl RegionRestriction {
+    pub fn blocks(&self, region: &str) -> bool {
+        let listed = |codes: &[String]| codes.iter().any(|code| code.eq_ignore_ascii_case(region));
+        listed(&self.blocked)
+            || self
+                .allowed
+                .as_deref()
+                .is_some_and(|allowed| !listed(allowed))
+    }
 }
 
 #[derive(Deserialize)]
 pub struct ChannelsListResponse {
+    #[serde(default)]
     pub items: Vec<ChannelItem>,
 }
 
@@ -92,6 +225,31 @@ pub struct ChannelsListResponse {
 pub struct ChannelItem {
     pub id: String,
     pub snippet: ChannelSnippet,
+    #[serde(rename = "contentDetails", default)]
+    pub content_details: Option<ChannelContentDetails>,
+}
+
+#[derive(Deserialize)]
+pub struct ChannelContentDetails {
+    #[serde(rename = "relatedPlaylists", default)]
+    pub related_playlists: RelatedPlaylists,
+}
+
+#[derive(Deserialize, Default)]
+pub struct RelatedPlaylists {
+    /// Playlist holding every public upload, newest first.
+    pub uploads: Option<String>,
+}
+
+impl ChannelItem {
+    pub fn uploads_playlist(&self) -> Option<&str> {
+        self.content_details
+            .as_ref()?
+            .related_playlists
+            .uploads
+            .as_deref()
+            .filter(|id| !id.is_empty())
+    }
 }
 
 #[derive(Deserialize)]
@@ -100,3 +258,37 @@ pub struct ChannelSnippet {
     #[serde(rename = "customUrl")]
     pub custom_url: Option<String>,
 }
+
+impl ChannelSnippet {
+    /// `customUrl` as an `@handle`; YouTube returns it with or without the `@`.
+    pub fn handle(&self) -> Option<String> {
+        self.custom_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(|url| format!("@{}", url.trim_start_matches('@')))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct PlaylistItemsResponse {
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: Option<String>,
+    #[serde(default)]
+    pub items: Vec<PlaylistItem>,
+}
+
+#[derive(Deserialize)]
+pub struct PlaylistItem {
+    #[serde(rename = "contentDetails")]
+    pub content_details: PlaylistItemDetails,
+}
+
+#[derive(Deserialize)]
+pub struct PlaylistItemDetails {
+    #[serde(rename = "videoId")]
+    pub video_id: String,
+    /// Missing for private or deleted videos still listed in the playlist.
+    #[serde(rename = "videoPublishedAt", default)]
+    pub video_published_at: Option<String>,
+}
@@ -1,5 +1,5 @@
 use super::types::VideosListResponse;
-use anyhow::bail;
+use anyhow::anyhow;
 use serde::Deserialize;
 use std::fs;
 
@@ -89,6 +89,25 @@ fn load_alt_keys(current: &str) -> Vec<String> {
     keys
 }
 
+/// Video looked up by [`check_key`]; any public ID works.
+const KEY_CHECK_VIDEO_ID: &str = "dQw4w9WgXcQ";
+
+/// Make one `videos.list` call (1 quota unit) with `api_key` alone, without falling back to
+/// alternate key files, so a bad key is reported rather than papered over.
+pub async fn check_key(api_key: &str) -> anyhow::Result<()> {
+    let url = format!(
+        "https://www.googleapis.com/youtube/v3/videos?part=id&id={KEY_CHECK_VIDEO_ID}&key={}",
+        api_key.trim()
+    );
+    let resp = super::http::send(super::http::client().get(&url)).await?;
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = resp.text().await.unwrap_or_default();
+    Err(anyhow!(format_youtube_error(status, &body, "videos.list")))
+}
+
 #[allow(dead_code)]
 pub async fn videos_list(api_key: &str, ids: &[String]) -> anyhow::Result<VideosListResponse> {
     if ids.is_empty() {
@@ -101,8 +120,8 @@ pub async fn videos_list(api_key: &str, ids: &[String]) -> anyhow::Result<Videos
     url.push_str("&key=");
     url.push_str(api_key.trim());
 
-    let client = reqwest::Client::new();
-    let mut resp = client.get(&url).send().await?;
+    let client = super::http::client();
+    let mut resp = super::http::send(client.get(&url)).await?;
     let mut status = resp.status();
     let mut bytes = resp.bytes().await?;
     if !status.is_success() {
@@ -126,7 +145,7 @@ pub async fn videos_list(api_key: &str, ids: &[String]) -> anyhow::Result<Videos
                 alt_url.push_str("&key=");
                 alt_url.push_str(alt_key.trim());
 
-                resp = client.get(&alt_url).send().await?;
+                resp = super::http::send(client.get(&alt_url)).await?;
                 status = resp.status();
                 bytes = resp.bytes().await?;
                 if status.is_success() {
@@ -137,7 +156,9 @@ pub async fn videos_list(api_key: &str, ids: &[String]) -> anyhow::Result<Videos
             }
         }
         body_string = String::from_utf8_lossy(&bytes).to_string();
-        bail!(format_youtube_error(status, &body_string, "videos.list"));
+        let summary = format_youtube_error(status, &body_string, "videos.list");
+        // Keep the raw body as the underlying cause so callers can surface it.
+        return Err(anyhow!(body_string.trim().to_owned()).context(summary));
     }
     let parsed = serde_json::from_slice::<VideosListResponse>(&bytes)?;
     Ok(parsed)